@@ -131,20 +131,125 @@ export function register(...plugins) {
                         chart.plugin.max_columns = x;
                     }
 
-                    async render() {
+                    async to_image({format = "png", width, height} = {}) {
+                        if (format === "svg") {
+                            return this._render_svg(width, height);
+                        }
+
+                        return await this.render(width, height);
+                    }
+
+                    _render_svg(width, height) {
+                        const SVG_NS = "http://www.w3.org/2000/svg";
+                        const container =
+                            this.shadowRoot.querySelector("#container");
+                        const w = container.offsetWidth;
+                        const h = container.offsetHeight;
+                        const root = document.createElementNS(SVG_NS, "svg");
+                        root.setAttribute("xmlns", SVG_NS);
+                        root.setAttribute("width", width || w);
+                        root.setAttribute("height", height || h);
+                        root.setAttribute("viewBox", `0 0 ${w} ${h}`);
+                        root.setAttribute("preserveAspectRatio", "none");
+
+                        const style = window.getComputedStyle(this);
+                        const background = document.createElementNS(
+                            SVG_NS,
+                            "rect"
+                        );
+                        background.setAttribute("width", w);
+                        background.setAttribute("height", h);
+                        background.setAttribute(
+                            "fill",
+                            style.getPropertyValue("--plugin--background") ||
+                                "white"
+                        );
+
+                        root.appendChild(background);
+                        const text_color = style.getPropertyValue("color");
+                        const svgs = Array.from(
+                            this.shadowRoot.querySelectorAll(
+                                "svg:not(#dragHandles)"
+                            )
+                        );
+
+                        // Layered in the same order as `render()`.
+                        for (const svg of svgs.reverse()) {
+                            const new_svg = svg.cloneNode(true);
+                            new_svg.setAttribute("x", svg.parentNode.offsetLeft);
+                            new_svg.setAttribute("y", svg.parentNode.offsetTop);
+                            new_svg.setAttribute(
+                                "width",
+                                svg.parentNode.offsetWidth
+                            );
+                            new_svg.setAttribute(
+                                "height",
+                                svg.parentNode.offsetHeight
+                            );
+
+                            for (const text of new_svg.querySelectorAll(
+                                "text"
+                            )) {
+                                text.setAttribute("fill", text_color);
+                            }
+
+                            root.appendChild(new_svg);
+                        }
+
+                        // Canvas layers (e.g. WebGL series) are embedded as
+                        // bitmaps.
+                        const canvases = Array.from(
+                            this.shadowRoot.querySelectorAll("canvas")
+                        );
+
+                        for (const canvas of canvases.reverse()) {
+                            const image = document.createElementNS(
+                                SVG_NS,
+                                "image"
+                            );
+                            image.setAttribute("href", canvas.toDataURL());
+                            image.setAttribute("x", canvas.parentNode.offsetLeft);
+                            image.setAttribute("y", canvas.parentNode.offsetTop);
+                            image.setAttribute(
+                                "width",
+                                canvas.width / window.devicePixelRatio
+                            );
+                            image.setAttribute(
+                                "height",
+                                canvas.height / window.devicePixelRatio
+                            );
+
+                            root.appendChild(image);
+                        }
+
+                        const xml = new XMLSerializer().serializeToString(root);
+                        return new Blob([xml], {type: "image/svg+xml"});
+                    }
+
+                    async render(width, height) {
                         var canvas = document.createElement("canvas");
                         var container =
                             this.shadowRoot.querySelector("#container");
-                        canvas.width = container.offsetWidth;
-                        canvas.height = container.offsetHeight;
+                        canvas.width = width || container.offsetWidth;
+                        canvas.height = height || container.offsetHeight;
 
                         const context = canvas.getContext("2d");
+                        context.scale(
+                            canvas.width / container.offsetWidth,
+                            canvas.height / container.offsetHeight
+                        );
+
                         context.fillStyle =
                             window
                                 .getComputedStyle(this)
                                 .getPropertyValue("--plugin--background") ||
                             "white";
-                        context.fillRect(0, 0, canvas.width, canvas.height);
+                        context.fillRect(
+                            0,
+                            0,
+                            container.offsetWidth,
+                            container.offsetHeight
+                        );
                         const text_color = window
                             .getComputedStyle(this)
                             .getPropertyValue("color");
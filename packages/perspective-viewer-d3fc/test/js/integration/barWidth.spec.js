@@ -66,6 +66,12 @@ utils.with_server({}, () => {
                             settings: false,
                             expressions: [],
                             theme: "Material Light",
+                            header_height: null,
+                            row_height: null,
+                            settings_width: null,
+                            pinned_columns: [],
+                            type_defaults: {},
+                            density: "comfortable",
                         });
 
                         return await get_contents("ybar")(page);
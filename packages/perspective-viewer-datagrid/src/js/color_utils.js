@@ -55,3 +55,29 @@ export function make_color_record(color) {
         `rgba(${rgb[0]},${rgb[1]},${rgb[2]},0)`,
     ];
 }
+
+/**
+ * Convert the color `fields` of a column config to color records in place.
+ * A color `chroma-js` cannot parse is dropped with a warning, so the column
+ * falls back to the theme's default color rather than failing to render.
+ *
+ * @param {*} config A column config, e.g. from `restore()`.
+ * @param {string[]} fields The names of `config`'s color fields.
+ */
+export function make_color_records(config, fields) {
+    for (const field of fields) {
+        if (!config[field]) {
+            continue;
+        }
+
+        try {
+            config[field] = make_color_record(config[field]);
+        } catch (e) {
+            console.warn(
+                `Invalid color "${config[field]}" for "${field}", using default`
+            );
+
+            delete config[field];
+        }
+    }
+}
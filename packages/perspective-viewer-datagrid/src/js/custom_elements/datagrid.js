@@ -12,6 +12,9 @@ import {restore} from "../plugin/restore.js";
 import {connectedCallback} from "../plugin/connected";
 import {save} from "../plugin/save";
 import {draw} from "../plugin/draw";
+import {scroll_to, set_highlight} from "../plugin/scroll_to.js";
+import {get_selection, set_selection} from "../plugin/selection.js";
+import {set_pinned_columns} from "../plugin/pinned_columns.js";
 
 /**
  * The custom element class for this plugin.  The interface methods for this
@@ -87,6 +90,31 @@ export class HTMLPerspectiveViewerDatagridPluginElement extends HTMLElement {
         return restore.call(this, token);
     }
 
+    async scroll_to(target) {
+        return await scroll_to.call(this, target);
+    }
+
+    set_highlight(target) {
+        set_highlight.call(this, target);
+    }
+
+    get_selection() {
+        return get_selection.call(this);
+    }
+
+    set_selection(selection) {
+        set_selection.call(this, selection);
+    }
+
+    set_pinned_columns(columns) {
+        set_pinned_columns.call(this, columns);
+    }
+
+    set_row_sizing({row_height, header_height}) {
+        set_height(this.regular_table, "row", row_height);
+        set_height(this.regular_table, "header", header_height);
+    }
+
     async restyle(view) {
         await this.draw(view);
     }
@@ -98,3 +126,17 @@ export class HTMLPerspectiveViewerDatagridPluginElement extends HTMLElement {
         this.regular_table.clear();
     }
 }
+
+/**
+ * Set (or with `null`, clear) a `--datagrid-<name>--height` size override,
+ * which `regular_table.less` applies while the `<name>-height` class is set.
+ */
+function set_height(regular_table, name, height) {
+    const is_set = typeof height === "number";
+    regular_table.classList.toggle(`${name}-height`, is_set);
+    if (is_set) {
+        regular_table.style.setProperty(`--datagrid-${name}--height`, `${height}px`);
+    } else {
+        regular_table.style.removeProperty(`--datagrid-${name}--height`);
+    }
+}
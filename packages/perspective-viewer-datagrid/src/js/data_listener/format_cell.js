@@ -8,6 +8,7 @@
  */
 
 import {FormatterCache} from "./formatter_cache";
+import {with_type_defaults} from "../model/type_defaults.js";
 
 const FORMAT_CACHE = new FormatterCache();
 
@@ -26,17 +27,19 @@ export function format_cell(
     plugins = {},
     use_table_schema = false
 ) {
-    if (val === null) {
-        return "-";
-    }
-
     // TODO don't do this on every cell render ...
     const title = parts[parts.length - 1];
     const type =
         (use_table_schema && this._table_schema[title]) ||
         this._schema[title] ||
         "string";
-    const plugin = plugins[title] || {};
+    const plugin = with_type_defaults.call(this, type, plugins[title]) || {};
+    if (val === null) {
+        return (type === "string" && plugin.null_display) || "-";
+    } else if (val === "" && type === "string" && plugin.empty_display) {
+        return plugin.empty_display;
+    }
+
     const is_numeric = type === "integer" || type === "float";
     if (is_numeric && plugin?.number_fg_mode === "bar") {
         const a = Math.max(
@@ -68,6 +71,59 @@ export function format_cell(
         return anchor;
     } else {
         const formatter = FORMAT_CACHE.get(type, plugin);
-        return formatter ? formatter.format(val) : val;
+        if (is_numeric && plugin.hide_zeros && is_zero(formatter, plugin, val)) {
+            return "";
+        }
+
+        const formatted = formatter ? formatter.format(val) : val;
+        const rule = is_numeric && match_icon_rule(plugin.icon_rules, val);
+        return rule ? `${rule.icon} ${formatted}` : formatted;
     }
 }
+
+/**
+ * Whether a number column's `val` would display as zero, and should be hidden
+ * when `hide_zeros` is set:  exactly `0` or `-0`, or in `"standard"` notation
+ * any value which rounds to zero at the formatter's precision (e.g. `0.004`
+ * at 2 digits).  Must match `NumberColumnStyleConfig::is_hidden_zero()`.
+ *
+ * @param {*} formatter
+ * @param {*} plugin
+ * @param {*} val
+ * @returns
+ */
+function is_zero(formatter, plugin, val) {
+    if (val === 0) {
+        return true;
+    } else if ((plugin.notation || "standard") !== "standard") {
+        return false;
+    }
+
+    const {maximumFractionDigits} = formatter.resolvedOptions();
+    return Math.abs(val) < 0.5 * 10 ** -maximumFractionDigits;
+}
+
+/**
+ * Find the first of a number column's `icon_rules` whose comparison holds for
+ * `val`.
+ *
+ * @param {*} icon_rules
+ * @param {*} val
+ * @returns
+ */
+function match_icon_rule(icon_rules, val) {
+    return icon_rules?.find(({op, threshold}) => {
+        switch (op) {
+            case ">":
+                return val > threshold;
+            case ">=":
+                return val >= threshold;
+            case "<":
+                return val < threshold;
+            case "<=":
+                return val <= threshold;
+            case "==":
+                return val === threshold;
+        }
+    });
+}
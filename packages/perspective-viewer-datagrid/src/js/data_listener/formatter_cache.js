@@ -8,6 +8,7 @@
  */
 
 import {get_type_config} from "@finos/perspective/src/js/config/index.js";
+import {StrftimeFormat} from "./strftime_format.js";
 
 const FORMATTER_CONS = {
     datetime: Intl.DateTimeFormat,
@@ -28,6 +29,15 @@ export class FormatterCache {
 
     create_datetime_formatter(type, plugin) {
         const type_config = get_type_config(type);
+        if (plugin.custom_format !== undefined || plugin.week_numbers) {
+            // Matches the column style menu's preview, which also defaults to
+            // an ISO date when only `week_numbers` is set.
+            return new StrftimeFormat(plugin.custom_format || "%Y-%m-%d", {
+                timeZone: plugin.timeZone,
+                week_numbers: plugin.week_numbers,
+            });
+        }
+
         const options = {
             ...type_config.format,
             timeZone: plugin.timeZone,
@@ -57,6 +67,18 @@ export class FormatterCache {
             format.maximumFractionDigits = plugin.fixed;
         }
 
+        if (plugin.notation !== undefined) {
+            format.notation = plugin.notation;
+        }
+
+        if (plugin.locale !== undefined) {
+            try {
+                return new FORMATTER_CONS[type]([plugin.locale], format);
+            } catch (e) {
+                console.warn(`Invalid locale "${plugin.locale}"`);
+            }
+        }
+
         return new FORMATTER_CONS[type]([], format);
     }
 
@@ -69,9 +91,13 @@ export class FormatterCache {
         let formatter_key = [
             type,
             plugin.fixed,
+            plugin.notation,
+            plugin.locale,
             plugin.timeZone,
             plugin.dateStyle,
             plugin.timeStyle,
+            plugin.custom_format,
+            plugin.week_numbers,
         ].join("-");
 
         if (!this._formatters.has(formatter_key)) {
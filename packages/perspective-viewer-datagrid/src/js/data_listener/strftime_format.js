@@ -0,0 +1,158 @@
+/******************************************************************************
+ *
+ * Copyright (c) 2017, the Perspective Authors.
+ *
+ * This file is part of the Perspective library, distributed under the terms of
+ * the Apache License 2.0.  The full license can be found in the LICENSE file.
+ *
+ */
+
+const PAD = (x, n = 2, c = "0") => `${x}`.padStart(n, c);
+
+/**
+ * The ISO 8601 week-numbering year and week of a calendar date, e.g.
+ * `2021-01-01` is in week 53 of 2020.  Matches chrono's `iso_week()`, which
+ * the `<perspective-viewer>` column style menu uses for its preview.
+ *
+ * @param {*} year
+ * @param {*} month 1-indexed
+ * @param {*} day
+ * @returns
+ */
+function iso_week(year, month, day) {
+    const date = new Date(Date.UTC(year, month - 1, day));
+    const weekday = date.getUTCDay() || 7;
+    date.setUTCDate(date.getUTCDate() + 4 - weekday);
+    const iso_year = date.getUTCFullYear();
+    const jan1 = Date.UTC(iso_year, 0, 1);
+    const week = Math.ceil(((date - jan1) / 86400000 + 1) / 7);
+    return {iso_year, week};
+}
+
+/**
+ * A formatter for a date/datetime column's `custom_format`, a `strftime`-style
+ * format string as accepted by the column style menu, which only accepts the
+ * specifiers implemented here.  Implements the `format()` method of
+ * `Intl.DateTimeFormat` so it can be cached by `FormatterCache` alongside the
+ * `Intl` formatters.
+ */
+export class StrftimeFormat {
+    constructor(format, {timeZone, week_numbers} = {}) {
+        this._format = format;
+        this._week_numbers = week_numbers;
+        this._parts = new Intl.DateTimeFormat("en-US", {
+            timeZone,
+            hourCycle: "h23",
+            year: "numeric",
+            month: "numeric",
+            day: "numeric",
+            hour: "numeric",
+            minute: "numeric",
+            second: "numeric",
+            weekday: "long",
+        });
+
+        this._month_names = new Intl.DateTimeFormat("en-US", {
+            timeZone,
+            month: "long",
+        });
+    }
+
+    fields(val) {
+        const fields = {};
+        for (const {type, value} of this._parts.formatToParts(val)) {
+            fields[type] = value;
+        }
+
+        const month_name = this._month_names.format(val);
+        const year = parseInt(fields.year);
+        const month = parseInt(fields.month);
+        const day = parseInt(fields.day);
+        const hour = parseInt(fields.hour) % 24;
+        const date = Date.UTC(year, month - 1, day);
+        const ordinal = Math.round((date - Date.UTC(year, 0, 1)) / 86400000);
+        return {
+            year,
+            month,
+            day,
+            hour,
+            minute: parseInt(fields.minute),
+            second: parseInt(fields.second),
+            weekday: fields.weekday,
+            month_name,
+            ordinal: ordinal + 1,
+            dow: new Date(date).getUTCDay(),
+            ...iso_week(year, month, day),
+        };
+    }
+
+    format(val) {
+        const f = this.fields(val);
+        const hour12 = f.hour % 12 || 12;
+        const text = this._format.replace(/%([%a-zA-Z])/g, (token, x) => {
+            switch (x) {
+                case "%":
+                    return "%";
+                case "Y":
+                    return `${f.year}`;
+                case "C":
+                    return PAD(Math.floor(f.year / 100));
+                case "y":
+                    return PAD(f.year % 100);
+                case "m":
+                    return PAD(f.month);
+                case "b":
+                case "h":
+                    return f.month_name.slice(0, 3);
+                case "B":
+                    return f.month_name;
+                case "d":
+                    return PAD(f.day);
+                case "e":
+                    return PAD(f.day, 2, " ");
+                case "j":
+                    return PAD(f.ordinal, 3);
+                case "a":
+                    return f.weekday.slice(0, 3);
+                case "A":
+                    return f.weekday;
+                case "u":
+                    return `${f.dow || 7}`;
+                case "w":
+                    return `${f.dow}`;
+                case "H":
+                    return PAD(f.hour);
+                case "k":
+                    return PAD(f.hour, 2, " ");
+                case "I":
+                    return PAD(hour12);
+                case "l":
+                    return PAD(hour12, 2, " ");
+                case "M":
+                    return PAD(f.minute);
+                case "S":
+                    return PAD(f.second);
+                case "p":
+                    return f.hour < 12 ? "AM" : "PM";
+                case "P":
+                    return f.hour < 12 ? "am" : "pm";
+                case "F":
+                    return `${f.year}-${PAD(f.month)}-${PAD(f.day)}`;
+                case "D":
+                    return `${PAD(f.month)}/${PAD(f.day)}/${PAD(f.year % 100)}`;
+                case "T":
+                    return `${PAD(f.hour)}:${PAD(f.minute)}:${PAD(f.second)}`;
+                case "R":
+                    return `${PAD(f.hour)}:${PAD(f.minute)}`;
+                case "V":
+                    return PAD(f.week);
+                case "G":
+                    return `${f.iso_year}`;
+                default:
+                    return token;
+            }
+        });
+
+        return this._week_numbers ? `${text} (W${PAD(f.week)})` : text;
+    }
+}
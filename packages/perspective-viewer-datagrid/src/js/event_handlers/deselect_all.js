@@ -13,8 +13,10 @@ export async function deselect_all_listener(
     selected_rows_map
 ) {
     selected_rows_map.delete(regularTable);
+    this._selection = undefined;
     for (const td of regularTable.querySelectorAll("td,th")) {
         td.classList.toggle("psp-row-selected", false);
         td.classList.toggle("psp-row-subselected", false);
+        td.classList.toggle("psp-selected", false);
     }
 }
@@ -37,8 +37,10 @@ export async function selectionListener(
         let filter = [];
         if (is_deselect) {
             selected_rows_map.delete(regularTable);
+            this._selection = undefined;
         } else {
             selected_rows_map.set(regularTable, id);
+            this._selection = {rows: new Set([meta.y])};
             filter = await getCellConfig(this, meta.y, meta.x);
             filter = filter.config.filter;
         }
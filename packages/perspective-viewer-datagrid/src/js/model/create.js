@@ -117,6 +117,14 @@ export async function createModel(regular, table, view, extend = {}) {
         _is_editable.push(!!table_schema[column]);
     }
 
+    // Sparkline history is keyed by row and column paths, which are only
+    // meaningful for the `View` config they were recorded under.
+    const is_same_config =
+        JSON.stringify(extend._config) === JSON.stringify(config);
+
+    const _sparkline_history =
+        (is_same_config && extend._sparkline_history) || new Map();
+
     const model = Object.assign(extend, {
         _edit_port,
         _view: view,
@@ -128,6 +136,7 @@ export async function createModel(regular, table, view, extend = {}) {
         _ids: [],
         _open_column_styles_menu: [],
         _plugin_background,
+        _reduced_motion: !!this.reduced_motion,
         _color,
         _pos_fg_color,
         _neg_fg_color,
@@ -141,17 +150,28 @@ export async function createModel(regular, table, view, extend = {}) {
         }),
         _series_color_map: new Map(),
         _series_color_seed: new Map(),
+        _sparkline_history,
+        _pinned_columns: this._pinned_columns || [],
         get_psp_type,
     });
 
+    // Pinned columns must be rendered even when scrolled past, so they
+    // disable horizontal virtualization.
+    let virtual_mode =
+        window
+            .getComputedStyle(regular)
+            .getPropertyValue("--datagrid-virtual-mode")
+            ?.trim() || "both";
+
+    if (model._pinned_columns.length > 0) {
+        const no_horizontal = {both: "vertical", horizontal: "none"};
+        virtual_mode = no_horizontal[virtual_mode] || virtual_mode;
+    }
+
     // Re-use div factory
     model._div_factory = model._div_factory || new ElemFactory("div");
     regular.setDataListener(createDataListener().bind(model, regular), {
-        virtual_mode:
-            window
-                .getComputedStyle(regular)
-                .getPropertyValue("--datagrid-virtual-mode")
-                ?.trim() || "both",
+        virtual_mode,
     });
 
     return model;
@@ -0,0 +1,49 @@
+/******************************************************************************
+ *
+ * Copyright (c) 2017, the Perspective Authors.
+ *
+ * This file is part of the Perspective library, distributed under the terms of
+ * the Apache License 2.0.  The full license can be found in the LICENSE file.
+ *
+ */
+
+import {make_color_records} from "../color_utils.js";
+
+const COLOR_FIELDS = [
+    "pos_fg_color",
+    "neg_fg_color",
+    "pos_bg_color",
+    "neg_bg_color",
+    "color",
+    "bg_color",
+];
+
+/**
+ * Read the column style defaults set via the viewer's `setTypeDefaults()`.
+ * The style menu uses these as-is, while cell styles need their colors as
+ * records, so both forms are stored on the model.
+ *
+ * @param {*} viewer The `<perspective-viewer>` hosting this plugin.
+ */
+export function load_type_defaults(viewer) {
+    this._type_defaults = viewer?.getTypeDefaults?.() || {};
+    this._type_style_defaults = {};
+    for (const [type, config] of Object.entries(this._type_defaults)) {
+        const style = {...config};
+        make_color_records(style, COLOR_FIELDS);
+
+        this._type_style_defaults[type] = style;
+    }
+}
+
+/**
+ * Merge a column's style config over the style defaults for its type.
+ *
+ * @param {*} type The column's type.
+ * @param {*} config The column's own style config, if any.
+ * @returns The merged config, or `config` if there are no type defaults.
+ */
+export function with_type_defaults(type, config) {
+    const defaults = this._type_style_defaults?.[type];
+    return defaults ? {...defaults, ...config} : config;
+}
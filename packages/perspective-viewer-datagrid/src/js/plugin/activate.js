@@ -20,8 +20,13 @@ import {focus_style_listener} from "../style_handlers/focus.js";
 import {focusinListener, focusoutListener} from "../event_handlers/focus.js";
 import {keydownListener, clickListener} from "../event_handlers/edit_click.js";
 
+import {pinned_columns_style_listener} from "../style_handlers/pinned_columns";
+
 import {selectionListener} from "../event_handlers/row_select_click";
-import {selectionStyleListener} from "../style_handlers/selection";
+import {
+    selectionStyleListener,
+    selected_rows_style_listener,
+} from "../style_handlers/selection";
 import {deselect_all_listener} from "../event_handlers/deselect_all.js";
 
 import {createModel} from "../model/create.js";
@@ -55,6 +60,10 @@ export async function activate(view) {
             column_header_style_listener.bind(this.model, this.regular_table)
         );
 
+        this.regular_table.addStyleListener(
+            pinned_columns_style_listener.bind(this.model, this.regular_table)
+        );
+
         this.regular_table.addEventListener(
             "click",
             click_listener.bind(this.model, this.regular_table)
@@ -86,6 +95,10 @@ export async function activate(view) {
             )
         );
 
+        this.regular_table.addStyleListener(
+            selected_rows_style_listener.bind(this.model, this.regular_table)
+        );
+
         this.regular_table.addEventListener(
             "psp-deselect-all",
             deselect_all_listener.bind(
@@ -9,6 +9,13 @@
 
 import {restore_column_size_overrides} from "../model/column_overrides.js";
 import {save_column_size_overrides} from "../model/column_overrides.js";
+import {load_type_defaults} from "../model/type_defaults.js";
+
+// Used by `print_mode` when no row has been rendered yet to measure.
+const DEFAULT_ROW_HEIGHT = 19;
+
+// Matches the `psp-redraw-fade` animation duration in `regular_table.less`.
+const REDRAW_FADE_MS = 300;
 
 /**
  * Draw this datagrid instance.
@@ -25,7 +32,9 @@ export async function draw(view) {
         return;
     }
 
+    load_type_defaults.call(this.model, this.parentElement);
     const old_sizes = save_column_size_overrides.call(this);
+    set_print_height.call(this);
     const draw = this.regular_table.draw({invalid_columns: true});
     if (!this.model._preserve_focus_state) {
         this.regular_table.scrollTop = 0;
@@ -40,6 +49,7 @@ export async function draw(view) {
 
     restore_column_size_overrides.call(this, old_sizes);
     await draw;
+    play_redraw_fade.call(this);
 
     this._toolbar.classList.toggle(
         "aggregated",
@@ -47,3 +57,39 @@ export async function draw(view) {
             this.model._config.split_by.length > 0
     );
 }
+
+/**
+ * In `print_mode`, size the `<regular-table>` to fit every row so it renders
+ * the whole `View` rather than only the rows in the viewport.
+ */
+function set_print_height() {
+    if (this.print_mode) {
+        const table = this.regular_table;
+        const row_height =
+            table.querySelector("tbody tr")?.offsetHeight || DEFAULT_ROW_HEIGHT;
+        const header_height = table.querySelector("thead")?.offsetHeight || 0;
+        const height = header_height + row_height * this.model._num_rows;
+        table.style.height = `${height}px`;
+    } else {
+        this.regular_table.style.height = "";
+    }
+}
+
+/**
+ * When `<perspective-viewer>` sets `redraw_fade`, fade the newly drawn cells
+ * in over the previous render, removing the class afterwards so cells drawn
+ * by scrolling are not animated.
+ */
+function play_redraw_fade() {
+    const table = this.regular_table;
+    clearTimeout(this._redraw_fade_timeout);
+    table.classList.remove("psp-redraw-fade");
+    if (this.redraw_fade && !this.print_mode) {
+        // Force a reflow so the animation restarts on consecutive draws.
+        void table.offsetWidth;
+        table.classList.add("psp-redraw-fade");
+        this._redraw_fade_timeout = setTimeout(() => {
+            table.classList.remove("psp-redraw-fade");
+        }, REDRAW_FADE_MS);
+    }
+}
@@ -0,0 +1,18 @@
+/******************************************************************************
+ *
+ * Copyright (c) 2017, the Perspective Authors.
+ *
+ * This file is part of the Perspective library, distributed under the terms of
+ * the Apache License 2.0.  The full license can be found in the LICENSE file.
+ *
+ */
+
+/**
+ * Set the columns to freeze to the left during horizontal scroll.
+ * `<perspective-viewer>` calls this before each `draw()`, which applies it.
+ *
+ * @param {string[]} columns
+ */
+export function set_pinned_columns(columns) {
+    this._pinned_columns = columns;
+}
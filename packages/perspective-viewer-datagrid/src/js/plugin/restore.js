@@ -10,7 +10,7 @@
 import {restore_column_size_overrides} from "../model/column_overrides.js";
 import {toggle_edit_mode, toggle_scroll_lock} from "../model/toolbar.js";
 import {PRIVATE_PLUGIN_SYMBOL} from "../model";
-import {make_color_record} from "../color_utils.js";
+import {make_color_records} from "../color_utils.js";
 
 /**
  * Restore this plugin's state from a previously saved `token`.
@@ -28,27 +28,14 @@ export function restore(token) {
                 delete col_config["column_size_override"];
             }
 
-            if (col_config?.pos_fg_color) {
-                col_config.pos_fg_color = make_color_record(
-                    col_config.pos_fg_color
-                );
-                col_config.neg_fg_color = make_color_record(
-                    col_config.neg_fg_color
-                );
-            }
-
-            if (col_config?.pos_bg_color) {
-                col_config.pos_bg_color = make_color_record(
-                    col_config.pos_bg_color
-                );
-                col_config.neg_bg_color = make_color_record(
-                    col_config.neg_bg_color
-                );
-            }
-
-            if (col_config?.color) {
-                col_config.color = make_color_record(col_config.color);
-            }
+            // With `color_sides`, either color of a pair may be omitted.
+            make_color_records(col_config, [
+                "pos_fg_color",
+                "neg_fg_color",
+                "pos_bg_color",
+                "neg_bg_color",
+                "color",
+            ]);
 
             if (Object.keys(col_config).length === 0) {
                 delete token.columns[col];
@@ -72,6 +59,14 @@ export function restore(token) {
         // TODO fix this regular-table API
     }
 
+    // Columns whose style was cleared also forget their style menu state.
+    const ui_state = this.model?._column_style_ui_state || {};
+    for (const col of Object.keys(ui_state)) {
+        if (!token.columns?.[col]) {
+            delete ui_state[col];
+        }
+    }
+
     restore_column_size_overrides.call(this, overrides, true);
     datagrid[PRIVATE_PLUGIN_SYMBOL] = token.columns;
 }
@@ -29,7 +29,12 @@ export function save() {
                 {},
                 datagrid[PRIVATE_PLUGIN_SYMBOL][col]
             );
-            if (config?.pos_fg_color || config?.pos_bg_color) {
+            if (
+                config?.pos_fg_color ||
+                config?.pos_bg_color ||
+                config?.neg_fg_color ||
+                config?.neg_bg_color
+            ) {
                 config.pos_fg_color = config.pos_fg_color?.[0];
                 config.neg_fg_color = config.neg_fg_color?.[0];
                 config.pos_bg_color = config.pos_bg_color?.[0];
@@ -0,0 +1,64 @@
+/******************************************************************************
+ *
+ * Copyright (c) 2017, the Perspective Authors.
+ *
+ * This file is part of the Perspective library, distributed under the terms of
+ * the Apache License 2.0.  The full license can be found in the LICENSE file.
+ *
+ */
+
+/**
+ * Whether the column path `path` is `column`, either as a full `split_by`
+ * path or as a column name under any `split_by` group.
+ *
+ * @param {string} path
+ * @param {string} column
+ * @returns
+ */
+export function is_column_path(path, column) {
+    if (path === column) {
+        return true;
+    }
+
+    const parts = path.split("|");
+    return parts[parts.length - 1] === column;
+}
+
+/**
+ * Scroll `row` and, if provided, the first column matching `column` into
+ * view.  `<perspective-viewer>` clamps `row` to the `View`'s rows before
+ * calling this.
+ *
+ * @param {*} target
+ * @returns
+ */
+export async function scroll_to({row, column}) {
+    if (!this.model) {
+        return;
+    }
+
+    const {_column_paths, _num_rows} = this.model;
+    const x = _column_paths.findIndex((path) => is_column_path(path, column));
+
+    await this.regular_table.scrollToCell(
+        Math.max(x, 0),
+        row,
+        _column_paths.length,
+        _num_rows
+    );
+}
+
+/**
+ * Highlight the cell at `target` (e.g. the current `search()` match), or
+ * clear the highlight if `target` is `null`.
+ *
+ * @param {*} target
+ */
+export function set_highlight(target) {
+    if (!this.model) {
+        return;
+    }
+
+    this.model._highlight = target;
+    this.regular_table.draw({preserve_width: true});
+}
@@ -0,0 +1,45 @@
+/******************************************************************************
+ *
+ * Copyright (c) 2017, the Perspective Authors.
+ *
+ * This file is part of the Perspective library, distributed under the terms of
+ * the Apache License 2.0.  The full license can be found in the LICENSE file.
+ *
+ */
+
+/**
+ * The current selection, as row indices into the `View` and optionally
+ * column names, or `undefined` if nothing is selected.  Set by
+ * `set_selection()`, or by clicking a row when the `<perspective-viewer>` is
+ * `selectable`, and cleared by the next `draw()` of a new `View`.
+ *
+ * @returns
+ */
+export function get_selection() {
+    const selection = this.model?._selection;
+    if (!selection) {
+        return undefined;
+    }
+
+    return {
+        rows: [...selection.rows],
+        ...(selection.columns ? {columns: [...selection.columns]} : {}),
+    };
+}
+
+/**
+ * Replace the current selection, in the format returned by
+ * `get_selection()`.  An empty `rows` clears the selection.
+ *
+ * @param {*} selection
+ */
+export function set_selection({rows, columns}) {
+    if (!this.model) {
+        return;
+    }
+
+    this.model._selection =
+        rows.length > 0 ? {rows: new Set(rows), columns} : undefined;
+
+    this.regular_table.draw({preserve_width: true});
+}
@@ -0,0 +1,46 @@
+/******************************************************************************
+ *
+ * Copyright (c) 2017, the Perspective Authors.
+ *
+ * This file is part of the Perspective library, distributed under the terms of
+ * the Apache License 2.0.  The full license can be found in the LICENSE file.
+ *
+ */
+
+import {is_column_path} from "../plugin/scroll_to.js";
+
+/**
+ * Freeze the columns set via `set_pinned_columns()` with `position: sticky`,
+ * each offset by the width of the row headers and the pinned columns to its
+ * left.  `createModel()` disables horizontal virtualization while columns
+ * are pinned, so every pinned column is rendered however far the table is
+ * scrolled.
+ *
+ * @param {*} regularTable
+ */
+export function pinned_columns_style_listener(regularTable) {
+    const pinned = this._pinned_columns;
+    for (const tr of regularTable.querySelectorAll("thead tr,tbody tr")) {
+        let left = 0;
+        for (const cell of tr.children) {
+            const meta = regularTable.getMeta(cell);
+            const is_column = meta?.x !== undefined && meta.x >= 0;
+            const is_pinned =
+                is_column &&
+                pinned.some((column) =>
+                    is_column_path(this._column_paths[meta.x], column)
+                );
+
+            cell.classList.toggle("psp-pinned", is_pinned);
+            if (is_pinned) {
+                cell.style.left = `${left}px`;
+                left += cell.offsetWidth;
+            } else {
+                cell.style.left = "";
+                if (!is_column) {
+                    left += cell.offsetWidth;
+                }
+            }
+        }
+    }
+}
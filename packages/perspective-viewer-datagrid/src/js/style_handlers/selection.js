@@ -7,6 +7,8 @@
  *
  */
 
+import {is_column_path} from "../plugin/scroll_to.js";
+
 export function selectionStyleListener(
     regularTable,
     viewer,
@@ -60,6 +62,30 @@ export function selectionStyleListener(
     }
 }
 
+/**
+ * Style the cells of the rows (and, if set, columns) selected via
+ * `set_selection()`.  Unlike `selectionStyleListener`, this does not require
+ * the `selectable` attribute.
+ *
+ * @param {*} regularTable
+ */
+export function selected_rows_style_listener(regularTable) {
+    const selection = this._selection;
+    for (const td of regularTable.querySelectorAll("tbody td,tbody th")) {
+        const meta = regularTable.getMeta(td);
+        const is_selected =
+            !!selection &&
+            selection.rows.has(meta.y) &&
+            (!selection.columns ||
+                (meta.x >= 0 &&
+                    selection.columns.some((column) =>
+                        is_column_path(this._column_paths[meta.x], column)
+                    )));
+
+        td.classList.toggle("psp-selected", is_selected);
+    }
+}
+
 //  export function configureRowSelectable(table, viewer) {
 //      table.addStyleListener(selectionStyleListener.bind(this, table, viewer));
 //      table.addEventListener(
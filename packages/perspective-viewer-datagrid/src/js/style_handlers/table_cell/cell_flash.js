@@ -7,12 +7,16 @@
  *
  */
 
+export const DEFAULT_PULSE_DURATION_MS = 500;
+
 export function style_cell_flash(
     metadata,
     td,
     [, , , , , pos_s, pos_e],
-    [, , , , , neg_s, neg_e]
+    [, , , , , neg_s, neg_e],
+    duration_ms = DEFAULT_PULSE_DURATION_MS
 ) {
+    const duration = `${duration_ms / 1000}s`;
     const id = this._ids?.[metadata.dy]?.join("|");
     const metadata_path = metadata.column_header.join("|");
     if (
@@ -27,17 +31,17 @@ export function style_cell_flash(
             td.style.setProperty("--pulse--background-color-start", neg_s);
             td.style.setProperty("--pulse--background-color-end", neg_e);
             if (td.style.animationName === "pulse_neg") {
-                td.style.animation = "pulse_neg2 0.5s linear";
+                td.style.animation = `pulse_neg2 ${duration} linear`;
             } else {
-                td.style.animation = "pulse_neg 0.5s linear";
+                td.style.animation = `pulse_neg ${duration} linear`;
             }
         } else if (this.last_meta?.[col_idx]?.[row_idx] < metadata.user) {
             td.style.setProperty("--pulse--background-color-start", pos_s);
             td.style.setProperty("--pulse--background-color-end", pos_e);
             if (td.style.animationName === "pulse_pos") {
-                td.style.animation = "pulse_pos2 0.5s linear";
+                td.style.animation = `pulse_pos2 ${duration} linear`;
             } else {
-                td.style.animation = "pulse_pos 0.5s linear";
+                td.style.animation = `pulse_pos ${duration} linear`;
             }
         } else if (row_idx !== metadata.dy) {
             td.style.animation = "";
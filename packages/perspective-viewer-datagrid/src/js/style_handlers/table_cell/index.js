@@ -8,12 +8,24 @@
  */
 
 import {PRIVATE_PLUGIN_SYMBOL} from "../../model";
+import {with_type_defaults} from "../../model/type_defaults.js";
 
 import {cell_style_numeric} from "./numeric.js";
 import {cell_style_string} from "./string.js";
 import {cell_style_datetime} from "./datetime.js";
 import {cell_style_boolean} from "./boolean.js";
 import {cell_style_row_header} from "./row_header.js";
+import {is_column_path} from "../../plugin/scroll_to.js";
+
+function is_highlight(metadata) {
+    const highlight = this._highlight;
+    return (
+        !!highlight &&
+        metadata.x >= 0 &&
+        metadata.y === highlight.row &&
+        is_column_path(this._column_paths[metadata.x], highlight.column)
+    );
+}
 
 function get_psp_type(metadata) {
     if (metadata.x >= 0) {
@@ -33,9 +45,15 @@ export function table_cell_style_listener(regularTable) {
                 metadata.column_header?.[metadata.column_header?.length - 1];
 
             let type = get_psp_type.call(this, metadata);
-            const plugin = plugins[column_name];
+            const plugin = with_type_defaults.call(
+                this,
+                type,
+                plugins[column_name]
+            );
             const is_numeric = type === "integer" || type === "float";
 
+            // Only set by a `"sparkline"` number column.
+            td.style.backgroundImage = "";
             if (is_numeric) {
                 cell_style_numeric.call(this, plugin, td, metadata);
             } else if (type === "boolean") {
@@ -65,6 +83,11 @@ export function table_cell_style_listener(regularTable) {
                 "psp-color-mode-bar",
                 plugin?.number_fg_mode === "bar" && is_numeric
             );
+
+            td.classList.toggle(
+                "psp-search-highlight",
+                is_highlight.call(this, metadata)
+            );
         }
     }
 }
@@ -8,14 +8,42 @@
  */
 
 import {style_cell_flash} from "./cell_flash.js";
+import {style_cell_sparkline} from "./sparkline.js";
+import chroma from "chroma-js";
 import {
     rgbaToRgb,
     infer_foreground_from_background,
 } from "../../color_utils.js";
 
+/**
+ * The `"bands"` background color of `value`, which is that of the band with
+ * the greatest `threshold` not above `value`, or `undefined` if `value` is
+ * below every threshold.
+ *
+ * @param {*} color_bands
+ * @param {number} value
+ * @returns
+ */
+function match_color_band(color_bands, value) {
+    let match;
+    for (const band of color_bands || []) {
+        if (
+            band.threshold <= value &&
+            (match === undefined || band.threshold > match.threshold)
+        ) {
+            match = band;
+        }
+    }
+
+    return match?.color;
+}
+
 export function cell_style_numeric(plugin, td, metadata) {
-    const is_positive = metadata.user > 0;
-    const is_negative = metadata.user < 0;
+    // Values of a sign disabled by `color_sides` are styled as if zero.
+    const is_positive =
+        metadata.user > 0 && plugin?.color_sides !== "negative_only";
+    const is_negative =
+        metadata.user < 0 && plugin?.color_sides !== "positive_only";
 
     let pos_bg_color;
     if (plugin?.pos_bg_color !== undefined) {
@@ -41,7 +69,10 @@ export function cell_style_numeric(plugin, td, metadata) {
         const [hex, r, g, b, _gradhex] = bg_tuple;
 
         td.style.position = "";
-        if (plugin?.number_bg_mode === "color") {
+        if (
+            plugin?.number_bg_mode === "color" ||
+            (plugin?.number_bg_mode === "pulse" && this._reduced_motion)
+        ) {
             td.style.animation = "";
             td.style.backgroundColor = hex;
         } else if (plugin?.number_bg_mode === "gradient") {
@@ -57,6 +88,10 @@ export function cell_style_numeric(plugin, td, metadata) {
             td.style.animation = "";
             td.style.color = foreground;
             td.style.backgroundColor = `rgba(${r},${g},${b},${a})`;
+        } else if (plugin?.number_bg_mode === "bands") {
+            td.style.animation = "";
+            td.style.backgroundColor =
+                match_color_band(plugin.color_bands, metadata.user) ?? "";
         } else if (plugin?.number_bg_mode === "pulse") {
             // TODO!
             style_cell_flash.call(
@@ -64,7 +99,8 @@ export function cell_style_numeric(plugin, td, metadata) {
                 metadata,
                 td,
                 pos_bg_color,
-                neg_bg_color
+                neg_bg_color,
+                plugin?.pulse_duration_ms
             );
             td.style.backgroundColor = "";
         } else if (
@@ -79,21 +115,11 @@ export function cell_style_numeric(plugin, td, metadata) {
         }
     }
 
-    const [hex, r, g, b, gradhex] = (() => {
-        if (plugin?.pos_fg_color !== undefined) {
-            return is_positive
-                ? plugin.pos_fg_color
-                : is_negative
-                ? plugin.neg_fg_color
-                : ["", ...this._plugin_background, ""];
-        } else {
-            return is_positive
-                ? this._pos_fg_color
-                : is_negative
-                ? this._neg_fg_color
-                : ["", ...this._plugin_background, ""];
-        }
-    })();
+    const [hex, r, g, b, gradhex] = is_positive
+        ? plugin?.pos_fg_color ?? this._pos_fg_color
+        : is_negative
+        ? plugin?.neg_fg_color ?? this._neg_fg_color
+        : ["", ...this._plugin_background, ""];
 
     if (plugin?.number_fg_mode === "disabled") {
         if (plugin?.number_bg_mode === "color") {
@@ -116,7 +142,43 @@ export function cell_style_numeric(plugin, td, metadata) {
         ) {
             td.children[0].style.background = gradhex;
         }
+    } else if (plugin?.number_fg_mode === "sparkline") {
+        td.style.color = "";
+        const [line_color] = plugin?.pos_fg_color ?? this._pos_fg_color;
+        style_cell_sparkline.call(this, metadata, td, line_color);
     } else if (plugin?.number_fg_mode === "color" || !plugin?.number_fg_mode) {
         td.style.color = hex;
     }
+
+    // Black or white text by the luminance of the filled background.
+    if (plugin?.auto_text_contrast && plugin?.number_bg_mode === "bands") {
+        const color = match_color_band(plugin.color_bands, metadata.user);
+        if (color) {
+            const [r, g, b] = chroma(color).rgb();
+            td.style.color = infer_foreground_from_background(
+                rgbaToRgb([r, g, b, 1], this._plugin_background)
+            );
+        } else {
+            td.style.color = "";
+        }
+    } else if (
+        plugin?.auto_text_contrast &&
+        (plugin?.number_bg_mode === "color" ||
+            plugin?.number_bg_mode === "gradient")
+    ) {
+        const a =
+            plugin.number_bg_mode === "gradient"
+                ? Math.max(
+                      0,
+                      Math.min(1, Math.abs(metadata.user / plugin.bg_gradient))
+                  )
+                : 1;
+
+        td.style.color = infer_foreground_from_background(
+            rgbaToRgb(
+                [bg_tuple[1], bg_tuple[2], bg_tuple[3], a],
+                this._plugin_background
+            )
+        );
+    }
 }
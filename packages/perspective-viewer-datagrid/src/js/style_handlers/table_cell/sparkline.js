@@ -0,0 +1,73 @@
+/******************************************************************************
+ *
+ * Copyright (c) 2017, the Perspective Authors.
+ *
+ * This file is part of the Perspective library, distributed under the terms of
+ * the Apache License 2.0.  The full license can be found in the LICENSE file.
+ *
+ */
+
+// The number of distinct values kept per cell.
+const SPARKLINE_LENGTH = 20;
+
+// The number of cells whose history is kept, evicting the least recently
+// drawn (e.g. long since scrolled out of the viewport) first.
+const SPARKLINE_MAX_CELLS = 10000;
+
+/**
+ * Record a cell's value in its history, keyed by its row group and column
+ * paths, and draw that history as a line in the cell's background.  History
+ * is cleared when the `View` config changes, and capped at
+ * `SPARKLINE_MAX_CELLS` cells.
+ *
+ * @param {*} metadata
+ * @param {*} td
+ * @param {*} color
+ */
+export function style_cell_sparkline(metadata, td, color) {
+    const key = JSON.stringify([metadata.row_header, metadata.column_header]);
+    const cache = this._sparkline_history;
+    let history = cache.get(key);
+    if (history === undefined) {
+        history = [];
+    } else {
+        // Re-insert so the `Map` iterates least recently drawn first.
+        cache.delete(key);
+    }
+
+    cache.set(key, history);
+    if (cache.size > SPARKLINE_MAX_CELLS) {
+        cache.delete(cache.keys().next().value);
+    }
+
+    const last = history[history.length - 1];
+    if (metadata.user !== null && last !== metadata.user) {
+        history.push(metadata.user);
+        if (history.length > SPARKLINE_LENGTH) {
+            history.shift();
+        }
+    }
+
+    td.style.backgroundImage = sparkline_url(history, color);
+    td.style.backgroundSize = "100% 100%";
+    td.style.backgroundRepeat = "no-repeat";
+}
+
+function sparkline_url(history, color) {
+    if (history.length < 2) {
+        return "";
+    }
+
+    const min = Math.min(...history);
+    const range = Math.max(...history) - min || 1;
+    const points = history
+        .map((val, idx) => {
+            const x = (idx / (history.length - 1)) * 100;
+            const y = 90 - ((val - min) / range) * 80;
+            return `${x.toFixed(2)},${y.toFixed(2)}`;
+        })
+        .join(" ");
+
+    const svg = `<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100" preserveAspectRatio="none"><polyline points="${points}" fill="none" stroke="${color}" stroke-width="1.5" vector-effect="non-scaling-stroke"/></svg>`;
+    return `url("data:image/svg+xml,${encodeURIComponent(svg)}")`;
+}
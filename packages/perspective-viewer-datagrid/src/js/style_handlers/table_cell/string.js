@@ -17,6 +17,8 @@ export function cell_style_string(plugin, td, metadata) {
     const column_name =
         metadata.column_header?.[metadata.column_header?.length - 1];
 
+    td.style.textAlign = plugin?.align || "";
+
     const [hex, r, g, b, gradhex] = (() => {
         if (plugin?.color !== undefined) {
             return plugin.color;
@@ -9,6 +9,7 @@
 
 import {make_color_record} from "./color_utils.js";
 import {PRIVATE_PLUGIN_SYMBOL} from "./model";
+import {DEFAULT_PULSE_DURATION_MS} from "./style_handlers/table_cell/cell_flash.js";
 
 export function activate_plugin_menu(regularTable, target, column_max) {
     const target_meta = regularTable.getMeta(target);
@@ -41,6 +42,7 @@ export function activate_plugin_menu(regularTable, target, column_max) {
             pos_bg_color: this._pos_bg_color[0],
             neg_bg_color: this._neg_bg_color[0],
             number_bg_mode: "disabled",
+            pulse_duration_ms: DEFAULT_PULSE_DURATION_MS,
         };
     } else {
         // date, datetime, string, boolean
@@ -66,17 +68,23 @@ export function activate_plugin_menu(regularTable, target, column_max) {
         return;
     }
 
+    // Type-level defaults from `setTypeDefaults()` replace the built-in
+    // defaults, and are themselves overridden by this column's config.
+    Object.assign(default_config, this._type_defaults?.[column_type]);
+
     const scroll_handler = () => MENU.blur();
     const update_handler = (event) => {
         const config = event.detail;
-        if (config.pos_fg_color) {
-            config.pos_fg_color = make_color_record(config.pos_fg_color);
-            config.neg_fg_color = make_color_record(config.neg_fg_color);
-        }
-
-        if (config.pos_bg_color) {
-            config.pos_bg_color = make_color_record(config.pos_bg_color);
-            config.neg_bg_color = make_color_record(config.neg_bg_color);
+        // With `color_sides`, either color of a pair may be omitted.
+        for (const key of [
+            "pos_fg_color",
+            "neg_fg_color",
+            "pos_bg_color",
+            "neg_bg_color",
+        ]) {
+            if (config[key]) {
+                config[key] = make_color_record(config[key]);
+            }
         }
 
         if (config.color) {
@@ -90,12 +98,24 @@ export function activate_plugin_menu(regularTable, target, column_max) {
         regularTable[PRIVATE_PLUGIN_SYMBOL] =
             regularTable[PRIVATE_PLUGIN_SYMBOL] || {};
         regularTable[PRIVATE_PLUGIN_SYMBOL][column_name] = config;
+        if (Object.keys(config).length === 0) {
+            delete this._column_style_ui_state[column_name];
+        }
+
         regularTable.draw({preserve_width: true});
         regularTable.parentElement.parentElement.dispatchEvent(
             new Event("perspective-config-update")
         );
     };
 
+    // Editor state which is not part of the column's config, e.g. the last
+    // selected (but since disabled) background mode, kept per column so
+    // re-opening the menu restores it.
+    this._column_style_ui_state = this._column_style_ui_state || {};
+    const ui_state_handler = (event) => {
+        this._column_style_ui_state[column_name] = event.detail;
+    };
+
     const blur_handler = async () => {
         regularTable.removeEventListener(
             "regular-table-scroll",
@@ -107,6 +127,11 @@ export function activate_plugin_menu(regularTable, target, column_max) {
             update_handler
         );
 
+        MENU.removeEventListener(
+            "perspective-column-style-ui-change",
+            ui_state_handler
+        );
+
         MENU.removeEventListener("blur", blur_handler);
         this._open_column_styles_menu.pop();
         regularTable.parentElement.parentElement.dispatchEvent(
@@ -118,6 +143,11 @@ export function activate_plugin_menu(regularTable, target, column_max) {
     };
 
     MENU.addEventListener("perspective-column-style-change", update_handler);
+    MENU.addEventListener(
+        "perspective-column-style-ui-change",
+        ui_state_handler
+    );
+
     MENU.addEventListener("blur", blur_handler);
     regularTable.addEventListener("regular-table-scroll", scroll_handler);
 
@@ -128,7 +158,12 @@ export function activate_plugin_menu(regularTable, target, column_max) {
         (pset[column_name] = pset[column_name] || {})
     );
 
-    if (config.pos_fg_color || config.pos_bg_color) {
+    if (
+        config.pos_fg_color ||
+        config.pos_bg_color ||
+        config.neg_fg_color ||
+        config.neg_bg_color
+    ) {
         config.pos_fg_color = config.pos_fg_color?.[0];
         config.neg_fg_color = config.neg_fg_color?.[0];
         config.pos_bg_color = config.pos_bg_color?.[0];
@@ -143,5 +178,10 @@ export function activate_plugin_menu(regularTable, target, column_max) {
         config.bg_color = config.bg_color[0];
     }
 
-    MENU.open(target, config, default_config);
+    MENU.open(
+        target,
+        config,
+        default_config,
+        this._column_style_ui_state[column_name]
+    );
 }
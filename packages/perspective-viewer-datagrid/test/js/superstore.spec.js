@@ -110,6 +110,46 @@ utils.with_server({}, () => {
                 }
             );
 
+            test.capture(
+                "date columns render with a custom format and week numbers",
+                async (page) => {
+                    const viewer = await page.waitForSelector(
+                        "perspective-viewer"
+                    );
+
+                    const text = await page.evaluate(async (viewer) => {
+                        await viewer.getTable();
+                        await viewer.restore({
+                            columns: ["Order Date"],
+                            plugin_config: {
+                                columns: {
+                                    "Order Date": {
+                                        custom_format: "%d %b %Y",
+                                        week_numbers: true,
+                                    },
+                                },
+                            },
+                        });
+
+                        return viewer.querySelector(
+                            "regular-table tbody tr:first-child td"
+                        ).textContent;
+                    }, viewer);
+
+                    expect(text).toMatch(
+                        /^\d{2} [A-Z][a-z]{2} \d{4} \(W\d{2}\)$/
+                    );
+
+                    // The formatted cells depend on the local timezone, so
+                    // capture the default grid instead.
+                    await page.evaluate(async (viewer) => {
+                        await viewer.reset();
+                    }, viewer);
+
+                    return get_contents(page);
+                }
+            );
+
             // test.capture("resets viewable area when the logical size expands.", async page => {
             //     const viewer = await page.$("perspective-viewer");
             //     await page.evaluate(async () => await document.querySelector("perspective-viewer").toggleConfig());
@@ -100,6 +100,24 @@ function tests(extract) {
             expect(config.viewers.One.settings).toEqual(false);
             delete config.viewers.One["settings"];
 
+            expect(config.viewers.One.density).toEqual("comfortable");
+            delete config.viewers.One.density;
+
+            expect(config.viewers.One.type_defaults).toEqual({});
+            delete config.viewers.One.type_defaults;
+
+            expect(config.viewers.One.pinned_columns).toEqual([]);
+            delete config.viewers.One.pinned_columns;
+
+            expect(config.viewers.One.settings_width).toEqual(null);
+            delete config.viewers.One.settings_width;
+
+            expect(config.viewers.One.row_height).toEqual(null);
+            delete config.viewers.One.row_height;
+
+            expect(config.viewers.One.header_height).toEqual(null);
+            delete config.viewers.One.header_height;
+
             expect(config).toEqual(current);
             expect(convert(old, {replace_defaults: true})).toEqual(current);
             return extract(page);
@@ -185,7 +185,13 @@ impl Component for ActiveColumn {
                     Some(ctx.props().dragdrop.get_drag_column().unwrap()),
                 )
             }
-            ActiveColumnState::Column(label, name) => (label.clone(), Some(name.to_owned())),
+            ActiveColumnState::Column(label, name) => {
+                if ctx.props().renderer.get_pinned_columns().contains(name) {
+                    classes.push("pinned");
+                }
+
+                (label.clone(), Some(name.to_owned()))
+            }
             ActiveColumnState::Required(label) => (label.clone(), None),
         };
 
@@ -117,8 +117,8 @@ impl AggregateSelector {
             .session
             .metadata()
             .get_column_aggregates(&ctx.props().column)
-            .expect("Bad Aggs")
-            .collect::<Vec<_>>();
+            .map(|x| x.collect::<Vec<_>>())
+            .unwrap_or_default();
 
         let multi_aggregates = aggregates
             .iter()
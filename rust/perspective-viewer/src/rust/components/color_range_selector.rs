@@ -6,22 +6,114 @@
 // of the Apache License 2.0.  The full license can be found in the LICENSE
 // file.
 
+use super::containers::select::*;
 use crate::*;
 
+use std::fmt::Display;
+use std::str::FromStr;
 use wasm_bindgen::JsCast;
 use web_sys::*;
 use yew::prelude::*;
 
+/// Curated `(pos, neg)` color pairs from the Okabe-Ito palette, which remain
+/// distinguishable for the named color vision deficiency, offered as seed
+/// values for the more common (and inaccessible) red/green.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ColorRangePreset {
+    Custom,
+    BlueOrange,
+    BlueYellow,
+    GreenPurple,
+}
+
+impl ColorRangePreset {
+    pub fn values() -> &'static [Self] {
+        &[
+            Self::Custom,
+            Self::BlueOrange,
+            Self::BlueYellow,
+            Self::GreenPurple,
+        ]
+    }
+
+    /// The `(pos, neg)` colors of this preset, or `None` for `Custom`.
+    pub const fn colors(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Self::Custom => None,
+            Self::BlueOrange => Some(("#0072b2", "#e69f00")),
+            Self::BlueYellow => Some(("#0072b2", "#f0e442")),
+            Self::GreenPurple => Some(("#009e73", "#cc79a7")),
+        }
+    }
+
+    /// The preset whose colors are exactly `pos_color` and `neg_color`, or
+    /// `Custom` if there is none.
+    pub fn from_colors(pos_color: &str, neg_color: &str) -> Self {
+        Self::values()
+            .iter()
+            .find(|x| {
+                x.colors().map_or(false, |(pos, neg)| {
+                    pos.eq_ignore_ascii_case(pos_color) && neg.eq_ignore_ascii_case(neg_color)
+                })
+            })
+            .cloned()
+            .unwrap_or(Self::Custom)
+    }
+}
+
+impl Display for ColorRangePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Self::Custom => "Custom",
+            Self::BlueOrange => "Blue/Orange (Deuteranopia)",
+            Self::BlueYellow => "Blue/Yellow (Protanopia)",
+            Self::GreenPurple => "Green/Purple (Tritanopia)",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+impl FromStr for ColorRangePreset {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::values()
+            .iter()
+            .find(|x| x.to_string() == s)
+            .cloned()
+            .ok_or_else(|| format!("Unknown ColorRangePreset::{}", s))
+    }
+}
+
 #[derive(Properties, PartialEq)]
 pub struct ColorRangeProps {
     pub pos_color: String,
     pub neg_color: String,
     pub on_pos_color: Callback<String>,
     pub on_neg_color: Callback<String>,
+
+    /// Whether to show the selector for the positive color.
+    #[prop_or(true)]
+    pub show_pos: bool,
+
+    /// Whether to show the selector for the negative color.
+    #[prop_or(true)]
+    pub show_neg: bool,
 }
 
 #[function_component(ColorRangeSelector)]
 pub fn color_chooser_component(props: &ColorRangeProps) -> Html {
+    let on_preset = {
+        clone!(props.on_pos_color, props.on_neg_color);
+        Callback::from(move |preset: ColorRangePreset| {
+            if let Some((pos, neg)) = preset.colors() {
+                on_pos_color.emit(pos.to_owned());
+                on_neg_color.emit(neg.to_owned());
+            }
+        })
+    };
+
+    let preset = ColorRangePreset::from_colors(&props.pos_color, &props.neg_color);
     let on_pos_color = props.on_pos_color.reform(|event: InputEvent| {
         event
             .target()
@@ -39,17 +131,27 @@ pub fn color_chooser_component(props: &ColorRangeProps) -> Html {
     });
 
     html_template! {
-        <input
-            id="color-param"
-            class="parameter"
-            type="color"
-            value={ props.pos_color.to_owned() }
-            oninput={ on_pos_color }/>
-        <input
-            id="neg-color-param"
-            class="parameter"
-            type="color"
-            value={ props.neg_color.to_owned() }
-            oninput={ on_neg_color }/>
+        if props.show_pos {
+            <input
+                id="color-param"
+                class="parameter"
+                type="color"
+                value={ props.pos_color.to_owned() }
+                oninput={ on_pos_color }/>
+        }
+        if props.show_neg {
+            <input
+                id="neg-color-param"
+                class="parameter"
+                type="color"
+                value={ props.neg_color.to_owned() }
+                oninput={ on_neg_color }/>
+        }
+        <Select<ColorRangePreset>
+            class="color-preset-param"
+            selected={ preset }
+            on_select={ on_preset }
+            values={ ColorRangePreset::values().iter().map(|x| SelectItem::Option(*x)).collect::<Vec<_>>() } >
+        </Select<ColorRangePreset>>
     }
 }
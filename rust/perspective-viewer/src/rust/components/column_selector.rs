@@ -69,12 +69,13 @@ pub enum ColumnSelectorMsg {
     Drop((String, DragTarget, DragEffect, usize)),
     OpenExpressionEditor(bool),
     SaveExpression(JsValue),
+    PinnedColumnsChanged,
 }
 
 /// A `ColumnSelector` controls the `columns` field of the `ViewConfig`,
 /// deriving its options from the table columns and `ViewConfig` expressions.
 pub struct ColumnSelector {
-    _subscriptions: [Subscription; 5],
+    _subscriptions: [Subscription; 6],
     add_expression_ref: NodeRef,
     named_row_count: usize,
     expression_editor: Option<ExpressionEditorElement>,
@@ -110,6 +111,13 @@ impl Component for ColumnSelector {
             ctx.props().dragdrop.dragend_received.add_listener(cb)
         };
 
+        let pinned_sub = {
+            let cb = ctx
+                .link()
+                .callback(|_| ColumnSelectorMsg::PinnedColumnsChanged);
+            ctx.props().renderer.pinned_columns_changed.add_listener(cb)
+        };
+
         let named = maybe! {
             let plugin =
                 ctx.props().renderer.get_active_plugin().ok()?;
@@ -120,7 +128,14 @@ impl Component for ColumnSelector {
         let named_row_count = named.unwrap_or_default();
 
         ColumnSelector {
-            _subscriptions: [table_sub, view_sub, drop_sub, drag_sub, dragend_sub],
+            _subscriptions: [
+                table_sub,
+                view_sub,
+                drop_sub,
+                drag_sub,
+                dragend_sub,
+                pinned_sub,
+            ],
             add_expression_ref: NodeRef::default(),
             expression_editor: None,
             named_row_count,
@@ -133,6 +148,7 @@ impl Component for ColumnSelector {
             ColumnSelectorMsg::Drag(_) => true,
             ColumnSelectorMsg::DragEnd => true,
             ColumnSelectorMsg::TableLoaded => true,
+            ColumnSelectorMsg::PinnedColumnsChanged => true,
             ColumnSelectorMsg::ViewCreated => {
                 let named = maybe! {
                     let plugin =
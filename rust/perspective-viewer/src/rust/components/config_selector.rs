@@ -158,6 +158,10 @@ impl Component for ConfigSelector {
             }
             ConfigSelectorMsg::Close(index, DragTarget::Sort) => {
                 let mut sort = ctx.props().session.get_view_config().sort.clone();
+                if index >= sort.len() {
+                    return false;
+                }
+
                 sort.remove(index as usize);
                 let sort = Some(sort);
                 ctx.props().update_and_render(ViewConfigUpdate {
@@ -317,11 +321,11 @@ impl Component for ConfigSelector {
 
                 <SortSelector
                     name="sort"
-                    allow_duplicates=true
                     parent={ ctx.link().clone() }
                     dragdrop={ ctx.props().dragdrop.clone() }
                     is_dragover={ ctx.props().dragdrop.is_dragover(DragTarget::Sort).map(|(index, name)| {
-                        (index, Sort(name, SortDir::Asc))
+                        let sort = config.sort.iter().find(|x| x.0 == name).cloned();
+                        (index, sort.unwrap_or(Sort(name, SortDir::Asc)))
                     }) }>
                     {
                         for config.sort.iter().enumerate().map(|(idx, sort)| {
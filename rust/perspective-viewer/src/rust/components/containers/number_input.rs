@@ -13,27 +13,77 @@ use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct NumberInputProps {
+    #[prop_or("Max")]
+    pub label: &'static str,
+
     pub max_value: f64,
     pub on_max_value: Callback<String>,
+
+    /// Lower bound, rendered as the `min` attribute and applied to both the
+    /// displayed and emitted values.
+    #[prop_or_default]
+    pub min: Option<f64>,
+
+    /// Upper bound, rendered as the `max` attribute and applied to both the
+    /// displayed and emitted values.
+    #[prop_or_default]
+    pub max: Option<f64>,
+
+    /// Rendered as the `step` attribute.
+    #[prop_or_default]
+    pub step: Option<f64>,
+
+    /// Fires when the input is cleared, distinct from a parsed number.  When
+    /// unset, the empty string is sent to `on_max_value` instead, so the
+    /// parent may substitute a default.
+    #[prop_or_default]
+    pub on_clear: Option<Callback<()>>,
+}
+
+impl NumberInputProps {
+    /// Clamp `value` to this input's `min` and `max`, if any.
+    pub fn clamp(&self, value: f64) -> f64 {
+        clamp(value, self.min, self.max)
+    }
+}
+
+fn clamp(value: f64, min: Option<f64>, max: Option<f64>) -> f64 {
+    let value = min.map_or(value, |min| value.max(min));
+    max.map_or(value, |max| value.min(max))
 }
 
 #[function_component(NumberInput)]
 pub fn number_input(props: &NumberInputProps) -> Html {
-    let oninput = props.on_max_value.reform(|event: InputEvent| {
-        event
-            .target()
-            .unwrap()
-            .unchecked_into::<HtmlInputElement>()
-            .value()
-    });
+    let oninput = {
+        let (min, max) = (props.min, props.max);
+        let on_max_value = props.on_max_value.clone();
+        let on_clear = props.on_clear.clone();
+        Callback::from(move |event: InputEvent| {
+            let value = event
+                .target()
+                .unwrap()
+                .unchecked_into::<HtmlInputElement>()
+                .value();
+
+            // Other non-numeric input is passed through for the parent to
+            // interpret.
+            match (value.parse::<f64>(), &on_clear) {
+                (Ok(x), _) => on_max_value.emit(format!("{}", clamp(x, min, max))),
+                (Err(_), Some(on_clear)) if value.is_empty() => on_clear.emit(()),
+                (Err(_), _) => on_max_value.emit(value),
+            }
+        })
+    };
 
     html_template! {
-        <label>{ "Max" }</label>
+        <label>{ props.label }</label>
         <input
-            value={ format!("{}", props.max_value) }
+            value={ format!("{}", props.clamp(props.max_value)) }
             class="parameter"
             type="number"
-            min="0"
+            min={ props.min.map(|x| format!("{}", x)) }
+            max={ props.max.map(|x| format!("{}", x)) }
+            step={ props.step.map(|x| format!("{}", x)) }
             oninput={ oninput } />
     }
 }
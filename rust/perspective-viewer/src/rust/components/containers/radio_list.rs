@@ -7,6 +7,7 @@
 // file.
 
 use super::radio_list_item::*;
+use super::split_panel::Orientation;
 use crate::*;
 use std::fmt::Display;
 use std::str::FromStr;
@@ -56,6 +57,11 @@ where
     #[prop_or_default]
     pub name: Option<&'static str>,
 
+    /// Whether to lay out items in a column (the default) or side-by-side in a
+    /// row.
+    #[prop_or(Orientation::Vertical)]
+    pub orientation: Orientation,
+
     #[cfg(test)]
     #[prop_or_default]
     pub weak_link: WeakScope<RadioList<T>>,
@@ -70,15 +76,22 @@ where
             && self.disabled == other.disabled
             && self.selected == other.selected
             && self.class == other.class
+            && self.orientation == other.orientation
     }
 }
 
 pub enum RadioListMsg {
     Change(String),
+
+    /// Move the selection by `1` or `-1` items, e.g. via the arrow keys,
+    /// skipping disabled items.
+    Step(isize),
 }
 
-/// A `RadioList` is a vertical collection of components with radio buttons,
-/// only one of which can be selected at a time.  The generic parameter `T`
+/// A `RadioList` is a vertical (or, via the `orientation` prop, horizontal)
+/// collection of components with radio buttons, only one of which can be
+/// selected at a time.  The arrow keys move the selection between enabled
+/// items, as an ARIA `radiogroup`.  The generic parameter `T`
 /// is the selectable type (typically an enum), which must be serializable to
 /// and from `String` (via `Display` and `FromStr`, respectively) so it can be
 /// incorporated into the DOM `value` attribute.
@@ -104,6 +117,8 @@ where
     T: Clone + Display + FromStr + PartialEq + 'static,
 {
     selected: T,
+    group_ref: NodeRef,
+    focus_selected: bool,
 }
 
 impl<T> Component for RadioList<T>
@@ -117,6 +132,8 @@ where
         enable_weak_link_test!(ctx.props(), ctx.link());
         Self {
             selected: ctx.props().selected.clone(),
+            group_ref: NodeRef::default(),
+            focus_selected: false,
         }
     }
 
@@ -128,6 +145,19 @@ where
                     ctx.props().on_change.emit(x);
                 }
             }
+            RadioListMsg::Step(step) if !ctx.props().disabled => {
+                let children = ctx.props().children.iter().collect::<Vec<_>>();
+                let disabled = children.iter().map(|x| x.props.disabled);
+                let current = children.iter().position(|x| x.props.value == self.selected);
+                if let Some(idx) = next_enabled(&disabled.collect::<Vec<_>>(), current, step) {
+                    let value = children[idx].props.value.clone();
+                    self.selected = value.clone();
+                    self.focus_selected = true;
+                    ctx.props().on_change.emit(value);
+                    return true;
+                }
+            }
+            RadioListMsg::Step(_) => (),
         };
         false
     }
@@ -148,22 +178,92 @@ where
             )
         });
 
+        let onkeydown = ctx.link().batch_callback(|event: KeyboardEvent| {
+            let step = match event.key().as_str() {
+                "ArrowDown" | "ArrowRight" => 1,
+                "ArrowUp" | "ArrowLeft" => -1,
+                _ => return None,
+            };
+
+            event.prevent_default();
+            Some(RadioListMsg::Step(step))
+        });
+
         let class = match ctx.props().class {
             Some(x) => format!("radio-list-item {}", x),
             None => "radio-list-item".to_owned(),
         };
 
-        ctx.props()
+        // Only one item is in the tab order, the selected item (or the first
+        // if nothing is selected);  the arrow keys move between items.
+        let tab_stop = ctx
+            .props()
+            .children
+            .iter()
+            .position(|x| x.props.value == self.selected)
+            .unwrap_or_default();
+
+        let items = ctx
+            .props()
             .children
             .iter()
             .enumerate()
             .map(|(idx, child)| {
-                self.render_item(ctx, idx, child, &class, on_change.clone(), &self.selected)
+                let on_change = on_change.clone();
+                let is_tab_stop = idx == tab_stop;
+                self.render_item(ctx, idx, child, &class, on_change, is_tab_stop)
             })
-            .collect::<Html>()
+            .collect::<Html>();
+
+        let (group_class, aria_orientation) = match ctx.props().orientation {
+            Orientation::Vertical => ("radio-list", "vertical"),
+            Orientation::Horizontal => ("radio-list-horizontal", "horizontal"),
+        };
+
+        html! {
+            <div
+                ref={ self.group_ref.clone() }
+                class={ group_class }
+                role="radiogroup"
+                aria-orientation={ aria_orientation }
+                aria-disabled={ ctx.props().disabled.to_string() }
+                onkeydown={ onkeydown }>
+
+                { items }
+            </div>
+        }
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if std::mem::take(&mut self.focus_selected) {
+            let checked = self
+                .group_ref
+                .cast::<web_sys::Element>()
+                .and_then(|x| x.query_selector("input:checked").ok().flatten());
+
+            if let Some(input) = checked {
+                let _ = input.unchecked_into::<web_sys::HtmlElement>().focus();
+            }
+        }
     }
 }
 
+/// The index of the next enabled item `step` items from `current`, wrapping
+/// around either end, or `None` if every item is disabled.  When nothing is
+/// `current`ly selected, this starts from the first (or last) item.
+fn next_enabled(disabled: &[bool], current: Option<usize>, step: isize) -> Option<usize> {
+    let len = disabled.len() as isize;
+    let start = match current {
+        Some(idx) => idx as isize,
+        None if step > 0 => -1,
+        None => len,
+    };
+
+    (1..=len)
+        .map(|offset| (start + step * offset).rem_euclid(len) as usize)
+        .find(|idx| !disabled[*idx])
+}
+
 impl<T> RadioList<T>
 where
     T: Clone + Display + FromStr + PartialEq + 'static,
@@ -175,6 +275,7 @@ where
     /// * `child` - The `Html` row content.
     /// * `class` - The `class` attribute string.
     /// * `on_change` - The callback when this `<input>` changes.
+    /// * `is_tab_stop` - Whether this row's `<input>` is in the tab order.
     fn render_item(
         &self,
         ctx: &Context<Self>,
@@ -182,20 +283,24 @@ where
         child: yew::virtual_dom::VChild<RadioListItem<T>>,
         class: &str,
         on_change: Callback<InputEvent>,
-        selected: &T,
+        is_tab_stop: bool,
     ) -> Html {
         let val = child.props.value.clone();
+        let is_checked = self.selected == val;
         html! {
             <div class={ class.to_string() }>
                 <input
                     id={ format!("radio-list-{}", idx) }
                     name={ ctx.props().name.unwrap_or("radio-list") }
                     type="radio"
+                    role="radio"
                     value={ format!("{}", val) }
                     class="parameter"
                     oninput={ on_change }
-                    disabled={ ctx.props().disabled }
-                    checked={ selected == &val } />
+                    disabled={ ctx.props().disabled || child.props.disabled }
+                    aria-checked={ is_checked.to_string() }
+                    tabindex={ if is_tab_stop { "0" } else { "-1" } }
+                    checked={ is_checked } />
                 { child }
             </div>
         }
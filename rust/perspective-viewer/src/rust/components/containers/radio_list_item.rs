@@ -25,6 +25,10 @@ where
 {
     pub children: Children,
     pub value: T,
+
+    /// Whether this item is disabled, independently of its `RadioList`.
+    #[prop_or_default]
+    pub disabled: bool,
 }
 
 impl<T> Component for RadioListItem<T>
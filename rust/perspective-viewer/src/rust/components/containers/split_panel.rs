@@ -111,11 +111,7 @@ impl ResizingState {
     }
 
     pub fn get_style(&self, client_offset: i32) -> Option<String> {
-        let offset = self.get_offset(client_offset);
-        Some(match self.orientation {
-            Orientation::Horizontal => format!("width:{}px", offset),
-            Orientation::Vertical => format!("height:{}px", offset),
-        })
+        Some(self.orientation.size_style(self.get_offset(client_offset)))
     }
 
     pub fn get_dimensions(&self, client_offset: i32) -> (i32, i32) {
@@ -160,6 +156,16 @@ pub enum Orientation {
     Vertical,
 }
 
+impl Orientation {
+    /// The inline style which sets a panel's size along this orientation.
+    fn size_style(self, size: i32) -> String {
+        match self {
+            Orientation::Horizontal => format!("width:{}px", size),
+            Orientation::Vertical => format!("height:{}px", size),
+        }
+    }
+}
+
 impl Default for Orientation {
     fn default() -> Orientation {
         Orientation::Horizontal
@@ -179,6 +185,10 @@ pub struct SplitPanelProps {
     #[prop_or_default]
     pub reverse: bool,
 
+    /// The size of the first child in pixels, until it is resized.
+    #[prop_or_default]
+    pub initial_size: Option<i32>,
+
     #[prop_or_default]
     pub on_reset: Option<Callback<()>>,
 
@@ -205,6 +215,7 @@ impl PartialEq for SplitPanelProps {
             && self.children == other.children
             && self.orientation == other.orientation
             && self.reverse == other.reverse
+            && self.initial_size == other.initial_size
     }
 }
 
@@ -234,6 +245,7 @@ pub struct SplitPanel {
     resize_state: Option<ResizingState>,
     refs: Vec<NodeRef>,
     styles: Vec<Option<String>>,
+    initial_size: Option<i32>,
     on_reset: Option<Callback<()>>,
 }
 
@@ -249,6 +261,7 @@ impl Component for SplitPanel {
             resize_state: None,
             refs: vec![Default::default(); len],
             styles: vec![Default::default(); len],
+            initial_size: ctx.props().initial_size,
             on_reset: None,
         }
     }
@@ -302,6 +315,11 @@ impl Component for SplitPanel {
         let new_len = ctx.props().children.len();
         self.refs.resize(new_len, Default::default());
         self.styles.resize(new_len, Default::default());
+        if self.initial_size != ctx.props().initial_size {
+            self.initial_size = ctx.props().initial_size;
+            self.styles[0] = None;
+        }
+
         true
     }
 
@@ -317,10 +335,14 @@ impl Component for SplitPanel {
             classes.push("orient-reverse");
         }
 
+        let first_style = self.styles[0]
+            .clone()
+            .or_else(|| self.initial_size.map(|x| orientation.size_style(x)));
+
         html! {
             <div id={ ctx.props().id.clone() } class={ classes }>
                 <SplitPanelChild
-                    style={ self.styles[0].clone() }
+                    style={ first_style }
                     ref_={ self.refs[0].clone() }>
 
                     { iter.next().unwrap() }
@@ -1,2 +1,3 @@
+mod number_input;
 mod radio_list;
 mod split_panel;
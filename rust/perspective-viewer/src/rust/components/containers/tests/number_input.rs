@@ -0,0 +1,72 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use super::super::number_input::{NumberInput, NumberInputProps};
+use crate::utils::await_animation_frame;
+use crate::*;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+pub fn test_clamp() {
+    let props = yew::props!(NumberInputProps {
+        max_value: 0.0,
+        on_max_value: Callback::from(|_| ()),
+        min: Some(0.0),
+        max: Some(10.0)
+    });
+
+    assert_eq!(props.clamp(-1.0), 0.0);
+    assert_eq!(props.clamp(5.5), 5.5);
+    assert_eq!(props.clamp(11.0), 10.0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_clamp_unbounded() {
+    let props = yew::props!(NumberInputProps {
+        max_value: 0.0,
+        on_max_value: Callback::from(|_| ())
+    });
+
+    assert_eq!(props.clamp(-1e9), -1e9);
+    assert_eq!(props.clamp(1e9), 1e9);
+}
+
+#[wasm_bindgen_test]
+pub async fn test_renders_bounds() {
+    let div = NodeRef::default();
+    test_html! {
+        <div ref={ div.clone() }>
+            <NumberInput
+                max_value={ 50.0 }
+                on_max_value={ Callback::from(|_| ()) }
+                min={ Some(0.0) }
+                max={ Some(10.0) }
+                step={ Some(0.5) } />
+        </div>
+    };
+
+    await_animation_frame().await.unwrap();
+    let input: HtmlInputElement = div
+        .cast::<web_sys::Element>()
+        .unwrap()
+        .query_selector("input")
+        .unwrap()
+        .unwrap()
+        .unchecked_into();
+
+    assert_eq!(input.get_attribute("min").unwrap(), "0");
+    assert_eq!(input.get_attribute("max").unwrap(), "10");
+    assert_eq!(input.get_attribute("step").unwrap(), "0.5");
+    assert_eq!(input.value(), "10");
+}
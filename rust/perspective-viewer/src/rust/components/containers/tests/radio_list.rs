@@ -8,12 +8,15 @@
 
 use super::super::radio_list::{RadioList, RadioListMsg};
 use super::super::radio_list_item::RadioListItem;
+use super::super::split_panel::Orientation;
 use crate::utils::{await_animation_frame, WeakScope};
 use crate::*;
 
 use std::cell::RefCell;
 use std::rc::Rc;
+use wasm_bindgen::JsCast;
 use wasm_bindgen_test::*;
+use web_sys::HtmlElement;
 use yew::prelude::*;
 
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
@@ -58,3 +61,126 @@ pub async fn test_change_u32() {
 
     assert_eq!(*result.borrow(), "1");
 }
+
+#[wasm_bindgen_test]
+pub async fn test_change_horizontal() {
+    let link: WeakScope<RadioList<String>> = WeakScope::default();
+    let root = NodeRef::default();
+    let result: Rc<RefCell<String>> = Rc::new(RefCell::new("false".to_owned()));
+    let on_change = {
+        clone!(result);
+        Callback::from(move |val| {
+            *result.borrow_mut() = val;
+        })
+    };
+
+    test_html! {
+        <RadioList<String>
+            disabled=false
+            selected="2"
+            orientation={ Orientation::Horizontal }
+            on_change={ on_change }
+            ref={ root.clone() }
+            weak_link={ link.clone() }>
+
+            <RadioListItem<String> value="1"><span>{ "One" }</span></RadioListItem<String>>
+            <RadioListItem<String> value="2"><span>{ "Two" }</span></RadioListItem<String>>
+
+        </RadioList<String>>
+    };
+
+    await_animation_frame().await.unwrap();
+    let elem = root.cast::<HtmlElement>().unwrap();
+    assert_eq!(elem.class_name(), "radio-list-horizontal");
+    assert_eq!(elem.child_element_count(), 2);
+    let span = elem
+        .query_selector(".radio-list-item span")
+        .unwrap()
+        .unwrap();
+    assert_eq!(span.text_content().unwrap(), "One");
+
+    let radio_list = link.borrow().clone().unwrap();
+    radio_list.send_message(RadioListMsg::Change("1".to_owned()));
+    await_animation_frame().await.unwrap();
+    assert_eq!(*result.borrow(), "1");
+}
+
+#[wasm_bindgen_test]
+pub async fn test_step_skips_disabled_items() {
+    let link: WeakScope<RadioList<String>> = WeakScope::default();
+    let root = NodeRef::default();
+    let result: Rc<RefCell<String>> = Rc::new(RefCell::new("false".to_owned()));
+    let on_change = {
+        clone!(result);
+        Callback::from(move |val| {
+            *result.borrow_mut() = val;
+        })
+    };
+
+    test_html! {
+        <RadioList<String>
+            disabled=false
+            selected="1"
+            on_change={ on_change }
+            ref={ root.clone() }
+            weak_link={ link.clone() }>
+
+            <RadioListItem<String> value="1"><span>{ "One" }</span></RadioListItem<String>>
+            <RadioListItem<String> value="2" disabled=true><span>{ "Two" }</span></RadioListItem<String>>
+            <RadioListItem<String> value="3"><span>{ "Three" }</span></RadioListItem<String>>
+
+        </RadioList<String>>
+    };
+
+    await_animation_frame().await.unwrap();
+    let elem = root.cast::<HtmlElement>().unwrap();
+    assert_eq!(elem.get_attribute("role").unwrap(), "radiogroup");
+
+    let radio_list = link.borrow().clone().unwrap();
+    radio_list.send_message(RadioListMsg::Step(1));
+    await_animation_frame().await.unwrap();
+    assert_eq!(*result.borrow(), "3");
+
+    let checked = elem.query_selector("input:checked").unwrap().unwrap();
+    assert_eq!(checked.get_attribute("aria-checked").unwrap(), "true");
+    assert_eq!(checked.get_attribute("tabindex").unwrap(), "0");
+
+    radio_list.send_message(RadioListMsg::Step(1));
+    await_animation_frame().await.unwrap();
+    assert_eq!(*result.borrow(), "1");
+
+    radio_list.send_message(RadioListMsg::Step(-1));
+    await_animation_frame().await.unwrap();
+    assert_eq!(*result.borrow(), "3");
+}
+
+#[wasm_bindgen_test]
+pub async fn test_step_ignored_when_disabled() {
+    let link: WeakScope<RadioList<String>> = WeakScope::default();
+    let result: Rc<RefCell<String>> = Rc::new(RefCell::new("false".to_owned()));
+    let on_change = {
+        clone!(result);
+        Callback::from(move |val| {
+            *result.borrow_mut() = val;
+        })
+    };
+
+    test_html! {
+        <RadioList<String>
+            disabled=true
+            selected="1"
+            on_change={ on_change }
+            weak_link={ link.clone() }>
+
+            <RadioListItem<String> value="1"><span>{ "One" }</span></RadioListItem<String>>
+            <RadioListItem<String> value="2"><span>{ "Two" }</span></RadioListItem<String>>
+
+        </RadioList<String>>
+    };
+
+    await_animation_frame().await.unwrap();
+    let radio_list = link.borrow().clone().unwrap();
+    radio_list.send_message(RadioListMsg::Step(1));
+    await_animation_frame().await.unwrap();
+    assert_eq!(*result.borrow(), "false");
+}
@@ -75,13 +75,25 @@ fn get_menu_items(has_render: bool) -> Vec<CopyDropDownMenuItem> {
         CopyDropDownMenuItem::OptGroup(
             "Current View".into(),
             if has_render {
-                vec![ExportMethod::Csv, ExportMethod::Json, ExportMethod::Png]
+                vec![
+                    ExportMethod::Csv,
+                    ExportMethod::CsvNoHeader,
+                    ExportMethod::Json,
+                    ExportMethod::Markdown,
+                    ExportMethod::Png,
+                ]
             } else {
-                vec![ExportMethod::Csv, ExportMethod::Json]
+                vec![
+                    ExportMethod::Csv,
+                    ExportMethod::CsvNoHeader,
+                    ExportMethod::Json,
+                    ExportMethod::Markdown,
+                ]
             },
         ),
         CopyDropDownMenuItem::OptGroup("All".into(), vec![
             ExportMethod::CsvAll,
+            ExportMethod::CsvAllNoHeader,
             ExportMethod::JsonAll,
         ]),
         CopyDropDownMenuItem::OptGroup("Config".into(), vec![ExportMethod::JsonConfig]),
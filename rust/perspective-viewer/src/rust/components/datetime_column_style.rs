@@ -12,7 +12,7 @@ use super::containers::radio_list_item::RadioListItem;
 use super::containers::select::*;
 use super::modal::{ModalLink, SetModalLink};
 use crate::config::*;
-use crate::utils::WeakScope;
+use crate::utils::{format_now, WeakScope};
 use crate::*;
 use lazy_static::*;
 use wasm_bindgen::*;
@@ -41,6 +41,33 @@ lazy_static! {
     .unwrap();
 }
 
+/// Format the current time with a config's `Intl.DateTimeFormat` options, in
+/// the selected timezone (or the user's local timezone).
+fn make_preview_text(
+    config: &DatetimeColumnStyleConfig,
+    enable_time_config: bool,
+) -> Result<String, JsValue> {
+    let options = js_sys::Object::new();
+    let time_zone = config.time_zone.as_ref().unwrap_or(&*USER_TIMEZONE);
+    js_sys::Reflect::set(&options, &"timeZone".into(), &time_zone.into())?;
+    if config.date_style != DatetimeFormat::Disabled {
+        let date_style = config.date_style.to_string();
+        js_sys::Reflect::set(&options, &"dateStyle".into(), &date_style.into())?;
+    }
+
+    if enable_time_config && config.time_style != DatetimeFormat::Disabled {
+        let time_style = config.time_style.to_string();
+        js_sys::Reflect::set(&options, &"timeStyle".into(), &time_style.into())?;
+    }
+
+    let formatter = js_sys::Intl::DateTimeFormat::new(&js_sys::Array::new(), &options);
+    formatter
+        .format()
+        .call1(&JsValue::UNDEFINED, &js_sys::Date::new_0())?
+        .as_string()
+        .ok_or_else(|| JsValue::from("Bad date"))
+}
+
 pub static CSS: &str = include_str!("../../../build/css/column-style.css");
 
 pub enum DatetimeColumnStyleMsg {
@@ -54,6 +81,8 @@ pub enum DatetimeColumnStyleMsg {
     ColorModeEnabled(bool),
     ColorModeChanged(DatetimeColorMode),
     ColorChanged(String),
+    CustomFormatChanged(String),
+    WeekNumbersChanged(bool),
 }
 
 #[derive(Properties)]
@@ -70,7 +99,7 @@ pub struct DatetimeColumnStyleProps {
     pub on_change: Callback<DatetimeColumnStyleConfig>,
 
     #[prop_or_default]
-    weak_link: WeakScope<DatetimeColumnStyle>,
+    pub weak_link: WeakScope<DatetimeColumnStyle>,
 }
 
 impl ModalLink<DatetimeColumnStyle> for DatetimeColumnStyleProps {
@@ -90,9 +119,24 @@ impl PartialEq for DatetimeColumnStyleProps {
 /// JSON serializable config record and the defaults record).
 pub struct DatetimeColumnStyle {
     config: DatetimeColumnStyleConfig,
+    custom_format: String,
+    custom_format_error: Option<String>,
 }
 
 impl DatetimeColumnStyle {
+    fn reset(config: &DatetimeColumnStyleConfig) -> Self {
+        let mut config = config.clone();
+        if config.time_zone.as_ref() == Some(&*USER_TIMEZONE) {
+            config.time_zone = None;
+        }
+
+        DatetimeColumnStyle {
+            custom_format: config.custom_format.clone().unwrap_or_default(),
+            custom_format_error: None,
+            config,
+        }
+    }
+
     /// When this config has changed, we must signal the wrapper element.
     fn dispatch_config(&self, ctx: &Context<Self>) {
         ctx.props().on_change.emit(self.config.clone());
@@ -130,16 +174,14 @@ impl Component for DatetimeColumnStyle {
 
     fn create(ctx: &Context<Self>) -> Self {
         ctx.set_modal_link();
-        DatetimeColumnStyle {
-            config: ctx.props().config.clone(),
-        }
+        DatetimeColumnStyle::reset(&ctx.props().config)
     }
 
     // TODO could be more conservative here with re-rendering
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             DatetimeColumnStyleMsg::Reset(config) => {
-                self.config = config;
+                *self = DatetimeColumnStyle::reset(&config);
                 true
             }
             DatetimeColumnStyleMsg::TimezoneEnabled => {
@@ -198,6 +240,31 @@ impl Component for DatetimeColumnStyle {
                 self.dispatch_config(ctx);
                 true
             }
+            DatetimeColumnStyleMsg::CustomFormatChanged(format) => {
+                self.custom_format_error = match format_now(&format, false) {
+                    _ if format.trim().is_empty() => {
+                        self.config.custom_format = None;
+                        None
+                    }
+                    Ok(_) => {
+                        self.config.custom_format = Some(format.clone());
+                        None
+                    }
+                    Err(err) => Some(err),
+                };
+
+                self.custom_format = format;
+                if self.custom_format_error.is_none() {
+                    self.dispatch_config(ctx);
+                }
+
+                true
+            }
+            DatetimeColumnStyleMsg::WeekNumbersChanged(enabled) => {
+                self.config.week_numbers = enabled;
+                self.dispatch_config(ctx);
+                true
+            }
         }
     }
 
@@ -226,6 +293,33 @@ impl Component for DatetimeColumnStyle {
             .callback(|_| DatetimeColumnStyleMsg::TimezoneEnabled);
 
         let on_date_reset = ctx.link().callback(|_| DatetimeColumnStyleMsg::DateEnabled);
+        let custom_format_oninput = ctx.link().callback(|event: InputEvent| {
+            DatetimeColumnStyleMsg::CustomFormatChanged(
+                event
+                    .target()
+                    .unwrap()
+                    .unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        });
+
+        let week_numbers_oninput = ctx.link().callback(|event: InputEvent| {
+            let input = event
+                .target()
+                .unwrap()
+                .unchecked_into::<web_sys::HtmlInputElement>();
+            DatetimeColumnStyleMsg::WeekNumbersChanged(input.checked())
+        });
+
+        let custom_format_preview = match &self.custom_format_error {
+            Some(err) => err.to_owned(),
+            None => format_now(
+                self.config.custom_format.as_deref().unwrap_or("%Y-%m-%d"),
+                self.config.week_numbers,
+            )
+            .unwrap_or_default(),
+        };
+
         let on_time_reset = ctx.link().callback(|_| DatetimeColumnStyleMsg::TimeEnabled);
 
         // TODO this checkbox should be disabled if the timezone is local but
@@ -237,6 +331,11 @@ impl Component for DatetimeColumnStyle {
             <div id="column-style-container">
 
                 if ctx.props().enable_time_config {
+                    <div class="column-style-label">
+                        <label id="datetime-preview" class="indent">
+                            { make_preview_text(&self.config, ctx.props().enable_time_config).unwrap_or_default() }
+                        </label>
+                    </div>
                     <div class="column-style-label">
                         <label class="indent">{ "Timezone" }</label>
                     </div>
@@ -290,6 +389,30 @@ impl Component for DatetimeColumnStyle {
                     </Select<DatetimeFormat>>
                 </div>
 
+                if !ctx.props().enable_time_config {
+                    <div class="column-style-label">
+                        <label
+                            id="custom-format-preview"
+                            class={ classes!("indent", self.custom_format_error.as_ref().map(|_| "invalid")) }>
+                            { custom_format_preview }
+                        </label>
+                    </div>
+                    <div class="row section">
+                        <input
+                            type="checkbox"
+                            title="Show ISO week numbers"
+                            oninput={ week_numbers_oninput }
+                            checked={ self.config.week_numbers } />
+                        <input
+                            id="custom-format-param"
+                            class="parameter"
+                            type="text"
+                            placeholder="%Y-%m-%d"
+                            value={ self.custom_format.clone() }
+                            oninput={ custom_format_oninput } />
+                    </div>
+                }
+
                 <div class="column-style-label">
                     <label class="indent">{ "Color" }</label>
                 </div>
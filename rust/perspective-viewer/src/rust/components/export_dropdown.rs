@@ -41,10 +41,15 @@ pub struct ExportDropDownMenu {
     _sub: Option<Subscription>,
     input_ref: NodeRef,
     invalid: bool,
+    copied: bool,
 }
 
 pub enum ExportDropDownMenuMsg {
     TitleChange,
+
+    /// Show (or clear) the confirmation that the config was copied to the
+    /// clipboard.
+    Copied(bool),
 }
 
 fn get_menu_items(name: &str, has_render: bool) -> Vec<ExportDropDownMenuItem> {
@@ -57,6 +62,7 @@ fn get_menu_items(name: &str, has_render: bool) -> Vec<ExportDropDownMenuItem> {
                     ExportMethod::Json.new_file(name),
                     ExportMethod::Arrow.new_file(name),
                     ExportMethod::Html.new_file(name),
+                    ExportMethod::Markdown.new_file(name),
                     ExportMethod::Png.new_file(name),
                 ]
             } else {
@@ -65,6 +71,7 @@ fn get_menu_items(name: &str, has_render: bool) -> Vec<ExportDropDownMenuItem> {
                     ExportMethod::Json.new_file(name),
                     ExportMethod::Arrow.new_file(name),
                     ExportMethod::Html.new_file(name),
+                    ExportMethod::Markdown.new_file(name),
                 ]
             },
         ),
@@ -74,7 +81,8 @@ fn get_menu_items(name: &str, has_render: bool) -> Vec<ExportDropDownMenuItem> {
             ExportMethod::ArrowAll.new_file(name),
         ]),
         ExportDropDownMenuItem::OptGroup("Config".into(), vec![
-            ExportMethod::JsonConfig.new_file(name)
+            ExportMethod::JsonConfig.new_file(name),
+            ExportMethod::CopyConfig.new_file(name),
         ]),
     ]
 }
@@ -98,6 +106,9 @@ impl Component for ExportDropDownMenu {
                 values={ Rc::new(get_menu_items(&self.title, has_render)) }
                 callback={ ctx.props().callback.clone() }>
             </DropDownMenu<ExportFile>>
+            if self.copied {
+                <span class="dropdown-group-label">{ "Config copied!" }</span>
+            }
         }
     }
 
@@ -113,6 +124,10 @@ impl Component for ExportDropDownMenu {
                 self.invalid = self.title.is_empty();
                 true
             }
+            ExportDropDownMenuMsg::Copied(copied) => {
+                self.copied = copied;
+                true
+            }
         }
     }
 
@@ -8,15 +8,14 @@
 
 use super::containers::split_panel::*;
 use super::modal::*;
+use crate::config::{duplicate_expression_aliases, split_expression_alias};
 use crate::exprtk::*;
 use crate::js::monaco::*;
-use crate::js::perspective::*;
-use crate::session::Session;
+use crate::session::{ExpressionError, ExpressionSnippet, Session};
 use crate::utils::*;
 use crate::*;
 
 use std::cell::RefCell;
-use std::convert::TryFrom;
 use std::ops::Deref;
 use std::rc::Rc;
 use wasm_bindgen::{prelude::*, JsCast};
@@ -34,6 +33,7 @@ pub enum ExpressionEditorMsg {
     Validate(JsValue),
     EnableSave(bool),
     SaveExpr,
+    InsertSnippet(String),
 }
 
 #[derive(Properties)]
@@ -134,6 +134,10 @@ impl Component for ExpressionEditor {
                 *self.state.theme.borrow_mut() = Some(theme);
                 false
             }
+            ExpressionEditorMsg::InsertSnippet(expression) => {
+                self.state.insert_text(&expression);
+                false
+            }
             ExpressionEditorMsg::SaveExpr => {
                 if self.save_enabled {
                     if let Some((_, x)) = self.state.editor.borrow().as_ref() {
@@ -177,6 +181,26 @@ impl Component for ExpressionEditor {
             .0
             .into();
 
+        let snippets = ctx
+            .props()
+            .session
+            .get_expression_snippets()
+            .into_iter()
+            .map(|ExpressionSnippet { name, expression }| {
+                let title = expression.clone();
+                let onmousedown = ctx.link().callback(move |event: MouseEvent| {
+                    event.prevent_default();
+                    ExpressionEditorMsg::InsertSnippet(expression.clone())
+                });
+
+                html! {
+                    <button class="psp-expression-snippet" { title } { onmousedown }>
+                        { name }
+                    </button>
+                }
+            })
+            .collect::<Html>();
+
         html_template! {
             <style>
                 { &CSS }
@@ -212,7 +236,10 @@ impl Component for ExpressionEditor {
                     </div>
                     <div></div>
                 </SplitPanel>
-                <div></div>
+                <div id="expression-snippets">
+                    <span id="expression-snippets-title">{ "Snippets" }</span>
+                    { snippets }
+                </div>
             </SplitPanel>
         }
     }
@@ -343,13 +370,20 @@ impl ExpressionEditorState {
     /// if the expression is valid.
     async fn validate_expr(self) -> Result<JsValue, JsValue> {
         let (monaco, editor) = self.editor.borrow().as_ref().unwrap().clone();
-        let expr = editor.get_value();
+        let text = editor.get_value().as_string().unwrap_or_default();
         self.on_validate_complete.emit(true);
         let model = editor.get_model();
-        let (msg, arr) = match self.session.validate_expr(expr).await? {
-            None => (true, js_sys::Array::new()),
+        let (msg, arr) = match self.session.validate_expr(&text).await?.details {
+            None => match self.duplicate_alias_marker(&text) {
+                None => (true, js_sys::Array::new()),
+                Some(marker) => {
+                    let args = JsValue::from_serde(&marker).unwrap();
+                    let arr = [args].iter().collect::<js_sys::Array>();
+                    (false, arr)
+                }
+            },
             Some(err) => {
-                let marker = error_to_marker(err);
+                let marker = error_to_marker(&text, err);
                 let args = JsValue::from_serde(&marker).unwrap();
                 let arr = [args].iter().collect::<js_sys::Array>();
                 (false, arr)
@@ -361,6 +395,49 @@ impl ExpressionEditorState {
         Ok(JsValue::UNDEFINED)
     }
 
+    /// Underline the alias line of `expr` if saving it would give two
+    /// `expressions` the same alias, besides the expression being edited
+    /// (which `expr` replaces).
+    fn duplicate_alias_marker(&self, expr: &str) -> Option<JsMonacoModelMarker<'static>> {
+        let mut expressions = self.session.get_view_config().expressions.clone();
+        if let Some(alias) = &self.alias {
+            expressions.retain(|x| split_expression_alias(x).0 != alias);
+        }
+
+        expressions.retain(|x| x != expr);
+        expressions.push(expr.to_owned());
+        let duplicate = duplicate_expression_aliases(&expressions)
+            .into_iter()
+            .next()?;
+
+        let first_line = expr.lines().next().map_or(0, |x| x.chars().count());
+        let (end_line_number, end_column) = ExpressionError::to_position(expr, first_line);
+        Some(JsMonacoModelMarker {
+            code: "".to_owned(),
+            start_line_number: 1,
+            end_line_number,
+            start_column: 1,
+            end_column,
+            severity: "error",
+            message: duplicate.to_string(),
+        })
+    }
+
+    /// Replace the editor's selection (or insert at the cursor) with `text`.
+    /// The edit fires `onDidChangeContent`, which re-validates the expression.
+    fn insert_text(&self, text: &str) {
+        if let Some((_, ref editor)) = *self.editor.borrow() {
+            let edits = json!([{
+                "range": editor.get_selection(),
+                "text": text,
+                "forceMoveMarkers": true
+            }]);
+
+            editor.execute_edits("snippets", &edits);
+            editor.focus();
+        }
+    }
+
     // Ideally we'd set the monaco dimension explicitly, but the API
     // does not provide independent width/height updates, nor a
     // convenient way to get the current dimensions (need to select the
@@ -378,14 +455,17 @@ impl ExpressionEditorState {
     }
 }
 
-fn error_to_marker(err: PerspectiveValidationError) -> JsMonacoModelMarker<'static> {
+/// Underline the token at which `err` occurred in `expr`.
+fn error_to_marker(expr: &str, err: ExpressionError) -> JsMonacoModelMarker<'static> {
+    let (start_line_number, start_column) = ExpressionError::to_position(expr, err.start);
+    let (end_line_number, end_column) = ExpressionError::to_position(expr, err.end);
     JsMonacoModelMarker {
         code: "".to_owned(),
-        start_line_number: u32::try_from(err.line + 1).unwrap_or(0),
-        end_line_number: u32::try_from(err.line + 1).unwrap_or(0),
-        start_column: u32::try_from(err.column).unwrap_or(0),
-        end_column: u32::try_from(err.column).unwrap_or(0),
+        start_line_number,
+        end_line_number,
+        start_column,
+        end_column,
         severity: "error",
-        message: err.error_message,
+        message: err.message,
     }
 }
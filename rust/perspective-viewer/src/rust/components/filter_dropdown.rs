@@ -6,27 +6,67 @@
 // of the Apache License 2.0.  The full license can be found in the LICENSE
 // file.
 
+use super::containers::number_input::*;
 use super::modal::*;
 use crate::utils::WeakScope;
 use crate::*;
 
+use wasm_bindgen::JsCast;
 use web_sys::*;
 use yew::prelude::*;
+use yew::*;
 
 static CSS: &str = include_str!("../../../build/css/filter-dropdown.css");
 
 pub enum FilterDropDownMsg {
     SetValues(Vec<String>),
     SetCallback(Callback<String>),
+    SetMultiSelect(Option<FilterMultiSelect>),
+    SetChecked(Vec<String>),
+    SetRange(Option<FilterRange>),
+    RangeMin(String),
+    RangeMax(String),
+    AppendValues(Vec<String>, bool),
+    CheckAll(Vec<String>),
+    LoadMore,
+    SelectAll,
+    ClearAll,
+    ItemToggle(String),
     ItemDown,
     ItemUp,
     ItemSelect,
 }
 
+/// State for the "in"/"not in" mode of the dropdown, which renders a
+/// checkbox per value and fetches further pages of values on scroll.
+/// `on_load_all` is called by "Select All" when not every value has been
+/// fetched yet, and should reply with `CheckAll`.
+pub struct FilterMultiSelect {
+    pub checked: Vec<String>,
+    pub has_more: bool,
+    pub on_change: Callback<Vec<String>>,
+    pub on_load_more: Callback<()>,
+    pub on_load_all: Callback<()>,
+}
+
+/// State for the numeric range mode of the dropdown, which renders a min and
+/// max input defaulting to the column's `bounds`.  `on_blur` is called when
+/// focus leaves the inputs.
+pub struct FilterRange {
+    pub min: f64,
+    pub max: f64,
+    pub bounds: Option<(f64, f64)>,
+    pub on_change: Callback<(f64, f64)>,
+    pub on_blur: Callback<()>,
+}
+
 pub struct FilterDropDown {
     values: Option<Vec<String>>,
     selected: usize,
     on_select: Option<Callback<String>>,
+    multi_select: Option<FilterMultiSelect>,
+    range: Option<FilterRange>,
+    is_loading: bool,
     // link: Scope<Self>,
 }
 
@@ -52,11 +92,14 @@ impl Component for FilterDropDown {
             values: Some(vec![]),
             selected: 0,
             on_select: None,
+            multi_select: None,
+            range: None,
+            is_loading: false,
             // link,
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             FilterDropDownMsg::SetCallback(callback) => {
                 self.on_select = Some(callback);
@@ -67,6 +110,126 @@ impl Component for FilterDropDown {
                 self.selected = 0;
                 true
             }
+            FilterDropDownMsg::SetMultiSelect(multi_select) => {
+                self.multi_select = multi_select;
+                self.range = None;
+                self.is_loading = false;
+                true
+            }
+            FilterDropDownMsg::SetRange(range) => {
+                self.range = range;
+                self.multi_select = None;
+                true
+            }
+            FilterDropDownMsg::RangeMin(value) => match (&mut self.range, value.parse()) {
+                (Some(range), Ok(min)) => {
+                    range.min = min;
+                    if range.min <= range.max {
+                        range.on_change.emit((range.min, range.max));
+                    }
+
+                    true
+                }
+                _ => false,
+            },
+            FilterDropDownMsg::RangeMax(value) => match (&mut self.range, value.parse()) {
+                (Some(range), Ok(max)) => {
+                    range.max = max;
+                    if range.min <= range.max {
+                        range.on_change.emit((range.min, range.max));
+                    }
+
+                    true
+                }
+                _ => false,
+            },
+            FilterDropDownMsg::SetChecked(checked) => match &mut self.multi_select {
+                Some(multi_select) => {
+                    multi_select.checked = checked;
+                    true
+                }
+                None => false,
+            },
+            FilterDropDownMsg::AppendValues(values, has_more) => {
+                self.is_loading = false;
+                if let Some(multi_select) = &mut self.multi_select {
+                    multi_select.has_more = has_more;
+                }
+
+                self.values.get_or_insert_with(Vec::new).extend(values);
+                true
+            }
+            FilterDropDownMsg::CheckAll(values) => {
+                self.is_loading = false;
+                if let Some(multi_select) = &mut self.multi_select {
+                    multi_select.has_more = false;
+                    multi_select.checked = values.clone();
+                    multi_select.on_change.emit(values.clone());
+                }
+
+                self.values = Some(values);
+                true
+            }
+            FilterDropDownMsg::SelectAll => match &mut self.multi_select {
+                Some(multi_select) if multi_select.has_more => {
+                    self.is_loading = true;
+                    multi_select.on_load_all.emit(());
+                    false
+                }
+                Some(multi_select) => {
+                    multi_select.checked = self.values.clone().unwrap_or_default();
+                    multi_select.on_change.emit(multi_select.checked.clone());
+                    true
+                }
+                None => false,
+            },
+            FilterDropDownMsg::ClearAll => match &mut self.multi_select {
+                Some(multi_select) if !multi_select.checked.is_empty() => {
+                    multi_select.checked.clear();
+                    multi_select.on_change.emit(vec![]);
+                    true
+                }
+                _ => false,
+            },
+            FilterDropDownMsg::LoadMore => {
+                if let Some(multi_select) = &self.multi_select {
+                    if multi_select.has_more && !self.is_loading {
+                        self.is_loading = true;
+                        multi_select.on_load_more.emit(());
+                    }
+                }
+
+                false
+            }
+            FilterDropDownMsg::ItemToggle(value) => {
+                if let Some(multi_select) = &mut self.multi_select {
+                    let checked = &mut multi_select.checked;
+                    if let Some(idx) = checked.iter().position(|x| *x == value) {
+                        checked.remove(idx);
+                    } else {
+                        checked.push(value);
+                    }
+
+                    multi_select.on_change.emit(checked.clone());
+                }
+
+                true
+            }
+            FilterDropDownMsg::ItemSelect if self.multi_select.is_some() => {
+                let value = self
+                    .values
+                    .as_ref()
+                    .and_then(|values| values.get(self.selected).cloned());
+
+                match value {
+                    Some(value) => ctx
+                        .link()
+                        .send_message(FilterDropDownMsg::ItemToggle(value)),
+                    None => console::error_1(&"Selected out-of-bounds".into()),
+                };
+
+                false
+            }
             FilterDropDownMsg::ItemSelect => {
                 if let Some(ref values) = self.values {
                     match values.get(self.selected) {
@@ -111,8 +274,27 @@ impl Component for FilterDropDown {
         false
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
-        let body = html! {
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let body = if let Some(multi_select) = &self.multi_select {
+            self.view_multi_select(ctx, multi_select)
+        } else if let Some(range) = &self.range {
+            self.view_range(ctx, range)
+        } else {
+            self.view_single_select()
+        };
+
+        html_template! {
+            <style>
+                { &CSS }
+            </style>
+            { body }
+        }
+    }
+}
+
+impl FilterDropDown {
+    fn view_single_select(&self) -> Html {
+        html! {
             if let Some(ref values) = self.values {
                 if !values.is_empty() {
                     {
@@ -138,13 +320,101 @@ impl Component for FilterDropDown {
                     <span class="no-results">{ "No Completions" }</span>
                 }
             }
-        };
+        }
+    }
 
-        html_template! {
-            <style>
-                { &CSS }
-            </style>
-            { body }
+    /// Render a min and max input, bounded by the column's values.  A range
+    /// with `min > max` is not emitted.
+    fn view_range(&self, ctx: &Context<Self>, range: &FilterRange) -> Html {
+        let onfocusout = Callback::from({
+            let on_blur = range.on_blur.clone();
+            move |event: FocusEvent| {
+                let container = event.current_target().unwrap().unchecked_into::<Node>();
+                let related = event.related_target().map(|x| x.unchecked_into::<Node>());
+                if !container.contains(related.as_ref()) {
+                    on_blur.emit(());
+                }
+            }
+        });
+
+        let min_props = props!(NumberInputProps {
+            label: "Min",
+            max_value: range.min,
+            on_max_value: ctx.link().callback(FilterDropDownMsg::RangeMin),
+            min: range.bounds.map(|x| x.0),
+            max: range.bounds.map(|x| x.1)
+        });
+
+        let max_props = props!(NumberInputProps {
+            label: "Max",
+            max_value: range.max,
+            on_max_value: ctx.link().callback(FilterDropDownMsg::RangeMax),
+            min: range.bounds.map(|x| x.0),
+            max: range.bounds.map(|x| x.1)
+        });
+
+        html! {
+            <div id="range" onfocusout={ onfocusout }>
+                <NumberInput ..min_props />
+                <NumberInput ..max_props />
+                if range.min > range.max {
+                    <span class="range-error">{ "Min must not exceed Max" }</span>
+                }
+            </div>
+        }
+    }
+
+    /// Render the values as checkboxes, requesting the next page of values
+    /// when the list is scrolled near its end.
+    fn view_multi_select(&self, ctx: &Context<Self>, multi_select: &FilterMultiSelect) -> Html {
+        let onscroll = ctx.link().batch_callback(|event: Event| {
+            let elem = event.target()?.unchecked_into::<HtmlElement>();
+            let remaining = elem.scroll_height() - elem.scroll_top() - elem.client_height();
+            (remaining < 24).then(|| FilterDropDownMsg::LoadMore)
+        });
+
+        // Keep focus in the filter `<input>`, which closes this dropdown on
+        // blur.
+        let onmousedown = Callback::from(|event: MouseEvent| event.prevent_default());
+        let values = self.values.as_deref().unwrap_or_default();
+        let select_all = ctx
+            .link()
+            .callback(|_: MouseEvent| FilterDropDownMsg::SelectAll);
+        let clear_all = ctx
+            .link()
+            .callback(|_: MouseEvent| FilterDropDownMsg::ClearAll);
+        html! {
+            <div id="multi-select" onscroll={ onscroll } onmousedown={ onmousedown }>
+                if values.is_empty() && !multi_select.has_more {
+                    <span class="no-results">{ "No Values" }</span>
+                } else {
+                    <div id="multi-select-actions">
+                        <span id="select-all" onmousedown={ select_all }>{ "Select All" }</span>
+                        <span id="clear-all" onmousedown={ clear_all }>{ "Clear" }</span>
+                    </div>
+                }
+                {
+                    for values.iter().enumerate().map(|(idx, value)| {
+                        let toggle = ctx.link().callback({
+                            let value = value.clone();
+                            move |_: MouseEvent| FilterDropDownMsg::ItemToggle(value.clone())
+                        });
+
+                        let checked = multi_select.checked.contains(value);
+                        html! {
+                            <span
+                                class={ classes!((idx == self.selected).then(|| "selected")) }
+                                onmousedown={ toggle }>
+                                <input type="checkbox" tabindex="-1" checked={ checked }/>
+                                { value }
+                            </span>
+                        }
+                    })
+                }
+                if multi_select.has_more {
+                    <span class="no-results">{ "Loading ..." }</span>
+                }
+            </div>
         }
     }
 }
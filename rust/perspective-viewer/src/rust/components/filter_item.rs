@@ -35,8 +35,17 @@ pub enum FilterItemMsg {
     Close,
     FilterOpSelect(FilterOp),
     FilterKeyDown(u32),
+    FilterValuesSelect(Vec<String>),
+    ShowRelativeDates((usize, String)),
+    RelativeDateSelect(String),
+    ShowRange((usize, String)),
+    RangeBlur(Option<EventTarget>),
+    RangeSelect((f64, f64)),
 }
 
+/// Dropdown option which reverts a relative date filter to a fixed date.
+const FIXED_DATE_OPTION: &str = "fixed date";
+
 #[derive(Properties, Clone)]
 pub struct FilterItemProps {
     pub filter: Filter,
@@ -68,12 +77,24 @@ impl DragDropListItemProps for FilterItemProps {
 impl FilterItemProps {
     /// Does this filter item get a "suggestions" auto-complete modal?
     fn is_suggestable(&self) -> bool {
-        (self.filter.1 == FilterOp::EQ
-            || self.filter.1 == FilterOp::NE
-            || self.filter.1 == FilterOp::In)
+        (self.filter.1 == FilterOp::EQ || self.filter.1 == FilterOp::NE)
+            && self.get_filter_type() == Some(Type::String)
+    }
+
+    /// Does this filter item get a multi-value checkbox modal, e.g. for the
+    /// "in" and "not in" operators?
+    fn is_multi_select(&self) -> bool {
+        (self.filter.1 == FilterOp::In || self.filter.1 == FilterOp::NotIn)
             && self.get_filter_type() == Some(Type::String)
     }
 
+    /// Does this filter item get a min/max range modal, e.g. for the `>=` and
+    /// `<=` operators on a numeric column?
+    fn is_range(&self) -> bool {
+        matches!(self.filter.1, FilterOp::GTE | FilterOp::LTE)
+            && matches!(self.get_filter_type(), Some(Type::Integer | Type::Float))
+    }
+
     /// Get this filter's type, e.g. the type of the column.
     fn get_filter_type(&self) -> Option<Type> {
         self.session
@@ -123,6 +144,7 @@ impl FilterItemProps {
                 FilterOp::Contains,
                 FilterOp::EndsWith,
                 FilterOp::In,
+                FilterOp::NotIn,
                 FilterOp::IsNotNull,
                 FilterOp::IsNull,
             ],
@@ -143,13 +165,37 @@ impl FilterItemProps {
         }
     }
 
-    /// Update the filter comparison operator.
+    /// Update the filter comparison operator.  A `RelativeDateFilter` only
+    /// applies to `>=`, so for any other operator it is converted to its
+    /// current fixed date.
     ///
     /// # Arguments
     /// - `op` The new `FilterOp`.
     fn update_filter_op(&self, op: FilterOp) {
         let mut filter = self.session.get_view_config().filter.clone();
         let filter_item = &mut filter.get_mut(self.idx).expect("Filter on no column");
+        let is_multi = |op| matches!(op, FilterOp::In | FilterOp::NotIn);
+        match &filter_item.2 {
+            FilterTerm::Scalar(Scalar::String(x)) if is_multi(op) => {
+                filter_item.2 = FilterTerm::Array(
+                    x.split(',')
+                        .map(|x| x.trim())
+                        .filter(|x| !x.is_empty())
+                        .map(|x| Scalar::String(x.to_owned()))
+                        .collect(),
+                );
+            }
+            FilterTerm::Array(xs) if !is_multi(op) => {
+                filter_item.2 = FilterTerm::Scalar(xs.first().cloned().unwrap_or(Scalar::Null));
+            }
+            FilterTerm::Relative { relative } if op != FilterOp::GTE => {
+                filter_item.2 = self
+                    .session
+                    .resolve_relative_date(&filter_item.0, *relative);
+            }
+            _ => (),
+        };
+
         filter_item.1 = op;
         let update = ViewConfigUpdate {
             filter: Some(filter),
@@ -167,7 +213,7 @@ impl FilterItemProps {
         let mut filter = self.session.get_view_config().filter.clone();
         let filter_item = &mut filter.get_mut(self.idx).expect("Filter on no column");
         let filter_input = match filter_item.1 {
-            FilterOp::In => Some(FilterTerm::Array(
+            FilterOp::In | FilterOp::NotIn => Some(FilterTerm::Array(
                 val.split(',')
                     .map(|x| Scalar::String(x.trim().to_owned()))
                     .collect(),
@@ -222,6 +268,69 @@ impl FilterItemProps {
             self.update_and_render(update);
         }
     }
+
+    /// Set or clear a `RelativeDateFilter` preset for a `Date`/`Datetime`
+    /// filter, which always selects dates on or after the preset's start.
+    ///
+    /// # Arguments
+    /// - `relative` The new preset, or `None` to revert to a fixed date.
+    fn update_relative_date(&self, relative: Option<RelativeDateFilter>) {
+        let mut filter = self.session.get_view_config().filter.clone();
+        let filter_item = &mut filter.get_mut(self.idx).expect("Filter on no column");
+        match relative {
+            Some(relative) => {
+                filter_item.1 = FilterOp::GTE;
+                filter_item.2 = FilterTerm::Relative { relative };
+            }
+            None => filter_item.2 = FilterTerm::Scalar(Scalar::Null),
+        };
+
+        let update = ViewConfigUpdate {
+            filter: Some(filter),
+            ..ViewConfigUpdate::default()
+        };
+
+        self.update_and_render(update);
+    }
+
+    /// Update this filter and its `>=`/`<=` partner from the range dropdown,
+    /// adding the partner if this filter is not yet a range.
+    ///
+    /// # Arguments
+    /// - `min` The new lower bound.
+    /// - `max` The new upper bound.
+    fn update_filter_range(&self, min: f64, max: f64) {
+        let (min, max) = match self.get_filter_type() {
+            Some(Type::Integer) => (min.ceil(), max.floor()),
+            _ => (min, max),
+        };
+
+        let mut filter = self.session.get_view_config().filter.clone();
+        set_filter_range(&mut filter, self.idx, min, max);
+        let update = ViewConfigUpdate {
+            filter: Some(filter),
+            ..ViewConfigUpdate::default()
+        };
+
+        self.update_and_render(update);
+    }
+
+    /// Update the filter value from the values checked in the "in"/"not in"
+    /// dropdown.
+    ///
+    /// # Arguments
+    /// - `values` The new filter values.
+    fn update_filter_values(&self, values: Vec<String>) {
+        let mut filter = self.session.get_view_config().filter.clone();
+        let filter_item = &mut filter.get_mut(self.idx).expect("Filter on no column");
+        filter_item.2 = FilterTerm::Array(values.into_iter().map(Scalar::String).collect());
+        let update = ViewConfigUpdate {
+            filter: Some(filter),
+            ..ViewConfigUpdate::default()
+        };
+
+        self.update_and_render(update);
+    }
 }
 
 type FilterOpSelector = Select<FilterOp>;
@@ -260,14 +369,23 @@ impl Component for FilterItem {
                 if ctx.props().is_suggestable() {
                     ctx.props().filter_dropdown.autocomplete(
                         column,
-                        if ctx.props().filter.1 == FilterOp::In {
-                            input.split(',').last().unwrap().to_owned()
-                        } else {
-                            input.clone()
-                        },
+                        input.clone(),
                         target.unchecked_into(),
                         ctx.props().on_keydown.clone(),
                     );
+                } else if ctx.props().is_multi_select() {
+                    let checked = input
+                        .split(',')
+                        .map(|x| x.trim().to_owned())
+                        .filter(|x| !x.is_empty())
+                        .collect();
+
+                    ctx.props().filter_dropdown.select_multiple(
+                        column,
+                        checked,
+                        target.unchecked_into(),
+                        ctx.link().callback(FilterItemMsg::FilterValuesSelect),
+                    );
                 }
 
                 ctx.props().update_filter_input(input);
@@ -277,6 +395,8 @@ impl Component for FilterItem {
                 if ctx.props().is_suggestable() {
                     ctx.props().filter_dropdown.item_down();
                     ctx.props().filter_dropdown.item_select();
+                } else if ctx.props().is_multi_select() {
+                    ctx.props().filter_dropdown.item_down();
                 }
                 false
             }
@@ -284,6 +404,8 @@ impl Component for FilterItem {
                 if ctx.props().is_suggestable() {
                     ctx.props().filter_dropdown.item_up();
                     ctx.props().filter_dropdown.item_select();
+                } else if ctx.props().is_multi_select() {
+                    ctx.props().filter_dropdown.item_up();
                 }
                 false
             }
@@ -295,6 +417,8 @@ impl Component for FilterItem {
                 if ctx.props().is_suggestable() {
                     ctx.props().filter_dropdown.item_select();
                     ctx.props().filter_dropdown.hide().unwrap();
+                } else if ctx.props().is_multi_select() {
+                    ctx.props().filter_dropdown.item_select();
                 }
                 false
             }
@@ -308,6 +432,65 @@ impl Component for FilterItem {
                 ctx.props().update_filter_op(op);
                 true
             }
+            FilterItemMsg::FilterValuesSelect(values) => {
+                ctx.props().update_filter_values(values);
+                false
+            }
+            FilterItemMsg::ShowRelativeDates(column) => {
+                // Focus the input so its `blur` closes the dropdown.
+                let target = self.input_ref.cast::<HtmlElement>().unwrap();
+                target.focus().unwrap();
+                let mut values = RelativeDateFilter::ALL
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<_>>();
+
+                if matches!(ctx.props().filter.2, FilterTerm::Relative { .. }) {
+                    values.push(FIXED_DATE_OPTION.to_owned());
+                }
+
+                ctx.props().filter_dropdown.suggest(
+                    column,
+                    values,
+                    target,
+                    ctx.link().callback(FilterItemMsg::RelativeDateSelect),
+                );
+
+                false
+            }
+            FilterItemMsg::RelativeDateSelect(value) => {
+                ctx.props().filter_dropdown.hide().unwrap();
+                ctx.props().update_relative_date(value.parse().ok());
+                false
+            }
+            FilterItemMsg::ShowRange(column) => {
+                if ctx.props().is_range() {
+                    let target = self.input_ref.cast::<HtmlElement>().unwrap();
+                    let filter = &ctx.props().session.get_view_config().filter;
+                    let current = get_filter_range(filter, ctx.props().idx);
+                    ctx.props().filter_dropdown.select_range(
+                        column,
+                        current,
+                        target,
+                        ctx.link().callback(FilterItemMsg::RangeSelect),
+                    );
+                }
+
+                false
+            }
+            FilterItemMsg::RangeBlur(related) => {
+                // Focus moving into the range inputs should not close them.
+                let dropdown = &ctx.props().filter_dropdown;
+                if !related.map_or(false, |x| dropdown.is_target(&x)) {
+                    dropdown.hide().unwrap();
+                }
+
+                false
+            }
+            FilterItemMsg::RangeSelect((min, max)) => {
+                ctx.props().update_filter_range(min, max);
+                false
+            }
         }
     }
 
@@ -347,6 +530,23 @@ impl Component for FilterItem {
             }
         });
 
+        let relative_mousedown = ctx.link().callback({
+            let column = column.clone();
+            move |event: MouseEvent| {
+                event.prevent_default();
+                FilterItemMsg::ShowRelativeDates((idx, column.clone()))
+            }
+        });
+
+        let range_focus = ctx.link().callback({
+            let column = column.clone();
+            move |_: FocusEvent| FilterItemMsg::ShowRange((idx, column.clone()))
+        });
+
+        let range_blur = ctx
+            .link()
+            .callback(|event: FocusEvent| FilterItemMsg::RangeBlur(event.related_target()));
+
         let focus = ctx.link().callback({
             let input = self.input.clone();
             move |_: FocusEvent| FilterItemMsg::FilterInput((idx, column.clone()), input.clone())
@@ -380,7 +580,18 @@ impl Component for FilterItem {
             _ => "",
         };
 
+        let is_relative = matches!(filter.2, FilterTerm::Relative { .. });
         let input_elem = match col_type {
+            Some(Type::Date | Type::Datetime) if is_relative => html! {
+                <input
+                    type="text"
+                    size="4"
+                    readonly=true
+                    class="relative-date-filter"
+                    ref={ noderef.clone() }
+                    onblur={ blur }
+                    value={ self.input.clone() }/>
+            },
             Some(Type::Integer) => html! {
                 <input
                     type="number"
@@ -389,6 +600,8 @@ impl Component for FilterItem {
                     step="1"
                     ref={ noderef.clone() }
                     onkeydown={ keydown }
+                    onfocus={ range_focus }
+                    onblur={ range_blur }
                     value={ self.input.clone() }
                     oninput={ input }/>
             },
@@ -399,6 +612,8 @@ impl Component for FilterItem {
                     class="num-filter"
                     ref={ noderef.clone() }
                     onkeydown={ keydown }
+                    onfocus={ range_focus }
+                    onblur={ range_blur }
                     value={ self.input.clone() }
                     oninput={ input }/>
             },
@@ -424,6 +639,7 @@ impl Component for FilterItem {
                     class="date-filter"
                     ref={ noderef.clone() }
                     onkeydown={ keydown }
+                    onblur={ blur }
                     value={ self.input.clone() }
                     oninput={ input }/>
             },
@@ -435,6 +651,7 @@ impl Component for FilterItem {
                     step="0.001"
                     ref={ noderef.clone() }
                     onkeydown={ keydown }
+                    onblur={ blur }
                     value={ self.input.clone() }
                     oninput={ input }/>
             },
@@ -489,6 +706,13 @@ impl Component for FilterItem {
                             input_elem
                         }
                     </label>
+                    if matches!(col_type, Some(Type::Date | Type::Datetime)) {
+                        <span
+                            class="relative-date-button"
+                            title="Relative Dates"
+                            onmousedown={ relative_mousedown }>
+                        </span>
+                    }
                 }
             }
         }
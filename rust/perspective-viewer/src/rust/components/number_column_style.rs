@@ -10,10 +10,13 @@ use super::color_range_selector::*;
 use super::containers::number_input::*;
 use super::containers::radio_list::RadioList;
 use super::containers::radio_list_item::RadioListItem;
+use super::containers::select::*;
 use super::modal::*;
 use crate::config::*;
-use crate::utils::WeakScope;
+use crate::utils::{is_same_color, normalize_color, validate_color, WeakScope};
 use crate::*;
+use itertools::Itertools;
+use lazy_static::*;
 use wasm_bindgen::*;
 use web_sys::*;
 use yew::prelude::*;
@@ -23,12 +26,35 @@ pub static CSS: &str = include_str!("../../../build/css/column-style.css");
 
 type Side = bool;
 
+/// The value formatted by the notation preview in `make_fixed_text()`.
+const NOTATION_SAMPLE: f64 = 1234.5678;
+
+/// Common locales offered by the locale `Select`, which differ in their
+/// decimal and grouping separators.
+const NUMBER_LOCALES: &[&str] = &[
+    "en-US", "en-GB", "en-IN", "de-DE", "de-CH", "fr-FR", "es-ES", "it-IT", "nl-NL", "pt-BR",
+    "ru-RU", "ja-JP", "zh-CN",
+];
+
+lazy_static! {
+    static ref USER_LOCALE: String = js_sys::Reflect::get(
+        &js_sys::Intl::NumberFormat::new(&json!([]), &json!({})).resolved_options(),
+        &JsValue::from("locale")
+    )
+    .unwrap()
+    .as_string()
+    .unwrap();
+}
+
 pub enum NumberColumnStyleMsg {
     Reset(
         Box<NumberColumnStyleConfig>,
         Box<NumberColumnStyleDefaultConfig>,
+        NumberColumnStyleUiState,
     ),
     FixedChanged(String),
+    NotationChanged(NumberNotation),
+    LocaleChanged(String),
     ForeEnabledChanged(bool),
     BackEnabledChanged(bool),
     PosColorChanged(Side, String),
@@ -36,6 +62,16 @@ pub enum NumberColumnStyleMsg {
     NumberForeModeChanged(NumberForegroundMode),
     NumberBackModeChanged(NumberBackgroundMode),
     GradientChanged(Side, String),
+    PulseDurationChanged(String),
+    TextContrastChanged(bool),
+    HideZerosChanged(bool),
+    ColorSidesChanged(ColorSides),
+    IconRuleAdded,
+    IconRuleRemoved(usize),
+    IconRuleChanged(usize, IconRule),
+    ColorBandAdded,
+    ColorBandRemoved(usize),
+    ColorBandChanged(usize, ColorBand),
 }
 
 /// A `ColumnStyle` component is mounted to the window anchored at the screen
@@ -49,9 +85,15 @@ pub struct NumberColumnStyleProps {
     #[prop_or_default]
     pub default_config: NumberColumnStyleDefaultConfig,
 
+    #[prop_or_default]
+    pub ui_state: NumberColumnStyleUiState,
+
     #[prop_or_default]
     pub on_change: Callback<NumberColumnStyleConfig>,
 
+    #[prop_or_default]
+    pub on_ui_state_change: Callback<NumberColumnStyleUiState>,
+
     #[prop_or_default]
     pub weak_link: WeakScope<NumberColumnStyle>,
 }
@@ -83,6 +125,23 @@ pub struct NumberColumnStyle {
     neg_bg_color: String,
     fg_gradient: f64,
     bg_gradient: f64,
+    pulse_duration_ms: u32,
+
+    /// Whether the fixed precision input holds text which is not a valid
+    /// precision.  This is view state only, and never affects `config`.
+    fixed_invalid: bool,
+
+    /// Stable `key`s for the rows of the icon rules list, parallel to
+    /// `config.icon_rules`, so removing a rule does not move focus between
+    /// the inputs of its siblings.
+    icon_rule_keys: Vec<usize>,
+    next_icon_rule_key: usize,
+
+    /// The `Bands` background steps, kept while another mode is selected so
+    /// switching back restores them, and their stable `key`s.
+    color_bands: Vec<ColorBand>,
+    color_band_keys: Vec<usize>,
+    next_color_band_key: usize,
 }
 
 impl Component for NumberColumnStyle {
@@ -91,23 +150,27 @@ impl Component for NumberColumnStyle {
 
     fn create(ctx: &Context<Self>) -> Self {
         ctx.set_modal_link();
-        NumberColumnStyle::reset(&ctx.props().config, &ctx.props().default_config)
+        let props = ctx.props();
+        NumberColumnStyle::reset(&props.config, &props.default_config, &props.ui_state)
     }
 
     fn changed(&mut self, ctx: &Context<Self>) -> bool {
-        let mut new = NumberColumnStyle::reset(&ctx.props().config, &ctx.props().default_config);
+        let props = ctx.props();
+        let mut new =
+            NumberColumnStyle::reset(&props.config, &props.default_config, &props.ui_state);
         std::mem::swap(self, &mut new);
         true
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            NumberColumnStyleMsg::Reset(config, default_config) => {
-                let mut new = NumberColumnStyle::reset(&config, &default_config);
+            NumberColumnStyleMsg::Reset(config, default_config, ui_state) => {
+                let mut new = NumberColumnStyle::reset(&config, &default_config, &ui_state);
                 std::mem::swap(self, &mut new);
                 true
             }
             NumberColumnStyleMsg::FixedChanged(fixed) => {
+                self.fixed_invalid = !fixed.is_empty() && fixed.parse::<u32>().is_err();
                 let fixed = match fixed.parse::<u32>() {
                     Ok(x) if x != ctx.props().default_config.fixed => Some(x),
                     Ok(_) => None,
@@ -115,7 +178,27 @@ impl Component for NumberColumnStyle {
                     Err(_) => None,
                 };
 
-                self.config.fixed = fixed.map(|x| std::cmp::min(15, x));
+                let max_precision = ctx.props().default_config.max_precision;
+                self.config.fixed = fixed.map(|x| std::cmp::min(max_precision, x));
+                self.dispatch_config(ctx);
+                true
+            }
+            NumberColumnStyleMsg::NotationChanged(notation) => {
+                self.config.notation = match notation {
+                    NumberNotation::Standard => None,
+                    x => Some(x),
+                };
+
+                self.dispatch_config(ctx);
+                true
+            }
+            NumberColumnStyleMsg::LocaleChanged(locale) => {
+                if *USER_LOCALE != locale {
+                    self.config.locale = Some(locale);
+                } else {
+                    self.config.locale = None;
+                }
+
                 self.dispatch_config(ctx);
                 true
             }
@@ -142,6 +225,7 @@ impl Component for NumberColumnStyle {
                 }
 
                 self.dispatch_config(ctx);
+                self.dispatch_ui_state(ctx);
                 true
             }
             NumberColumnStyleMsg::BackEnabledChanged(val) => {
@@ -164,12 +248,23 @@ impl Component for NumberColumnStyle {
                     self.config.pos_bg_color = None;
                     self.config.neg_bg_color = None;
                     self.config.bg_gradient = None;
+                    self.config.auto_text_contrast = None;
                 }
 
+                self.config.pulse_duration_ms = self.configured_pulse_duration(ctx);
+                self.config.color_bands = self.configured_color_bands();
                 self.dispatch_config(ctx);
+                self.dispatch_ui_state(ctx);
                 true
             }
             NumberColumnStyleMsg::PosColorChanged(side, val) => {
+                let default_config = &ctx.props().default_config;
+                let val = if side {
+                    validate_color(&val, &default_config.pos_fg_color)
+                } else {
+                    validate_color(&val, &default_config.pos_bg_color)
+                };
+
                 if side {
                     self.pos_fg_color = val;
                     self.config.pos_fg_color = Some(self.pos_fg_color.to_owned());
@@ -179,9 +274,16 @@ impl Component for NumberColumnStyle {
                 }
 
                 self.dispatch_config(ctx);
-                false
+                true
             }
             NumberColumnStyleMsg::NegColorChanged(side, val) => {
+                let default_config = &ctx.props().default_config;
+                let val = if side {
+                    validate_color(&val, &default_config.neg_fg_color)
+                } else {
+                    validate_color(&val, &default_config.neg_bg_color)
+                };
+
                 if side {
                     self.neg_fg_color = val;
                     self.config.neg_fg_color = Some(self.neg_fg_color.to_owned());
@@ -191,7 +293,7 @@ impl Component for NumberColumnStyle {
                 }
 
                 self.dispatch_config(ctx);
-                false
+                true
             }
             NumberColumnStyleMsg::NumberForeModeChanged(val) => {
                 self.fg_mode = val;
@@ -203,6 +305,7 @@ impl Component for NumberColumnStyle {
                 }
 
                 self.dispatch_config(ctx);
+                self.dispatch_ui_state(ctx);
                 true
             }
             NumberColumnStyleMsg::NumberBackModeChanged(val) => {
@@ -214,7 +317,14 @@ impl Component for NumberColumnStyle {
                     self.config.bg_gradient = None;
                 }
 
+                if !self.bg_mode.is_fill() {
+                    self.config.auto_text_contrast = None;
+                }
+
+                self.config.pulse_duration_ms = self.configured_pulse_duration(ctx);
+                self.config.color_bands = self.configured_color_bands();
                 self.dispatch_config(ctx);
+                self.dispatch_ui_state(ctx);
                 true
             }
             NumberColumnStyleMsg::GradientChanged(side, gradient) => {
@@ -248,6 +358,97 @@ impl Component for NumberColumnStyle {
                 self.dispatch_config(ctx);
                 false
             }
+            NumberColumnStyleMsg::PulseDurationChanged(duration) => {
+                self.pulse_duration_ms = duration
+                    .parse::<u32>()
+                    .unwrap_or(ctx.props().default_config.pulse_duration_ms);
+
+                self.config.pulse_duration_ms = self.configured_pulse_duration(ctx);
+                self.dispatch_config(ctx);
+                false
+            }
+            NumberColumnStyleMsg::TextContrastChanged(val) => {
+                self.config.auto_text_contrast = Some(true).filter(|_| val);
+                self.dispatch_config(ctx);
+                false
+            }
+            NumberColumnStyleMsg::HideZerosChanged(val) => {
+                self.config.hide_zeros = Some(true).filter(|_| val);
+                self.dispatch_config(ctx);
+                false
+            }
+            NumberColumnStyleMsg::ColorSidesChanged(color_sides) => {
+                self.config.color_sides = color_sides;
+                self.dispatch_config(ctx);
+                true
+            }
+            NumberColumnStyleMsg::IconRuleAdded => {
+                self.config
+                    .icon_rules
+                    .get_or_insert_with(Vec::new)
+                    .push(IconRule::default());
+
+                self.icon_rule_keys.push(self.next_icon_rule_key);
+                self.next_icon_rule_key += 1;
+                self.dispatch_config(ctx);
+                true
+            }
+            NumberColumnStyleMsg::IconRuleRemoved(idx) => {
+                let mut rules = self.config.icon_rules.take().unwrap_or_default();
+                if idx < rules.len() {
+                    rules.remove(idx);
+                    self.icon_rule_keys.remove(idx);
+                }
+
+                self.config.icon_rules = if rules.is_empty() { None } else { Some(rules) };
+                self.dispatch_config(ctx);
+                true
+            }
+            NumberColumnStyleMsg::IconRuleChanged(idx, rule) => {
+                let rules = self.config.icon_rules.as_mut();
+                if let Some(x) = rules.and_then(|rules| rules.get_mut(idx)) {
+                    *x = rule;
+                }
+
+                self.dispatch_config(ctx);
+                true
+            }
+            NumberColumnStyleMsg::ColorBandAdded => {
+                let threshold = self.color_bands.last().map_or(0.0, |x| x.threshold);
+                let color = normalize_color(&self.pos_bg_color)
+                    .unwrap_or_else(|| ctx.props().default_config.pos_bg_color.to_owned());
+
+                self.color_bands.push(ColorBand { threshold, color });
+
+                self.color_band_keys.push(self.next_color_band_key);
+                self.next_color_band_key += 1;
+                self.config.color_bands = self.configured_color_bands();
+                self.dispatch_config(ctx);
+                true
+            }
+            NumberColumnStyleMsg::ColorBandRemoved(idx) => {
+                if idx < self.color_bands.len() {
+                    self.color_bands.remove(idx);
+                    self.color_band_keys.remove(idx);
+                }
+
+                self.config.color_bands = self.configured_color_bands();
+                self.dispatch_config(ctx);
+                true
+            }
+            NumberColumnStyleMsg::ColorBandChanged(idx, band) => {
+                let default_color = &ctx.props().default_config.pos_bg_color;
+                if let Some(x) = self.color_bands.get_mut(idx) {
+                    *x = ColorBand {
+                        color: validate_color(&band.color, default_color),
+                        ..band
+                    };
+                }
+
+                self.config.color_bands = self.configured_color_bands();
+                self.dispatch_config(ctx);
+                true
+            }
         }
     }
 
@@ -286,6 +487,22 @@ impl Component for NumberColumnStyle {
             NumberColumnStyleMsg::BackEnabledChanged(input.checked())
         });
 
+        let text_contrast_oninput = ctx.link().callback(move |event: InputEvent| {
+            let input = event
+                .target()
+                .unwrap()
+                .unchecked_into::<web_sys::HtmlInputElement>();
+            NumberColumnStyleMsg::TextContrastChanged(input.checked())
+        });
+
+        let hide_zeros_oninput = ctx.link().callback(move |event: InputEvent| {
+            let input = event
+                .target()
+                .unwrap()
+                .unchecked_into::<web_sys::HtmlInputElement>();
+            NumberColumnStyleMsg::HideZerosChanged(input.checked())
+        });
+
         let selected_fg_mode = match self.fg_mode {
             NumberForegroundMode::Disabled => NumberForegroundMode::default(),
             x => x,
@@ -310,6 +527,7 @@ impl Component for NumberColumnStyle {
             if self.config.number_fg_mode == NumberForegroundMode::Color {
                 <div class="row inner_section">
                     <ColorRangeSelector ..self.color_props(true, ctx) />
+                    { self.gradient_preview(true, false) }
                 </div>
             }
         };
@@ -320,6 +538,16 @@ impl Component for NumberColumnStyle {
                 <div class="row inner_section">
                     <ColorRangeSelector ..self.color_props(true, ctx) />
                     <NumberInput ..self.max_value_props(true, ctx) />
+                    { self.gradient_preview(true, true) }
+                </div>
+            }
+        };
+
+        let fg_sparkline_controls = html_template! {
+            <span class="row">{ "Sparkline" }</span>
+            if self.config.number_fg_mode == NumberForegroundMode::Sparkline {
+                <div class="row inner_section">
+                    <ColorRangeSelector ..self.color_props(true, ctx) />
                 </div>
             }
         };
@@ -329,6 +557,7 @@ impl Component for NumberColumnStyle {
             if self.config.number_bg_mode == NumberBackgroundMode::Color {
                 <div class="row inner_section">
                     <ColorRangeSelector ..self.color_props(false, ctx) />
+                    { self.gradient_preview(false, false) }
                 </div>
             }
         };
@@ -339,6 +568,7 @@ impl Component for NumberColumnStyle {
                 <div class="row inner_section">
                     <ColorRangeSelector ..self.color_props(false, ctx) />
                     <NumberInput ..self.max_value_props(false, ctx) />
+                    { self.gradient_preview(false, true) }
                 </div>
             }
         };
@@ -348,10 +578,51 @@ impl Component for NumberColumnStyle {
             if self.config.number_bg_mode == NumberBackgroundMode::Pulse {
                 <div class="row inner_section">
                     <ColorRangeSelector ..self.color_props(false, ctx) />
+                    <NumberInput ..self.pulse_duration_props(ctx) />
+                </div>
+            }
+        };
+
+        let color_bands = self
+            .color_bands
+            .iter()
+            .zip(self.color_band_keys.iter())
+            .enumerate()
+            .map(|(idx, (band, key))| self.color_band_row(ctx, idx, band, *key))
+            .collect::<Html>();
+
+        let on_color_band_add = ctx
+            .link()
+            .callback(|_| NumberColumnStyleMsg::ColorBandAdded);
+        let bg_bands_controls = html_template! {
+            <span class="row">{ "Bands" }</span>
+            if self.config.number_bg_mode == NumberBackgroundMode::Bands {
+                <div id="color-bands" class="inner_section">
+                    { color_bands }
+                    <span
+                        id="color-band-add"
+                        class="color-band-button"
+                        onclick={ on_color_band_add }>
+                        { "+ Add Band" }
+                    </span>
                 </div>
             }
         };
 
+        let icon_rules = self
+            .config
+            .icon_rules
+            .iter()
+            .flatten()
+            .zip(self.icon_rule_keys.iter())
+            .enumerate()
+            .map(|(idx, (rule, key))| self.icon_rule_row(ctx, idx, rule, *key))
+            .collect::<Html>();
+
+        let on_icon_rule_add = ctx.link().callback(|_| NumberColumnStyleMsg::IconRuleAdded);
+        let is_color_enabled =
+            self.config.number_fg_mode.is_enabled() || !self.config.number_bg_mode.is_disabled();
+
         html_template! {
             <style>
                 { &CSS }
@@ -366,13 +637,48 @@ impl Component for NumberColumnStyle {
                     <input type="checkbox" checked=true disabled=true/>
                     <input
                         id="fixed-param"
-                        class="parameter"
+                        class={ classes!("parameter", self.fixed_invalid.then(|| "invalid")) }
                         type="number"
                         min="0"
+                        max={ ctx.props().default_config.max_precision.to_string() }
                         step="1"
                         value={ fixed_value }
                         oninput={ fixed_oninput }/>
                 </div>
+                <div class="column-style-label">
+                    <label class="indent">{ "Notation" }</label>
+                </div>
+                <div class="row section">
+                    <input type="checkbox" checked=true disabled=true/>
+                    <Select<NumberNotation>
+                        id="notation-param"
+                        selected={ self.config.notation.unwrap_or_default() }
+                        on_select={ ctx.link().callback(NumberColumnStyleMsg::NotationChanged) }
+                        values={ NumberNotation::values().iter().map(|x| SelectItem::Option(*x)).collect::<Vec<_>>() } >
+                    </Select<NumberNotation>>
+                </div>
+                <div class="column-style-label">
+                    <label class="indent">{ "Locale" }</label>
+                </div>
+                <div class="row section">
+                    <input type="checkbox" checked=true disabled=true/>
+                    <Select<String>
+                        id="locale-param"
+                        selected={ self.config.locale.as_ref().unwrap_or(&*USER_LOCALE).clone() }
+                        on_select={ ctx.link().callback(NumberColumnStyleMsg::LocaleChanged) }
+                        values={ self.locale_items() } >
+                    </Select<String>>
+                </div>
+                <div class="column-style-label">
+                    <label class="indent">{ "Zeros" }</label>
+                </div>
+                <div id="hide-zeros" class="row section">
+                    <input
+                        type="checkbox"
+                        oninput={ hide_zeros_oninput }
+                        checked={ self.config.hide_zeros.unwrap_or_default() } />
+                    <span>{ "Hide" }</span>
+                </div>
                 <div class="column-style-label">
                     <label class="indent">{ "Foreground" }</label>
                 </div>
@@ -396,6 +702,10 @@ impl Component for NumberColumnStyle {
                             value={ NumberForegroundMode::Bar }>
                             { fg_bar_controls }
                         </RadioListItem<NumberForegroundMode>>
+                        <RadioListItem<NumberForegroundMode>
+                            value={ NumberForegroundMode::Sparkline }>
+                            { fg_sparkline_controls }
+                        </RadioListItem<NumberForegroundMode>>
                     </RadioList<NumberForegroundMode>>
                 </div>
                 <div class="column-style-label">
@@ -425,7 +735,46 @@ impl Component for NumberColumnStyle {
                             value={ NumberBackgroundMode::Pulse }>
                             { bg_pulse_controls }
                         </RadioListItem<NumberBackgroundMode>>
+                        <RadioListItem<NumberBackgroundMode>
+                            value={ NumberBackgroundMode::Bands }>
+                            { bg_bands_controls }
+                        </RadioListItem<NumberBackgroundMode>>
                     </RadioList<NumberBackgroundMode>>
+                    if self.config.number_bg_mode.is_fill() {
+                        <div id="text-contrast" class="row indent">
+                            <input
+                                type="checkbox"
+                                oninput={ text_contrast_oninput }
+                                checked={ self.config.auto_text_contrast.unwrap_or_default() } />
+                            <span>{ "Contrast Text" }</span>
+                        </div>
+                    }
+                </div>
+                if is_color_enabled {
+                    <div class="column-style-label">
+                        <label class="indent">{ "Color Sides" }</label>
+                    </div>
+                    <div class="row section">
+                        <input type="checkbox" checked=true disabled=true/>
+                        <Select<ColorSides>
+                            id="color-sides-param"
+                            selected={ self.config.color_sides }
+                            on_select={ ctx.link().callback(NumberColumnStyleMsg::ColorSidesChanged) }
+                            values={ ColorSides::values().iter().map(|x| SelectItem::Option(*x)).collect::<Vec<_>>() } >
+                        </Select<ColorSides>>
+                    </div>
+                }
+                <div class="column-style-label">
+                    <label class="indent">{ "Icons" }</label>
+                </div>
+                <div id="icon-rules" class="section">
+                    { icon_rules }
+                    <span
+                        id="icon-rule-add"
+                        class="icon-rule-button indent"
+                        onclick={ on_icon_rule_add }>
+                        { "+ Add Icon" }
+                    </span>
                 </div>
             </div>
         }
@@ -441,8 +790,8 @@ impl NumberColumnStyle {
                 pos_fg_color: Some(pos_color),
                 neg_fg_color: Some(neg_color),
                 ..
-            } if *pos_color == ctx.props().default_config.pos_fg_color
-                && *neg_color == ctx.props().default_config.neg_fg_color =>
+            } if is_same_color(pos_color, &ctx.props().default_config.pos_fg_color)
+                && is_same_color(neg_color, &ctx.props().default_config.neg_fg_color) =>
             {
                 config.pos_fg_color = None;
                 config.neg_fg_color = None;
@@ -455,8 +804,8 @@ impl NumberColumnStyle {
                 pos_bg_color: Some(pos_color),
                 neg_bg_color: Some(neg_color),
                 ..
-            } if *pos_color == ctx.props().default_config.pos_bg_color
-                && *neg_color == ctx.props().default_config.neg_bg_color =>
+            } if is_same_color(pos_color, &ctx.props().default_config.pos_bg_color)
+                && is_same_color(neg_color, &ctx.props().default_config.neg_bg_color) =>
             {
                 config.pos_bg_color = None;
                 config.neg_bg_color = None;
@@ -464,9 +813,30 @@ impl NumberColumnStyle {
             _ => {}
         };
 
+        if !self.config.color_sides.has_pos() {
+            config.pos_fg_color = None;
+            config.pos_bg_color = None;
+        }
+
+        if !self.config.color_sides.has_neg() {
+            config.neg_fg_color = None;
+            config.neg_bg_color = None;
+        }
+
         ctx.props().on_change.emit(config);
     }
 
+    /// Report the radio selections of the color sections, which the plugin
+    /// keeps as this column's `NumberColumnStyleUiState`.
+    fn dispatch_ui_state(&self, ctx: &Context<Self>) {
+        let ui_state = NumberColumnStyleUiState {
+            fg_mode: Some(self.fg_mode).filter(|x| x.is_enabled()),
+            bg_mode: Some(self.bg_mode).filter(|x| !x.is_disabled()),
+        };
+
+        ctx.props().on_ui_state_change.emit(ui_state);
+    }
+
     fn color_props(&self, side: bool, ctx: &Context<Self>) -> ColorRangeProps {
         let on_pos_color = ctx
             .link()
@@ -489,10 +859,197 @@ impl NumberColumnStyle {
             }
             .to_owned(),
             on_pos_color,
-            on_neg_color
+            on_neg_color,
+            show_pos: self.config.color_sides.has_pos(),
+            show_neg: self.config.color_sides.has_neg()
         })
     }
 
+    /// A swatch previewing how cell colors range from the most negative to
+    /// the most positive value.  Gradient modes fade through transparent at
+    /// zero, while solid color modes split abruptly at zero.  The ramp is
+    /// symmetric and does not reflect the max value, which only sets where
+    /// cells reach full color.  A side disabled by `color_sides` is
+    /// transparent.
+    fn gradient_preview(&self, side: bool, is_gradient: bool) -> Html {
+        let (pos_color, neg_color) = if side {
+            (&self.pos_fg_color, &self.neg_fg_color)
+        } else {
+            (&self.pos_bg_color, &self.neg_bg_color)
+        };
+
+        let color_sides = self.config.color_sides;
+        let pos_color = if color_sides.has_pos() {
+            pos_color.as_str()
+        } else {
+            "transparent"
+        };
+
+        let neg_color = if color_sides.has_neg() {
+            neg_color.as_str()
+        } else {
+            "transparent"
+        };
+
+        let style = if is_gradient {
+            format!(
+                "background:linear-gradient(to right,{},transparent,{})",
+                neg_color, pos_color
+            )
+        } else {
+            format!(
+                "background:linear-gradient(to right,{} 50%,{} 50%)",
+                neg_color, pos_color
+            )
+        };
+
+        html! {
+            <div class="gradient-preview" style={ style }></div>
+        }
+    }
+
+    /// One editable row of the icon rules list:  the comparison, its
+    /// threshold, the icon text and a remove button.
+    fn icon_rule_row(&self, ctx: &Context<Self>, idx: usize, rule: &IconRule, key: usize) -> Html {
+        let on_op = ctx.link().callback({
+            let rule = rule.clone();
+            move |op| NumberColumnStyleMsg::IconRuleChanged(idx, IconRule { op, ..rule.clone() })
+        });
+
+        let on_threshold = ctx.link().batch_callback({
+            let rule = rule.clone();
+            move |event: InputEvent| {
+                let threshold = event
+                    .target()
+                    .unwrap()
+                    .unchecked_into::<web_sys::HtmlInputElement>()
+                    .value()
+                    .parse::<f64>()
+                    .ok()?;
+
+                let rule = IconRule {
+                    threshold,
+                    ..rule.clone()
+                };
+
+                Some(NumberColumnStyleMsg::IconRuleChanged(idx, rule))
+            }
+        });
+
+        let on_icon = ctx.link().callback({
+            let rule = rule.clone();
+            move |event: InputEvent| {
+                let icon = event
+                    .target()
+                    .unwrap()
+                    .unchecked_into::<web_sys::HtmlInputElement>()
+                    .value();
+
+                NumberColumnStyleMsg::IconRuleChanged(idx, IconRule {
+                    icon,
+                    ..rule.clone()
+                })
+            }
+        });
+
+        let on_remove = ctx
+            .link()
+            .callback(move |_| NumberColumnStyleMsg::IconRuleRemoved(idx));
+
+        html! {
+            <div class="row icon-rule indent" key={ key }>
+                <Select<IconComparison>
+                    class="icon-rule-op"
+                    selected={ rule.op }
+                    on_select={ on_op }
+                    values={ IconComparison::values().iter().map(|x| SelectItem::Option(*x)).collect::<Vec<_>>() } >
+                </Select<IconComparison>>
+                <input
+                    class="parameter icon-rule-threshold"
+                    type="number"
+                    value={ rule.threshold.to_string() }
+                    oninput={ on_threshold } />
+                <input
+                    class="parameter icon-rule-icon"
+                    type="text"
+                    value={ rule.icon.clone() }
+                    oninput={ on_icon } />
+                <span class="icon-rule-button icon-rule-remove" onclick={ on_remove }>
+                    { "✕" }
+                </span>
+            </div>
+        }
+    }
+
+    /// One editable row of the `Bands` list:  the threshold, its color and a
+    /// remove button.
+    fn color_band_row(
+        &self,
+        ctx: &Context<Self>,
+        idx: usize,
+        band: &ColorBand,
+        key: usize,
+    ) -> Html {
+        let on_threshold = ctx.link().batch_callback({
+            let band = band.clone();
+            move |event: InputEvent| {
+                let threshold = event
+                    .target()
+                    .unwrap()
+                    .unchecked_into::<web_sys::HtmlInputElement>()
+                    .value()
+                    .parse::<f64>()
+                    .ok()?;
+
+                let band = ColorBand {
+                    threshold,
+                    ..band.clone()
+                };
+
+                Some(NumberColumnStyleMsg::ColorBandChanged(idx, band))
+            }
+        });
+
+        let on_color = ctx.link().callback({
+            let band = band.clone();
+            move |event: InputEvent| {
+                let color = event
+                    .target()
+                    .unwrap()
+                    .unchecked_into::<web_sys::HtmlInputElement>()
+                    .value();
+
+                NumberColumnStyleMsg::ColorBandChanged(idx, ColorBand {
+                    color,
+                    ..band.clone()
+                })
+            }
+        });
+
+        let on_remove = ctx
+            .link()
+            .callback(move |_| NumberColumnStyleMsg::ColorBandRemoved(idx));
+
+        html! {
+            <div class="row color-band" key={ key }>
+                <span>{ "≥" }</span>
+                <input
+                    class="parameter color-band-threshold"
+                    type="number"
+                    value={ band.threshold.to_string() }
+                    oninput={ on_threshold } />
+                <input
+                    class="parameter color-band-color"
+                    type="color"
+                    value={ band.color.clone() }
+                    oninput={ on_color } />
+                <span class="color-band-button color-band-remove" onclick={ on_remove }>
+                    { "✕" }
+                </span>
+            </div>
+        }
+    }
+
     fn max_value_props(&self, side: bool, ctx: &Context<Self>) -> NumberInputProps {
         let on_max_value = ctx
             .link()
@@ -504,27 +1061,91 @@ impl NumberColumnStyle {
             } else {
                 self.bg_gradient
             },
-            on_max_value
+            on_max_value,
+            min: Some(0.0)
+        })
+    }
+
+    fn pulse_duration_props(&self, ctx: &Context<Self>) -> NumberInputProps {
+        let on_max_value = ctx
+            .link()
+            .callback(NumberColumnStyleMsg::PulseDurationChanged);
+
+        props!(NumberInputProps {
+            label: "Duration (ms)",
+            max_value: self.pulse_duration_ms as f64,
+            on_max_value,
+            min: Some(0.0),
+            step: Some(100.0)
         })
     }
 
-    /// Human readable precision hint, e.g. "Prec 0.001" for `{fixed: 3}`.
+    /// The `pulse_duration_ms` config value for the current state, which is
+    /// `None` when `Pulse` is not active or the duration is the default.
+    fn configured_pulse_duration(&self, ctx: &Context<Self>) -> Option<u32> {
+        let is_pulse = self.config.number_bg_mode == NumberBackgroundMode::Pulse;
+        let is_default = self.pulse_duration_ms == ctx.props().default_config.pulse_duration_ms;
+        (is_pulse && !is_default).then(|| self.pulse_duration_ms)
+    }
+
+    /// The `color_bands` config value for the current state, which is `None`
+    /// when `Bands` is not active or has no bands.
+    fn configured_color_bands(&self) -> Option<Vec<ColorBand>> {
+        let is_bands = self.config.number_bg_mode == NumberBackgroundMode::Bands;
+        (is_bands && !self.color_bands.is_empty()).then(|| self.color_bands.clone())
+    }
+
+    /// Human readable precision hint, e.g. "Prec 0.001" for `{fixed: 3}`, or a
+    /// formatted sample value when a non-standard `notation` is set, e.g.
+    /// "Sci 1.23e3" for `{fixed: 2, notation: "scientific"}`.
     fn make_fixed_text(&self, ctx: &Context<Self>) -> String {
-        let fixed = match self.config.fixed {
-            Some(x) if x > 0 => format!("0.{}1", "0".repeat(x as usize - 1)),
-            None if ctx.props().default_config.fixed > 0 => {
-                let n = ctx.props().default_config.fixed as usize - 1;
-                format!("0.{}1", "0".repeat(n))
+        let precision = self
+            .config
+            .fixed
+            .unwrap_or(ctx.props().default_config.fixed) as usize;
+
+        let text = match self.config.notation.unwrap_or_default() {
+            NumberNotation::Standard if precision > 0 => {
+                format!("Prec 0.{}1", "0".repeat(precision - 1))
+            }
+            NumberNotation::Standard => "Prec 1".to_owned(),
+            NumberNotation::Scientific => format!("Sci {:.*e}", precision, NOTATION_SAMPLE),
+            NumberNotation::Engineering => {
+                format!("Eng {:.*}e3", precision, NOTATION_SAMPLE / 1000.0)
+            }
+            NumberNotation::Compact => {
+                format!("Compact {:.*}K", precision, NOTATION_SAMPLE / 1000.0)
             }
-            Some(_) | None => "1".to_owned(),
         };
 
-        format!("Prec {}", fixed)
+        match self
+            .config
+            .locale
+            .as_deref()
+            .and_then(|x| locale_sample(x, precision))
+        {
+            Some(sample) => format!("{} ({})", text, sample),
+            None => text,
+        }
+    }
+
+    /// The locale `Select` options, including a restored `locale` which is
+    /// not one of `NUMBER_LOCALES`.
+    fn locale_items(&self) -> Vec<SelectItem<String>> {
+        let mut locales = vec![USER_LOCALE.clone()];
+        locales.extend(self.config.locale.iter().cloned());
+        locales.extend(NUMBER_LOCALES.iter().map(|x| x.to_string()));
+        locales
+            .into_iter()
+            .unique()
+            .map(SelectItem::Option)
+            .collect()
     }
 
     fn reset(
         config: &NumberColumnStyleConfig,
         default_config: &NumberColumnStyleDefaultConfig,
+        ui_state: &NumberColumnStyleUiState,
     ) -> NumberColumnStyle {
         let mut config = config.clone();
         let fg_gradient = match config.fg_gradient {
@@ -537,32 +1158,23 @@ impl NumberColumnStyle {
             None => default_config.bg_gradient,
         };
 
-        let pos_fg_color = config
-            .pos_fg_color
-            .as_ref()
-            .unwrap_or(&default_config.pos_fg_color)
-            .to_owned();
-
-        let neg_fg_color = config
-            .neg_fg_color
-            .as_ref()
-            .unwrap_or(&default_config.neg_fg_color)
-            .to_owned();
-
-        let pos_bg_color = config
-            .pos_bg_color
-            .as_ref()
-            .unwrap_or(&default_config.pos_bg_color)
-            .to_owned();
-
-        let neg_bg_color = config
-            .neg_bg_color
-            .as_ref()
-            .unwrap_or(&default_config.neg_bg_color)
-            .to_owned();
+        let pulse_duration_ms = config
+            .pulse_duration_ms
+            .unwrap_or(default_config.pulse_duration_ms);
+
+        // Defaults are normalized too, as `<input type="color">` only accepts
+        // `#rrggbb` while theme colors may be in any CSS format.
+        let init_color = |color: &Option<String>, default: &str| {
+            validate_color(color.as_deref().unwrap_or(default), default)
+        };
+
+        let pos_fg_color = init_color(&config.pos_fg_color, &default_config.pos_fg_color);
+        let neg_fg_color = init_color(&config.neg_fg_color, &default_config.neg_fg_color);
+        let pos_bg_color = init_color(&config.pos_bg_color, &default_config.pos_bg_color);
+        let neg_bg_color = init_color(&config.neg_bg_color, &default_config.neg_bg_color);
 
         let fg_mode = match config.number_fg_mode {
-            NumberForegroundMode::Disabled => NumberForegroundMode::default(),
+            NumberForegroundMode::Disabled => ui_state.fg_mode.unwrap_or_default(),
             x => {
                 config.pos_fg_color = Some(pos_fg_color.to_owned());
                 config.neg_fg_color = Some(neg_fg_color.to_owned());
@@ -571,7 +1183,7 @@ impl NumberColumnStyle {
         };
 
         let bg_mode = match config.number_bg_mode {
-            NumberBackgroundMode::Disabled => NumberBackgroundMode::default(),
+            NumberBackgroundMode::Disabled => ui_state.bg_mode.unwrap_or_default(),
             x => {
                 config.pos_bg_color = Some(pos_bg_color.to_owned());
                 config.neg_bg_color = Some(neg_bg_color.to_owned());
@@ -579,6 +1191,18 @@ impl NumberColumnStyle {
             }
         };
 
+        let num_icon_rules = config.icon_rules.as_ref().map_or(0, |x| x.len());
+        let color_bands = config
+            .color_bands
+            .iter()
+            .flatten()
+            .map(|x| ColorBand {
+                color: validate_color(&x.color, &default_config.pos_bg_color),
+                ..x.clone()
+            })
+            .collect::<Vec<_>>();
+
+        let num_color_bands = color_bands.len();
         NumberColumnStyle {
             config,
             fg_mode,
@@ -589,6 +1213,33 @@ impl NumberColumnStyle {
             neg_bg_color,
             fg_gradient,
             bg_gradient,
+            pulse_duration_ms,
+            fixed_invalid: false,
+            icon_rule_keys: (0..num_icon_rules).collect(),
+            next_icon_rule_key: num_icon_rules,
+            color_bands,
+            color_band_keys: (0..num_color_bands).collect(),
+            next_color_band_key: num_color_bands,
         }
     }
 }
+
+/// `NOTATION_SAMPLE` formatted in `locale` to `precision` digits, e.g.
+/// "1.234,57" for "de-DE", or `None` if `locale` is not a known locale (which
+/// `Intl.NumberFormat` would throw on).
+fn locale_sample(locale: &str, precision: usize) -> Option<String> {
+    if *USER_LOCALE != locale && !NUMBER_LOCALES.contains(&locale) {
+        return None;
+    }
+
+    let options = json!({
+        "minimumFractionDigits": precision,
+        "maximumFractionDigits": precision
+    });
+
+    js_sys::Intl::NumberFormat::new(&json!([locale]), &options)
+        .format()
+        .call1(&JsValue::UNDEFINED, &NOTATION_SAMPLE.into())
+        .ok()?
+        .as_string()
+}
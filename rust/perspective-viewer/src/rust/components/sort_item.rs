@@ -64,7 +64,11 @@ impl Component for SortItem {
             SortItemMsg::SortDirClick(shift_key) => {
                 let is_split = ctx.props().session.get_view_config().split_by.is_empty();
                 let mut sort = ctx.props().session.get_view_config().sort.clone();
-                let sort_item = &mut sort.get_mut(ctx.props().idx).expect("Sort on no column");
+                let sort_item = match sort.get_mut(ctx.props().idx) {
+                    Some(sort_item) => sort_item,
+                    None => return false,
+                };
+
                 sort_item.1 = sort_item.1.cycle(!is_split, shift_key);
                 let update = ViewConfigUpdate {
                     sort: Some(sort),
@@ -110,6 +114,7 @@ impl Component for SortItem {
             </span>
             <span
                 class={ format!("sort-icon {}", ctx.props().sort.1) }
+                title={ format!("Sort {} (click to change, shift-click for abs)", ctx.props().sort.1) }
                 onmousedown={ onclick }>
             </span>
         }
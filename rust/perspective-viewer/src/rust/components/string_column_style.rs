@@ -11,7 +11,7 @@ use super::containers::radio_list::RadioList;
 use super::containers::radio_list_item::RadioListItem;
 use super::modal::{ModalLink, SetModalLink};
 use crate::config::*;
-use crate::utils::WeakScope;
+use crate::utils::{normalize_color, validate_color, WeakScope};
 use crate::*;
 use wasm_bindgen::*;
 use web_sys::*;
@@ -27,6 +27,9 @@ pub enum StringColumnStyleMsg {
     ColorModeEnabled(bool),
     ColorModeChanged(StringColorMode),
     ColorChanged(String),
+    AlignChanged(TextAlign),
+    NullDisplayChanged(String),
+    EmptyDisplayChanged(String),
 }
 
 #[derive(Properties)]
@@ -41,7 +44,7 @@ pub struct StringColumnStyleProps {
     pub on_change: Callback<StringColumnStyleConfig>,
 
     #[prop_or_default]
-    weak_link: WeakScope<StringColumnStyle>,
+    pub weak_link: WeakScope<StringColumnStyle>,
 }
 
 impl ModalLink<StringColumnStyle> for StringColumnStyleProps {
@@ -73,14 +76,14 @@ impl StringColumnStyle {
     /// variant.
     fn color_select_row(&self, ctx: &Context<Self>, mode: &StringColorMode, title: &str) -> Html {
         let on_color = ctx.link().callback(StringColumnStyleMsg::ColorChanged);
-        let color = self
-            .config
-            .color
-            .clone()
-            .unwrap_or_else(|| ctx.props().default_config.color.to_owned());
+        let default_color = &ctx.props().default_config.color;
+        let color = self.config.color.as_deref().unwrap_or(default_color);
+        let color = normalize_color(color).unwrap_or_else(|| default_color.to_owned());
 
         let color_props = props!(ColorProps { color, on_color });
-        if let Some(x) = &self.config.string_color_mode && x == mode {
+        if let Some(x) = &self.config.string_color_mode
+            && x == mode
+        {
             html_template! {
                 <span class="row">{ title }</span>
                 <div class="row inner_section">
@@ -144,10 +147,26 @@ impl Component for StringColumnStyle {
                 true
             }
             StringColumnStyleMsg::ColorChanged(color) => {
+                let color = validate_color(&color, &ctx.props().default_config.color);
                 self.config.color = Some(color);
                 self.dispatch_config(ctx);
                 true
             }
+            StringColumnStyleMsg::AlignChanged(align) => {
+                self.config.align = Some(align).filter(|x| *x != TextAlign::default());
+                self.dispatch_config(ctx);
+                true
+            }
+            StringColumnStyleMsg::NullDisplayChanged(text) => {
+                self.config.null_display = Some(text).filter(|x| !x.is_empty());
+                self.dispatch_config(ctx);
+                false
+            }
+            StringColumnStyleMsg::EmptyDisplayChanged(text) => {
+                self.config.empty_display = Some(text).filter(|x| !x.is_empty());
+                self.dispatch_config(ctx);
+                false
+            }
         }
     }
 
@@ -180,6 +199,25 @@ impl Component for StringColumnStyle {
         let background_controls =
             self.color_select_row(ctx, &StringColorMode::Background, "Background");
 
+        let null_display_oninput = ctx.link().callback(|event: InputEvent| {
+            let input = event
+                .target()
+                .unwrap()
+                .unchecked_into::<web_sys::HtmlInputElement>();
+            StringColumnStyleMsg::NullDisplayChanged(input.value())
+        });
+
+        let empty_display_oninput = ctx.link().callback(|event: InputEvent| {
+            let input = event
+                .target()
+                .unwrap()
+                .unchecked_into::<web_sys::HtmlInputElement>();
+            StringColumnStyleMsg::EmptyDisplayChanged(input.value())
+        });
+
+        let selected_align = self.config.align.unwrap_or_default();
+        let align_changed = ctx.link().callback(StringColumnStyleMsg::AlignChanged);
+
         html_template! {
             <style>
                 { &CSS }
@@ -244,6 +282,56 @@ impl Component for StringColumnStyle {
                         </RadioListItem<StringColorMode>>
                     </RadioList<StringColorMode>>
                 </div>
+                <div class="column-style-label">
+                    <label class="indent">{ "Align" }</label>
+                </div>
+                <div class="section">
+                    <RadioList<TextAlign>
+                        class="indent"
+                        name="align-radio-list"
+                        selected={ selected_align }
+                        on_change={ align_changed } >
+
+                        <RadioListItem<TextAlign>
+                            value={ TextAlign::Left }>
+                            <span>{ "Left" }</span>
+                        </RadioListItem<TextAlign>>
+                        <RadioListItem<TextAlign>
+                            value={ TextAlign::Center }>
+                            <span>{ "Center" }</span>
+                        </RadioListItem<TextAlign>>
+                        <RadioListItem<TextAlign>
+                            value={ TextAlign::Right }>
+                            <span>{ "Right" }</span>
+                        </RadioListItem<TextAlign>>
+                    </RadioList<TextAlign>>
+                </div>
+                <div class="column-style-label">
+                    <label class="indent">{ "Null" }</label>
+                </div>
+                <div class="row section">
+                    <input type="checkbox" checked=true disabled=true/>
+                    <input
+                        id="null-display-param"
+                        class="parameter"
+                        type="text"
+                        placeholder="-"
+                        value={ self.config.null_display.clone().unwrap_or_default() }
+                        oninput={ null_display_oninput }/>
+                </div>
+                <div class="column-style-label">
+                    <label class="indent">{ "Empty" }</label>
+                </div>
+                <div class="row section">
+                    <input type="checkbox" checked=true disabled=true/>
+                    <input
+                        id="empty-display-param"
+                        class="parameter"
+                        type="text"
+                        placeholder="(blank)"
+                        value={ self.config.empty_display.clone().unwrap_or_default() }
+                        oninput={ empty_display_oninput }/>
+                </div>
             </div>
         }
     }
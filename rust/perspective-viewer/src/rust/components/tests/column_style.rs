@@ -6,6 +6,7 @@
 // of the Apache License 2.0.  The full license can be found in the LICENSE
 // file.
 
+use crate::components::color_range_selector::*;
 use crate::components::number_column_style::*;
 use crate::config::*;
 use crate::utils::{await_animation_frame, WeakScope};
@@ -90,6 +91,165 @@ pub async fn test_fixed_msg_overrides_default() {
     );
 }
 
+#[wasm_bindgen_test]
+pub async fn test_fixed_invalid_class() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let panel_div = NodeRef::default();
+    test_html! {
+        <NumberColumnStyle
+            ref={ panel_div.clone() }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::FixedChanged("1.5".to_owned()));
+    await_animation_frame().await.unwrap();
+    assert!(cs_query(&panel_div, "#fixed-param")
+        .class_list()
+        .contains("invalid"));
+
+    column_style.send_message(NumberColumnStyleMsg::FixedChanged("3".to_owned()));
+    await_animation_frame().await.unwrap();
+    assert!(!cs_query(&panel_div, "#fixed-param")
+        .class_list()
+        .contains("invalid"));
+}
+
+#[wasm_bindgen_test]
+pub async fn test_fixed_clamped_to_max_precision() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let panel_div = NodeRef::default();
+    let default_config = NumberColumnStyleDefaultConfig {
+        max_precision: 20,
+        ..NumberColumnStyleDefaultConfig::default()
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            default_config={ default_config }
+            ref={ panel_div.clone() }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::FixedChanged("18".to_owned()));
+    await_animation_frame().await.unwrap();
+    assert_eq!(
+        cs_query(&panel_div, "#fixed-examples").inner_text(),
+        "Prec 0.000000000000000001"
+    );
+
+    column_style.send_message(NumberColumnStyleMsg::FixedChanged("25".to_owned()));
+    await_animation_frame().await.unwrap();
+    assert_eq!(
+        cs_query(&panel_div, "#fixed-examples").inner_text(),
+        "Prec 0.00000000000000000001"
+    );
+}
+
+#[wasm_bindgen_test]
+pub async fn test_notation_preview() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let panel_div = NodeRef::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    let config = NumberColumnStyleConfig {
+        fixed: Some(2),
+        ..NumberColumnStyleConfig::default()
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            config={ config }
+            on_change={ on_change }
+            ref={ panel_div.clone() }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::NotationChanged(
+        NumberNotation::Scientific,
+    ));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().notation, Some(NumberNotation::Scientific));
+    assert_eq!(
+        cs_query(&panel_div, "#fixed-examples").inner_text(),
+        "Sci 1.23e3"
+    );
+
+    column_style.send_message(NumberColumnStyleMsg::NotationChanged(
+        NumberNotation::Compact,
+    ));
+    await_animation_frame().await.unwrap();
+    assert_eq!(
+        cs_query(&panel_div, "#fixed-examples").inner_text(),
+        "Compact 1.23K"
+    );
+
+    column_style.send_message(NumberColumnStyleMsg::NotationChanged(
+        NumberNotation::Standard,
+    ));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().notation, None);
+    assert_eq!(
+        cs_query(&panel_div, "#fixed-examples").inner_text(),
+        "Prec 0.01"
+    );
+}
+
+#[wasm_bindgen_test]
+pub async fn test_locale_preview() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let panel_div = NodeRef::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    let config = NumberColumnStyleConfig {
+        fixed: Some(2),
+        ..NumberColumnStyleConfig::default()
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            config={ config }
+            on_change={ on_change }
+            ref={ panel_div.clone() }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::LocaleChanged("de-DE".to_owned()));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().locale, Some("de-DE".to_owned()));
+    assert_eq!(
+        cs_query(&panel_div, "#fixed-examples").inner_text(),
+        "Prec 0.01 (1.234,57)"
+    );
+}
+
 #[wasm_bindgen_test]
 pub async fn test_fixed_is_0() {
     let panel_div = NodeRef::default();
@@ -191,6 +351,46 @@ pub async fn test_color_mode_changed() {
     assert_eq!(result.borrow().pos_fg_color, None);
 }
 
+#[wasm_bindgen_test]
+pub async fn test_sparkline_mode() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            on_change={ on_change }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::NumberForeModeChanged(
+        NumberForegroundMode::Sparkline,
+    ));
+    await_animation_frame().await.unwrap();
+
+    assert!(!NumberForegroundMode::Sparkline.needs_gradient());
+    assert_eq!(
+        result.borrow().number_fg_mode,
+        NumberForegroundMode::Sparkline
+    );
+    assert_eq!(result.borrow().fg_gradient, None);
+
+    let json = serde_json::to_value(&*result.borrow()).unwrap();
+    assert_eq!(json["number_fg_mode"], serde_json::json!("sparkline"));
+    let config: NumberColumnStyleConfig = serde_json::from_value(json).unwrap();
+    assert_eq!(config.number_fg_mode, NumberForegroundMode::Sparkline);
+}
+
 #[wasm_bindgen_test]
 pub async fn test_pos_color_changed_override_defaults() {
     let link: WeakScope<NumberColumnStyle> = WeakScope::default();
@@ -229,8 +429,8 @@ pub async fn test_pos_color_changed_override_defaults() {
     await_animation_frame().await.unwrap();
 
     assert_eq!(result.borrow().number_fg_mode, NumberForegroundMode::Color);
-    assert_eq!(result.borrow().pos_fg_color, Some("#666".to_owned()));
-    assert_eq!(result.borrow().neg_fg_color, Some("#321".to_owned()));
+    assert_eq!(result.borrow().pos_fg_color, Some("#666666".to_owned()));
+    assert_eq!(result.borrow().neg_fg_color, Some("#332211".to_owned()));
     column_style.send_message(NumberColumnStyleMsg::PosColorChanged(
         true,
         "#123".to_owned(),
@@ -289,8 +489,8 @@ pub async fn test_pos_color_and_mode_changed_override_defaults() {
 
     await_animation_frame().await.unwrap();
     assert_eq!(result.borrow().number_fg_mode, NumberForegroundMode::Bar);
-    assert_eq!(result.borrow().pos_fg_color, Some("#666".to_owned()));
-    assert_eq!(result.borrow().neg_fg_color, Some("#321".to_owned()));
+    assert_eq!(result.borrow().pos_fg_color, Some("#666666".to_owned()));
+    assert_eq!(result.borrow().neg_fg_color, Some("#332211".to_owned()));
     column_style.send_message(NumberColumnStyleMsg::PosColorChanged(
         true,
         "#123".to_owned(),
@@ -301,3 +501,503 @@ pub async fn test_pos_color_and_mode_changed_override_defaults() {
     assert_eq!(result.borrow().pos_fg_color, None);
     assert_eq!(result.borrow().neg_fg_color, None);
 }
+
+#[wasm_bindgen_test]
+pub async fn test_gradient_preview_follows_colors() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let panel_div = NodeRef::default();
+    let default_config = NumberColumnStyleDefaultConfig {
+        pos_fg_color: "#123".to_owned(),
+        neg_fg_color: "#321".to_owned(),
+        ..NumberColumnStyleDefaultConfig::default()
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            default_config={ default_config }
+            ref={ panel_div.clone() }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let style = cs_query(&panel_div, ".gradient-preview").get_attribute("style");
+    assert_eq!(
+        style.as_deref(),
+        Some("background:linear-gradient(to right,#321 50%,#123 50%)")
+    );
+
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::PosColorChanged(
+        true,
+        "#666".to_owned(),
+    ));
+    await_animation_frame().await.unwrap();
+
+    let style = cs_query(&panel_div, ".gradient-preview").get_attribute("style");
+    assert_eq!(
+        style.as_deref(),
+        Some("background:linear-gradient(to right,#321 50%,#666 50%)")
+    );
+}
+
+#[wasm_bindgen_test]
+pub async fn test_pulse_duration_changed() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+    let config = NumberColumnStyleConfig {
+        number_bg_mode: NumberBackgroundMode::Pulse,
+        ..NumberColumnStyleConfig::default()
+    };
+
+    let default_config = NumberColumnStyleDefaultConfig {
+        pulse_duration_ms: 500,
+        ..NumberColumnStyleDefaultConfig::default()
+    };
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            config={ config }
+            default_config={ default_config }
+            on_change={ on_change }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::PulseDurationChanged(
+        "2000".to_owned(),
+    ));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().pulse_duration_ms, Some(2000));
+
+    column_style.send_message(NumberColumnStyleMsg::PulseDurationChanged("500".to_owned()));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().pulse_duration_ms, None);
+
+    column_style.send_message(NumberColumnStyleMsg::PulseDurationChanged(
+        "2000".to_owned(),
+    ));
+    column_style.send_message(NumberColumnStyleMsg::NumberBackModeChanged(
+        NumberBackgroundMode::Color,
+    ));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().pulse_duration_ms, None);
+}
+
+#[wasm_bindgen_test]
+pub async fn test_icon_rules_add_change_remove() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let panel_div = NodeRef::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            on_change={ on_change }
+            ref={ panel_div.clone() }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::IconRuleAdded);
+    column_style.send_message(NumberColumnStyleMsg::IconRuleAdded);
+    await_animation_frame().await.unwrap();
+    assert_eq!(
+        result.borrow().icon_rules.as_ref().map(|x| x.len()),
+        Some(2)
+    );
+
+    let rule = IconRule {
+        op: IconComparison::Lt,
+        threshold: -1.0,
+        icon: "▼".to_owned(),
+    };
+
+    column_style.send_message(NumberColumnStyleMsg::IconRuleChanged(1, rule.clone()));
+    await_animation_frame().await.unwrap();
+    let icon = cs_query(
+        &panel_div,
+        "div.icon-rule:nth-child(2) input.icon-rule-icon",
+    );
+    assert_eq!(icon.unchecked_into::<HtmlInputElement>().value(), "▼");
+
+    column_style.send_message(NumberColumnStyleMsg::IconRuleRemoved(0));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().icon_rules, Some(vec![rule]));
+
+    column_style.send_message(NumberColumnStyleMsg::IconRuleRemoved(0));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().icon_rules, None);
+}
+
+#[wasm_bindgen_test]
+pub async fn test_color_bands_add_change_remove() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let panel_div = NodeRef::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+    let band = ColorBand {
+        threshold: 10.0,
+        color: "#ff0000".to_owned(),
+    };
+
+    let config = NumberColumnStyleConfig {
+        number_bg_mode: NumberBackgroundMode::Bands,
+        color_bands: Some(vec![band.clone()]),
+        ..NumberColumnStyleConfig::default()
+    };
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            config={ config }
+            on_change={ on_change }
+            ref={ panel_div.clone() }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let threshold = cs_query(&panel_div, "div.color-band input.color-band-threshold");
+    assert_eq!(threshold.unchecked_into::<HtmlInputElement>().value(), "10");
+
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::ColorBandAdded);
+    let high = ColorBand {
+        threshold: 100.0,
+        color: "#00ff00".to_owned(),
+    };
+
+    column_style.send_message(NumberColumnStyleMsg::ColorBandChanged(1, high.clone()));
+    await_animation_frame().await.unwrap();
+    assert_eq!(
+        result.borrow().color_bands,
+        Some(vec![band.clone(), high.clone()])
+    );
+
+    column_style.send_message(NumberColumnStyleMsg::NumberBackModeChanged(
+        NumberBackgroundMode::Color,
+    ));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().color_bands, None);
+
+    column_style.send_message(NumberColumnStyleMsg::NumberBackModeChanged(
+        NumberBackgroundMode::Bands,
+    ));
+    column_style.send_message(NumberColumnStyleMsg::ColorBandRemoved(0));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().color_bands, Some(vec![high]));
+
+    column_style.send_message(NumberColumnStyleMsg::ColorBandRemoved(0));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().color_bands, None);
+}
+
+#[wasm_bindgen_test]
+pub async fn test_color_sides_omits_disabled_side() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let panel_div = NodeRef::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    let default_config = NumberColumnStyleDefaultConfig {
+        pos_fg_color: "#123".to_owned(),
+        neg_fg_color: "#321".to_owned(),
+        ..NumberColumnStyleDefaultConfig::default()
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            default_config={ default_config }
+            on_change={ on_change }
+            ref={ panel_div.clone() }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::ForeEnabledChanged(true));
+    column_style.send_message(NumberColumnStyleMsg::PosColorChanged(
+        true,
+        "#ff0000".to_owned(),
+    ));
+
+    column_style.send_message(NumberColumnStyleMsg::ColorSidesChanged(
+        ColorSides::NegativeOnly,
+    ));
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().color_sides, ColorSides::NegativeOnly);
+    assert_eq!(result.borrow().pos_fg_color, None);
+    assert_eq!(result.borrow().neg_fg_color, Some("#332211".to_owned()));
+    let container = panel_div
+        .cast::<HtmlElement>()
+        .unwrap()
+        .next_sibling()
+        .unwrap()
+        .unchecked_into::<HtmlElement>();
+
+    assert!(container.query_selector("#color-param").unwrap().is_none());
+    assert!(container
+        .query_selector("#neg-color-param")
+        .unwrap()
+        .is_some());
+
+    column_style.send_message(NumberColumnStyleMsg::ColorSidesChanged(ColorSides::Both));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().pos_fg_color, Some("#ff0000".to_owned()));
+}
+
+#[wasm_bindgen_test]
+pub async fn test_text_contrast_toggle() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let panel_div = NodeRef::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            on_change={ on_change }
+            ref={ panel_div.clone() }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    let container = panel_div
+        .cast::<HtmlElement>()
+        .unwrap()
+        .next_sibling()
+        .unwrap()
+        .unchecked_into::<HtmlElement>();
+
+    assert!(container
+        .query_selector("#text-contrast")
+        .unwrap()
+        .is_none());
+    column_style.send_message(NumberColumnStyleMsg::BackEnabledChanged(true));
+    await_animation_frame().await.unwrap();
+    assert!(container
+        .query_selector("#text-contrast")
+        .unwrap()
+        .is_some());
+
+    column_style.send_message(NumberColumnStyleMsg::TextContrastChanged(true));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().auto_text_contrast, Some(true));
+
+    column_style.send_message(NumberColumnStyleMsg::TextContrastChanged(false));
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().auto_text_contrast, None);
+
+    column_style.send_message(NumberColumnStyleMsg::TextContrastChanged(true));
+    column_style.send_message(NumberColumnStyleMsg::NumberBackModeChanged(
+        NumberBackgroundMode::Pulse,
+    ));
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().auto_text_contrast, None);
+    assert!(container
+        .query_selector("#text-contrast")
+        .unwrap()
+        .is_none());
+}
+
+#[wasm_bindgen_test]
+pub fn test_is_hidden_zero() {
+    let mut config = NumberColumnStyleConfig {
+        hide_zeros: Some(true),
+        ..NumberColumnStyleConfig::default()
+    };
+
+    assert!(config.is_hidden_zero(0.0, 2));
+    assert!(config.is_hidden_zero(-0.004, 2));
+    assert!(!config.is_hidden_zero(0.005, 2));
+    assert!(!config.is_hidden_zero(f64::NAN, 2));
+    config.fixed = Some(0);
+    assert!(config.is_hidden_zero(0.4, 2));
+    config.notation = Some(NumberNotation::Scientific);
+    assert!(!config.is_hidden_zero(0.4, 2));
+    config.hide_zeros = None;
+    assert!(!config.is_hidden_zero(0.0, 2));
+}
+
+#[wasm_bindgen_test]
+pub fn test_icon_rule_matches() {
+    let rule = IconRule {
+        op: IconComparison::Gte,
+        threshold: 10.0,
+        icon: "!".to_owned(),
+    };
+
+    assert!(rule.matches(10.0));
+    assert!(rule.matches(11.0));
+    assert!(!rule.matches(9.0));
+}
+
+#[wasm_bindgen_test]
+pub fn test_band_color() {
+    let band = |threshold, color: &str| ColorBand {
+        threshold,
+        color: color.to_owned(),
+    };
+
+    let config = NumberColumnStyleConfig {
+        color_bands: Some(vec![band(100.0, "high"), band(0.0, "low")]),
+        ..NumberColumnStyleConfig::default()
+    };
+
+    assert_eq!(config.band_color(-1.0), None);
+    assert_eq!(config.band_color(0.0), Some("low"));
+    assert_eq!(config.band_color(99.0), Some("low"));
+    assert_eq!(config.band_color(100.0), Some("high"));
+    assert_eq!(config.band_color(f64::NAN), None);
+}
+
+#[wasm_bindgen_test]
+pub async fn test_color_preset_sets_colors() {
+    let panel_div = NodeRef::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+    let default_config = NumberColumnStyleDefaultConfig {
+        pos_fg_color: "#00ff00".to_owned(),
+        neg_fg_color: "#ff0000".to_owned(),
+        ..NumberColumnStyleDefaultConfig::default()
+    };
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            default_config={ default_config }
+            on_change={ on_change }
+            ref={ panel_div.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let select = cs_query(&panel_div, "select.color-preset-param");
+    let select = select.unchecked_into::<HtmlSelectElement>();
+    assert_eq!(select.value(), "Custom");
+
+    select.set_value("Blue/Orange (Deuteranopia)");
+    select
+        .dispatch_event(&Event::new("change").unwrap())
+        .unwrap();
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().pos_fg_color, Some("#0072b2".to_owned()));
+    assert_eq!(result.borrow().neg_fg_color, Some("#e69f00".to_owned()));
+}
+
+#[wasm_bindgen_test]
+pub fn test_color_preset_from_colors() {
+    assert_eq!(
+        ColorRangePreset::from_colors("#0072B2", "#F0E442"),
+        ColorRangePreset::BlueYellow
+    );
+
+    assert_eq!(
+        ColorRangePreset::from_colors("#00ff00", "#ff0000"),
+        ColorRangePreset::Custom
+    );
+}
+
+#[wasm_bindgen_test]
+pub async fn test_ui_state_restores_disabled_mode() {
+    let link: WeakScope<NumberColumnStyle> = WeakScope::default();
+    let result: Rc<RefCell<NumberColumnStyleConfig>> =
+        Rc::new(RefCell::new(NumberColumnStyleConfig::default()));
+    let ui_result: Rc<RefCell<NumberColumnStyleUiState>> = Rc::default();
+    let ui_state = NumberColumnStyleUiState {
+        bg_mode: Some(NumberBackgroundMode::Gradient),
+        ..NumberColumnStyleUiState::default()
+    };
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    let on_ui_state_change = {
+        clone!(ui_result);
+        Callback::from(move |ui_state| {
+            *ui_result.borrow_mut() = ui_state;
+        })
+    };
+
+    test_html! {
+        <NumberColumnStyle
+            ui_state={ ui_state }
+            on_change={ on_change }
+            on_ui_state_change={ on_ui_state_change }
+            weak_link={ link.clone() }>
+        </NumberColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(NumberColumnStyleMsg::BackEnabledChanged(true));
+    await_animation_frame().await.unwrap();
+    assert_eq!(
+        result.borrow().number_bg_mode,
+        NumberBackgroundMode::Gradient
+    );
+
+    column_style.send_message(NumberColumnStyleMsg::NumberBackModeChanged(
+        NumberBackgroundMode::Pulse,
+    ));
+    await_animation_frame().await.unwrap();
+    assert_eq!(
+        ui_result.borrow().bg_mode,
+        Some(NumberBackgroundMode::Pulse)
+    );
+}
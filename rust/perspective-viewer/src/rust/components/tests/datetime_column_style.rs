@@ -0,0 +1,70 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::components::datetime_column_style::*;
+use crate::config::*;
+use crate::utils::{await_animation_frame, WeakScope};
+use crate::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+use yew::prelude::*;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+pub async fn test_custom_format_changed() {
+    let link: WeakScope<DatetimeColumnStyle> = WeakScope::default();
+    let result: Rc<RefCell<DatetimeColumnStyleConfig>> =
+        Rc::new(RefCell::new(DatetimeColumnStyleConfig::default()));
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    test_html! {
+        <DatetimeColumnStyle
+            enable_time_config={ false }
+            on_change={ on_change }
+            weak_link={ link.clone() }>
+        </DatetimeColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(DatetimeColumnStyleMsg::CustomFormatChanged(
+        "%d/%m/%Y".to_owned(),
+    ));
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().custom_format, Some("%d/%m/%Y".to_owned()));
+    column_style.send_message(DatetimeColumnStyleMsg::CustomFormatChanged("%Q".to_owned()));
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().custom_format, Some("%d/%m/%Y".to_owned()));
+    column_style.send_message(DatetimeColumnStyleMsg::CustomFormatChanged(
+        "%-d %z".to_owned(),
+    ));
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().custom_format, Some("%d/%m/%Y".to_owned()));
+    column_style.send_message(DatetimeColumnStyleMsg::CustomFormatChanged("".to_owned()));
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().custom_format, None);
+}
+
+#[wasm_bindgen_test]
+pub fn test_custom_format_omitted_when_blank() {
+    let config = DatetimeColumnStyleConfig::default();
+    let json = serde_json::to_value(&config).unwrap();
+    assert!(json.get("custom_format").is_none());
+    assert!(json.get("week_numbers").is_none());
+}
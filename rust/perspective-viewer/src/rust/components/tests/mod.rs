@@ -7,7 +7,9 @@
 // file.
 
 mod column_style;
+mod datetime_column_style;
 mod plugin_selector;
 mod status_bar;
 mod status_bar_counter;
+mod string_column_style;
 mod viewer;
@@ -0,0 +1,99 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::components::string_column_style::*;
+use crate::config::*;
+use crate::utils::{await_animation_frame, WeakScope};
+use crate::*;
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen_test::*;
+use yew::prelude::*;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+pub async fn test_align_changed() {
+    let link: WeakScope<StringColumnStyle> = WeakScope::default();
+    let result: Rc<RefCell<StringColumnStyleConfig>> =
+        Rc::new(RefCell::new(StringColumnStyleConfig::default()));
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    test_html! {
+        <StringColumnStyle
+            on_change={ on_change }
+            weak_link={ link.clone() }>
+        </StringColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(StringColumnStyleMsg::AlignChanged(TextAlign::Center));
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().align, Some(TextAlign::Center));
+    let json = serde_json::to_value(&*result.borrow()).unwrap();
+    assert_eq!(json.get("align"), Some(&serde_json::json!("center")));
+    column_style.send_message(StringColumnStyleMsg::AlignChanged(TextAlign::Left));
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().align, None);
+    let json = serde_json::to_value(&*result.borrow()).unwrap();
+    assert!(json.get("align").is_none());
+}
+
+#[wasm_bindgen_test]
+pub async fn test_null_and_empty_display() {
+    let link: WeakScope<StringColumnStyle> = WeakScope::default();
+    let result: Rc<RefCell<StringColumnStyleConfig>> =
+        Rc::new(RefCell::new(StringColumnStyleConfig::default()));
+
+    let on_change = {
+        clone!(result);
+        Callback::from(move |config| {
+            *result.borrow_mut() = config;
+        })
+    };
+
+    test_html! {
+        <StringColumnStyle
+            on_change={ on_change }
+            weak_link={ link.clone() }>
+        </StringColumnStyle>
+    };
+
+    await_animation_frame().await.unwrap();
+    let column_style = link.borrow().clone().unwrap();
+    column_style.send_message(StringColumnStyleMsg::NullDisplayChanged(
+        "(none)".to_owned(),
+    ));
+    column_style.send_message(StringColumnStyleMsg::EmptyDisplayChanged(
+        "(blank)".to_owned(),
+    ));
+
+    await_animation_frame().await.unwrap();
+    let json = serde_json::to_value(&*result.borrow()).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({"null_display": "(none)", "empty_display": "(blank)"})
+    );
+
+    let config: StringColumnStyleConfig = serde_json::from_value(json).unwrap();
+    assert_eq!(config, *result.borrow());
+    column_style.send_message(StringColumnStyleMsg::NullDisplayChanged("".to_owned()));
+
+    await_animation_frame().await.unwrap();
+    assert_eq!(result.borrow().null_display, None);
+    let json = serde_json::to_value(&*result.borrow()).unwrap();
+    assert!(json.get("null_display").is_none());
+}
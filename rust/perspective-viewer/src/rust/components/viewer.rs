@@ -60,6 +60,10 @@ pub enum Msg {
     ToggleSettingsComplete(SettingsUpdate, Sender<()>),
     PreloadFontsUpdate,
     RenderLimits(Option<(usize, usize, Option<usize>, Option<usize>)>),
+    SettingsResize(i32),
+    SettingsResizeFinished,
+    SettingsReset,
+    SettingsWidthChanged,
 }
 
 pub struct PerspectiveViewer {
@@ -67,9 +71,10 @@ pub struct PerspectiveViewer {
     on_rendered: Option<Sender<()>>,
     fonts: FontLoaderProps,
     settings_open: bool,
+    settings_width: Option<i32>,
     on_resize: Rc<PubSub<()>>,
     on_dimensions_reset: Rc<PubSub<()>>,
-    _subscriptions: [Subscription; 1],
+    _subscriptions: [Subscription; 2],
 }
 
 impl Component for PerspectiveViewer {
@@ -84,14 +89,23 @@ impl Component for PerspectiveViewer {
             ctx.props().renderer.limits_changed.add_listener(callback)
         };
 
+        let width_sub = {
+            let callback = ctx.link().callback(|_| Msg::SettingsWidthChanged);
+            ctx.props()
+                .renderer
+                .settings_width_changed
+                .add_listener(callback)
+        };
+
         Self {
             dimensions: None,
             on_rendered: None,
             fonts: FontLoaderProps::new(&elem, callback),
             settings_open: false,
+            settings_width: None,
             on_resize: Default::default(),
             on_dimensions_reset: Default::default(),
-            _subscriptions: [limit_sub],
+            _subscriptions: [limit_sub, width_sub],
         }
     }
 
@@ -160,6 +174,24 @@ impl Component for PerspectiveViewer {
                 self.on_rendered = Some(resolve);
                 true
             }
+            Msg::SettingsResize(width) => {
+                self.settings_width = Some(width);
+                false
+            }
+            Msg::SettingsResizeFinished => {
+                if let Some(width) = self.settings_width.take() {
+                    ctx.props().renderer.set_settings_width(Some(width));
+                }
+
+                ctx.props().render_callback().emit(());
+                false
+            }
+            Msg::SettingsReset => {
+                ctx.props().renderer.set_settings_width(None);
+                self.on_dimensions_reset.emit_all(());
+                false
+            }
+            Msg::SettingsWidthChanged => true,
             Msg::RenderLimits(dimensions) => {
                 if self.dimensions != dimensions {
                     self.dimensions = dimensions;
@@ -209,8 +241,10 @@ impl Component for PerspectiveViewer {
             if self.settings_open {
                 <SplitPanel
                     id="app_panel"
-                    on_reset={ self.on_dimensions_reset.callback() }
-                    on_resize_finished={ ctx.props().render_callback() }>
+                    initial_size={ ctx.props().renderer.get_settings_width() }
+                    on_reset={ ctx.link().callback(|_| Msg::SettingsReset) }
+                    on_resize={ ctx.link().callback(|(width, _)| Msg::SettingsResize(width)) }
+                    on_resize_finished={ ctx.link().callback(|_| Msg::SettingsResizeFinished) }>
                     <div id="side_panel" class="column noselect">
                         <PluginSelector
                             session={ ctx.props().session.clone() }
@@ -7,6 +7,7 @@
 // file.
 
 use super::column_type::*;
+use itertools::Itertools;
 use std::str::FromStr;
 
 use serde::Deserialize;
@@ -234,6 +235,18 @@ const NUMBER_AGGREGATES: &[SingleAggregate] = &[
     SingleAggregate::Var,
 ];
 
+impl SingleAggregate {
+    /// Every `SingleAggregate` which is valid for at least one column type.
+    pub fn all() -> impl Iterator<Item = SingleAggregate> {
+        NUMBER_AGGREGATES
+            .iter()
+            .chain(STRING_AGGREGATES.iter())
+            .copied()
+            .sorted()
+            .dedup()
+    }
+}
+
 impl Type {
     pub fn aggregates_iter(&self) -> Box<dyn Iterator<Item = Aggregate>> {
         match self {
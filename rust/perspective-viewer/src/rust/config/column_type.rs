@@ -6,10 +6,10 @@
 // of the Apache License 2.0.  The full license can be found in the LICENSE
 // file.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Deserialize, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
 pub enum Type {
     #[serde(rename = "string")]
     String,
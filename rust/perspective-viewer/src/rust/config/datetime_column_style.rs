@@ -147,6 +147,12 @@ pub struct DatetimeColumnStyleConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_format: Option<String>,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub week_numbers: bool,
 }
 
 impl Default for DatetimeColumnStyleConfig {
@@ -157,6 +163,8 @@ impl Default for DatetimeColumnStyleConfig {
             time_zone: Default::default(),
             datetime_color_mode: Default::default(),
             color: Default::default(),
+            custom_format: Default::default(),
+            week_numbers: Default::default(),
         }
     }
 }
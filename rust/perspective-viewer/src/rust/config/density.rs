@@ -0,0 +1,67 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(test)]
+use wasm_bindgen_test::*;
+
+/// The row height and cell padding plugins should render with.  `Compact` is
+/// applied as the `density-compact` class on `<perspective-viewer>`, which
+/// plugin stylesheets select on.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Density {
+    #[serde(rename = "comfortable")]
+    Comfortable,
+
+    #[serde(rename = "compact")]
+    Compact,
+}
+
+impl Default for Density {
+    fn default() -> Self {
+        Density::Comfortable
+    }
+}
+
+impl Display for Density {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Comfortable => "comfortable",
+            Self::Compact => "compact",
+        })
+    }
+}
+
+impl FromStr for Density {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "comfortable" => Ok(Self::Comfortable),
+            "compact" => Ok(Self::Compact),
+            x => Err(format!("Unknown density \"{}\"", x)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_round_trip() {
+        for density in [Density::Comfortable, Density::Compact] {
+            assert_eq!(Density::from_str(&density.to_string()), Ok(density));
+        }
+
+        assert!(Density::from_str("cozy").is_err());
+    }
+}
@@ -6,6 +6,7 @@
 // of the Apache License 2.0.  The full license can be found in the LICENSE
 // file.
 
+use chrono::{Datelike, Duration, NaiveDate};
 use itertools::Itertools;
 use serde::Deserialize;
 use serde::Serialize;
@@ -125,11 +126,80 @@ impl FromStr for FilterOp {
     }
 }
 
+/// A date range preset for `Date`/`Datetime` filters, which is resolved to a
+/// concrete lower bound each time the `View` is created so that e.g. "last 7
+/// days" stays current.
+#[derive(Clone, Copy, Deserialize, Debug, Eq, PartialEq, Serialize)]
+#[serde()]
+pub enum RelativeDateFilter {
+    #[serde(rename = "today")]
+    Today,
+
+    #[serde(rename = "last 7 days")]
+    Last7Days,
+
+    #[serde(rename = "last 30 days")]
+    Last30Days,
+
+    #[serde(rename = "this month")]
+    ThisMonth,
+
+    #[serde(rename = "year to date")]
+    YearToDate,
+}
+
+impl RelativeDateFilter {
+    pub const ALL: [Self; 5] = [
+        Self::Today,
+        Self::Last7Days,
+        Self::Last30Days,
+        Self::ThisMonth,
+        Self::YearToDate,
+    ];
+
+    /// The first date included by this preset, relative to `today`.
+    pub fn start_date(&self, today: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Today => today,
+            Self::Last7Days => today - Duration::days(6),
+            Self::Last30Days => today - Duration::days(29),
+            Self::ThisMonth => today.with_day(1).unwrap(),
+            Self::YearToDate => NaiveDate::from_ymd(today.year(), 1, 1),
+        }
+    }
+}
+
+impl Display for RelativeDateFilter {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        let name = match self {
+            Self::Today => "today",
+            Self::Last7Days => "last 7 days",
+            Self::Last30Days => "last 30 days",
+            Self::ThisMonth => "this month",
+            Self::YearToDate => "year to date",
+        };
+
+        write!(fmt, "{}", name)
+    }
+}
+
+impl FromStr for RelativeDateFilter {
+    type Err = String;
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        Self::ALL
+            .iter()
+            .find(|x| x.to_string() == input)
+            .copied()
+            .ok_or_else(|| format!("Unknown relative date {}", input))
+    }
+}
+
 #[derive(Clone, Deserialize, Debug, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum FilterTerm {
     Scalar(Scalar),
     Array(Vec<Scalar>),
+    Relative { relative: RelativeDateFilter },
 }
 
 impl Display for FilterTerm {
@@ -144,6 +214,7 @@ impl Display for FilterTerm {
                 Itertools::intersperse(xs.iter().map(|x| format!("{}", x)), ",".to_owned())
                     .collect::<String>()
             )?,
+            FilterTerm::Relative { relative } => write!(fmt, "{}", relative)?,
         }
 
         Ok(())
@@ -153,3 +224,102 @@ impl Display for FilterTerm {
 #[derive(Clone, Deserialize, Debug, PartialEq, Serialize)]
 #[serde()]
 pub struct Filter(pub String, pub FilterOp, pub FilterTerm);
+
+impl Filter {
+    /// The `>=`/`<=` op which completes a numeric range with this filter.
+    fn range_partner_op(&self) -> Option<FilterOp> {
+        match self.1 {
+            FilterOp::GTE => Some(FilterOp::LTE),
+            FilterOp::LTE => Some(FilterOp::GTE),
+            _ => None,
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self.2 {
+            FilterTerm::Scalar(Scalar::Float(x)) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+/// Find the filter which pairs with `filter[idx]` as a numeric range, e.g. a
+/// `<=` filter on the same column as a `>=` filter.
+fn find_range_partner(filter: &[Filter], idx: usize) -> Option<usize> {
+    let op = filter.get(idx)?.range_partner_op()?;
+    filter
+        .iter()
+        .enumerate()
+        .position(|(i, x)| i != idx && x.0 == filter[idx].0 && x.1 == op)
+}
+
+/// Get the `(min, max)` of the numeric range which `filter[idx]` is one side
+/// of, either of which may be missing.
+pub fn get_filter_range(filter: &[Filter], idx: usize) -> (Option<f64>, Option<f64>) {
+    let this = filter.get(idx).and_then(|x| x.as_f64());
+    let partner = find_range_partner(filter, idx).and_then(|x| filter[x].as_f64());
+    match filter.get(idx).map(|x| x.1) {
+        Some(FilterOp::LTE) => (partner, this),
+        _ => (this, partner),
+    }
+}
+
+/// Set the numeric range which `filter[idx]` is one side of, inserting the
+/// partner filter after it if it does not exist yet.
+///
+/// # Arguments
+/// - `filter` The `ViewConfig` filters.
+/// - `idx` The index of a `>=` or `<=` filter.
+/// - `min` The new lower bound.
+/// - `max` The new upper bound.
+pub fn set_filter_range(filter: &mut Vec<Filter>, idx: usize, min: f64, max: f64) {
+    let (op, value, partner_value) = match filter[idx].1 {
+        FilterOp::LTE => (FilterOp::GTE, max, min),
+        _ => (FilterOp::LTE, min, max),
+    };
+
+    filter[idx].2 = FilterTerm::Scalar(Scalar::Float(value));
+    let partner_term = FilterTerm::Scalar(Scalar::Float(partner_value));
+    match find_range_partner(filter, idx) {
+        Some(partner) => filter[partner].2 = partner_term,
+        None => {
+            let column = filter[idx].0.clone();
+            filter.insert(idx + 1, Filter(column, op, partner_term));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn num_filter(column: &str, op: FilterOp, x: f64) -> Filter {
+        Filter(column.to_owned(), op, FilterTerm::Scalar(Scalar::Float(x)))
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_set_filter_range_inserts_partner() {
+        let mut filter = vec![num_filter("x", FilterOp::GTE, 0.0)];
+        set_filter_range(&mut filter, 0, 1.0, 5.0);
+        assert_eq!(filter, vec![
+            num_filter("x", FilterOp::GTE, 1.0),
+            num_filter("x", FilterOp::LTE, 5.0),
+        ]);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_filter_range_round_trip() {
+        let mut filter = vec![
+            num_filter("x", FilterOp::LTE, 9.0),
+            num_filter("y", FilterOp::GTE, 2.0),
+            num_filter("x", FilterOp::GTE, 3.0),
+        ];
+
+        assert_eq!(get_filter_range(&filter, 0), (Some(3.0), Some(9.0)));
+        set_filter_range(&mut filter, 0, 4.0, 8.0);
+        assert_eq!(filter.len(), 3);
+        assert_eq!(get_filter_range(&filter, 2), (Some(4.0), Some(8.0)));
+        assert_eq!(get_filter_range(&filter, 1), (Some(2.0), None));
+    }
+}
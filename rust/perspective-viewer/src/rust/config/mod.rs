@@ -12,6 +12,7 @@
 mod aggregates;
 mod column_type;
 mod datetime_column_style;
+mod density;
 mod filters;
 mod number_column_style;
 mod sort;
@@ -22,6 +23,7 @@ mod viewer_config;
 pub use aggregates::*;
 pub use column_type::*;
 pub use datetime_column_style::*;
+pub use density::*;
 pub use filters::*;
 pub use number_column_style::*;
 pub use sort::*;
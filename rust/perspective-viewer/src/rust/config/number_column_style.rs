@@ -21,6 +21,11 @@ pub enum NumberForegroundMode {
 
     #[serde(rename = "bar")]
     Bar,
+
+    /// A line of this cell's recent values, for grouped views which update
+    /// over time.
+    #[serde(rename = "sparkline")]
+    Sparkline,
 }
 
 impl Default for NumberForegroundMode {
@@ -37,6 +42,7 @@ impl Display for NumberForegroundMode {
         let text = match self {
             Self::Color => Ok("color"),
             Self::Bar => Ok("bar"),
+            Self::Sparkline => Ok("sparkline"),
             _ => Err(std::fmt::Error),
         }?;
 
@@ -50,6 +56,7 @@ impl FromStr for NumberForegroundMode {
         match s {
             "color" => Ok(Self::Color),
             "bar" => Ok(Self::Bar),
+            "sparkline" => Ok(Self::Sparkline),
             x => Err(format!("Unknown NumberForegroundMode::{}", x)),
         }
     }
@@ -82,6 +89,10 @@ pub enum NumberBackgroundMode {
 
     #[serde(rename = "pulse")]
     Pulse,
+
+    /// Discrete steps of color by value, per the column's `color_bands`.
+    #[serde(rename = "bands")]
+    Bands,
 }
 
 impl Default for NumberBackgroundMode {
@@ -96,6 +107,7 @@ impl Display for NumberBackgroundMode {
             Self::Color => Ok("color"),
             Self::Gradient => Ok("gradient"),
             Self::Pulse => Ok("pulse"),
+            Self::Bands => Ok("bands"),
             _ => Err(std::fmt::Error),
         }?;
 
@@ -110,6 +122,7 @@ impl FromStr for NumberBackgroundMode {
             "color" => Ok(Self::Color),
             "gradient" => Ok(Self::Gradient),
             "pulse" => Ok(Self::Pulse),
+            "bands" => Ok(Self::Bands),
             x => Err(format!("Unknown NumberBackgroundMode::{}", x)),
         }
     }
@@ -123,6 +136,234 @@ impl NumberBackgroundMode {
     pub fn needs_gradient(&self) -> bool {
         *self == Self::Gradient
     }
+
+    /// Does this mode fill the cell with a static color, which
+    /// `auto_text_contrast` should pick a readable text color for?
+    pub fn is_fill(&self) -> bool {
+        *self == Self::Color || *self == Self::Gradient || *self == Self::Bands
+    }
+}
+
+/// The `Intl.NumberFormat` `notation` used to format a number column.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum NumberNotation {
+    #[serde(rename = "standard")]
+    Standard,
+
+    #[serde(rename = "scientific")]
+    Scientific,
+
+    #[serde(rename = "engineering")]
+    Engineering,
+
+    #[serde(rename = "compact")]
+    Compact,
+}
+
+impl NumberNotation {
+    pub fn values() -> &'static [Self] {
+        &[
+            Self::Standard,
+            Self::Scientific,
+            Self::Engineering,
+            Self::Compact,
+        ]
+    }
+}
+
+impl Default for NumberNotation {
+    fn default() -> Self {
+        NumberNotation::Standard
+    }
+}
+
+impl Display for NumberNotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            NumberNotation::Standard => "standard",
+            NumberNotation::Scientific => "scientific",
+            NumberNotation::Engineering => "engineering",
+            NumberNotation::Compact => "compact",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+impl FromStr for NumberNotation {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(NumberNotation::Standard),
+            "scientific" => Ok(NumberNotation::Scientific),
+            "engineering" => Ok(NumberNotation::Engineering),
+            "compact" => Ok(NumberNotation::Compact),
+            x => Err(format!("Unknown NumberNotation::{}", x)),
+        }
+    }
+}
+
+/// Which signs of value the foreground and background colors apply to.  The
+/// color for a disabled side is omitted from the config, and values of that
+/// sign are left uncolored.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ColorSides {
+    #[serde(rename = "both")]
+    Both,
+
+    #[serde(rename = "positive_only")]
+    PositiveOnly,
+
+    #[serde(rename = "negative_only")]
+    NegativeOnly,
+}
+
+impl ColorSides {
+    pub fn values() -> &'static [Self] {
+        &[Self::Both, Self::PositiveOnly, Self::NegativeOnly]
+    }
+
+    pub fn is_both(&self) -> bool {
+        *self == Self::Both
+    }
+
+    pub fn has_pos(&self) -> bool {
+        *self != Self::NegativeOnly
+    }
+
+    pub fn has_neg(&self) -> bool {
+        *self != Self::PositiveOnly
+    }
+}
+
+impl Default for ColorSides {
+    fn default() -> Self {
+        ColorSides::Both
+    }
+}
+
+impl Display for ColorSides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            ColorSides::Both => "Both",
+            ColorSides::PositiveOnly => "Positive Only",
+            ColorSides::NegativeOnly => "Negative Only",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+impl FromStr for ColorSides {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Both" => Ok(ColorSides::Both),
+            "Positive Only" => Ok(ColorSides::PositiveOnly),
+            "Negative Only" => Ok(ColorSides::NegativeOnly),
+            x => Err(format!("Unknown ColorSides::{}", x)),
+        }
+    }
+}
+
+/// The comparison an `IconRule` makes between a cell's value and its
+/// threshold.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum IconComparison {
+    #[serde(rename = ">")]
+    Gt,
+
+    #[serde(rename = ">=")]
+    Gte,
+
+    #[serde(rename = "<")]
+    Lt,
+
+    #[serde(rename = "<=")]
+    Lte,
+
+    #[serde(rename = "==")]
+    Eq,
+}
+
+impl IconComparison {
+    pub fn values() -> &'static [Self] {
+        &[Self::Gt, Self::Gte, Self::Lt, Self::Lte, Self::Eq]
+    }
+}
+
+impl Default for IconComparison {
+    fn default() -> Self {
+        IconComparison::Gt
+    }
+}
+
+impl Display for IconComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            IconComparison::Gt => ">",
+            IconComparison::Gte => ">=",
+            IconComparison::Lt => "<",
+            IconComparison::Lte => "<=",
+            IconComparison::Eq => "==",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+impl FromStr for IconComparison {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            ">" => Ok(IconComparison::Gt),
+            ">=" => Ok(IconComparison::Gte),
+            "<" => Ok(IconComparison::Lt),
+            "<=" => Ok(IconComparison::Lte),
+            "==" => Ok(IconComparison::Eq),
+            x => Err(format!("Unknown IconComparison::{}", x)),
+        }
+    }
+}
+
+/// Prepend `icon` to a number cell's formatted value when the comparison
+/// `value <op> threshold` holds.  The first matching rule in a column's
+/// `icon_rules` wins.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct IconRule {
+    pub op: IconComparison,
+    pub threshold: f64,
+    pub icon: String,
+}
+
+impl Default for IconRule {
+    fn default() -> Self {
+        IconRule {
+            op: IconComparison::default(),
+            threshold: 0.0,
+            icon: "▲".to_owned(),
+        }
+    }
+}
+
+impl IconRule {
+    pub fn matches(&self, value: f64) -> bool {
+        match self.op {
+            IconComparison::Gt => value > self.threshold,
+            IconComparison::Gte => value >= self.threshold,
+            IconComparison::Lt => value < self.threshold,
+            IconComparison::Lte => value <= self.threshold,
+            IconComparison::Eq => (value - self.threshold).abs() < f64::EPSILON,
+        }
+    }
+}
+
+/// One step of a `Bands` background:  cells with a value of at least
+/// `threshold` (and less than the next band's) are filled with `color`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ColorBand {
+    pub threshold: f64,
+    pub color: String,
 }
 
 #[cfg_attr(test, derive(Debug))]
@@ -139,6 +380,14 @@ pub struct NumberColumnStyleConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fixed: Option<u32>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notation: Option<NumberNotation>,
+
+    /// The BCP-47 locale whose separators format this column, or `None` for
+    /// the browser's locale.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pos_fg_color: Option<String>,
 
@@ -151,20 +400,93 @@ pub struct NumberColumnStyleConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub neg_bg_color: Option<String>,
 
+    #[serde(default, skip_serializing_if = "ColorSides::is_both")]
+    pub color_sides: ColorSides,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fg_gradient: Option<f64>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bg_gradient: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pulse_duration_ms: Option<u32>,
+
+    /// When `Some(true)`, the plugin picks black or white text for each cell
+    /// by the luminance of its `Color` or `Gradient` background.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_text_contrast: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon_rules: Option<Vec<IconRule>>,
+
+    /// The steps of the `Bands` background mode, in ascending `threshold`
+    /// order as edited, though `band_color()` does not depend on the order.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_bands: Option<Vec<ColorBand>>,
+
+    /// When `Some(true)`, the plugin renders zero values as blank cells, per
+    /// `is_hidden_zero()`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hide_zeros: Option<bool>,
 }
 
 derive_wasm_abi!(NumberColumnStyleConfig, FromWasmAbi, IntoWasmAbi);
 
+impl NumberColumnStyleConfig {
+    /// Whether `value` renders blank under `hide_zeros`, which is when it
+    /// would display as zero:  `0.0` or `-0.0` exactly, or in `Standard`
+    /// notation any value which rounds to zero at the `fixed` precision, e.g.
+    /// `0.004` (but not `0.005`) at 2 digits.  Other notations show small
+    /// values in full (e.g. `4E-3`), so only exact zeros are hidden.  `NaN`
+    /// is never hidden.
+    ///
+    /// # Arguments
+    /// - `value` The cell's value.
+    /// - `default_fixed` The precision to use when `fixed` is `None`.
+    pub fn is_hidden_zero(&self, value: f64, default_fixed: u32) -> bool {
+        if !self.hide_zeros.unwrap_or_default() {
+            false
+        } else if self.notation.unwrap_or_default() == NumberNotation::Standard {
+            let fixed = self.fixed.unwrap_or(default_fixed) as i32;
+            value.abs() < 0.5 * 10_f64.powi(-fixed)
+        } else {
+            value == 0.0
+        }
+    }
+
+    /// The `Bands` background color of `value`, which is that of the band
+    /// with the greatest `threshold` not above `value`;  values below every
+    /// threshold (and `NaN`) are uncolored.
+    pub fn band_color(&self, value: f64) -> Option<&str> {
+        self.color_bands
+            .iter()
+            .flatten()
+            .filter(|x| x.threshold <= value)
+            .max_by(|x, y| x.threshold.total_cmp(&y.threshold))
+            .map(|x| x.color.as_str())
+    }
+}
+
+/// GUI state of a column's `NumberColumnStyle` editor which is not part of its
+/// `NumberColumnStyleConfig`, such as the radio selection (and thus expanded
+/// controls) of a color section which has since been disabled.  The plugin
+/// keeps this per column so re-opening the editor restores it, but it is
+/// never saved.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct NumberColumnStyleUiState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fg_mode: Option<NumberForegroundMode>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bg_mode: Option<NumberBackgroundMode>,
+}
+
 /// Exactly like a `ColumnStyleConfig`, except without `Option<>` fields, as
 /// this struct represents the default values we should use in the GUI when they
 /// are `None` in the real config.  It is also used to decide when to omit a
 /// field when serialized a `ColumnStyleConfig` to JSON.
-#[derive(Deserialize, Clone, Default, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct NumberColumnStyleDefaultConfig {
     pub fg_gradient: f64,
     pub bg_gradient: f64,
@@ -175,6 +497,42 @@ pub struct NumberColumnStyleDefaultConfig {
     pub neg_bg_color: String,
     pub number_fg_mode: NumberForegroundMode,
     pub number_bg_mode: NumberBackgroundMode,
+
+    /// The plugin's built-in `Pulse` animation duration, which falls back to
+    /// the datagrid's 500ms for plugins which do not provide one.
+    #[serde(default = "default_pulse_duration_ms")]
+    pub pulse_duration_ms: u32,
+
+    /// The largest `fixed` precision the GUI allows, which deployments
+    /// rendering very small magnitudes may raise.
+    #[serde(default = "default_max_precision")]
+    pub max_precision: u32,
+}
+
+fn default_pulse_duration_ms() -> u32 {
+    500
+}
+
+fn default_max_precision() -> u32 {
+    15
+}
+
+impl Default for NumberColumnStyleDefaultConfig {
+    fn default() -> Self {
+        NumberColumnStyleDefaultConfig {
+            fg_gradient: 0.0,
+            bg_gradient: 0.0,
+            fixed: 0,
+            pos_fg_color: String::default(),
+            neg_fg_color: String::default(),
+            pos_bg_color: String::default(),
+            neg_bg_color: String::default(),
+            number_fg_mode: NumberForegroundMode::default(),
+            number_bg_mode: NumberBackgroundMode::default(),
+            pulse_duration_ms: default_pulse_duration_ms(),
+            max_precision: default_max_precision(),
+        }
+    }
 }
 
 derive_wasm_abi!(NumberColumnStyleDefaultConfig, FromWasmAbi);
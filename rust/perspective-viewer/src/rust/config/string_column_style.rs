@@ -99,6 +99,48 @@ impl FromStr for FormatMode {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum TextAlign {
+    #[serde(rename = "left")]
+    Left,
+
+    #[serde(rename = "center")]
+    Center,
+
+    #[serde(rename = "right")]
+    Right,
+}
+
+impl Default for TextAlign {
+    fn default() -> Self {
+        TextAlign::Left
+    }
+}
+
+impl Display for TextAlign {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            TextAlign::Left => "left",
+            TextAlign::Center => "center",
+            TextAlign::Right => "right",
+        };
+
+        write!(f, "{}", text)
+    }
+}
+
+impl FromStr for TextAlign {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "left" => Ok(TextAlign::Left),
+            "center" => Ok(TextAlign::Center),
+            "right" => Ok(TextAlign::Right),
+            x => Err(format!("Unknown TextAlign::{}", x)),
+        }
+    }
+}
+
 #[cfg_attr(test, derive(Debug))]
 #[derive(Clone, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct StringColumnStyleConfig {
@@ -110,6 +152,19 @@ pub struct StringColumnStyleConfig {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
+
+    /// Cell text alignment, omitted when the default `TextAlign::Left`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub align: Option<TextAlign>,
+
+    /// Text to render for `null` cells, or `None` for the plugin default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub null_display: Option<String>,
+
+    /// Text to render for empty string cells, so they can be distinguished
+    /// from `null`, or `None` for the plugin default (blank).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub empty_display: Option<String>,
 }
 
 #[derive(Clone, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -13,6 +13,7 @@ use super::aggregates::*;
 use super::filters::*;
 use super::sort::*;
 
+use itertools::Itertools;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -50,6 +51,75 @@ pub struct ViewConfig {
     pub aggregates: HashMap<String, Aggregate>,
 }
 
+/// Split an expression into its alias and the expression itself.  As in
+/// `perspective`, the alias is the text of a `// alias` first line, or if
+/// there is none, the whole expression.
+pub fn split_expression_alias(expr: &str) -> (&str, &str) {
+    let aliased = expr
+        .strip_prefix("//")
+        .and_then(|x| x.split_once('\n'))
+        .map(|(alias, body)| (alias.trim(), body))
+        .filter(|(alias, _)| !alias.is_empty());
+
+    aliased.unwrap_or((expr, expr))
+}
+
+/// Expressions which share an alias, of which only one would be available
+/// as a column.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DuplicateAlias {
+    pub alias: String,
+    pub expressions: Vec<String>,
+}
+
+impl std::fmt::Display for DuplicateAlias {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let expressions = self
+            .expressions
+            .iter()
+            .map(|x| format!("{:?}", x))
+            .join(", ");
+
+        write!(
+            f,
+            "Duplicate expression alias \"{}\" in `expressions` {}",
+            self.alias, expressions
+        )
+    }
+}
+
+/// Group `expressions` by alias, returning those aliases shared by more than
+/// one expression, in the order they first appear.
+pub fn duplicate_expression_aliases(expressions: &[String]) -> Vec<DuplicateAlias> {
+    let alias = |expr: &&String| split_expression_alias(expr).0;
+    expressions
+        .iter()
+        .map(|x| alias(&x))
+        .unique()
+        .filter_map(|name| {
+            let group = expressions
+                .iter()
+                .filter(|x| alias(x) == name)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            (group.len() > 1).then(|| DuplicateAlias {
+                alias: name.to_owned(),
+                expressions: group,
+            })
+        })
+        .collect()
+}
+
+/// The inverse of `split_expression_alias()`.
+pub fn join_expression_alias(alias: &str, expr: &str) -> String {
+    if alias == expr {
+        expr.to_owned()
+    } else {
+        format!("// {}\n{}", alias, expr)
+    }
+}
+
 impl ViewConfig {
     fn _apply<T>(field: &mut T, update: Option<T>) -> bool {
         match update {
@@ -103,33 +173,129 @@ impl ViewConfig {
             || self.filter.iter().any(|x| x.0 == name)
             || self.columns.contains(&Some(name))
     }
+
+    /// The distinct column names referenced by this config, in the order
+    /// they first appear.  These may include expression aliases.
+    pub fn referenced_columns(&self) -> Vec<&str> {
+        let aggregates = self.aggregates.iter().flat_map(|(name, agg)| {
+            let arg = match agg {
+                Aggregate::MultiAggregate(_, arg) => Some(arg),
+                Aggregate::SingleAggregate(_) => None,
+            };
+
+            std::iter::once(name).chain(arg)
+        });
+
+        self.columns
+            .iter()
+            .flatten()
+            .chain(self.group_by.iter())
+            .chain(self.split_by.iter())
+            .chain(self.sort.iter().map(|x| &x.0))
+            .chain(self.filter.iter().map(|x| &x.0))
+            .chain(aggregates)
+            .map(|x| x.as_str())
+            .unique()
+            .collect()
+    }
+
+    /// Whether a plugin's scroll position for a `View` of `self` addresses the
+    /// same rows and columns in a `View` of `other`, e.g. when only
+    /// `aggregates` or `expressions` differ.
+    pub fn is_scroll_compatible(&self, other: &ViewConfig) -> bool {
+        self.group_by == other.group_by
+            && self.split_by == other.split_by
+            && self.columns == other.columns
+            && self.sort == other.sort
+            && self.filter == other.filter
+    }
 }
 
-#[derive(Deserialize, Default)]
+#[derive(Deserialize, Default, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ViewConfigUpdate {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub group_by: Option<Vec<String>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub split_by: Option<Vec<String>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub columns: Option<Vec<Option<String>>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub filter: Option<Vec<Filter>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sort: Option<Vec<Sort>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub expressions: Option<Vec<String>>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub aggregates: Option<HashMap<String, Aggregate>>,
 }
 
+impl ViewConfigUpdate {
+    /// Merge `other` into this update, preferring `other` for any field it
+    /// defines.
+    pub fn merge(&mut self, other: ViewConfigUpdate) {
+        fn merge_field<T>(x: &mut Option<T>, y: Option<T>) {
+            if y.is_some() {
+                *x = y;
+            }
+        }
+
+        merge_field(&mut self.group_by, other.group_by);
+        merge_field(&mut self.split_by, other.split_by);
+        merge_field(&mut self.columns, other.columns);
+        merge_field(&mut self.filter, other.filter);
+        merge_field(&mut self.sort, other.sort);
+        merge_field(&mut self.expressions, other.expressions);
+        merge_field(&mut self.aggregates, other.aggregates);
+    }
+
+    /// The minimal update which, applied after `old`, has the same effect as
+    /// `new`:  the fields `new` defines which differ from `old`, and an empty
+    /// value for fields only `old` defines.
+    pub fn diff(old: &ViewConfigUpdate, new: &ViewConfigUpdate) -> ViewConfigUpdate {
+        fn diff_field<T>(x: &Option<T>, y: &Option<T>) -> Option<T>
+        where
+            T: Clone + Default + PartialEq,
+        {
+            match (x, y) {
+                (Some(x), None) if *x != T::default() => Some(T::default()),
+                (x, Some(y)) if x.as_ref() != Some(y) => Some(y.clone()),
+                _ => None,
+            }
+        }
+
+        ViewConfigUpdate {
+            group_by: diff_field(&old.group_by, &new.group_by),
+            split_by: diff_field(&old.split_by, &new.split_by),
+            columns: diff_field(&old.columns, &new.columns),
+            filter: diff_field(&old.filter, &new.filter),
+            sort: diff_field(&old.sort, &new.sort),
+            expressions: diff_field(&old.expressions, &new.expressions),
+            aggregates: diff_field(&old.aggregates, &new.aggregates),
+        }
+    }
+}
+
+impl From<ViewConfig> for ViewConfigUpdate {
+    fn from(config: ViewConfig) -> Self {
+        ViewConfigUpdate {
+            group_by: Some(config.group_by),
+            split_by: Some(config.split_by),
+            columns: Some(config.columns),
+            filter: Some(config.filter),
+            sort: Some(config.sort),
+            expressions: Some(config.expressions),
+            aggregates: Some(config.aggregates),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,6 +315,68 @@ mod tests {
         );
     }
 
+    #[wasm_bindgen_test]
+    pub fn test_expression_alias() {
+        assert_eq!(
+            split_expression_alias("// Profit\n\"Sales\" - \"Cost\""),
+            ("Profit", "\"Sales\" - \"Cost\"")
+        );
+        assert_eq!(split_expression_alias("1 + 2"), ("1 + 2", "1 + 2"));
+        assert_eq!(
+            split_expression_alias("//\n1 + 2"),
+            ("//\n1 + 2", "//\n1 + 2")
+        );
+        assert_eq!(join_expression_alias("1 + 2", "1 + 2"), "1 + 2");
+        assert_eq!(join_expression_alias("Three", "1 + 2"), "// Three\n1 + 2");
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_update_merge() {
+        let mut x: ViewConfigUpdate = json!({
+            "group_by": ["A"],
+            "columns": ["B"]
+        })
+        .into_serde()
+        .unwrap();
+
+        let y: ViewConfigUpdate = json!({
+            "group_by": ["C"],
+            "sort": [["B", "asc"]]
+        })
+        .into_serde()
+        .unwrap();
+
+        x.merge(y);
+        assert_eq!(x.group_by, Some(vec!["C".to_owned()]));
+        assert_eq!(x.columns, Some(vec![Some("B".to_owned())]));
+        assert_eq!(x.sort.map(|x| x.len()), Some(1));
+        assert_eq!(x.split_by, None);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_update_diff() {
+        let x: ViewConfigUpdate = json!({
+            "group_by": ["A"],
+            "columns": ["B"]
+        })
+        .into_serde()
+        .unwrap();
+
+        let y: ViewConfigUpdate = json!({
+            "group_by": ["A"],
+            "columns": ["C"],
+            "sort": [["C", "desc"]]
+        })
+        .into_serde()
+        .unwrap();
+
+        let diff = ViewConfigUpdate::diff(&x, &y);
+        assert_eq!(diff.group_by, None);
+        assert_eq!(diff.columns, Some(vec![Some("C".to_owned())]));
+        assert_eq!(diff.sort.map(|x| x.len()), Some(1));
+        assert_eq!(diff.split_by, None);
+    }
+
     #[wasm_bindgen_test]
     pub fn test_group_by() {
         let x = json!({
@@ -205,6 +433,71 @@ mod tests {
         );
     }
 
+    #[wasm_bindgen_test]
+    pub fn test_column_filters_not_in_round_trip() {
+        let values = ["a", "b"]
+            .iter()
+            .map(|x| JsValue::from(*x))
+            .collect::<Array>();
+        let filter = [
+            JsValue::from("Test"),
+            JsValue::from("not in"),
+            values.into(),
+        ]
+        .iter()
+        .collect::<Array>();
+
+        let x = json!({ "filter": [filter] });
+        let rec: ViewConfig = x.into_serde().unwrap();
+        let expected = Filter(
+            "Test".to_owned(),
+            FilterOp::NotIn,
+            FilterTerm::Array(vec![
+                Scalar::String("a".to_owned()),
+                Scalar::String("b".to_owned()),
+            ]),
+        );
+
+        assert_eq!(rec.filter, vec![expected]);
+        let saved: ViewConfig = JsValue::from_serde(&rec).unwrap().into_serde().unwrap();
+        assert_eq!(saved, rec);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_column_filters_relative_date_round_trip() {
+        let filter = [
+            JsValue::from("Test"),
+            JsValue::from(">="),
+            json!({ "relative": "last 7 days" }).into(),
+        ]
+        .iter()
+        .collect::<Array>();
+
+        let x = json!({ "filter": [filter] });
+        let rec: ViewConfig = x.into_serde().unwrap();
+        assert_eq!(rec.filter, vec![Filter(
+            "Test".to_owned(),
+            FilterOp::GTE,
+            FilterTerm::Relative {
+                relative: RelativeDateFilter::Last7Days
+            }
+        )]);
+
+        let saved: ViewConfig = JsValue::from_serde(&rec).unwrap().into_serde().unwrap();
+        assert_eq!(saved, rec);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_relative_date_filter_start_date() {
+        let today = chrono::NaiveDate::from_ymd(2022, 3, 15);
+        let start = |x: RelativeDateFilter| x.start_date(today).to_string();
+        assert_eq!(start(RelativeDateFilter::Today), "2022-03-15");
+        assert_eq!(start(RelativeDateFilter::Last7Days), "2022-03-09");
+        assert_eq!(start(RelativeDateFilter::Last30Days), "2022-02-14");
+        assert_eq!(start(RelativeDateFilter::ThisMonth), "2022-03-01");
+        assert_eq!(start(RelativeDateFilter::YearToDate), "2022-01-01");
+    }
+
     #[wasm_bindgen_test]
     pub fn test_column_sorts() {
         let sort = ["Test", "asc"]
@@ -286,4 +579,33 @@ mod tests {
         assert_eq!(view_config.group_by, vec!("Test".to_owned()));
         assert_eq!(view_config.split_by, vec!("Test2".to_owned()));
     }
+
+    #[wasm_bindgen_test]
+    pub fn test_referenced_columns() {
+        let x = json!({
+            "columns": ["A", null, "B"],
+            "group_by": ["C"],
+            "sort": [["A", "desc"]],
+            "aggregates": {
+                "B": ["weighted_mean", "D"]
+            }
+        });
+
+        let rec: ViewConfig = x.into_serde().unwrap();
+        assert_eq!(rec.referenced_columns(), vec!["A", "B", "C", "D"]);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_is_scroll_compatible() {
+        let x: ViewConfig = json!({"columns": ["A", "B"], "group_by": ["C"]})
+            .into_serde()
+            .unwrap();
+
+        let mut y = x.clone();
+        y.expressions = vec!["1 + 2".to_owned()];
+        assert!(x.is_scroll_compatible(&y));
+
+        y.group_by = vec![];
+        assert!(!x.is_scroll_compatible(&y));
+    }
 }
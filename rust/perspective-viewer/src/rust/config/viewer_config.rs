@@ -15,7 +15,9 @@ use flate2::Compression;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
+use serde::Serializer;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::io::Read;
 use std::io::Write;
 use std::str::FromStr;
@@ -44,13 +46,19 @@ impl FromStr for ViewerConfigEncoding {
 
 /// The state of an entire `custom_elements::PerspectiveViewerElement` component
 /// and its `Plugin`.
-#[derive(Serialize, PartialEq)]
+#[derive(Clone, Serialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct ViewerConfig {
     pub plugin: String,
     pub plugin_config: Value,
     pub settings: bool,
     pub theme: Option<String>,
+    pub density: Density,
+    pub type_defaults: HashMap<String, Value>,
+    pub pinned_columns: Vec<String>,
+    pub settings_width: Option<i32>,
+    pub row_height: Option<f64>,
+    pub header_height: Option<f64>,
 
     #[serde(flatten)]
     pub view_config: ViewConfig,
@@ -82,23 +90,53 @@ impl ViewerConfig {
             None | Some(ViewerConfigEncoding::Json) => JsValue::from_serde(self).into_jserror(),
         }
     }
+
+    /// Encode a `ViewerConfig` as `ViewerConfigEncoding::String`, translated to
+    /// the URL-safe base64 alphabet without padding, so that it can be used
+    /// verbatim in a URL fragment.
+    pub fn to_url_fragment(&self) -> Result<String, JsValue> {
+        let encoded = self.encode(&Some(ViewerConfigEncoding::String))?;
+        let encoded = encoded.as_string().into_jserror()?;
+        Ok(encoded
+            .trim_end_matches('=')
+            .replace('+', "-")
+            .replace('/', "_"))
+    }
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ViewerConfigUpdate {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
     pub plugin: PluginUpdate,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
     pub theme: ThemeUpdate,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
     pub settings: SettingsUpdate,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
+    pub density: DensityUpdate,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub plugin_config: Option<Value>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_defaults: Option<HashMap<String, Value>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_columns: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
+    pub settings_width: SettingsWidthUpdate,
+
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
+    pub row_height: RowHeightUpdate,
+
+    #[serde(default, skip_serializing_if = "OptionalUpdate::is_missing")]
+    pub header_height: RowHeightUpdate,
+
     #[serde(flatten)]
     pub view_config: ViewConfigUpdate,
 }
@@ -107,34 +145,214 @@ impl ViewerConfigUpdate {
     /// Decode a `JsValue` into a `ViewerConfigUpdate` by auto-detecting format
     /// from JavaScript type.
     pub fn decode(update: &JsValue) -> Result<Self, JsValue> {
-        if update.is_string() {
-            let js_str = update.as_string().into_jserror()?;
-            let bytes = base64::decode(js_str).into_jserror()?;
-            let mut decoder = ZlibDecoder::new(&*bytes);
-            let mut decoded = vec![];
-            decoder.read_to_end(&mut decoded).into_jserror()?;
-            rmp_serde::from_slice(&decoded).into_jserror()
+        let format = if update.is_string() {
+            ViewerConfigEncoding::String
         } else if update.is_instance_of::<js_sys::ArrayBuffer>() {
-            let uint8array = js_sys::Uint8Array::new(update);
-            let mut slice = vec![0; uint8array.length() as usize];
-            uint8array.copy_to(&mut slice[..]);
-            rmp_serde::from_slice(&slice).into_jserror()
+            ViewerConfigEncoding::ArrayBuffer
         } else {
-            update.into_serde().into_jserror()
+            ViewerConfigEncoding::Json
+        };
+
+        Self::decode_as(update, &format)
+    }
+
+    /// Decode a `JsValue` into a `ViewerConfigUpdate` in an explicit format,
+    /// for callers whose serialized configs could be mis-detected by
+    /// `decode()`, e.g. a JSON string which is also valid base64.  In `Json`
+    /// format, a string is parsed as JSON text.
+    ///
+    /// # Arguments
+    /// - `update` The serialized update.
+    /// - `format` The encoding `update` is in.
+    pub fn decode_as(update: &JsValue, format: &ViewerConfigEncoding) -> Result<Self, JsValue> {
+        match format {
+            ViewerConfigEncoding::String => {
+                let js_str = update.as_string().into_jserror()?;
+                let bytes = base64::decode(js_str).into_jserror()?;
+                let mut decoder = ZlibDecoder::new(&*bytes);
+                let mut decoded = vec![];
+                decoder.read_to_end(&mut decoded).into_jserror()?;
+                rmp_serde::from_slice(&decoded).into_jserror()
+            }
+            ViewerConfigEncoding::ArrayBuffer => {
+                let buffer = update.dyn_ref::<js_sys::ArrayBuffer>().into_jserror()?;
+                let uint8array = js_sys::Uint8Array::new(buffer);
+                let mut slice = vec![0; uint8array.length() as usize];
+                uint8array.copy_to(&mut slice[..]);
+                rmp_serde::from_slice(&slice).into_jserror()
+            }
+            ViewerConfigEncoding::Json | ViewerConfigEncoding::JSONString => {
+                match update.as_string() {
+                    Some(text) => serde_json::from_str(&text).into_jserror(),
+                    None => update.into_serde().into_jserror(),
+                }
+            }
+        }
+    }
+
+    /// Decode a `ViewerConfigUpdate` from a URL fragment created by
+    /// `ViewerConfig::to_url_fragment()`.
+    pub fn from_url_fragment(fragment: &str) -> Result<Self, JsValue> {
+        let mut encoded = fragment.replace('-', "+").replace('_', "/");
+        let padding = (4 - encoded.len() % 4) % 4;
+        encoded.extend(std::iter::repeat('=').take(padding));
+        Self::decode_as(&JsValue::from(encoded), &ViewerConfigEncoding::String)
+    }
+
+    /// Merge `other` into this update, such that fields defined in `other`
+    /// take precedence.  Used to coalesce a sequence of updates into a single
+    /// render.
+    pub fn merge(&mut self, other: ViewerConfigUpdate) {
+        self.plugin.merge(other.plugin);
+        self.theme.merge(other.theme);
+        self.settings.merge(other.settings);
+        self.density.merge(other.density);
+        self.settings_width.merge(other.settings_width);
+        self.row_height.merge(other.row_height);
+        self.header_height.merge(other.header_height);
+        if other.plugin_config.is_some() {
+            self.plugin_config = other.plugin_config;
+        }
+
+        if other.type_defaults.is_some() {
+            self.type_defaults = other.type_defaults;
+        }
+
+        if other.pinned_columns.is_some() {
+            self.pinned_columns = other.pinned_columns;
         }
+
+        self.view_config.merge(other.view_config);
+    }
+
+    /// The minimal update which, applied after `old` (e.g. via `restore()`),
+    /// has the same effect as `new`.  A field `new` resets to its default, or
+    /// does not define at all while `old` does, is `null` (or empty, for
+    /// fields which have no `null` form).
+    pub fn diff(old: &ViewerConfigUpdate, new: &ViewerConfigUpdate) -> ViewerConfigUpdate {
+        fn diff_field<T>(x: &Option<T>, y: &Option<T>, default: T) -> Option<T>
+        where
+            T: Clone + PartialEq,
+        {
+            match (x, y) {
+                (Some(x), None) if *x != default => Some(default),
+                (x, Some(y)) if x.as_ref() != Some(y) => Some(y.clone()),
+                _ => None,
+            }
+        }
+
+        let empty_plugin_config = Value::Object(Default::default());
+        ViewerConfigUpdate {
+            plugin: old.plugin.diff(&new.plugin),
+            theme: old.theme.diff(&new.theme),
+            settings: old.settings.diff(&new.settings),
+            density: old.density.diff(&new.density),
+            plugin_config: diff_field(&old.plugin_config, &new.plugin_config, empty_plugin_config),
+            type_defaults: diff_field(&old.type_defaults, &new.type_defaults, HashMap::new()),
+            pinned_columns: diff_field(&old.pinned_columns, &new.pinned_columns, vec![]),
+            settings_width: old.settings_width.diff(&new.settings_width),
+            row_height: old.row_height.diff(&new.row_height),
+            header_height: old.header_height.diff(&new.header_height),
+            view_config: ViewConfigUpdate::diff(&old.view_config, &new.view_config),
+        }
+    }
+}
+
+/// A structured diff between two serialized configs, as returned by
+/// `diffConfig()`.  Fields are compared in their serialized form, so
+/// `view_config` fields are listed alongside `plugin`, `theme`, etc.
+#[derive(Serialize)]
+pub struct ViewerConfigDiff {
+    /// Fields only the new config defines, and their values.
+    pub added: serde_json::Map<String, Value>,
+
+    /// Fields only the old config defines.
+    pub removed: Vec<String>,
+
+    /// Fields whose values differ, as `{old, new}` pairs.
+    pub changed: serde_json::Map<String, Value>,
+
+    /// The partial update which `restore()`s the old config to the new one,
+    /// per `ViewerConfigUpdate::diff()`.
+    pub update: ViewerConfigUpdate,
+}
+
+impl ViewerConfigDiff {
+    pub fn new(old: &ViewerConfigUpdate, new: &ViewerConfigUpdate) -> Result<Self, JsValue> {
+        let to_map = |x: &ViewerConfigUpdate| match serde_json::to_value(x).into_jserror()? {
+            Value::Object(map) => Ok(map),
+            _ => Err(JsValue::from("Config did not serialize to an object")),
+        };
+
+        let (old_map, new_map) = (to_map(old)?, to_map(new)?);
+        let mut diff = ViewerConfigDiff {
+            added: serde_json::Map::new(),
+            removed: vec![],
+            changed: serde_json::Map::new(),
+            update: ViewerConfigUpdate::diff(old, new),
+        };
+
+        for (key, old_value) in old_map.iter() {
+            match new_map.get(key) {
+                None => diff.removed.push(key.to_owned()),
+                Some(new_value) if new_value != old_value => {
+                    let pair = serde_json::json!({"old": old_value, "new": new_value});
+                    diff.changed.insert(key.to_owned(), pair);
+                }
+                Some(_) => (),
+            }
+        }
+
+        for (key, new_value) in new_map.into_iter() {
+            if !old_map.contains_key(&key) {
+                diff.added.insert(key, new_value);
+            }
+        }
+
+        Ok(diff)
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum OptionalUpdate<T: Clone> {
     SetDefault,
     Missing,
     Update(T),
 }
 
+impl<T: Clone> OptionalUpdate<T> {
+    /// Replace this update with `other`, unless `other` is `Missing`.
+    pub fn merge(&mut self, other: Self) {
+        if !matches!(other, Self::Missing) {
+            *self = other;
+        }
+    }
+
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Self::Missing)
+    }
+}
+
+impl<T: Clone + PartialEq> OptionalUpdate<T> {
+    /// `other`, or `Missing` if applying it after this update would change
+    /// nothing.  A `Missing` `other` is treated as the default, so it is
+    /// `SetDefault` if this update is an `Update`.
+    pub fn diff(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Self::Update(_), Self::Missing) => Self::SetDefault,
+            (_, Self::Missing) => Self::Missing,
+            (x, y) if x == y => Self::Missing,
+            (_, y) => y.clone(),
+        }
+    }
+}
+
 pub type PluginUpdate = OptionalUpdate<String>;
 pub type SettingsUpdate = OptionalUpdate<bool>;
 pub type ThemeUpdate = OptionalUpdate<String>;
+pub type DensityUpdate = OptionalUpdate<Density>;
+pub type SettingsWidthUpdate = OptionalUpdate<i32>;
+pub type RowHeightUpdate = OptionalUpdate<f64>;
 
 /// Handles `{}` when included as a field with `#[serde(default)]`.
 impl<T: Clone> Default for OptionalUpdate<T> {
@@ -167,3 +385,170 @@ where
         Option::deserialize(deserializer).map(Into::into)
     }
 }
+
+/// The inverse of `Deserialize`, serializing `SetDefault` as `null`.  Fields
+/// which are `Missing` should be skipped with `OptionalUpdate::is_missing`.
+impl<T> Serialize for OptionalUpdate<T>
+where
+    T: Serialize + Clone,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Update(x) => serializer.serialize_some(x),
+            _ => serializer.serialize_none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use wasm_bindgen_test::*;
+
+    fn test_config() -> ViewerConfig {
+        ViewerConfig {
+            plugin: "Datagrid".to_owned(),
+            plugin_config: Value::Null,
+            settings: true,
+            theme: None,
+            density: Density::Compact,
+            type_defaults: HashMap::new(),
+            pinned_columns: vec!["Sales".to_owned()],
+            settings_width: Some(320),
+            row_height: Some(24.0),
+            header_height: None,
+            view_config: ViewConfig::default(),
+        }
+    }
+
+    fn plugin_name(update: ViewerConfigUpdate) -> Option<String> {
+        match update.plugin {
+            OptionalUpdate::Update(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_decode_as_json() {
+        let update = json!({"plugin": "Datagrid"});
+        let decoded = ViewerConfigUpdate::decode_as(&update, &ViewerConfigEncoding::Json);
+        assert_eq!(plugin_name(decoded.unwrap()), Some("Datagrid".to_owned()));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_decode_as_string() {
+        let format = Some(ViewerConfigEncoding::String);
+        let encoded = test_config().encode(&format).unwrap();
+        let decoded = ViewerConfigUpdate::decode_as(&encoded, &ViewerConfigEncoding::String);
+        let decoded = decoded.unwrap();
+        assert_eq!(decoded.density, OptionalUpdate::Update(Density::Compact));
+        assert_eq!(decoded.pinned_columns, Some(vec!["Sales".to_owned()]));
+        assert_eq!(decoded.settings_width, OptionalUpdate::Update(320));
+        assert_eq!(decoded.row_height, OptionalUpdate::Update(24.0));
+        assert_eq!(decoded.header_height, OptionalUpdate::SetDefault);
+        assert_eq!(plugin_name(decoded), Some("Datagrid".to_owned()));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_encode_default_fields() {
+        let config = ViewerConfig {
+            density: Density::Comfortable,
+            pinned_columns: vec![],
+            settings_width: None,
+            row_height: None,
+            ..test_config()
+        };
+
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["density"], serde_json::json!("comfortable"));
+        assert_eq!(json["type_defaults"], serde_json::json!({}));
+        assert_eq!(json["pinned_columns"], serde_json::json!([]));
+        assert_eq!(json.get("settings_width"), Some(&Value::Null));
+        assert_eq!(json.get("row_height"), Some(&Value::Null));
+        assert_eq!(json.get("header_height"), Some(&Value::Null));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_decode_as_arraybuffer() {
+        let format = Some(ViewerConfigEncoding::ArrayBuffer);
+        let encoded = test_config().encode(&format).unwrap();
+        let decoded = ViewerConfigUpdate::decode_as(&encoded, &ViewerConfigEncoding::ArrayBuffer);
+        assert_eq!(plugin_name(decoded.unwrap()), Some("Datagrid".to_owned()));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_url_fragment() {
+        let fragment = test_config().to_url_fragment().unwrap();
+        assert!(fragment
+            .chars()
+            .all(|x| x.is_ascii_alphanumeric() || x == '-' || x == '_'));
+
+        let decoded = ViewerConfigUpdate::from_url_fragment(&fragment).unwrap();
+        assert_eq!(decoded.row_height, OptionalUpdate::Update(24.0));
+        assert_eq!(plugin_name(decoded), Some("Datagrid".to_owned()));
+        assert!(ViewerConfigUpdate::from_url_fragment("not a config").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_decode_as_wrong_format_fails() {
+        let update = json!({"plugin": "Datagrid"});
+        let decoded = ViewerConfigUpdate::decode_as(&update, &ViewerConfigEncoding::ArrayBuffer);
+        assert!(decoded.is_err());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_decode_json_string_misdetected() {
+        let update = JsValue::from("{\"plugin\": \"Datagrid\"}");
+        assert!(ViewerConfigUpdate::decode(&update).is_err());
+        let decoded = ViewerConfigUpdate::decode_as(&update, &ViewerConfigEncoding::Json);
+        assert_eq!(plugin_name(decoded.unwrap()), Some("Datagrid".to_owned()));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_diff() {
+        let old = json!({"plugin": "Datagrid", "theme": "Pro Dark", "group_by": ["A"]});
+        let new = json!({"plugin": "Y Bar", "theme": null, "group_by": ["A"]});
+        let old = ViewerConfigUpdate::decode(&old).unwrap();
+        let new = ViewerConfigUpdate::decode(&new).unwrap();
+        let diff = ViewerConfigUpdate::diff(&old, &new);
+        assert_eq!(diff.plugin, OptionalUpdate::Update("Y Bar".to_owned()));
+        assert_eq!(diff.theme, OptionalUpdate::SetDefault);
+        assert_eq!(diff.settings, OptionalUpdate::Missing);
+        assert_eq!(diff.view_config.group_by, None);
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json, serde_json::json!({"plugin": "Y Bar", "theme": null}));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_diff_omitted_fields() {
+        let old = json!({"plugin": "Datagrid", "pinned_columns": ["A"], "group_by": ["A"]});
+        let new = json!({"plugin": "Datagrid", "split_by": ["B"]});
+        let old = ViewerConfigUpdate::decode(&old).unwrap();
+        let new = ViewerConfigUpdate::decode(&new).unwrap();
+        let diff = ViewerConfigDiff::new(&old, &new).unwrap();
+        assert_eq!(diff.update.plugin, OptionalUpdate::Missing);
+        assert_eq!(diff.update.pinned_columns, Some(vec![]));
+        assert_eq!(diff.update.view_config.group_by, Some(vec![]));
+        assert_eq!(diff.update.view_config.split_by, Some(vec!["B".to_owned()]));
+        assert_eq!(diff.removed, vec![
+            "group_by".to_owned(),
+            "pinned_columns".to_owned()
+        ]);
+        assert_eq!(
+            Value::Object(diff.added),
+            serde_json::json!({"split_by": ["B"]})
+        );
+        assert!(diff.changed.is_empty());
+
+        let old = ViewerConfigUpdate::decode(&json!({"theme": "Pro Dark"})).unwrap();
+        let new = ViewerConfigUpdate::decode(&json!({})).unwrap();
+        let diff = ViewerConfigDiff::new(&old, &new).unwrap();
+        assert_eq!(diff.update.theme, OptionalUpdate::SetDefault);
+        assert_eq!(diff.removed, vec!["theme".to_owned()]);
+    }
+}
@@ -0,0 +1,227 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::config::*;
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// The default maximum number of entries kept by a `ConfigHistory`.
+pub const DEFAULT_HISTORY_LIMIT: usize = 50;
+
+/// Changes recorded within this many milliseconds of the previous change are
+/// merged into a single entry, so that e.g. one column drag is one `undo()`.
+const HISTORY_COALESCE_MS: f64 = 500.0;
+
+pub struct ConfigHistoryData {
+    entries: RefCell<VecDeque<ViewerConfig>>,
+    index: Cell<usize>,
+    limit: Cell<usize>,
+    paused: Cell<usize>,
+    last_record: Cell<f64>,
+}
+
+impl Default for ConfigHistoryData {
+    fn default() -> Self {
+        Self {
+            entries: Default::default(),
+            index: Default::default(),
+            limit: Cell::new(DEFAULT_HISTORY_LIMIT),
+            paused: Default::default(),
+            last_record: Cell::new(f64::NEG_INFINITY),
+        }
+    }
+}
+
+/// A bounded undo/redo stack of `ViewerConfig`, recorded as the viewer
+/// dispatches `"perspective-config-update"`.  The entry at the cursor is
+/// always the viewer's current state.
+#[derive(Clone, Default)]
+pub struct ConfigHistory(Rc<ConfigHistoryData>);
+
+impl Deref for ConfigHistory {
+    type Target = ConfigHistoryData;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// While a `HistoryPause` is alive, recorded configs replace the current entry
+/// rather than creating a new one.
+pub struct HistoryPause(ConfigHistory);
+
+impl Drop for HistoryPause {
+    fn drop(&mut self) {
+        self.0.paused.set(self.0.paused.get() - 1);
+    }
+}
+
+impl ConfigHistory {
+    /// Record a new viewer state.
+    pub fn record(&self, config: ViewerConfig) {
+        self.record_at(config, js_sys::Date::now())
+    }
+
+    fn record_at(&self, config: ViewerConfig, now: f64) {
+        let mut entries = self.entries.borrow_mut();
+        let index = self.index.get();
+        if entries.get(index) == Some(&config) {
+            return;
+        }
+
+        if entries.is_empty() {
+            entries.push_back(config);
+            return;
+        }
+
+        if self.paused.get() > 0 {
+            entries[index] = config;
+            return;
+        }
+
+        let is_coalesced = index > 0
+            && index + 1 == entries.len()
+            && now - self.last_record.get() < HISTORY_COALESCE_MS;
+
+        self.last_record.set(now);
+        if is_coalesced {
+            entries[index] = config;
+        } else {
+            entries.truncate(index + 1);
+            entries.push_back(config);
+            if entries.len() > self.limit.get() {
+                entries.pop_front();
+            }
+
+            self.index.set(entries.len() - 1);
+        }
+    }
+
+    /// Move the cursor back one entry, returning the config to restore.
+    pub fn undo(&self) -> Option<ViewerConfig> {
+        let index = self.index.get().checked_sub(1)?;
+        self.move_to(index)
+    }
+
+    /// Move the cursor forward one entry, returning the config to restore.
+    pub fn redo(&self) -> Option<ViewerConfig> {
+        self.move_to(self.index.get() + 1)
+    }
+
+    fn move_to(&self, index: usize) -> Option<ViewerConfig> {
+        let config = self.entries.borrow().get(index).cloned()?;
+        self.index.set(index);
+        self.last_record.set(f64::NEG_INFINITY);
+        Some(config)
+    }
+
+    /// Set the maximum number of entries, dropping the oldest as needed (and
+    /// then the newest, as the current entry is always kept).
+    pub fn set_limit(&self, limit: usize) {
+        let limit = limit.max(1);
+        let mut entries = self.entries.borrow_mut();
+        while entries.len() > limit {
+            if self.index.get() > 0 {
+                entries.pop_front();
+                self.index.set(self.index.get() - 1);
+            } else {
+                entries.pop_back();
+            }
+        }
+
+        self.limit.set(limit);
+    }
+
+    /// Stop recording new entries until the returned guard is dropped, e.g.
+    /// for the duration of an `undo()` or a programmatic `restore()`.
+    pub fn pause(&self) -> HistoryPause {
+        self.paused.set(self.paused.get() + 1);
+        self.last_record.set(f64::NEG_INFINITY);
+        HistoryPause(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn config(plugin: &str) -> ViewerConfig {
+        ViewerConfig {
+            plugin: plugin.to_owned(),
+            plugin_config: serde_json::Value::Null,
+            settings: false,
+            theme: None,
+            density: Default::default(),
+            type_defaults: Default::default(),
+            pinned_columns: Default::default(),
+            settings_width: None,
+            row_height: None,
+            header_height: None,
+            view_config: Default::default(),
+        }
+    }
+
+    fn plugin(config: Option<ViewerConfig>) -> Option<String> {
+        config.map(|x| x.plugin)
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_undo_redo() {
+        let history = ConfigHistory::default();
+        history.record_at(config("A"), 0.0);
+        history.record_at(config("B"), 1000.0);
+        history.record_at(config("C"), 2000.0);
+        assert_eq!(plugin(history.undo()), Some("B".to_owned()));
+        assert_eq!(plugin(history.undo()), Some("A".to_owned()));
+        assert_eq!(plugin(history.undo()), None);
+        assert_eq!(plugin(history.redo()), Some("B".to_owned()));
+        history.record_at(config("D"), 3000.0);
+        assert_eq!(plugin(history.redo()), None);
+        assert_eq!(plugin(history.undo()), Some("B".to_owned()));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_coalesce() {
+        let history = ConfigHistory::default();
+        history.record_at(config("A"), 0.0);
+        history.record_at(config("B"), 1000.0);
+        history.record_at(config("C"), 1100.0);
+        history.record_at(config("D"), 1200.0);
+        assert_eq!(plugin(history.undo()), Some("A".to_owned()));
+        assert_eq!(plugin(history.redo()), Some("D".to_owned()));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_limit() {
+        let history = ConfigHistory::default();
+        history.set_limit(2);
+        history.record_at(config("A"), 0.0);
+        history.record_at(config("B"), 1000.0);
+        history.record_at(config("C"), 2000.0);
+        assert_eq!(plugin(history.undo()), Some("B".to_owned()));
+        assert_eq!(plugin(history.undo()), None);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_pause() {
+        let history = ConfigHistory::default();
+        history.record_at(config("A"), 0.0);
+        history.record_at(config("B"), 1000.0);
+        {
+            let _pause = history.pause();
+            history.record_at(config("C"), 2000.0);
+        }
+
+        history.record_at(config("D"), 3000.0);
+        assert_eq!(plugin(history.undo()), Some("C".to_owned()));
+        assert_eq!(plugin(history.undo()), Some("A".to_owned()));
+    }
+}
@@ -0,0 +1,111 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::config::*;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+
+const BATCH_APPLIED: &str = "Batch already applied";
+
+/// The mutation interface passed to the callback of `batch()`.  Each method
+/// merges its change into a pending `ViewerConfigUpdate` without rendering;
+/// the viewer applies the merged update once the callback completes.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct ViewerConfigBatch(Rc<RefCell<Option<ViewerConfigUpdate>>>);
+
+impl Default for ViewerConfigBatch {
+    fn default() -> Self {
+        Self(Rc::new(RefCell::new(Some(ViewerConfigUpdate::default()))))
+    }
+}
+
+#[wasm_bindgen]
+impl ViewerConfigBatch {
+    /// Merge a (possibly partial) config into this batch, fields from later
+    /// calls taking precedence.
+    ///
+    /// # Arguments
+    /// - `update` The config, in any format `restore()` accepts.
+    pub fn restore(&self, update: JsValue) -> Result<(), JsValue> {
+        let update = ViewerConfigUpdate::decode(&update)?;
+        let mut pending = self.0.borrow_mut();
+        pending.as_mut().ok_or(BATCH_APPLIED)?.merge(update);
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name = "setColumns")]
+    pub fn set_columns(&self, columns: JsValue) -> Result<(), JsValue> {
+        self.set_field("columns", columns)
+    }
+
+    #[wasm_bindgen(js_name = "setGroupBy")]
+    pub fn set_group_by(&self, group_by: JsValue) -> Result<(), JsValue> {
+        self.set_field("group_by", group_by)
+    }
+
+    #[wasm_bindgen(js_name = "setSplitBy")]
+    pub fn set_split_by(&self, split_by: JsValue) -> Result<(), JsValue> {
+        self.set_field("split_by", split_by)
+    }
+
+    #[wasm_bindgen(js_name = "setSort")]
+    pub fn set_sort(&self, sort: JsValue) -> Result<(), JsValue> {
+        self.set_field("sort", sort)
+    }
+
+    #[wasm_bindgen(js_name = "setFilter")]
+    pub fn set_filter(&self, filter: JsValue) -> Result<(), JsValue> {
+        self.set_field("filter", filter)
+    }
+}
+
+impl ViewerConfigBatch {
+    /// Take the merged update, after which this batch can no longer be
+    /// mutated, e.g. from a callback which outlives `batch()`.
+    pub fn take(&self) -> Result<ViewerConfigUpdate, JsValue> {
+        self.0
+            .borrow_mut()
+            .take()
+            .ok_or_else(|| BATCH_APPLIED.into())
+    }
+
+    fn set_field(&self, name: &str, value: JsValue) -> Result<(), JsValue> {
+        let update = js_sys::Object::new();
+        js_sys::Reflect::set(&update, &name.into(), &value)?;
+        self.restore(update.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_batch_merge() {
+        let batch = ViewerConfigBatch::default();
+        batch.set_group_by(json!(["State"]).into()).unwrap();
+        batch.restore(json!({"group_by": ["City"]}).into()).unwrap();
+        batch.set_split_by(json!(["Region"]).into()).unwrap();
+        let update = batch.take().unwrap();
+        assert_eq!(update.view_config.group_by, Some(vec!["City".to_owned()]));
+        assert_eq!(update.view_config.split_by, Some(vec!["Region".to_owned()]));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_batch_applied() {
+        let batch = ViewerConfigBatch::default();
+        batch.take().unwrap();
+        assert!(batch.set_columns(json!(["Sales"]).into()).is_err());
+        assert!(batch.take().is_err());
+    }
+}
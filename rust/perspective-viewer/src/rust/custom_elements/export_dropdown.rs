@@ -8,6 +8,7 @@
 
 use crate::components::export_dropdown::*;
 use crate::custom_elements::modal::*;
+use crate::js::*;
 use crate::model::*;
 use crate::utils::*;
 use crate::*;
@@ -81,7 +82,20 @@ impl ExportDropDownMenuElement {
             let model = model.cloned();
             let modal_rc = self.modal.clone();
             move |x: ExportFile| {
-                if !x.name.is_empty() {
+                if x.method == ExportMethod::CopyConfig {
+                    let js_task = model.export_method_to_jsvalue(x.method);
+                    let copy_task = copy_to_clipboard(js_task, x.method.mimetype());
+                    clone!(modal_rc);
+                    ApiFuture::spawn(async move {
+                        copy_task.await?;
+                        let modal = modal_rc.borrow().clone().into_jserror()?;
+                        modal.send_message(ExportDropDownMenuMsg::Copied(true));
+                        set_timeout(1000).await?;
+                        modal.hide()?;
+                        modal.send_message(ExportDropDownMenuMsg::Copied(false));
+                        Ok(())
+                    });
+                } else if !x.name.is_empty() {
                     clone!(modal_rc, model);
                     spawn_local(async move {
                         let val = model.export_method_to_jsvalue(x.method).await.unwrap();
@@ -19,10 +19,14 @@ use wasm_bindgen::JsCast;
 use web_sys::*;
 use yew::*;
 
+/// The number of values fetched per page in the "in"/"not in" dropdown.
+const MULTI_SELECT_PAGE_SIZE: usize = 100;
+
 #[wasm_bindgen]
 #[derive(Clone)]
 pub struct FilterDropDownElement {
     modal: ModalElement<FilterDropDown>,
+    elem: HtmlElement,
     session: Session,
     column: Rc<RefCell<Option<(usize, String)>>>,
     values: Rc<RefCell<Option<Vec<String>>>>,
@@ -39,10 +43,11 @@ impl FilterDropDownElement {
 
         let column: Rc<RefCell<Option<(usize, String)>>> = Rc::new(RefCell::new(None));
         let props = props!(FilterDropDownProps {});
-        let modal = ModalElement::new(dropdown, props, false);
+        let modal = ModalElement::new(dropdown.clone(), props, false);
         let values = Rc::new(RefCell::new(None));
         Self {
             modal,
+            elem: dropdown,
             session,
             column,
             values,
@@ -66,6 +71,7 @@ impl FilterDropDownElement {
             Some(filter_col) if filter_col == column => {
                 let values = filter_values(&input, &self.values);
                 self.modal.send_message_batch(vec![
+                    FilterDropDownMsg::SetMultiSelect(None),
                     FilterDropDownMsg::SetCallback(callback),
                     FilterDropDownMsg::SetValues(values),
                 ]);
@@ -82,6 +88,7 @@ impl FilterDropDownElement {
                         *values.borrow_mut() = Some(all_values);
                         let filter_values = filter_values(&input, &values);
                         modal.send_message_batch(vec![
+                            FilterDropDownMsg::SetMultiSelect(None),
                             FilterDropDownMsg::SetCallback(callback),
                             FilterDropDownMsg::SetValues(filter_values),
                         ]);
@@ -94,6 +101,183 @@ impl FilterDropDownElement {
         }
     }
 
+    /// Open the dropdown with a fixed list of `values`, rather than the
+    /// column's values, e.g. for relative date presets.
+    ///
+    /// # Arguments
+    /// - `column` The filter index and column name.
+    /// - `values` The options to list.
+    /// - `target` The filter `<input>` to anchor to.
+    /// - `callback` Called with the selected value.
+    pub fn suggest(
+        &self,
+        column: (usize, String),
+        values: Vec<String>,
+        target: HtmlElement,
+        callback: Callback<String>,
+    ) {
+        *self.column.borrow_mut() = Some(column);
+        *self.target.borrow_mut() = Some(target.clone());
+        *self.values.borrow_mut() = Some(values.clone());
+        self.modal.send_message_batch(vec![
+            FilterDropDownMsg::SetMultiSelect(None),
+            FilterDropDownMsg::SetCallback(callback),
+            FilterDropDownMsg::SetValues(values),
+        ]);
+
+        self.modal.open(target, None);
+    }
+
+    /// Open the dropdown as a checkbox list of `column`'s distinct values, for
+    /// the "in"/"not in" operators.  Values are fetched a page at a time as the
+    /// list is scrolled.
+    ///
+    /// # Arguments
+    /// - `column` The filter index and column name.
+    /// - `checked` The values currently in the filter term.
+    /// - `target` The filter `<input>` to anchor to.
+    /// - `callback` Called with the full list of checked values on change.
+    pub fn select_multiple(
+        &self,
+        column: (usize, String),
+        checked: Vec<String>,
+        target: HtmlElement,
+        callback: Callback<Vec<String>>,
+    ) {
+        let current_column = self.column.borrow().clone();
+        match current_column {
+            Some(filter_col) if filter_col == column => {
+                self.modal
+                    .send_message(FilterDropDownMsg::SetChecked(checked));
+            }
+            _ => {
+                *self.column.borrow_mut() = Some(column.clone());
+                *self.target.borrow_mut() = Some(target.clone());
+                *self.values.borrow_mut() = Some(vec![]);
+                ApiFuture::spawn({
+                    clone!(self.modal, self.session, self.values);
+                    let this = self.clone();
+                    let this_all = self.clone();
+                    async move {
+                        let page = session
+                            .get_column_values_range(column.1, Some((0, MULTI_SELECT_PAGE_SIZE)))
+                            .await?;
+
+                        let has_more = page.len() >= MULTI_SELECT_PAGE_SIZE;
+                        *values.borrow_mut() = Some(page.clone());
+                        let multi_select = FilterMultiSelect {
+                            checked,
+                            has_more,
+                            on_change: callback,
+                            on_load_more: Callback::from(move |()| this.load_more_values()),
+                            on_load_all: Callback::from(move |()| this_all.load_all_values()),
+                        };
+
+                        modal.send_message_batch(vec![
+                            FilterDropDownMsg::SetMultiSelect(Some(multi_select)),
+                            FilterDropDownMsg::SetValues(page),
+                        ]);
+
+                        modal.open(target, None);
+                        Ok(())
+                    }
+                });
+            }
+        }
+    }
+
+    /// Open the dropdown as a min/max input pair for a numeric `>=`/`<=`
+    /// range filter.  Missing bounds default to the column's min and max.
+    ///
+    /// # Arguments
+    /// - `column` The filter index and column name.
+    /// - `current` The current `(min, max)` of the range filter.
+    /// - `target` The filter `<input>` to anchor to.
+    /// - `callback` Called with the new `(min, max)` on change.
+    pub fn select_range(
+        &self,
+        column: (usize, String),
+        current: (Option<f64>, Option<f64>),
+        target: HtmlElement,
+        callback: Callback<(f64, f64)>,
+    ) {
+        *self.column.borrow_mut() = Some(column.clone());
+        *self.target.borrow_mut() = Some(target.clone());
+        ApiFuture::spawn({
+            clone!(self.modal, self.session);
+            let this = self.clone();
+            async move {
+                let bounds = session.get_column_range(column.1).await?;
+                let range = FilterRange {
+                    min: current.0.or(bounds.map(|x| x.0)).unwrap_or_default(),
+                    max: current.1.or(bounds.map(|x| x.1)).unwrap_or_default(),
+                    bounds,
+                    on_change: callback,
+                    on_blur: Callback::from(move |()| {
+                        let _ = this.hide();
+                    }),
+                };
+
+                modal.send_message(FilterDropDownMsg::SetRange(Some(range)));
+                modal.open(target, None);
+                Ok(())
+            }
+        });
+    }
+
+    /// Is `target` this dropdown, e.g. the `relatedTarget` of a `blur` event
+    /// which moved focus into its inputs?
+    pub fn is_target(&self, target: &EventTarget) -> bool {
+        let elem: &EventTarget = self.elem.as_ref();
+        elem == target
+    }
+
+    /// Fetch the next page of values for the "in"/"not in" dropdown.
+    fn load_more_values(&self) {
+        let column = match &*self.column.borrow() {
+            Some((_, column)) => column.clone(),
+            None => return,
+        };
+
+        ApiFuture::spawn({
+            clone!(self.modal, self.session, self.values);
+            async move {
+                let start = values.borrow().as_ref().map_or(0, |x| x.len());
+                let end = start + MULTI_SELECT_PAGE_SIZE;
+                let page = session
+                    .get_column_values_range(column, Some((start, end)))
+                    .await?;
+
+                let has_more = page.len() >= MULTI_SELECT_PAGE_SIZE;
+                if let Some(values) = &mut *values.borrow_mut() {
+                    values.extend(page.iter().cloned());
+                }
+
+                modal.send_message(FilterDropDownMsg::AppendValues(page, has_more));
+                Ok(())
+            }
+        });
+    }
+
+    /// Fetch every remaining value for the "in"/"not in" dropdown and check
+    /// them all, for "Select All".
+    fn load_all_values(&self) {
+        let column = match &*self.column.borrow() {
+            Some((_, column)) => column.clone(),
+            None => return,
+        };
+
+        ApiFuture::spawn({
+            clone!(self.modal, self.session, self.values);
+            async move {
+                let all_values = session.get_column_values(column).await?;
+                *values.borrow_mut() = Some(all_values.clone());
+                modal.send_message(FilterDropDownMsg::CheckAll(all_values));
+                Ok(())
+            }
+        });
+    }
+
     pub fn item_select(&self) {
         self.modal.send_message(FilterDropDownMsg::ItemSelect);
     }
@@ -108,6 +292,8 @@ impl FilterDropDownElement {
 
     pub fn hide(&self) -> Result<(), JsValue> {
         let result = self.modal.hide();
+        self.modal
+            .send_message(FilterDropDownMsg::SetMultiSelect(None));
         drop(self.column.borrow_mut().take());
         result
     }
@@ -0,0 +1,245 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::utils::*;
+
+use serde::Deserialize;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::*;
+
+/// A viewer action which can be bound to a keyboard shortcut.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    ToggleConfig,
+    ExportCsv,
+    Reset,
+}
+
+impl KeyAction {
+    const ALL: [KeyAction; 3] = [Self::ToggleConfig, Self::ExportCsv, Self::Reset];
+
+    fn default_chord(&self) -> &'static str {
+        match self {
+            Self::ToggleConfig => "Alt+Shift+C",
+            Self::ExportCsv => "Alt+Shift+E",
+            Self::Reset => "Alt+Shift+R",
+        }
+    }
+}
+
+/// A key combination, parsed from e.g. `"Ctrl+Shift+E"`.  Letter and digit
+/// keys match the physical key (`KeyboardEvent.code`), as `Alt` changes the
+/// produced character on some layouts;  other keys (e.g. `"Escape"`) match
+/// `KeyboardEvent.key`.  Modifiers must match exactly.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct KeyChord {
+    ctrl: bool,
+    shift: bool,
+    alt: bool,
+    meta: bool,
+    key: String,
+}
+
+impl FromStr for KeyChord {
+    type Err = JsValue;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut chord = KeyChord::default();
+        let (modifiers, key) = input.rsplit_once('+').unwrap_or(("", input));
+        for modifier in modifiers.split('+').filter(|x| !x.is_empty()) {
+            match modifier.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => chord.ctrl = true,
+                "shift" => chord.shift = true,
+                "alt" | "option" => chord.alt = true,
+                "meta" | "cmd" => chord.meta = true,
+                x => return Err(format!("Unknown modifier \"{}\" in \"{}\"", x, input).into()),
+            }
+        }
+
+        chord.key = key.trim().to_lowercase();
+        if chord.key.is_empty() {
+            Err(format!("Missing key in \"{}\"", input).into())
+        } else {
+            Ok(chord)
+        }
+    }
+}
+
+impl KeyChord {
+    fn matches(&self, event: &KeyboardEvent) -> bool {
+        let is_physical =
+            self.key.len() == 1 && self.key.chars().all(|x| x.is_ascii_alphanumeric());
+        let is_key = if is_physical {
+            let code = event.code();
+            code.strip_prefix("Key")
+                .or_else(|| code.strip_prefix("Digit"))
+                .map(|x| x.eq_ignore_ascii_case(&self.key))
+                .unwrap_or_default()
+        } else {
+            event.key().eq_ignore_ascii_case(&self.key)
+        };
+
+        is_key
+            && event.ctrl_key() == self.ctrl
+            && event.shift_key() == self.shift
+            && event.alt_key() == self.alt
+            && event.meta_key() == self.meta
+    }
+}
+
+/// The keyboard shortcuts of a `<perspective-viewer>`, shared between the
+/// custom element (which may rebind them) and its `KeyBindingsHandle`.
+#[derive(Clone)]
+pub struct KeyBindings(Rc<RefCell<HashMap<KeyAction, KeyChord>>>);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let bindings = KeyAction::ALL
+            .iter()
+            .map(|x| (*x, x.default_chord().parse().unwrap()))
+            .collect();
+
+        Self(Rc::new(RefCell::new(bindings)))
+    }
+}
+
+impl KeyBindings {
+    /// Override the bindings of some actions, leaving the rest unchanged.  If
+    /// any entry is invalid, no bindings are changed.
+    ///
+    /// # Arguments
+    /// - `bindings` An object mapping action names to a chord string, or to
+    ///   `null` to unbind the action.  `null` or `undefined` restores the
+    ///   defaults.
+    pub fn set(&self, bindings: &JsValue) -> Result<(), JsValue> {
+        if bindings.is_null() || bindings.is_undefined() {
+            *self.0.borrow_mut() = Self::default().0.take();
+            return Ok(());
+        }
+
+        let bindings = bindings
+            .into_serde::<HashMap<KeyAction, Option<String>>>()
+            .into_jserror()?
+            .into_iter()
+            .map(|(action, chord)| Ok((action, chord.map(|x| x.parse()).transpose()?)))
+            .collect::<Result<Vec<(KeyAction, Option<KeyChord>)>, JsValue>>()?;
+
+        let mut current = self.0.borrow_mut();
+        for (action, chord) in bindings {
+            match chord {
+                Some(chord) => current.insert(action, chord),
+                None => current.remove(&action),
+            };
+        }
+
+        Ok(())
+    }
+
+    fn get_action(&self, event: &KeyboardEvent) -> Option<KeyAction> {
+        self.0
+            .borrow()
+            .iter()
+            .find(|(_, chord)| chord.matches(event))
+            .map(|(action, _)| *action)
+    }
+}
+
+/// Listens for `"keydown"` on the viewer's element (including events which
+/// bubble from its shadow DOM) and calls `on_action` for bound chords, until
+/// dropped.
+pub struct KeyBindingsHandle {
+    elem: HtmlElement,
+    callback: Closure<dyn Fn(KeyboardEvent)>,
+}
+
+impl KeyBindingsHandle {
+    pub fn new(
+        elem: &HtmlElement,
+        bindings: &KeyBindings,
+        on_action: impl Fn(KeyAction) + 'static,
+    ) -> Result<Self, JsValue> {
+        let bindings = bindings.clone();
+        let callback = (move |event: KeyboardEvent| {
+            if event.default_prevented() || is_typing(&event) {
+                return;
+            }
+
+            if let Some(action) = bindings.get_action(&event) {
+                event.prevent_default();
+                on_action(action);
+            }
+        })
+        .into_closure();
+
+        elem.add_event_listener_with_callback("keydown", callback.as_ref().unchecked_ref())?;
+        Ok(Self {
+            elem: elem.clone(),
+            callback,
+        })
+    }
+}
+
+impl Drop for KeyBindingsHandle {
+    fn drop(&mut self) {
+        self.elem
+            .remove_event_listener_with_callback("keydown", self.callback.as_ref().unchecked_ref())
+            .unwrap();
+    }
+}
+
+/// Whether `event` originates from a text field, e.g. an `<input>` or the
+/// expression editor, where key presses are text rather than shortcuts.
+fn is_typing(event: &KeyboardEvent) -> bool {
+    event
+        .composed_path()
+        .get(0)
+        .dyn_into::<HtmlElement>()
+        .map(|x| {
+            matches!(x.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+                || x.is_content_editable()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_parse_chord() {
+        let chord: KeyChord = "Ctrl+Shift+e".parse().unwrap();
+        assert!(chord.ctrl && chord.shift && !chord.alt && !chord.meta);
+        assert_eq!(chord.key, "e");
+        assert_eq!("Escape".parse::<KeyChord>().unwrap().key, "escape");
+        assert!("Hyper+E".parse::<KeyChord>().is_err());
+        assert!("Ctrl+".parse::<KeyChord>().is_err());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_set_bindings() {
+        let bindings = KeyBindings::default();
+        assert_eq!(bindings.0.borrow().len(), 3);
+        let update = json!({"reset": null, "export_csv": "Ctrl+S"});
+        bindings.set(&update.into()).unwrap();
+        assert!(!bindings.0.borrow().contains_key(&KeyAction::Reset));
+        assert_eq!(bindings.0.borrow()[&KeyAction::ExportCsv].key, "s");
+        let invalid = json!({"export_csv": "Ctrl+A", "reset": "Foo+R"});
+        assert!(bindings.set(&invalid.into()).is_err());
+        assert_eq!(bindings.0.borrow()[&KeyAction::ExportCsv].key, "s");
+        bindings.set(&JsValue::NULL).unwrap();
+        assert_eq!(bindings.0.borrow().len(), 3);
+    }
+}
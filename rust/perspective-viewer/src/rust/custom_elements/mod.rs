@@ -6,12 +6,14 @@
 // of the Apache License 2.0.  The full license can be found in the LICENSE
 // file.
 
+pub mod config_batch;
 pub mod copy_dropdown;
 pub mod date_column_style;
 pub mod datetime_column_style;
 pub mod export_dropdown;
 pub mod expression_editor;
 mod filter_dropdown;
+pub mod keybindings;
 pub mod modal;
 pub mod number_column_style;
 pub mod string_column_style;
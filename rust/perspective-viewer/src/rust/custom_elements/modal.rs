@@ -19,6 +19,13 @@ use web_sys::*;
 use yew::prelude::*;
 
 type BlurHandlerType = Rc<RefCell<Option<Closure<dyn FnMut(FocusEvent)>>>>;
+type ViewportHandlerType = Rc<RefCell<Option<Closure<dyn Fn(Event)>>>>;
+type KeydownHandlerType = Rc<RefCell<Option<Closure<dyn Fn(KeyboardEvent)>>>>;
+
+/// Elements which can receive keyboard focus, for the focus trap.
+const FOCUSABLE_SELECTOR: &str = "button:not([disabled]), [href], input:not([disabled]), \
+                                  select:not([disabled]), textarea:not([disabled]), \
+                                  [tabindex]:not([tabindex='-1'])";
 
 /// A `ModalElement` wraps the parameterized yew `Component` in a Custom
 /// Element. Via the `open()` and `close()` methods, a `ModalElement` can be
@@ -41,7 +48,10 @@ where
     custom_element: HtmlElement,
     target: Rc<RefCell<Option<HtmlElement>>>,
     blurhandler: BlurHandlerType,
+    viewport_handler: ViewportHandlerType,
+    keydown_handler: KeydownHandlerType,
     own_focus: bool,
+    trap_focus: Rc<Cell<bool>>,
     resize_sub: Rc<RefCell<Option<Subscription>>>,
     anchor: Rc<Cell<ModalAnchor>>,
 }
@@ -159,6 +169,9 @@ where
             target: Rc::new(RefCell::new(None)),
             own_focus,
             blurhandler,
+            viewport_handler: Default::default(),
+            keydown_handler: Default::default(),
+            trap_focus: Rc::new(Cell::new(true)),
             resize_sub: Rc::new(RefCell::new(None)),
             anchor: Default::default(),
         }
@@ -190,13 +203,46 @@ where
     }
 
     async fn open_within_viewport(&self, target: HtmlElement) -> Result<(), JsValue> {
+        *self.target.borrow_mut() = Some(target.clone());
+        self.anchor_within_viewport(&target).await?;
+        self.add_viewport_listeners()?;
+        if self.own_focus {
+            let mut this = Some(self.clone());
+            *self.blurhandler.borrow_mut() = Some(
+                (move |_| this.take().and_then(|x| x.hide().ok()).unwrap_or(())).into_closure_mut(),
+            );
+
+            self.custom_element.add_event_listener_with_callback(
+                "blur",
+                self.blurhandler
+                    .borrow()
+                    .as_ref()
+                    .unwrap()
+                    .as_ref()
+                    .unchecked_ref(),
+            )?;
+
+            let this = self.clone();
+            let keydown = (move |event: KeyboardEvent| this.on_keydown(&event)).into_closure();
+            self.custom_element
+                .add_event_listener_with_callback("keydown", keydown.as_ref().unchecked_ref())?;
+
+            *self.keydown_handler.borrow_mut() = Some(keydown);
+            self.custom_element.focus()
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Attach this modal to `document.body` and position it relative to
+    /// `target`, first invisibly at the default anchor so it can be measured,
+    /// then at whichever anchor keeps it on-screen.
+    async fn anchor_within_viewport(&self, target: &HtmlElement) -> Result<(), JsValue> {
         let height = target.offset_height() as i32;
         let width = target.offset_width() as i32;
-        let elem = target.clone().unchecked_into::<HtmlElement>();
-        let rect = elem.get_bounding_client_rect();
+        let rect = target.get_bounding_client_rect();
         let top = rect.top() as i32;
         let left = rect.left() as i32;
-        *self.target.borrow_mut() = Some(target.clone());
 
         // Default, top left/bottom left
         let msg = ModalMsg::SetPos {
@@ -227,7 +273,7 @@ where
             width,
         ));
 
-        let (top, left) = self.calc_anchor_position(&target);
+        let (top, left) = self.calc_anchor_position(target);
         let msg = ModalMsg::SetPos {
             top,
             left,
@@ -236,29 +282,151 @@ where
         };
 
         self.root.borrow().as_ref().unwrap().send_message(msg);
+        Ok(())
+    }
 
-        if self.own_focus {
-            let mut this = Some(self.clone());
-            *self.blurhandler.borrow_mut() = Some(
-                (move |_| this.take().and_then(|x| x.hide().ok()).unwrap_or(())).into_closure_mut(),
-            );
+    /// Recompute this modal's position relative to its current target, e.g.
+    /// after the viewport has been resized or scrolled, clamped to the
+    /// viewport bounds.  If the target has been scrolled out of view, the
+    /// modal is hidden until the target is visible again.
+    fn reposition(&self) {
+        let target = match self.target.borrow().clone() {
+            Some(target) => target,
+            None => return,
+        };
 
-            self.custom_element.add_event_listener_with_callback(
-                "blur",
-                self.blurhandler
-                    .borrow()
-                    .as_ref()
-                    .unwrap()
-                    .as_ref()
-                    .unchecked_ref(),
-            )?;
+        let window = web_sys::window().unwrap();
+        let inner_width = window.inner_width().unwrap().as_f64().unwrap() as i32;
+        let inner_height = window.inner_height().unwrap().as_f64().unwrap() as i32;
+        let rect = target.get_bounding_client_rect();
+        let visible = rect.bottom() > 0.0
+            && rect.right() > 0.0
+            && (rect.top() as i32) < inner_height
+            && (rect.left() as i32) < inner_width;
 
-            self.custom_element.focus()
-        } else {
-            Ok(())
+        let self_rect = self.custom_element.get_bounding_client_rect();
+        let (top, left) = self.calc_anchor_position(&target);
+        let top = top.min(inner_height - self_rect.height() as i32).max(0);
+        let left = left.min(inner_width - self_rect.width() as i32).max(0);
+        let msg = ModalMsg::SetPos {
+            top,
+            left,
+            visible,
+            rev_vert: self.anchor.get().is_rev_vert(),
+        };
+
+        if let Some(root) = self.root.borrow().as_ref() {
+            root.send_message(msg);
         }
     }
 
+    /// Close on `Escape`, returning focus to the target element if it is
+    /// focusable, and cycle `Tab`/`Shift+Tab` within this modal's focusable
+    /// elements while the focus trap is active.  Keys already handled by a
+    /// child, e.g. an editor which indents on `Tab`, are ignored.
+    ///
+    /// `Escape` closes via `blur`, the same path as clicking outside the
+    /// modal, so hosts which listen for `blur` (e.g. the datagrid's column
+    /// style menu) can clean up.
+    fn on_keydown(&self, event: &KeyboardEvent) {
+        if event.default_prevented() {
+            return;
+        }
+
+        match event.key().as_str() {
+            "Escape" => {
+                event.prevent_default();
+                let target = self.target.borrow().clone();
+                self.custom_element.blur().unwrap();
+                if self.is_open() {
+                    let event = FocusEvent::new("blur").unwrap();
+                    self.custom_element.dispatch_event(&event).unwrap();
+                }
+
+                if let Some(target) = target.filter(|x| x.tab_index() >= 0) {
+                    target.focus().unwrap();
+                }
+            }
+            "Tab" if self.is_trapping_focus() => {
+                let focusable = self.focusable_elements();
+                let active = self
+                    .custom_element
+                    .shadow_root()
+                    .and_then(|x| x.active_element());
+
+                let (first, last) = match (focusable.first(), focusable.last()) {
+                    (Some(first), Some(last)) => (first, last),
+                    _ => {
+                        event.prevent_default();
+                        return;
+                    }
+                };
+
+                let (edge, wrap_to) = if event.shift_key() {
+                    (first, last)
+                } else {
+                    (last, first)
+                };
+
+                if active.is_none() || active.as_ref() == Some(edge) {
+                    event.prevent_default();
+                    wrap_to.unchecked_ref::<HtmlElement>().focus().unwrap();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// This modal's focusable elements, in DOM order.
+    fn focusable_elements(&self) -> Vec<Element> {
+        let root = match self.custom_element.shadow_root() {
+            Some(root) => root.unchecked_into::<Element>(),
+            None => return vec![],
+        };
+
+        let nodes = root.query_selector_all(FOCUSABLE_SELECTOR).unwrap();
+        (0..nodes.length())
+            .filter_map(|idx| nodes.get(idx))
+            .map(|x| x.unchecked_into::<Element>())
+            .collect()
+    }
+
+    /// Whether `Tab` should cycle within this modal.
+    fn is_trapping_focus(&self) -> bool {
+        self.own_focus && self.trap_focus.get()
+    }
+
+    /// Enable (the default) or disable cycling `Tab` focus within this modal,
+    /// for modals which intentionally allow background interaction.
+    /// `Escape` closes the modal either way.
+    pub fn set_trap_focus(&self, trap_focus: bool) {
+        self.trap_focus.set(trap_focus);
+    }
+
+    /// Track `window` resize and (any) scroll events while open, so this modal
+    /// stays attached to its target.
+    fn add_viewport_listeners(&self) -> Result<(), JsValue> {
+        let this = self.clone();
+        let handler = (move |_: Event| this.reposition()).into_closure();
+        let window = web_sys::window().unwrap();
+        let func = handler.as_ref().unchecked_ref();
+        window.add_event_listener_with_callback("resize", func)?;
+        window.add_event_listener_with_callback_and_bool("scroll", func, true)?;
+        *self.viewport_handler.borrow_mut() = Some(handler);
+        Ok(())
+    }
+
+    fn remove_viewport_listeners(&self) -> Result<(), JsValue> {
+        if let Some(handler) = self.viewport_handler.borrow_mut().take() {
+            let window = web_sys::window().unwrap();
+            let func = handler.as_ref().unchecked_ref();
+            window.remove_event_listener_with_callback("resize", func)?;
+            window.remove_event_listener_with_callback_and_bool("scroll", func, true)?;
+        }
+
+        Ok(())
+    }
+
     pub fn send_message(&self, msg: T::Message) {
         self.root
             .borrow()
@@ -284,19 +452,7 @@ where
     pub fn open(&self, target: web_sys::HtmlElement, resize_pubsub: Option<&PubSub<()>>) {
         if let Some(resize) = resize_pubsub {
             let this = self.clone();
-            let target = target.clone();
-            let anchor = self.anchor.clone();
-            *self.resize_sub.borrow_mut() = Some(resize.add_listener(move |()| {
-                let (top, left) = this.calc_anchor_position(&target);
-                let msg = ModalMsg::SetPos {
-                    top,
-                    left,
-                    visible: true,
-                    rev_vert: anchor.get().is_rev_vert(),
-                };
-
-                this.root.borrow().as_ref().unwrap().send_message(msg);
-            }));
+            *self.resize_sub.borrow_mut() = Some(resize.add_listener(move |()| this.reposition()));
         };
 
         if !self.is_open() {
@@ -321,6 +477,7 @@ where
     /// Remove from document.
     pub fn hide(&self) -> Result<(), JsValue> {
         if self.is_open() {
+            self.remove_viewport_listeners()?;
             if self.own_focus {
                 self.custom_element.remove_event_listener_with_callback(
                     "blur",
@@ -333,6 +490,12 @@ where
                 )?;
 
                 *self.blurhandler.borrow_mut() = None;
+                if let Some(keydown) = self.keydown_handler.borrow_mut().take() {
+                    self.custom_element.remove_event_listener_with_callback(
+                        "keydown",
+                        keydown.as_ref().unchecked_ref(),
+                    )?;
+                }
             }
 
             web_sys::window()
@@ -31,6 +31,25 @@ fn on_change(elem: &web_sys::HtmlElement, config: &NumberColumnStyleConfig) {
     elem.dispatch_event(&event.unwrap()).unwrap();
 }
 
+fn on_ui_state_change(elem: &web_sys::HtmlElement, ui_state: &NumberColumnStyleUiState) {
+    let mut event_init = web_sys::CustomEventInit::new();
+    event_init.detail(&JsValue::from_serde(ui_state).unwrap());
+    let event =
+        CustomEvent::new_with_event_init_dict("perspective-column-style-ui-change", &event_init);
+
+    elem.dispatch_event(&event.unwrap()).unwrap();
+}
+
+/// Parse an optional `NumberColumnStyleUiState` argument, which is `undefined`
+/// for a column the plugin has no UI state for yet.
+fn ui_state_from_jsvalue(ui_state: &JsValue) -> Result<NumberColumnStyleUiState, JsValue> {
+    if ui_state.is_undefined() || ui_state.is_null() {
+        Ok(NumberColumnStyleUiState::default())
+    } else {
+        ui_state.into_serde().into_jserror()
+    }
+}
+
 impl CustomElementMetadata for PerspectiveNumberColumnStyleElement {
     const CUSTOM_ELEMENT_NAME: &'static str = "perspective-number-column-style";
 }
@@ -49,12 +68,16 @@ impl PerspectiveNumberColumnStyleElement {
     /// * `config` - a `ColumnStyle` config in JSON form.
     /// * `default_config` - the default `ColumnStyle` config for this column
     ///   type, in JSON form.
+    /// * `ui_state` - this column's `NumberColumnStyleUiState` from a previous
+    ///   `"perspective-column-style-ui-change"` event, if any.
     pub fn reset(
         &mut self,
         config: NumberColumnStyleConfig,
         default_config: NumberColumnStyleDefaultConfig,
+        ui_state: JsValue,
     ) -> Result<(), JsValue> {
-        let msg = NumberColumnStyleMsg::Reset(config.into(), default_config.into());
+        let ui_state = ui_state_from_jsvalue(&ui_state)?;
+        let msg = NumberColumnStyleMsg::Reset(config.into(), default_config.into(), ui_state);
         self.modal.as_ref().into_jserror()?.send_message(msg);
         Ok(())
     }
@@ -64,24 +87,34 @@ impl PerspectiveNumberColumnStyleElement {
     ///
     /// # Arguments
     /// `target` - the relative target to pin this `ModalElement` to.
+    /// `ui_state` - this column's `NumberColumnStyleUiState`, if any.
     pub fn open(
         &mut self,
         target: web_sys::HtmlElement,
         config: NumberColumnStyleConfig,
         default_config: NumberColumnStyleDefaultConfig,
+        ui_state: JsValue,
     ) -> Result<(), JsValue> {
         if self.modal.is_some() {
-            self.reset(config, default_config)?;
+            self.reset(config, default_config, ui_state)?;
         } else {
             let on_change = {
                 clone!(self.elem);
                 Callback::from(move |x: NumberColumnStyleConfig| on_change(&elem, &x))
             };
 
+            let on_ui_state_change = {
+                clone!(self.elem);
+                Callback::from(move |x: NumberColumnStyleUiState| on_ui_state_change(&elem, &x))
+            };
+
+            let ui_state = ui_state_from_jsvalue(&ui_state)?;
             let props = props!(NumberColumnStyleProps {
                 config,
                 on_change,
+                on_ui_state_change,
                 default_config,
+                ui_state,
             });
 
             self.modal = Some(ModalElement::new(self.elem.clone(), props, true));
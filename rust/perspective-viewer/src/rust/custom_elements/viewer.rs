@@ -8,19 +8,29 @@
 
 use crate::components::{Msg, PerspectiveViewer, PerspectiveViewerProps};
 use crate::config::*;
+use crate::config_history::*;
+use crate::custom_elements::config_batch::ViewerConfigBatch;
+use crate::custom_elements::keybindings::*;
 use crate::custom_events::*;
 use crate::dragdrop::*;
 use crate::js::*;
 use crate::model::*;
 use crate::renderer::*;
-use crate::session::Session;
+use crate::session::{
+    ExpressionSnippet, LoadPhase, LoadProgress, SearchMatch, SearchOptions, SearchQuery,
+    SearchResults, Session, SEARCH_MAX_MATCHES,
+};
 use crate::theme::*;
 use crate::utils::*;
 use crate::*;
 
+use futures::channel::oneshot::*;
+use futures::future::{select, Either};
 use js_intern::*;
 use js_sys::*;
-use std::cell::RefCell;
+use serde::Deserialize;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
 use std::rc::Rc;
 use std::str::FromStr;
 use wasm_bindgen::prelude::*;
@@ -38,15 +48,23 @@ struct ResizeObserverHandle {
 impl ResizeObserverHandle {
     fn new(
         elem: &HtmlElement,
+        session: &Session,
         renderer: &Renderer,
         root: &AppHandle<PerspectiveViewer>,
+        threshold: &Rc<Cell<i32>>,
+        auto_suspend: &AutoSuspend,
     ) -> ResizeObserverHandle {
         let on_resize = root.callback(|()| Msg::Resize);
         let mut state = ResizeObserverState {
             elem: elem.clone(),
+            session: session.clone(),
             renderer: renderer.clone(),
+            auto_suspend: auto_suspend.clone(),
             width: elem.offset_width(),
             height: elem.offset_height(),
+            threshold: threshold.clone(),
+            is_pending: Rc::new(Cell::new(false)),
+            is_stale: Rc::new(Cell::new(false)),
             on_resize,
         };
 
@@ -70,38 +88,143 @@ impl Drop for ResizeObserverHandle {
 
 struct ResizeObserverState {
     elem: HtmlElement,
+    session: Session,
     renderer: Renderer,
+    auto_suspend: AutoSuspend,
     width: i32,
     height: i32,
+    threshold: Rc<Cell<i32>>,
+    is_pending: Rc<Cell<bool>>,
+    is_stale: Rc<Cell<bool>>,
     on_resize: Callback<()>,
 }
 
 impl ResizeObserverState {
     fn on_resize(&mut self, entries: &js_sys::Array) {
-        let is_visible = self
-            .elem
-            .offset_parent()
-            .map(|x| !x.is_null())
-            .unwrap_or(false);
+        let is_visible = is_visible(&self.elem);
+        if is_visible != self.auto_suspend.was_visible.replace(is_visible) {
+            self.auto_suspend
+                .on_visibility(is_visible, &self.session, &self.renderer);
+        }
 
+        let mut resized = false;
         for y in entries.iter() {
             let entry: ResizeObserverEntry = y.unchecked_into();
-            let content = entry.content_rect();
-            let content_width = content.width().floor() as i32;
-            let content_height = content.height().floor() as i32;
-            let resized = self.width != content_width || self.height != content_height;
-            if resized && is_visible {
-                clone!(self.on_resize, self.renderer);
-                ApiFuture::spawn(async move {
+            resized = self.update_dimensions(&entry) || resized;
+        }
+
+        if resized && is_visible {
+            self.request_resize();
+        }
+    }
+
+    /// Spawn a `resize()` unless one is already in flight, in which case mark
+    /// it stale so it runs once more when it settles.  Plugins measure the
+    /// element when they resize, so the final pass always sees the latest
+    /// dimensions, however many entries arrived in the meantime.
+    fn request_resize(&self) {
+        if self.is_pending.replace(true) {
+            self.is_stale.set(true);
+            return;
+        }
+
+        clone!(
+            self.on_resize,
+            self.renderer,
+            self.is_pending,
+            self.is_stale
+        );
+        ApiFuture::spawn(async move {
+            let result = async {
+                loop {
+                    is_stale.set(false);
                     renderer.resize().await?;
-                    on_resize.emit(());
-                    Ok(())
-                });
+                    if !is_stale.get() {
+                        break Ok::<_, JsValue>(());
+                    }
+                }
             }
+            .await;
+
+            is_pending.set(false);
+            result?;
+            on_resize.emit(());
+            Ok(())
+        });
+    }
+
+    /// Record the dimensions of `entry` and return whether they differ from
+    /// the last recorded dimensions by at least the pixel threshold.  Changes
+    /// below the threshold are not recorded, so a slow drift across several
+    /// entries still triggers a resize once it accumulates.
+    fn update_dimensions(&mut self, entry: &ResizeObserverEntry) -> bool {
+        let content = entry.content_rect();
+        let content_width = content.width().floor() as i32;
+        let content_height = content.height().floor() as i32;
+        let threshold = self.threshold.get().max(1);
+        let resized = (self.width - content_width).abs() >= threshold
+            || (self.height - content_height).abs() >= threshold;
 
+        if resized {
             self.width = content_width;
             self.height = content_height;
         }
+
+        resized
+    }
+}
+
+/// Is `elem` attached to the document and rendered (e.g. not `display: none`)?
+fn is_visible(elem: &HtmlElement) -> bool {
+    elem.offset_parent().map(|x| !x.is_null()).unwrap_or(false)
+}
+
+/// The opt-in state of `setAutoSuspend()`, which `ResizeObserverState` drives
+/// as this element is hidden and shown.
+#[derive(Clone)]
+struct AutoSuspend {
+    delay: Rc<Cell<Option<i32>>>,
+    generation: Rc<Cell<u32>>,
+    was_visible: Rc<Cell<bool>>,
+    is_suspended: Rc<Cell<bool>>,
+}
+
+impl Default for AutoSuspend {
+    fn default() -> Self {
+        Self {
+            delay: Default::default(),
+            generation: Default::default(),
+            was_visible: Rc::new(Cell::new(true)),
+            is_suspended: Default::default(),
+        }
+    }
+}
+
+impl AutoSuspend {
+    /// Cancel any pending suspend, then either resume the `View` if this
+    /// `AutoSuspend` suspended it, or schedule a suspend after `delay`.
+    fn on_visibility(&self, is_visible: bool, session: &Session, renderer: &Renderer) {
+        let current = self.generation.get().wrapping_add(1);
+        self.generation.set(current);
+        if is_visible {
+            if self.is_suspended.replace(false) {
+                clone!(session, renderer);
+                ApiFuture::spawn(async move {
+                    resume_view(&session, &renderer).await?;
+                    Ok(())
+                });
+            }
+        } else if let Some(delay) = self.delay.get() {
+            clone!(session, renderer, self.generation, self.is_suspended);
+            ApiFuture::spawn(async move {
+                set_timeout(delay).await?;
+                if generation.get() == current && suspend_view(&session, &renderer).await? {
+                    is_suspended.set(true);
+                }
+
+                Ok(())
+            });
+        }
     }
 }
 
@@ -131,9 +254,20 @@ pub struct PerspectiveViewerElement {
     elem: HtmlElement,
     root: Rc<RefCell<Option<AppHandle<PerspectiveViewer>>>>,
     resize_handle: Rc<RefCell<Option<ResizeObserverHandle>>>,
+    resize_threshold: Rc<Cell<i32>>,
+    auto_suspend: AutoSuspend,
+    max_export_rows: Rc<Cell<Option<u32>>>,
     session: Session,
     renderer: Renderer,
     theme: Theme,
+    restore_batch: Rc<RefCell<Option<RestoreBatch>>>,
+    presets: PresetStorage,
+    history: ConfigHistory,
+    search: Rc<RefCell<SearchResults>>,
+    keybindings: KeyBindings,
+    _keydown_handle: Option<KeyBindingsHandle>,
+    _reduced_motion_handle: Option<MediaQueryHandle>,
+    _high_contrast_handle: Option<MediaQueryHandle>,
     _events: CustomEvents,
     _subscriptions: Rc<Subscription>,
 }
@@ -181,18 +315,46 @@ impl PerspectiveViewerElement {
             }
         });
 
-        let _events = CustomEvents::new(&elem, &session, &renderer, &theme);
-        let resize_handle = ResizeObserverHandle::new(&elem, &renderer, &root);
-        PerspectiveViewerElement {
+        let history = ConfigHistory::default();
+        let _events = CustomEvents::new(&elem, &session, &renderer, &theme, &history);
+        let resize_threshold = Rc::new(Cell::new(1));
+        let auto_suspend = AutoSuspend::default();
+        let resize_handle = ResizeObserverHandle::new(
+            &elem,
+            &session,
+            &renderer,
+            &root,
+            &resize_threshold,
+            &auto_suspend,
+        );
+        let mut viewer = PerspectiveViewerElement {
             elem,
             root: Rc::new(RefCell::new(Some(root))),
             session,
             renderer,
             theme,
             resize_handle: Rc::new(RefCell::new(Some(resize_handle))),
+            resize_threshold,
+            auto_suspend,
+            max_export_rows: Default::default(),
+            restore_batch: Default::default(),
+            presets: Default::default(),
+            history,
+            search: Default::default(),
+            keybindings: KeyBindings::default(),
+            _keydown_handle: None,
+            _reduced_motion_handle: None,
+            _high_contrast_handle: None,
             _events,
             _subscriptions: Rc::new(update_sub),
-        }
+        };
+
+        let keydown_handle =
+            KeyBindingsHandle::new(&viewer.elem, &viewer.keybindings, viewer.on_key_action());
+        viewer._keydown_handle = Some(keydown_handle.unwrap());
+        viewer._reduced_motion_handle = viewer.on_reduced_motion_change().ok();
+        viewer._high_contrast_handle = viewer.on_high_contrast_change().ok();
+        viewer
     }
 
     #[wasm_bindgen(js_name = "connectedCallback")]
@@ -201,28 +363,95 @@ impl PerspectiveViewerElement {
     /// Loads a promise to a `JsPerspectiveTable` in this viewer.  Historially,
     /// `<perspective-viewer>` has accepted either a `Promise` or `Table` as an
     /// argument, so we preserve that behavior here with some loss of type
-    /// precision.
-    pub fn load(&self, table: JsValue) -> ApiFuture<()> {
+    /// precision.  An Arrow `ArrayBuffer` or `Uint8Array` (or a `Promise` to
+    /// one) is also accepted, in which case a `Table` is created for it on
+    /// the shared Perspective worker.
+    ///
+    /// # Arguments
+    /// - `table` The `Table`, Arrow data, or a `Promise` to either.
+    /// - `options` An optional `LoadOptions` object.
+    pub fn load(&self, table: JsValue, options: JsValue) -> ApiFuture<()> {
         let promise = table
             .clone()
             .dyn_into::<js_sys::Promise>()
             .unwrap_or_else(|_| js_sys::Promise::resolve(&table));
 
-        let mut config = ViewConfigUpdate::default();
-        self.session
-            .set_update_column_defaults(&mut config, &self.renderer.metadata());
+        let options = if options.is_undefined() || options.is_null() {
+            LoadOptions::default()
+        } else {
+            match options.into_serde::<LoadOptions>().into_jserror() {
+                Ok(options) => options,
+                Err(err) => {
+                    let result: Result<(), JsValue> = Err(err);
+                    let progress = LoadProgress::complete(&result);
+                    self.session.load_progress.emit_all(progress);
+                    return ApiFuture::new(async move { result });
+                }
+            }
+        };
+
+        if !options.skip_defaults {
+            let mut config = ViewConfigUpdate::default();
+            self.session
+                .set_update_column_defaults(&mut config, &self.renderer.metadata());
+
+            self.session.update_view_config(config);
+        }
 
-        self.session.update_view_config(config);
         clone!(self.renderer, self.session);
         ApiFuture::new(async move {
-            renderer
+            let result = renderer
                 .draw(async {
-                    let table = JsFuture::from(promise)
-                        .await?
-                        .unchecked_into::<JsPerspectiveTable>();
+                    let table = JsFuture::from(promise).await?;
+                    let table = if is_arrow_buffer(&table) {
+                        arrow_to_table(&table).await?
+                    } else {
+                        table.unchecked_into::<JsPerspectiveTable>()
+                    };
 
+                    let progress = &session.load_progress;
+                    progress.emit_all(LoadProgress::new(LoadPhase::SetTable));
                     session.reset_stats();
                     session.set_table(table).await?;
+                    progress.emit_all(LoadProgress::new(LoadPhase::Validate));
+                    let validated = session.validate().await?;
+                    progress.emit_all(LoadProgress::new(LoadPhase::CreateView));
+                    validated.create_view().await
+                })
+                .await;
+
+            session
+                .load_progress
+                .emit_all(LoadProgress::complete(&result));
+            result
+        })
+    }
+
+    /// Replace the `JsPerspectiveTable` in this viewer while keeping the
+    /// current `ViewConfig`, recreating the `View` in place rather than
+    /// resetting as `load()` does.  Accepts the same arguments as `load()`,
+    /// and rejects with details (leaving the current `Table` loaded) if the
+    /// config references columns the new `Table` lacks or which have
+    /// changed type.
+    #[wasm_bindgen(js_name = "replaceTable")]
+    pub fn replace_table(&self, table: JsValue) -> ApiFuture<()> {
+        let promise = table
+            .clone()
+            .dyn_into::<js_sys::Promise>()
+            .unwrap_or_else(|_| js_sys::Promise::resolve(&table));
+
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            renderer
+                .draw_in_place(async {
+                    let table = JsFuture::from(promise).await?;
+                    let table = if is_arrow_buffer(&table) {
+                        arrow_to_table(&table).await?
+                    } else {
+                        table.unchecked_into::<JsPerspectiveTable>()
+                    };
+
+                    session.replace_table(table).await?;
                     session.validate().await?.create_view().await
                 })
                 .await
@@ -247,6 +476,21 @@ impl PerspectiveViewerElement {
         }))
     }
 
+    /// Delete the `View` and clear the plugin, but keep the `Table` and
+    /// config, e.g. to free memory while this viewer is hidden.  `resume()`
+    /// recreates the `View` and redraws.
+    pub fn suspend(&self) -> ApiFuture<bool> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move { suspend_view(&session, &renderer).await })
+    }
+
+    /// Recreate the `View` deleted by `suspend()` from the unchanged config,
+    /// and redraw.  Does nothing if this viewer is not suspended.
+    pub fn resume(&self) -> ApiFuture<bool> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move { resume_view(&session, &renderer).await })
+    }
+
     /// Get the underlying `View` for thie viewer.
     #[wasm_bindgen(js_name = "getView")]
     pub fn get_view(&self) -> ApiFuture<JsPerspectiveView> {
@@ -259,6 +503,195 @@ impl PerspectiveViewerElement {
         })
     }
 
+    /// Get the current `View` and render statistics, as an object with
+    /// `num_rows`, `virtual_rows`, `num_columns`, `is_pivot` and
+    /// `last_render_time` fields.  Rejects if no `Table` has been loaded.
+    #[wasm_bindgen(js_name = "getStats")]
+    pub fn get_stats(&self) -> ApiFuture<JsValue> {
+        let task = self.get_viewer_stats();
+        ApiFuture::new(async move {
+            let stats = task.await?;
+            JsValue::from_serde(&stats).into_jserror()
+        })
+    }
+
+    /// Get the active plugin's preferred dimensions for the current `View`, as
+    /// an object with `width` and `height` fields, without drawing.  Rejects
+    /// if no plugin or `View` is ready.
+    pub fn measure(&self) -> ApiFuture<JsValue> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            let view = session
+                .get_view()
+                .ok_or_else(|| js_intern!("No table set"))?;
+
+            let dimensions = renderer.measure(&view).await?;
+            JsValue::from_serde(&dimensions).into_jserror()
+        })
+    }
+
+    /// Render the active plugin's current state to an image `Blob`, for
+    /// plugins which implement `Plugin::to_image` (e.g. charts).  Rejects for
+    /// plugins which cannot produce an image, such as the datagrid.
+    ///
+    /// # Arguments
+    /// - `format` `"png"` (the default) or `"svg"`.
+    /// - `width` The image width in pixels, defaulting to the plugin's width.
+    /// - `height` The image height in pixels, defaulting to the plugin's
+    ///   height.
+    #[wasm_bindgen(js_name = "toImage")]
+    pub fn to_image(
+        &self,
+        format: Option<String>,
+        width: Option<f64>,
+        height: Option<f64>,
+    ) -> ApiFuture<JsValue> {
+        clone!(self.renderer);
+        ApiFuture::new(async move {
+            let format = match format {
+                Some(format) => format.parse::<ImageFormat>()?,
+                None => ImageFormat::Png,
+            };
+
+            let options = ImageOptions {
+                format,
+                width,
+                height,
+            };
+
+            let plugin = renderer.get_active_plugin()?;
+            Ok(plugin.to_image(&options).await?.into())
+        })
+    }
+
+    /// Scroll a row (and optionally a column) of the active plugin into view,
+    /// e.g. to "jump to" a result from a search in the host application.
+    /// Rejects for plugins which do not implement `Plugin::scroll_to`.
+    ///
+    /// # Arguments
+    /// - `row` The row index in the current `View`, clamped to its first or
+    ///   last row if out of range.
+    /// - `column` The name of a column in the current `View`.
+    #[wasm_bindgen(js_name = "scrollTo")]
+    pub fn scroll_to(&self, row: f64, column: Option<String>) -> ApiFuture<()> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            let plugin = renderer.get_active_plugin()?;
+            let num_rows = session
+                .get_table_stats()
+                .and_then(|x| x.virtual_rows.or(x.num_rows))
+                .ok_or("No table set")?;
+
+            if let Some(column) = &column {
+                let config = session.get_view_config();
+                if !config.columns.iter().flatten().any(|x| x == column) {
+                    return Err(format!("Unknown column \"{}\"", column).into());
+                }
+            }
+
+            if num_rows == 0 {
+                return Ok(());
+            }
+
+            let row = row.max(0.0).min((num_rows - 1) as f64) as u32;
+            plugin.scroll_to(&ScrollTarget { row, column }).await
+        })
+    }
+
+    /// Find the cells of the current `View` whose values contain `query`, and
+    /// reset the position of `searchNext()` and `searchPrev()` to before the
+    /// first match.  Values are searched as unformatted text.  The search is
+    /// a snapshot, and is not updated when the `View` changes.
+    ///
+    /// # Arguments
+    /// - `query` The text to find, or `""` to clear the search.
+    /// - `options` An optional object with `regex` (treat `query` as a
+    ///   JavaScript `RegExp`) and `case_sensitive` flags, both `false` by
+    ///   default.
+    pub fn search(&self, query: String, options: JsValue) -> ApiFuture<JsValue> {
+        clone!(self.session, self.renderer, self.search);
+        ApiFuture::new(async move {
+            let options = if options.is_undefined() || options.is_null() {
+                SearchOptions::default()
+            } else {
+                options.into_serde().into_jserror()?
+            };
+
+            let matches = if query.is_empty() {
+                vec![]
+            } else {
+                let query = SearchQuery::new(&query, &options)?;
+                let (matches, is_complete) = session.search(&query, SEARCH_MAX_MATCHES).await?;
+                if !is_complete {
+                    let msg = format!(
+                        "Search stopped after {} matches;  refine the query to see the rest",
+                        SEARCH_MAX_MATCHES
+                    );
+
+                    web_sys::console::warn_1(&msg.into());
+                }
+
+                matches
+            };
+
+            renderer.get_active_plugin()?.set_highlight(None)?;
+            *search.borrow_mut() = SearchResults::new(matches);
+            JsValue::from_serde(search.borrow().matches()).into_jserror()
+        })
+    }
+
+    /// Scroll to and highlight the next match of the last `search()`,
+    /// wrapping to the first.  Resolves to the match, or `null` if there are
+    /// none.
+    #[wasm_bindgen(js_name = "searchNext")]
+    pub fn search_next(&self) -> ApiFuture<JsValue> {
+        self.navigate_search(SearchResults::next_match)
+    }
+
+    /// Scroll to and highlight the previous match of the last `search()`,
+    /// wrapping to the last.  Resolves to the match, or `null` if there are
+    /// none.
+    #[wasm_bindgen(js_name = "searchPrev")]
+    pub fn search_prev(&self) -> ApiFuture<JsValue> {
+        self.navigate_search(SearchResults::prev_match)
+    }
+
+    /// Select rows (and optionally columns) in the active plugin, e.g. to
+    /// highlight rows in response to an event in the host application.
+    ///
+    /// # Arguments
+    /// - `selection` An object with a `rows` array of row indices into the
+    ///   current `View`, and an optional `columns` array of column names.
+    #[wasm_bindgen(js_name = "setSelection")]
+    pub fn set_selection(&self, selection: JsValue) -> ApiFuture<()> {
+        clone!(self.renderer);
+        ApiFuture::new(async move {
+            let selection: PluginSelection = selection.into_serde().into_jserror()?;
+            renderer.get_active_plugin()?.set_selection(&selection)
+        })
+    }
+
+    /// Get the active plugin's current selection, in the format accepted by
+    /// `setSelection()`, or `null` if nothing is selected.  Rejects if the
+    /// active plugin does not support selection.
+    #[wasm_bindgen(js_name = "getSelection")]
+    pub fn get_selection(&self) -> ApiFuture<JsValue> {
+        clone!(self.renderer);
+        ApiFuture::new(async move {
+            let plugin = renderer.get_active_plugin()?;
+            if !plugin.supports_selection() {
+                return Err(
+                    format!("Plugin `{}` does not support selection", plugin.name()).into(),
+                );
+            }
+
+            match plugin.get_selection()? {
+                Some(selection) => JsValue::from_serde(&selection).into_jserror(),
+                None => Ok(JsValue::NULL),
+            }
+        })
+    }
+
     /// Get the underlying `Table` for this viewer.
     ///
     /// # Arguments
@@ -279,172 +712,897 @@ impl PerspectiveViewerElement {
         })
     }
 
-    pub fn flush(&self) -> ApiFuture<()> {
+    /// Set the columns the active plugin should freeze to the left during
+    /// horizontal scroll, and redraw.  Plugins which do not support pinning
+    /// ignore this (with a warning), but the columns are still persisted by
+    /// `save()` for plugins which do.
+    ///
+    /// # Arguments
+    /// - `columns` An array of column names, or `None` to unpin all columns.
+    #[wasm_bindgen(js_name = "setPinnedColumns")]
+    pub fn set_pinned_columns(&self, columns: JsValue) -> ApiFuture<()> {
         clone!(self.renderer, self.session);
         ApiFuture::new(async move {
-            if session.js_get_table().is_none() {
-                session.table_loaded.listen_once().await.into_jserror()?;
-                let _ = session
-                    .js_get_table()
-                    .ok_or_else(|| js_intern!("No table set"))?;
+            let columns: Vec<String> = if columns.is_null() || columns.is_undefined() {
+                vec![]
+            } else {
+                columns
+                    .into_serde()
+                    .map_err(|_| "Pinned columns must be an array of column names")?
             };
 
-            renderer.draw(async { Ok(&session) }).await
+            let plugin = renderer.get_active_plugin()?;
+            if !columns.is_empty() && !plugin.supports_pinned_columns() {
+                let msg = format!("Plugin `{}` does not support pinned columns", plugin.name());
+                web_sys::console::warn_1(&msg.into());
+            }
+
+            if renderer.set_pinned_columns(columns) {
+                renderer.draw(async { Ok(&session) }).await?;
+            }
+
+            Ok(())
         })
     }
 
-    /// Restores this element from a full/partial `JsPerspectiveViewConfig`.
+    /// Get the `columns` of this viewer's `ViewConfig`, in order.  Empty slots
+    /// (e.g. an unset axis of a chart plugin) are `null`.
+    #[wasm_bindgen(js_name = "getColumns")]
+    pub fn get_columns(&self) -> Result<JsValue, JsValue> {
+        JsValue::from_serde(&self.session.get_view_config().columns).into_jserror()
+    }
+
+    /// Get the `Table` type of every column, including `expressions` by alias,
+    /// as a `{column: type}` object.  Rejects if no `Table` is loaded.
+    #[wasm_bindgen(js_name = "getSchema")]
+    pub fn get_schema(&self) -> ApiFuture<JsValue> {
+        let session = self.session.clone();
+        ApiFuture::new(async move {
+            let schema = session.metadata().get_schema().ok_or("No table set")?;
+            JsValue::from_serde(&schema).into_jserror()
+        })
+    }
+
+    /// Get the aggregates which are valid for a column, given its type, in the
+    /// form accepted by `aggregates` in `restore()`.
     ///
     /// # Arguments
-    /// - `update` The config to restore to, as returned by `.save()` in either
-    ///   "json", "string" or "arraybuffer" format.
-    pub fn restore(&self, update: JsValue) -> ApiFuture<()> {
-        clone!(self.session, self.renderer, self.root, self.theme);
+    /// - `column` The column name (or expression alias).
+    #[wasm_bindgen(js_name = "getValidAggregates")]
+    pub fn get_valid_aggregates(&self, column: String) -> Result<JsValue, JsValue> {
+        let aggregates = self
+            .session
+            .metadata()
+            .get_column_aggregates(&column)
+            .map(|x| x.collect::<Vec<_>>())
+            .ok_or_else(|| format!("Unknown column \"{}\"", column))?;
+
+        JsValue::from_serde(&aggregates).into_jserror()
+    }
+
+    /// Replace only the `columns` of this viewer's `ViewConfig` and redraw,
+    /// leaving the rest of the config as-is.  Rejects without changing the
+    /// config if any column is not in the `Table` or `expressions`.
+    ///
+    /// # Arguments
+    /// - `columns` An array of column names, in order, which may contain `null`
+    ///   for empty slots.
+    #[wasm_bindgen(js_name = "setColumns")]
+    pub fn set_columns(&self, columns: JsValue) -> ApiFuture<()> {
+        clone!(self.renderer, self.session);
         ApiFuture::new(async move {
-            let ViewerConfigUpdate {
-                plugin,
-                plugin_config,
-                settings,
-                theme: theme_name,
-                mut view_config,
-            } = ViewerConfigUpdate::decode(&update)?;
-
-            let needs_restyle = match theme_name {
-                OptionalUpdate::SetDefault => {
-                    let current_name = theme.get_name().await;
-                    if None != current_name {
-                        theme.set_name(None).await?;
-                        true
-                    } else {
-                        false
-                    }
-                }
-                OptionalUpdate::Update(x) => {
-                    let current_name = theme.get_name().await;
-                    if current_name.is_some() && current_name.as_ref().unwrap() != &x {
-                        theme.set_name(Some(&x)).await?;
-                        true
-                    } else {
-                        false
-                    }
-                }
-                _ => false,
-            };
+            let columns: Vec<Option<String>> = columns
+                .into_serde()
+                .map_err(|_| "Columns must be an array of column names")?;
 
-            let plugin_changed = renderer.update_plugin(&plugin)?;
-            if plugin_changed {
-                session.set_update_column_defaults(&mut view_config, &renderer.metadata());
+            if session.get_table().is_none() {
+                return Err("No table set".into());
             }
 
-            session.update_view_config(view_config);
-            let draw_task = renderer.draw(async {
-                let task = root
-                    .borrow()
-                    .as_ref()
-                    .ok_or("Already deleted")?
-                    .promise_message(move |x| Msg::ToggleSettingsComplete(settings, x));
-
-                let result = async {
-                    let plugin = renderer.get_active_plugin()?;
-                    if let Some(plugin_config) = &plugin_config {
-                        let js_config = JsValue::from_serde(plugin_config);
-                        plugin.restore(&js_config.into_jserror()?);
-                    }
+            let unknown = {
+                let metadata = session.metadata();
+                columns
+                    .iter()
+                    .flatten()
+                    .filter(|x| metadata.get_column_table_type(x).is_none())
+                    .map(|x| format!("`{}`", x))
+                    .collect::<Vec<_>>()
+            };
 
-                    session.validate().await?.create_view().await
-                }
-                .await;
+            if !unknown.is_empty() {
+                return Err(format!("Unknown columns {}", unknown.join(", ")).into());
+            }
 
-                task.await.into_jserror()?;
-                result
+            session.update_view_config(ViewConfigUpdate {
+                columns: Some(columns),
+                ..ViewConfigUpdate::default()
             });
 
-            draw_task.await?;
+            renderer
+                .draw(async { session.validate().await?.create_view().await })
+                .await
+        })
+    }
 
-            // TODO this should be part of the API for `draw()` above, such that
-            // the plugin need not render twice when a theme is provided.
-            if needs_restyle {
-                let view = session.get_view().into_jserror()?;
-                renderer.restyle_all(&view).await?;
+    /// Check a config against the `Table` without applying it, resolving to an
+    /// object which lists the invalid entries of each `ViewConfig` field, e.g.
+    /// `{valid: false, columns: ["Profit"], ...}`.  Nothing is reset or
+    /// redrawn, unlike a `restore()` of an invalid config.
+    ///
+    /// # Arguments
+    /// - `config` An optional config in any format `restore()` accepts, which
+    ///   is checked as if applied to the current config.  Defaults to the
+    ///   current config.
+    #[wasm_bindgen(js_name = "validateConfig")]
+    pub fn validate_config(&self, config: JsValue) -> ApiFuture<JsValue> {
+        let session = self.session.clone();
+        ApiFuture::new(async move {
+            let mut view_config = session.get_view_config().clone();
+            if !config.is_undefined() && !config.is_null() {
+                let update = ViewerConfigUpdate::decode(&config)?;
+                view_config.apply_update(update.view_config);
             }
 
-            Ok(())
+            let validation = session.check_view_config(&view_config).await?;
+            JsValue::from_serde(&validation).into_jserror()
         })
     }
 
-    /// Save this element to serialized state object, one which can be restored
-    /// via the `.restore()` method.
+    /// Validate an expression against the `Table` schema without adding it to
+    /// this viewer's `expressions`.  Resolves to an object of the form
+    /// `{valid, type, error}`.
     ///
     /// # Arguments
-    /// - `format` Supports "json" (default), "arraybuffer" or "string".
-    pub fn save(&self, format: Option<String>) -> ApiFuture<JsValue> {
-        let viewer_config_task = self.get_viewer_config();
+    /// - `expr` The expression string to validate.
+    #[wasm_bindgen(js_name = "validateExpression")]
+    pub fn validate_expression(&self, expr: String) -> ApiFuture<JsValue> {
+        let session = self.session.clone();
         ApiFuture::new(async move {
-            let format = format
-                .as_ref()
-                .map(|x| ViewerConfigEncoding::from_str(x))
-                .transpose()?;
-
-            let viewer_config = viewer_config_task.await?;
-            viewer_config.encode(&format)
+            let validation = session.validate_expr(&expr).await?;
+            JsValue::from_serde(&validation).into_jserror()
         })
     }
 
-    /// Download this viewer's `View` or `Table` data as a `.csv` file.
+    /// Get this viewer's `expressions` as an object mapping each expression's
+    /// alias to the expression without its `// alias` line, in order.
+    #[wasm_bindgen(js_name = "getExpressions")]
+    pub fn get_expressions(&self) -> Result<Object, JsValue> {
+        let result = Object::new();
+        for expr in self.session.get_view_config().expressions.iter() {
+            let (alias, expr) = split_expression_alias(expr);
+            Reflect::set(&result, &alias.into(), &expr.into())?;
+        }
+
+        Ok(result)
+    }
+
+    /// Replace this viewer's `expressions` and redraw.  If any expression is
+    /// invalid, or a removed expression is still used by the config, nothing
+    /// is applied and this rejects with the failing aliases.
     ///
     /// # Arguments
-    /// - `flat` Whether to use the current `ViewConfig` to generate this data,
-    ///   or use the default.
-    pub fn download(&self, flat: Option<bool>) -> ApiFuture<()> {
-        let session = self.session.clone();
+    /// - `expressions` An object mapping each alias to its expression, in the
+    ///   format returned by `getExpressions()`.
+    #[wasm_bindgen(js_name = "setExpressions")]
+    pub fn set_expressions(&self, expressions: Object) -> ApiFuture<()> {
+        clone!(self.session, self.renderer, self.root, self.theme);
         ApiFuture::new(async move {
-            let val = session
-                .csv_as_jsvalue(flat.unwrap_or_default())
-                .await?
-                .as_blob()?;
-            download("untitled.csv", &val)
+            let mut aliases = vec![];
+            let mut new_expressions = vec![];
+            for entry in Object::entries(&expressions).iter() {
+                let entry: Array = entry.unchecked_into();
+                let alias = entry.get(0).as_string().into_jserror()?;
+                let expr = entry
+                    .get(1)
+                    .as_string()
+                    .ok_or_else(|| format!("Expression \"{}\" is not a string", alias))?;
+
+                new_expressions.push(join_expression_alias(&alias, &expr));
+                aliases.push(alias);
+            }
+
+            let in_use = session
+                .get_view_config()
+                .expressions
+                .iter()
+                .map(|x| split_expression_alias(x).0.to_owned())
+                .filter(|x| !aliases.contains(x) && session.is_column_expression_in_use(x))
+                .collect::<Vec<_>>();
+
+            let invalid = session.get_invalid_expressions(&new_expressions).await?;
+            if !invalid.is_empty() || !in_use.is_empty() {
+                let names = invalid
+                    .iter()
+                    .chain(in_use.iter())
+                    .map(|x| format!("\"{}\"", x))
+                    .collect::<Vec<_>>();
+
+                let names = names.join(", ");
+                return Err(format!("Invalid expressions {}", names).into());
+            }
+
+            let update = ViewerConfigUpdate {
+                view_config: ViewConfigUpdate {
+                    expressions: Some(new_expressions),
+                    ..ViewConfigUpdate::default()
+                },
+                ..ViewerConfigUpdate::default()
+            };
+
+            let viewer = (session, renderer, root, theme);
+            restore_update(viewer, update, false, Default::default()).await
         })
     }
 
-    /// Copy this viewer's `View` or `Table` data as CSV to the system
-    /// clipboard.
+    /// Get completion candidates for the partial token at `cursor` in an
+    /// expression, from this viewer's column names and the ExprTK function
+    /// list.
     ///
     /// # Arguments
-    /// - `flat` Whether to use the current `ViewConfig` to generate this data,
-    ///   or use the default.
-    pub fn copy(&self, flat: Option<bool>) -> ApiFuture<()> {
-        let method = if flat.unwrap_or_default() {
-            ExportMethod::CsvAll
-        } else {
-            ExportMethod::Csv
+    /// - `expr` The expression text.
+    /// - `cursor` The cursor position within `expr`, in characters.
+    #[wasm_bindgen(js_name = "getExpressionCompletions")]
+    pub fn get_expression_completions(
+        &self,
+        expr: &str,
+        cursor: usize,
+    ) -> Result<Box<[JsValue]>, JsValue> {
+        let columns = {
+            let metadata = self.session.metadata();
+            metadata
+                .get_table_columns()
+                .into_iter()
+                .flatten()
+                .chain(metadata.get_expression_columns())
+                .cloned()
+                .collect::<Vec<_>>()
         };
 
-        let js_task = self.export_method_to_jsvalue(method);
-        let copy_task = copy_to_clipboard(js_task, MimeType::TextPlain);
-        ApiFuture::new(copy_task)
+        crate::exprtk::get_expression_completions(expr, cursor, &columns)
+            .iter()
+            .map(JsValue::from_serde)
+            .collect::<Result<Box<[_]>, serde_json::Error>>()
+            .into_jserror()
     }
 
-    /// Reset the viewer's `ViewerConfig` to the default.
+    /// Flush any pending modifications to this element, waiting for `load()`
+    /// first if it has not yet been called.
     ///
     /// # Arguments
-    /// - `all` Whether to clear `expressions` also.
-    pub fn reset(&self, reset_expressions: Option<bool>) -> ApiFuture<()> {
-        let root = self.root.clone();
-        let all = reset_expressions.unwrap_or_default();
+    /// - `timeout_ms` How long to wait for `load()` before rejecting, or `None`
+    ///   to wait indefinitely.
+    pub fn flush(&self, timeout_ms: Option<f64>) -> ApiFuture<()> {
+        clone!(self.renderer, self.session);
         ApiFuture::new(async move {
-            let task = root
-                .borrow()
-                .as_ref()
-                .ok_or("Already deleted")?
-                .promise_message(move |x| Msg::Reset(all, Some(x)));
+            if session.js_get_table().is_none() {
+                let loaded = session.table_loaded.listen_once();
+                match timeout_ms {
+                    None => loaded.await.into_jserror()?,
+                    Some(timeout) => {
+                        let timer = set_timeout(timeout as i32);
+                        match select(Box::pin(loaded), Box::pin(timer)).await {
+                            Either::Left((result, _)) => result.into_jserror()?,
+                            Either::Right(_) => {
+                                return Err(format!(
+                                    "No table loaded within {}ms of `flush()`",
+                                    timeout
+                                )
+                                .into());
+                            }
+                        }
+                    }
+                };
 
-            task.await.into_jserror()
+                let _ = session
+                    .js_get_table()
+                    .ok_or_else(|| js_intern!("No table set"))?;
+            };
+
+            renderer.draw(async { Ok(&session) }).await
         })
     }
 
-    /// Recalculate the viewer's dimensions and redraw.
-    #[wasm_bindgen(js_name = "notifyResize")]
-    pub fn resize(&self, force: Option<bool>) -> ApiFuture<()> {
+    /// Restores this element from a full/partial `JsPerspectiveViewConfig`.
+    ///
+    /// # Arguments
+    /// - `update` The config to restore to, as returned by `.save()` in either
+    ///   "json", "string" or "arraybuffer" format.
+    /// - `preserve_scroll` Whether to reapply the plugin's scroll position
+    ///   after redrawing, if the plugin and the config's rows and columns are
+    ///   unchanged.
+    /// - `format` The format `update` is in, one of "json", "string" or
+    ///   "arraybuffer".  When omitted, the format is detected from the type of
+    ///   `update`, which treats every string as "string" format.
+    /// - `record_history` Whether this change can be reverted by `undo()` (the
+    ///   default), or should instead replace the current undo entry.
+    ///
+    /// The returned `Promise` has an `abort()` method.  Aborting a `restore()`
+    /// which is still waiting on a previous draw rejects without applying any
+    /// of `update`, leaving the viewer (including changes from `restore()`
+    /// calls queued after it) untouched;  once this `restore()`'s own draw has
+    /// begun, `abort()` has no effect.
+    pub fn restore(
+        &self,
+        update: JsValue,
+        preserve_scroll: Option<bool>,
+        format: Option<String>,
+        record_history: Option<bool>,
+    ) -> js_sys::Promise {
+        let aborted: Rc<Cell<bool>> = Default::default();
+        let on_abort = {
+            clone!(aborted);
+            move || aborted.set(true)
+        };
+
+        let history = match record_history {
+            Some(false) => Some((self.history.clone(), self.cloned())),
+            _ => None,
+        };
+
+        clone!(self.session, self.renderer, self.root, self.theme);
+        let task = ApiFuture::new(async move {
+            let update = match format {
+                Some(format) => {
+                    let format = ViewerConfigEncoding::from_str(&format)?;
+                    ViewerConfigUpdate::decode_as(&update, &format)?
+                }
+                None => ViewerConfigUpdate::decode(&update)?,
+            };
+
+            let preserve_scroll = preserve_scroll.unwrap_or_default();
+            let viewer = (session, renderer, root, theme);
+            let task = restore_update(viewer, update, preserve_scroll, aborted);
+            match history {
+                Some((history, model)) => without_history(&history, &model, task).await,
+                None => task.await,
+            }
+        });
+
+        task.into_abortable_promise(on_abort)
+    }
+
+    /// Like `restore()`, but coalesces calls made in quick succession into a
+    /// single render.  Updates received within `RESTORE_BATCH_WINDOW` of the
+    /// first pending call are merged, fields from later calls taking
+    /// precedence, and every caller's future resolves when the merged update
+    /// has been rendered.
+    ///
+    /// # Arguments
+    /// - `update` The config to restore to, in any format `restore()` accepts.
+    #[wasm_bindgen(js_name = "restoreBatched")]
+    pub fn restore_batched(&self, update: JsValue) -> ApiFuture<()> {
+        let update = match ViewerConfigUpdate::decode(&update) {
+            Ok(update) => update,
+            Err(err) => return ApiFuture::new(async move { Err(err) }),
+        };
+
+        let (sender, receiver) = channel::<Result<(), JsValue>>();
+        let mut batch = self.restore_batch.borrow_mut();
+        if let Some(batch) = batch.as_mut() {
+            batch.update.merge(update);
+            batch.listeners.push(sender);
+        } else {
+            *batch = Some(RestoreBatch {
+                update,
+                listeners: vec![sender],
+            });
+
+            clone!(
+                self.session,
+                self.renderer,
+                self.root,
+                self.theme,
+                self.restore_batch
+            );
+
+            ApiFuture::spawn(async move {
+                set_timeout(RESTORE_BATCH_WINDOW).await?;
+                let RestoreBatch { update, listeners } =
+                    restore_batch.borrow_mut().take().into_jserror()?;
+
+                let viewer = (session, renderer, root, theme);
+                let result = restore_update(viewer, update, false, Default::default()).await;
+                for listener in listeners {
+                    let _ = listener.send(result.clone());
+                }
+
+                Ok(())
+            });
+        }
+
+        ApiFuture::new(async move { receiver.await.into_jserror()? })
+    }
+
+    /// Apply several config changes with a single render.  `callback` is
+    /// called with a `ViewerConfigBatch` to mutate, and may return a
+    /// `Promise`;  once it completes, the merged changes are applied as one
+    /// `restore()`.  If `callback` throws (or its `Promise` rejects), nothing
+    /// is applied.
+    ///
+    /// # Arguments
+    /// - `callback` A function which mutates the `ViewerConfigBatch` it is
+    ///   passed.
+    pub fn batch(&self, callback: js_sys::Function) -> ApiFuture<()> {
+        clone!(self.session, self.renderer, self.root, self.theme);
+        ApiFuture::new(async move {
+            let batch = ViewerConfigBatch::default();
+            let result = callback.call1(&JsValue::UNDEFINED, &batch.clone().into());
+            let result = match result?.dyn_into::<js_sys::Promise>() {
+                Ok(promise) => JsFuture::from(promise).await.map(|_| ()),
+                Err(_) => Ok(()),
+            };
+
+            let update = batch.take()?;
+            result?;
+            let viewer = (session, renderer, root, theme);
+            restore_update(viewer, update, false, Default::default()).await
+        })
+    }
+
+    /// Revert this viewer to its state before the last interactive change (or
+    /// `restore()`), resolving to `false` if there is nothing to undo.  Changes
+    /// made in quick succession, e.g. while dragging a column, are undone
+    /// together.
+    pub fn undo(&self) -> ApiFuture<bool> {
+        let config = self.history.undo();
+        self.restore_history(config)
+    }
+
+    /// Re-apply the last change reverted by `undo()`, resolving to `false` if
+    /// there is nothing to redo.
+    pub fn redo(&self) -> ApiFuture<bool> {
+        let config = self.history.redo();
+        self.restore_history(config)
+    }
+
+    /// Set how many states `undo()` can revert to, dropping the oldest states
+    /// if there are already more.
+    ///
+    /// # Arguments
+    /// - `size` The maximum number of states, or `None` for the default of
+    ///   `DEFAULT_HISTORY_LIMIT`.
+    #[wasm_bindgen(js_name = "setHistorySize")]
+    pub fn set_history_size(&self, size: Option<f64>) {
+        let size = size.map_or(DEFAULT_HISTORY_LIMIT, |x| x.max(1.0) as usize);
+        self.history.set_limit(size);
+    }
+
+    /// Set the default column style config for every column of a type, which
+    /// is used as the style menu's `default_config` for these columns and
+    /// which a column's own style config may still override.  Type defaults
+    /// are persisted by `save()`.
+    ///
+    /// # Arguments
+    /// - `column_type` The column type to set defaults for, e.g. "integer".
+    /// - `config` A column style config, or `null` to clear this type's
+    ///   defaults.
+    #[wasm_bindgen(js_name = "setTypeDefaults")]
+    pub fn set_type_defaults(&self, column_type: String, config: JsValue) -> ApiFuture<()> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            serde_json::from_value::<Type>(column_type.clone().into())
+                .map_err(|_| format!("Unknown column type `{}`", column_type))?;
+
+            let config = if config.is_null() || config.is_undefined() {
+                None
+            } else {
+                Some(config.into_serde::<serde_json::Value>().into_jserror()?)
+            };
+
+            renderer.set_type_default(&column_type, config);
+            renderer.draw(async { Ok(&session) }).await
+        })
+    }
+
+    /// Get the column style defaults set by `setTypeDefaults()`, as an object
+    /// keyed by column type.
+    #[wasm_bindgen(js_name = "getTypeDefaults")]
+    pub fn get_type_defaults(&self) -> Result<JsValue, JsValue> {
+        JsValue::from_serde(&self.renderer.get_type_defaults()).into_jserror()
+    }
+
+    /// Save this element to serialized state object, one which can be restored
+    /// via the `.restore()` method.
+    ///
+    /// # Arguments
+    /// - `format` Supports "json" (default), "arraybuffer" or "string".
+    pub fn save(&self, format: Option<String>) -> ApiFuture<JsValue> {
+        let viewer_config_task = self.get_viewer_config();
+        ApiFuture::new(async move {
+            let format = format
+                .as_ref()
+                .map(|x| ViewerConfigEncoding::from_str(x))
+                .transpose()?;
+
+            let viewer_config = viewer_config_task.await?;
+            viewer_config.encode(&format)
+        })
+    }
+
+    /// Compute a `ViewerConfigDiff` between two serialized configs:  the
+    /// fields added, removed and changed, and the partial `update` which
+    /// transforms one into the other via `restore()`.  In `update`, fields
+    /// which are unchanged are omitted, and fields reset to their defaults (or
+    /// absent from `new`) are `null`.
+    ///
+    /// # Arguments
+    /// - `old` A config in any format returned by `save()`.
+    /// - `new` A config in any format returned by `save()`.
+    #[wasm_bindgen(js_name = "diffConfig")]
+    pub fn diff_config(&self, old: JsValue, new: JsValue) -> Result<JsValue, JsValue> {
+        let old = ViewerConfigUpdate::decode(&old)?;
+        let new = ViewerConfigUpdate::decode(&new)?;
+        let diff = ViewerConfigDiff::new(&old, &new)?;
+        JsValue::from_serde(&diff).into_jserror()
+    }
+
+    /// Get a URL which restores this viewer's current `ViewerConfig` when
+    /// passed to `restoreFromUrl()`, by encoding it in the URL's fragment.
+    /// Rejects if the URL would be longer than `PERMALINK_MAX_LENGTH`.
+    ///
+    /// # Arguments
+    /// - `base_url` The URL to link to, whose fragment is replaced;  defaults
+    ///   to the current page.
+    #[wasm_bindgen(js_name = "getPermalink")]
+    pub fn get_permalink(&self, base_url: Option<String>) -> ApiFuture<JsValue> {
+        let viewer_config_task = self.get_viewer_config();
+        ApiFuture::new(async move {
+            let viewer_config = viewer_config_task.await?;
+            Ok(permalink(base_url, &viewer_config)?.into())
+        })
+    }
+
+    /// Copy the URL `getPermalink()` returns to the system clipboard.
+    ///
+    /// # Arguments
+    /// - `base_url` The URL to link to, whose fragment is replaced;  defaults
+    ///   to the current page.
+    #[wasm_bindgen(js_name = "copyPermalink")]
+    pub fn copy_permalink(&self, base_url: Option<String>) -> ApiFuture<()> {
+        let viewer_config_task = self.get_viewer_config();
+        let js_task = async move {
+            let viewer_config = viewer_config_task.await?;
+            JsString::from(permalink(base_url, &viewer_config)?).as_blob()
+        };
+
+        ApiFuture::new(copy_to_clipboard(js_task, MimeType::TextPlain))
+    }
+
+    /// Restore this viewer from a URL created by `getPermalink()`, as
+    /// `restore()` would.  Resolves to `false` without changing the viewer if
+    /// the URL has no permalink fragment.
+    ///
+    /// # Arguments
+    /// - `url` The URL to read;  defaults to the current page.
+    #[wasm_bindgen(js_name = "restoreFromUrl")]
+    pub fn restore_from_url(&self, url: Option<String>) -> ApiFuture<bool> {
+        let update = url
+            .map_or_else(|| web_sys::window().unwrap().location().href(), Ok)
+            .and_then(|url| parse_permalink(&url));
+
+        let restore_task = update.map(|update| {
+            update.map(|update| self.restore(update, None, Some("json".to_owned()), None))
+        });
+
+        ApiFuture::new(async move {
+            match restore_task? {
+                Some(promise) => {
+                    JsFuture::from(promise).await?;
+                    Ok(true)
+                }
+                None => Ok(false),
+            }
+        })
+    }
+
+    /// Store this viewer's current `ViewerConfig` as a named preset, replacing
+    /// any existing preset of the same name.
+    ///
+    /// # Arguments
+    /// - `name` The name of the preset.
+    #[wasm_bindgen(js_name = "saveNamedPreset")]
+    pub fn save_named_preset(&self, name: String) -> ApiFuture<()> {
+        let viewer_config_task = self.get_viewer_config();
+        clone!(self.presets);
+        ApiFuture::new(async move {
+            let viewer_config = viewer_config_task.await?;
+            let config = viewer_config.encode(&Some(ViewerConfigEncoding::Json))?;
+            presets.set(&name, config).await
+        })
+    }
+
+    /// List the names of this viewer's stored presets, sorted.
+    #[wasm_bindgen(js_name = "listPresets")]
+    pub fn list_presets(&self) -> ApiFuture<JsValue> {
+        clone!(self.presets);
+        ApiFuture::new(async move {
+            let names = presets.list().await?;
+            Ok(names
+                .into_iter()
+                .map(JsValue::from)
+                .collect::<Array>()
+                .into())
+        })
+    }
+
+    /// Restore this viewer to a named preset, as `restore()` would, rejecting
+    /// if there is no preset of that name.
+    ///
+    /// # Arguments
+    /// - `name` The name of the preset.
+    #[wasm_bindgen(js_name = "applyPreset")]
+    pub fn apply_preset(&self, name: String) -> ApiFuture<()> {
+        clone!(
+            self.presets,
+            self.session,
+            self.renderer,
+            self.root,
+            self.theme
+        );
+        ApiFuture::new(async move {
+            let config = presets
+                .get(&name)
+                .await?
+                .ok_or_else(|| format!("Unknown preset \"{}\"", name))?;
+
+            let update = ViewerConfigUpdate::decode(&config)?;
+            let viewer = (session, renderer, root, theme);
+            restore_update(viewer, update, false, Default::default()).await
+        })
+    }
+
+    /// Set the storage backend for named presets, which defaults to an
+    /// in-memory store.  Presets are not copied from the previous backend.
+    ///
+    /// # Arguments
+    /// - `storage` An object with `get(name)`, `set(name, config)` and `list()`
+    ///   methods, each of which may return a `Promise`;  or `null`/`undefined`
+    ///   to revert to in-memory storage.
+    #[wasm_bindgen(js_name = "setPresetStorage")]
+    pub fn set_preset_storage(&self, storage: JsValue) -> Result<(), JsValue> {
+        if storage.is_undefined() || storage.is_null() {
+            self.presets.set_storage(None);
+        } else if JsPresetStorage::is_preset_storage(&storage) {
+            self.presets.set_storage(Some(storage.unchecked_into()));
+        } else {
+            return Err("Preset storage must implement `get()`, `set()` and `list()`".into());
+        }
+
+        Ok(())
+    }
+
+    /// Download this viewer's `View` or `Table` data as a `.csv` file, a `.md`
+    /// table, or as a standalone `.html` snapshot.
+    ///
+    /// # Arguments
+    /// - `mode` Whether to use the current `ViewConfig` to generate this data
+    ///   (`false`), use the default (`true`), export only the rows selected in
+    ///   the plugin (`"selection"`), export the `View` as a Markdown table
+    ///   (`"markdown"`), or export an `.html` file which embeds the `Table` as
+    ///   Arrow and restores this viewer's config (`"html"`).
+    pub fn download(&self, mode: JsValue) -> ApiFuture<()> {
+        let method = match ExportMethod::from_export_mode(&mode) {
+            Ok(method) => method,
+            Err(err) => return ApiFuture::new(async move { Err(err) }),
+        };
+
+        if let Err(err) = check_export_rows(
+            &self.renderer,
+            &self.session,
+            self.max_export_rows.get(),
+            method,
+        ) {
+            return ApiFuture::new(async move { Err(err) });
+        }
+
+        let filename = match method {
+            ExportMethod::Html => "untitled.html",
+            ExportMethod::Markdown => "untitled.md",
+            _ => "untitled.csv",
+        };
+
+        let js_task = self.export_method_to_jsvalue(method);
+        ApiFuture::new(async move {
+            let val = js_task.await?;
+            download(filename, &val)
+        })
+    }
+
+    /// Download this viewer's `View` or `Table` data as a `.csv` file, like
+    /// `download()`, but serialized in chunks of `CSV_CHUNK_ROWS` rows to
+    /// reduce peak memory for large `View`s.
+    ///
+    /// # Arguments
+    /// - `flat` Whether to use the current `ViewConfig` to generate this data
+    ///   (`false`), or use the default (`true`).
+    ///
+    /// The returned `Promise` has an `abort()` method, which stops the export
+    /// between chunks and rejects without downloading a partial file.
+    #[wasm_bindgen(js_name = "downloadStreaming")]
+    pub fn download_streaming(&self, flat: Option<bool>) -> js_sys::Promise {
+        let flat = flat.unwrap_or_default();
+        let method = if flat {
+            ExportMethod::CsvAll
+        } else {
+            ExportMethod::Csv
+        };
+
+        let aborted: Rc<Cell<bool>> = Default::default();
+        let on_abort = {
+            clone!(aborted);
+            move || aborted.set(true)
+        };
+
+        let checked = check_export_rows(
+            &self.renderer,
+            &self.session,
+            self.max_export_rows.get(),
+            method,
+        );
+        clone!(self.session);
+        let task = ApiFuture::new(async move {
+            checked?;
+            let blob = session
+                .csv_chunks_as_blob(flat, CSV_CHUNK_ROWS, aborted)
+                .await?;
+
+            download("untitled.csv", &blob)
+        });
+
+        task.into_abortable_promise(on_abort)
+    }
+
+    /// Copy this viewer's `View` or `Table` data as CSV to the system
+    /// clipboard.
+    ///
+    /// # Arguments
+    /// - `mode` Whether to use the current `ViewConfig` to generate this data
+    ///   (`false`), use the default (`true`), export only the rows selected in
+    ///   the plugin (`"selection"`), or copy the `View` as a Markdown table
+    ///   (`"markdown"`).
+    /// - `headers` Whether to include the header row (the default).  Only
+    ///   supported for the current or "flat" `View`.
+    pub fn copy(&self, mode: JsValue, headers: Option<bool>) -> ApiFuture<()> {
+        let method = ExportMethod::from_export_mode(&mode)
+            .and_then(|x| x.with_header(headers.unwrap_or(true)));
+
+        let method = match method {
+            Ok(method) => method,
+            Err(err) => return ApiFuture::new(async move { Err(err) }),
+        };
+
+        if let Err(err) = check_export_rows(
+            &self.renderer,
+            &self.session,
+            self.max_export_rows.get(),
+            method,
+        ) {
+            return ApiFuture::new(async move { Err(err) });
+        }
+
+        let js_task = self.export_method_to_jsvalue(method);
+        let copy_task = copy_to_clipboard(js_task, MimeType::TextPlain);
+        ApiFuture::new(copy_task)
+    }
+
+    /// Check whether `copy()` can write to the system clipboard, i.e. the page
+    /// is a secure context, the browser implements the clipboard API and
+    /// clipboard write permission has not been denied.  Hosts can offer a
+    /// manual copy dialog instead when this resolves `false`.
+    #[wasm_bindgen(js_name = "canCopy")]
+    pub fn can_copy(&self) -> ApiFuture<bool> {
+        ApiFuture::new(async { Ok(can_copy_to_clipboard().await) })
+    }
+
+    /// Read a window of rows from this viewer's `View` in columnar form, for
+    /// hosts which virtualize their own rendering and should not fetch the
+    /// whole `View` as `download()` does.
+    ///
+    /// # Arguments
+    /// - `start_row` The first row of the window, inclusive.
+    /// - `end_row` The last row of the window, exclusive.
+    /// - `columns` An optional list of column names to include;  defaults to
+    ///   all columns.
+    #[wasm_bindgen(js_name = "getViewWindow")]
+    pub fn get_view_window(
+        &self,
+        start_row: u32,
+        end_row: u32,
+        columns: Option<Box<[JsValue]>>,
+    ) -> ApiFuture<JsValue> {
+        let session = self.session.clone();
+        ApiFuture::new(async move {
+            let columns = match columns {
+                Some(columns) => Some(
+                    columns
+                        .iter()
+                        .map(|x| x.as_string())
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or("`columns` must be an `Array` of column names")?,
+                ),
+                None => None,
+            };
+
+            let window = session.window_as_jsvalue(start_row, end_row, columns);
+            Ok(window.await?.into())
+        })
+    }
+
+    /// Copy a single cell's value from this viewer's `View` to the system
+    /// clipboard, e.g. for a host application's context menu.
+    ///
+    /// # Arguments
+    /// - `row` The cell's row index in the `View`.
+    /// - `column` The cell's column index in the `View`.
+    #[wasm_bindgen(js_name = "copyCell")]
+    pub fn copy_cell(&self, row: u32, column: u32) -> ApiFuture<()> {
+        let session = self.session.clone();
+        let js_task = async move { session.cell_as_jsvalue(row, column).await?.as_blob() };
+        let copy_task = copy_to_clipboard(js_task, MimeType::TextPlain);
+        ApiFuture::new(copy_task)
+    }
+
+    /// Reset the viewer's `ViewerConfig` to the default.
+    ///
+    /// # Arguments
+    /// - `all` Whether to clear `expressions` also.
+    pub fn reset(&self, reset_expressions: Option<bool>) -> ApiFuture<()> {
+        let root = self.root.clone();
+        let all = reset_expressions.unwrap_or_default();
+        ApiFuture::new(async move {
+            let task = root
+                .borrow()
+                .as_ref()
+                .ok_or("Already deleted")?
+                .promise_message(move |x| Msg::Reset(all, Some(x)));
+
+            task.await.into_jserror()
+        })
+    }
+
+    /// Remove all `filter` terms and redraw, leaving the rest of the config
+    /// as-is.
+    #[wasm_bindgen(js_name = "clearFilter")]
+    pub fn clear_filter(&self) -> ApiFuture<()> {
+        self.clear_view_config(ViewConfigUpdate {
+            filter: Some(vec![]),
+            ..ViewConfigUpdate::default()
+        })
+    }
+
+    /// Remove all `sort` terms and redraw, leaving the rest of the config
+    /// as-is.
+    #[wasm_bindgen(js_name = "clearSort")]
+    pub fn clear_sort(&self) -> ApiFuture<()> {
+        self.clear_view_config(ViewConfigUpdate {
+            sort: Some(vec![]),
+            ..ViewConfigUpdate::default()
+        })
+    }
+
+    /// Remove all `group_by` columns and redraw, leaving the rest of the
+    /// config as-is.
+    #[wasm_bindgen(js_name = "clearGroupBy")]
+    pub fn clear_group_by(&self) -> ApiFuture<()> {
+        self.clear_view_config(ViewConfigUpdate {
+            group_by: Some(vec![]),
+            ..ViewConfigUpdate::default()
+        })
+    }
+
+    /// Remove all `split_by` columns and redraw, leaving the rest of the
+    /// config as-is.
+    #[wasm_bindgen(js_name = "clearSplitBy")]
+    pub fn clear_split_by(&self) -> ApiFuture<()> {
+        self.clear_view_config(ViewConfigUpdate {
+            split_by: Some(vec![]),
+            ..ViewConfigUpdate::default()
+        })
+    }
+
+    /// Recalculate the viewer's dimensions and redraw.
+    #[wasm_bindgen(js_name = "notifyResize")]
+    pub fn resize(&self, force: Option<bool>) -> ApiFuture<()> {
         if !force.unwrap_or_default() && self.resize_handle.borrow().is_some() {
             let msg: JsValue = "`notifyResize(false)` called, disabling auto-size.  It can be \
                                 re-enabled with `setAutoSize(true)`."
@@ -457,43 +1615,280 @@ impl PerspectiveViewerElement {
         ApiFuture::new(async move { renderer.resize().await })
     }
 
-    /// Sets the auto-size behavior of this component.  When `true`, this
-    /// `<perspective-viewer>` will register a `ResizeObserver` on itself and
-    /// call `resize()` whenever its own dimensions change.
+    /// Sets the auto-size behavior of this component.  When `true`, this
+    /// `<perspective-viewer>` will register a `ResizeObserver` on itself and
+    /// call `resize()` whenever its own dimensions change.
+    ///
+    /// # Arguments
+    /// - `autosize` Whether to register a `ResizeObserver` on this element or
+    ///   not.
+    #[wasm_bindgen(js_name = "setAutoSize")]
+    pub fn set_auto_size(&mut self, autosize: bool) {
+        if autosize {
+            let handle = Some(ResizeObserverHandle::new(
+                &self.elem,
+                &self.session,
+                &self.renderer,
+                self.root.borrow().as_ref().unwrap(),
+                &self.resize_threshold,
+                &self.auto_suspend,
+            ));
+            *self.resize_handle.borrow_mut() = handle;
+        } else {
+            *self.resize_handle.borrow_mut() = None;
+        }
+    }
+
+    /// Sets the minimum change in width or height, in pixels, which the
+    /// auto-size `ResizeObserver` will respond to.  Smaller changes (e.g.
+    /// intermediate frames of a CSS transition) are ignored until they
+    /// accumulate past the threshold.
+    ///
+    /// # Arguments
+    /// - `threshold` The threshold in pixels, or `None` to reset to the default
+    ///   of `1`.
+    #[wasm_bindgen(js_name = "setResizeThreshold")]
+    pub fn set_resize_threshold(&self, threshold: Option<f64>) {
+        let threshold = threshold.map_or(1, |x| x.max(1.0).floor() as i32);
+        self.resize_threshold.set(threshold);
+    }
+
+    /// Opt-in to suspending this viewer (as `suspend()`) once it has been
+    /// detached or hidden for `delay` milliseconds, resuming it when it is
+    /// shown again.  Visibility is observed by the auto-size `ResizeObserver`,
+    /// so this has no effect while `setAutoSize(false)`.
+    ///
+    /// # Arguments
+    /// - `delay` The delay in milliseconds, or `None` to disable auto-suspend
+    ///   (the default).
+    #[wasm_bindgen(js_name = "setAutoSuspend")]
+    pub fn set_auto_suspend(&self, delay: Option<f64>) {
+        let delay = delay.map(|x| x.max(0.0).floor() as i32);
+        self.auto_suspend.delay.set(delay);
+        let is_visible = is_visible(&self.elem);
+        self.auto_suspend.was_visible.set(is_visible);
+        self.auto_suspend
+            .on_visibility(is_visible, &self.session, &self.renderer);
+    }
+
+    /// Sets the snippets listed in the expression editor's side panel, which
+    /// insert their expression at the cursor when clicked.  Snippets are not
+    /// part of the `save()` config.
+    ///
+    /// # Arguments
+    /// - `snippets` An array of `{name, expression}` objects, or `None` to
+    ///   restore the built-in snippets.
+    #[wasm_bindgen(js_name = "setExpressionSnippets")]
+    pub fn set_expression_snippets(&self, snippets: JsValue) -> Result<(), JsValue> {
+        let snippets = if snippets.is_null() || snippets.is_undefined() {
+            None
+        } else {
+            Some(
+                snippets
+                    .into_serde::<Vec<ExpressionSnippet>>()
+                    .map_err(|_| "Snippets must be an array of `{name, expression}` objects")?,
+            )
+        };
+
+        self.session.set_expression_snippets(snippets);
+        Ok(())
+    }
+
+    /// Sets the maximum number of rows `download()` and `copy()` will export.
+    /// Larger exports are rejected before any data is serialized, rather than
+    /// risking a hung browser tab.
+    ///
+    /// # Arguments
+    /// - `max_rows` The row limit, or `None` to remove it (the default).
+    #[wasm_bindgen(js_name = "setMaxExportRows")]
+    pub fn set_max_export_rows(&self, max_rows: Option<f64>) {
+        let max_rows = max_rows.map(|x| x.max(0.0).floor() as u32);
+        self.max_export_rows.set(max_rows);
+    }
+
+    /// Override this viewer's keyboard shortcuts, which are handled while
+    /// focus is within the viewer (but not while typing in an input or the
+    /// expression editor).  The defaults are `"Alt+Shift+C"` to toggle the
+    /// settings panel, `"Alt+Shift+E"` to download a `.csv` and
+    /// `"Alt+Shift+R"` to reset the config.
+    ///
+    /// # Arguments
+    /// - `bindings` An object mapping actions (`"toggle_config"`,
+    ///   `"export_csv"`, `"reset"`) to a chord like `"Ctrl+Shift+E"`, or to
+    ///   `null` to unbind the action.  Actions not in `bindings` are
+    ///   unchanged;  `null` restores all of the defaults.
+    #[wasm_bindgen(js_name = "setKeybindings")]
+    pub fn set_keybindings(&self, bindings: JsValue) -> Result<(), JsValue> {
+        self.keybindings.set(&bindings)
+    }
+
+    /// Get this viewer's edit port for the currently loaded `Table`.
+    #[wasm_bindgen(js_name = "getEditPort")]
+    pub fn get_edit_port(&self) -> Result<f64, JsValue> {
+        self.session
+            .metadata()
+            .get_edit_port()
+            .ok_or_else(|| "No `Table` loaded".into())
+    }
+
+    /// Restyle all plugins from current document.
+    #[wasm_bindgen(js_name = "restyleElement")]
+    pub fn restyle_element(&self) -> ApiFuture<JsValue> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            let view = session.get_view().into_jserror()?;
+            renderer.restyle_all(&view).await
+        })
+    }
+
+    /// Override whether plugins render animations such as `Pulse` backgrounds,
+    /// then restyle.  This is not persisted by `save()`.
+    ///
+    /// # Arguments
+    /// - `mode` One of `"auto"` (the default, which follows the OS
+    ///   `prefers-reduced-motion` setting), `"on"` or `"off"`, or `None` for
+    ///   `"auto"`.
+    #[wasm_bindgen(js_name = "setReducedMotion")]
+    pub fn set_reduced_motion(&self, mode: Option<String>) -> ApiFuture<JsValue> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            let mode = match mode {
+                Some(x) => ReducedMotion::from_str(&x)?,
+                None => ReducedMotion::default(),
+            };
+
+            renderer.set_reduced_motion(mode);
+            match session.get_view() {
+                Some(view) => renderer.restyle_all(&view).await,
+                None => Ok(JsValue::UNDEFINED),
+            }
+        })
+    }
+
+    /// Opt-in to plugins fading in each redraw, e.g. after a `restore()` of a
+    /// new `sort`, for presentation dashboards.  Plugins opt in via their
+    /// `redraw_fade` property, which is always `false` while reduced motion is
+    /// active.  Only the rendering is animated;  the `View` is unchanged.  This
+    /// is not persisted by `save()`.
+    ///
+    /// # Arguments
+    /// - `enabled` Whether to fade in subsequent draws (the default is
+    ///   `false`).
+    #[wasm_bindgen(js_name = "setRedrawFade")]
+    pub fn set_redraw_fade(&self, enabled: bool) {
+        self.renderer.set_redraw_fade(enabled);
+    }
+
+    /// Render the entire `View` rather than only the visible rows, so the
+    /// browser's print captures every row, then redraw.  Plugins opt in via
+    /// their `print_mode` property.  This is not persisted by `save()`.
+    ///
+    /// # Arguments
+    /// - `enabled` Whether to render for print, or restore virtualized
+    ///   rendering.
+    #[wasm_bindgen(js_name = "setPrintMode")]
+    pub fn set_print_mode(&self, enabled: bool) -> ApiFuture<()> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            if !renderer.set_print_mode(enabled)? {
+                return Ok(());
+            }
+
+            let stats = session.get_table_stats();
+            let num_rows = stats.and_then(|x| x.virtual_rows.or(x.num_rows));
+            if let Some(num_rows) = num_rows.filter(|x| enabled && *x > PRINT_MODE_WARN_ROWS) {
+                let msg = format!(
+                    "Print mode renders all {} rows, which may be slow;  consider filtering \
+                     the view first",
+                    num_rows
+                );
+
+                web_sys::console::warn_1(&msg.into());
+            }
+
+            renderer.draw(async { Ok(&session) }).await
+        })
+    }
+
+    /// Set the row height and cell padding of plugins which support it, then
+    /// restyle.  Unlike `setReducedMotion()`, this is persisted by `save()`.
+    ///
+    /// # Arguments
+    /// - `density` One of `"comfortable"` (the default) or `"compact"`, or
+    ///   `None` for `"comfortable"`.
+    #[wasm_bindgen(js_name = "setDensity")]
+    pub fn set_density(&self, density: Option<String>) -> ApiFuture<JsValue> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            let density = match density {
+                Some(x) => Density::from_str(&x)?,
+                None => Density::default(),
+            };
+
+            if !renderer.set_density(density)? {
+                return Ok(JsValue::UNDEFINED);
+            }
+
+            match session.get_view() {
+                Some(view) => renderer.restyle_all(&view).await,
+                None => Ok(JsValue::UNDEFINED),
+            }
+        })
+    }
+
+    /// Set the row height of plugins which support custom sizing, then
+    /// restyle.  Like `setDensity()`, this is persisted by `save()`.
     ///
     /// # Arguments
-    /// - `autosize` Whether to register a `ResizeObserver` on this element or
-    ///   not.
-    #[wasm_bindgen(js_name = "setAutoSize")]
-    pub fn set_auto_size(&mut self, autosize: bool) {
-        if autosize {
-            let handle = Some(ResizeObserverHandle::new(
-                &self.elem,
-                &self.renderer,
-                self.root.borrow().as_ref().unwrap(),
-            ));
-            *self.resize_handle.borrow_mut() = handle;
-        } else {
-            *self.resize_handle.borrow_mut() = None;
-        }
+    /// - `height` The row height in pixels, or `None` for the plugin's
+    ///   default.
+    #[wasm_bindgen(js_name = "setRowHeight")]
+    pub fn set_row_height(&self, height: Option<f64>) -> ApiFuture<JsValue> {
+        self.restyle_row_sizing(RowSizing {
+            row_height: height,
+            ..self.renderer.get_row_sizing()
+        })
     }
 
-    /// Get this viewer's edit port for the currently loaded `Table`.
-    #[wasm_bindgen(js_name = "getEditPort")]
-    pub fn get_edit_port(&self) -> Result<f64, JsValue> {
-        self.session
-            .metadata()
-            .get_edit_port()
-            .ok_or_else(|| "No `Table` loaded".into())
+    /// Set the column header height of plugins which support custom sizing,
+    /// then restyle.  Like `setDensity()`, this is persisted by `save()`.
+    ///
+    /// # Arguments
+    /// - `height` The header height in pixels, or `None` for the plugin's
+    ///   default.
+    #[wasm_bindgen(js_name = "setHeaderHeight")]
+    pub fn set_header_height(&self, height: Option<f64>) -> ApiFuture<JsValue> {
+        self.restyle_row_sizing(RowSizing {
+            header_height: height,
+            ..self.renderer.get_row_sizing()
+        })
     }
 
-    /// Restyle all plugins from current document.
-    #[wasm_bindgen(js_name = "restyleElement")]
-    pub fn restyle_element(&self) -> ApiFuture<JsValue> {
-        clone!(self.renderer, self.session);
+    /// Apply a high-contrast overlay (stronger borders and text) on top of
+    /// whichever theme is selected, then restyle if it changed.  This is not
+    /// persisted by `save()`.
+    ///
+    /// # Arguments
+    /// - `mode` One of `"auto"` (the default, which follows the OS
+    ///   `prefers-contrast` setting), `"on"` or `"off"`, or `None` for
+    ///   `"auto"`.
+    #[wasm_bindgen(js_name = "setHighContrast")]
+    pub fn set_high_contrast(&self, mode: Option<String>) -> ApiFuture<JsValue> {
+        clone!(self.renderer, self.session, self.theme);
         ApiFuture::new(async move {
-            let view = session.get_view().into_jserror()?;
-            renderer.restyle_all(&view).await
+            let mode = match mode {
+                Some(x) => HighContrast::from_str(&x)?,
+                None => HighContrast::default(),
+            };
+
+            if !theme.set_high_contrast(mode).await? {
+                return Ok(JsValue::UNDEFINED);
+            }
+
+            match session.get_view() {
+                Some(view) => renderer.restyle_all(&view).await,
+                None => Ok(JsValue::UNDEFINED),
+            }
         })
     }
 
@@ -523,10 +1918,95 @@ impl PerspectiveViewerElement {
         })
     }
 
+    /// Register themes from their CSS text, making them available to the
+    /// theme selector and `restore()` as if they were detected in the
+    /// document's stylesheets.  A theme with the same name as one previously
+    /// registered replaces it, and the plugin is restyled if the active theme
+    /// changed as a result.
+    ///
+    /// # Arguments
+    /// - `themes` An object mapping theme names to CSS text, which should style
+    ///   `perspective-viewer[theme="<name>"]`.
+    #[wasm_bindgen(js_name = "setThemes")]
+    pub fn set_themes(&self, themes: JsValue) -> ApiFuture<()> {
+        clone!(self.renderer, self.session, self.theme);
+        ApiFuture::new(async move {
+            // Read the entries in the object's own order, so themes register
+            // (and appear in the theme selector) in the order given.
+            let themes = themes
+                .dyn_into::<js_sys::Object>()
+                .ok()
+                .and_then(|x| {
+                    js_sys::Object::entries(&x)
+                        .iter()
+                        .map(|entry| {
+                            let entry = entry.unchecked_into::<js_sys::Array>();
+                            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+                        })
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or("Themes must be an object of theme names to CSS text")?;
+
+            let theme_name = theme.get_name().await;
+            for (name, css) in themes.iter() {
+                theme.register(name, css).await?;
+            }
+
+            let new_name = theme.get_name().await;
+            theme.set_name(new_name.as_deref()).await?;
+            let is_affected = new_name != theme_name
+                || themes
+                    .iter()
+                    .any(|(name, _)| Some(name) == new_name.as_ref());
+
+            if is_affected {
+                if let Some(view) = session.get_view() {
+                    renderer.restyle_all(&view).await?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Get the name of the currently applied theme, or `null` if there are no
+    /// themes available and the default styling is active.
+    #[wasm_bindgen(js_name = "getTheme")]
+    pub fn get_theme(&self) -> ApiFuture<JsValue> {
+        let theme = self.theme.clone();
+        ApiFuture::new(async move {
+            let name = theme.get_name().await;
+            Ok(name.map_or(JsValue::NULL, JsValue::from))
+        })
+    }
+
+    /// Advance to the next available theme (or previous, if `reverse`),
+    /// wrapping around, and restyle.  Does nothing if only one theme is
+    /// available.
+    ///
+    /// # Arguments
+    /// - `reverse` Whether to cycle to the previous theme instead.
+    #[wasm_bindgen(js_name = "cycleTheme")]
+    pub fn cycle_theme(&self, reverse: Option<bool>) -> ApiFuture<JsValue> {
+        clone!(self.renderer, self.session, self.theme);
+        ApiFuture::new(async move {
+            let changed = if reverse.unwrap_or_default() {
+                theme.cycle_prev().await?
+            } else {
+                theme.cycle_next().await?
+            };
+
+            match session.get_view() {
+                Some(view) if changed => renderer.restyle_all(&view).await,
+                _ => Ok(JsValue::UNDEFINED),
+            }
+        })
+    }
+
     /// Determines the render throttling behavior. Can be an integer, for
     /// millisecond window to throttle render event; or, if `None`, adaptive
     /// throttling will be calculated from the measured render time of the
-    /// last 5 frames.
+    /// last 5 frames (see `setThrottleTuning()`).
     ///
     /// # Examples
     /// // Only draws at most 1 frame/sec.
@@ -540,14 +2020,55 @@ impl PerspectiveViewerElement {
         self.renderer.set_throttle(val);
     }
 
-    /// Toggle (or force) the config panel open/closed.
+    /// Tune adaptive throttling, which waits the average render time of the
+    /// last `window` renders between renders.  Has no effect while a constant
+    /// throttle is set via `setThrottle()`, but persists across calls to it.
+    ///
+    /// # Arguments
+    /// - `window` How many recent renders to average, or `None` for the
+    ///   default of `DEFAULT_THROTTLE_WINDOW`.
+    /// - `target` The minimum wait between renders in milliseconds, or `None`
+    ///   for no minimum.
+    #[wasm_bindgen(js_name = "setThrottleTuning")]
+    pub fn set_throttle_tuning(
+        &self,
+        window: Option<usize>,
+        target: Option<f64>,
+    ) -> Result<(), JsValue> {
+        let tuning = ThrottleTuning {
+            window: window.unwrap_or(DEFAULT_THROTTLE_WINDOW),
+            target: target.unwrap_or_default(),
+        };
+
+        if tuning.window == 0 {
+            return Err("Throttle window must be at least 1".into());
+        }
+
+        if !tuning.target.is_finite() || tuning.target < 0_f64 {
+            return Err("Throttle target must be a non-negative number".into());
+        }
+
+        self.renderer.set_throttle_tuning(tuning);
+        Ok(())
+    }
+
+    /// The current wait between renders in milliseconds;  the constant
+    /// throttle set by `setThrottle()`, or else the adaptive throttle from
+    /// recent render times, which is `0` until `window` renders are measured.
+    #[wasm_bindgen(js_name = "getThrottle")]
+    pub fn get_throttle(&self) -> i32 {
+        self.renderer.get_throttle()
+    }
+
+    /// Toggle (or force) the config panel open/closed.  Resolves once any CSS
+    /// transition on the panels has finished, so the host may measure layout.
     ///
     /// # Arguments
     /// - `force` Force the state of the panel open or closed, or `None` to
     ///   toggle.
     #[wasm_bindgen(js_name = "toggleConfig")]
     pub fn toggle_config(&self, force: Option<bool>) -> ApiFuture<JsValue> {
-        let root = self.root.clone();
+        clone!(self.root, self.elem);
         ApiFuture::new(async move {
             let force = force.map(SettingsUpdate::Update);
             let task = root
@@ -556,10 +2077,25 @@ impl PerspectiveViewerElement {
                 .into_jserror()?
                 .promise_message(|x| Msg::ToggleSettingsInit(force, Some(x)));
 
-            task.await.map_err(|_| JsValue::from("Cancelled"))?
+            let result = task.await.map_err(|_| JsValue::from("Cancelled"))??;
+            let shadow_root = elem.shadow_root().into_jserror()?;
+            for id in ["side_panel", "main_panel_container"] {
+                if let Some(panel) = shadow_root.get_element_by_id(id) {
+                    await_transition_end(&panel).await?;
+                }
+            }
+
+            Ok(result)
         })
     }
 
+    /// Whether the config panel is currently open, as of the last completed
+    /// `toggleConfig()` or `restore()`.
+    #[wasm_bindgen(js_name = "isConfigOpen")]
+    pub fn is_config_open(&self) -> bool {
+        self.renderer.is_settings_open()
+    }
+
     /// Get an `Array` of all of the plugin custom elements registered for this
     /// element. This may not include plugins which called
     /// `registerPlugin()` after the host has rendered for the first time.
@@ -568,6 +2104,18 @@ impl PerspectiveViewerElement {
         self.renderer.get_all_plugins().iter().collect::<Array>()
     }
 
+    /// Resolves to an `Array` of all of the plugin custom elements, as
+    /// `getAllPlugins()`, once the `document` has loaded and every plugin
+    /// registered by then has been created.
+    #[wasm_bindgen(js_name = "awaitPluginsReady")]
+    pub fn await_plugins_ready(&self) -> ApiFuture<Array> {
+        let renderer = self.renderer.clone();
+        ApiFuture::new(async move {
+            renderer.await_plugins_ready().await?;
+            Ok(renderer.get_all_plugins().iter().collect::<Array>())
+        })
+    }
+
     /// Gets a plugin Custom Element with the `name` field, or get the active
     /// plugin if no `name` is provided.
     ///
@@ -582,6 +2130,30 @@ impl PerspectiveViewerElement {
         }
     }
 
+    /// Gets the metadata of a plugin with the `name` field, or of the active
+    /// plugin if no `name` is provided, as an object with `name`, `category`,
+    /// `select_mode` and optional `min_columns`, `max_columns`, `max_cells`,
+    /// `config_column_names` and `view_types` fields.
+    ///
+    /// # Arguments
+    /// - `name` The `name` property of a perspective plugin Custom Element, or
+    ///   `None` for the active plugin.
+    #[wasm_bindgen(js_name = "getPluginInfo")]
+    pub fn get_plugin_info(&self, name: Option<String>) -> Result<JsValue, JsValue> {
+        let plugin = self.get_plugin(name)?;
+        JsValue::from_serde(&plugin.get_info()).into_jserror()
+    }
+
+    /// Gets the config controls the active plugin supports, as an object with
+    /// `aggregates`, `group_by`, `split_by`, `sort`, `filter`, `min_columns`,
+    /// `max_columns` and `view_types` fields.  Unlike `getPluginInfo()`, every
+    /// field is present, with `null` for limits the plugin does not declare.
+    #[wasm_bindgen(js_name = "getPluginCapabilities")]
+    pub fn get_plugin_capabilities(&self) -> Result<JsValue, JsValue> {
+        let plugin = self.renderer.get_active_plugin()?;
+        JsValue::from_serde(&plugin.get_capabilities()).into_jserror()
+    }
+
     /// Internal Only.
     ///
     /// Get this custom element model's raw pointer.
@@ -590,3 +2162,560 @@ impl PerspectiveViewerElement {
         std::ptr::addr_of!(*self)
     }
 }
+
+/// `setPrintMode(true)` warns for `View`s larger than this, as rendering every
+/// row may hang the browser.
+const PRINT_MODE_WARN_ROWS: u32 = 10_000;
+
+/// How many rows `downloadStreaming()` serializes per chunk.
+const CSV_CHUNK_ROWS: u32 = 50_000;
+
+/// Reject an export via `method` if it would serialize more than `max_rows`
+/// rows, counted from the `Session`'s current stats, or from the active
+/// plugin's selection for `Selection`.  Methods which do not export rows
+/// (e.g. `Png`) are never rejected.
+fn check_export_rows(
+    renderer: &Renderer,
+    session: &Session,
+    max_rows: Option<u32>,
+    method: ExportMethod,
+) -> Result<(), JsValue> {
+    let stats = session.get_table_stats();
+    let num_rows = match method {
+        ExportMethod::Csv
+        | ExportMethod::CsvNoHeader
+        | ExportMethod::Json
+        | ExportMethod::Markdown
+        | ExportMethod::Arrow => stats.and_then(|x| x.virtual_rows.or(x.num_rows)),
+        ExportMethod::CsvAll
+        | ExportMethod::CsvAllNoHeader
+        | ExportMethod::JsonAll
+        | ExportMethod::ArrowAll
+        | ExportMethod::Html => stats.and_then(|x| x.num_rows),
+        ExportMethod::Selection => renderer
+            .get_active_plugin()
+            .ok()
+            .and_then(|x| x.get_selection().ok().flatten())
+            .map(|x| x.rows.len() as u32),
+        _ => None,
+    };
+
+    match (max_rows, num_rows) {
+        (Some(max_rows), Some(num_rows)) if num_rows > max_rows => Err(format!(
+            "Export of {} rows exceeds the limit of {} rows set by `setMaxExportRows()`",
+            num_rows, max_rows
+        )
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// The prefix of the URL fragment which encodes a permalink's config.
+const PERMALINK_PREFIX: &str = "#perspective=";
+
+/// Permalinks longer than this log a warning, as some browsers and chat
+/// clients truncate URLs beyond ~2,000 characters.
+const PERMALINK_WARN_LENGTH: usize = 2_000;
+
+/// Permalinks longer than this are refused.
+const PERMALINK_MAX_LENGTH: usize = 8_192;
+
+/// Create a permalink to `viewer_config` by replacing the fragment of
+/// `base_url` (or the current page).
+fn permalink(base_url: Option<String>, viewer_config: &ViewerConfig) -> Result<String, JsValue> {
+    let base_url = match base_url {
+        Some(base_url) => base_url,
+        None => web_sys::window().unwrap().location().href()?,
+    };
+
+    let base_url = base_url.split('#').next().unwrap_or_default();
+    let fragment = viewer_config.to_url_fragment()?;
+    let url = format!("{}{}{}", base_url, PERMALINK_PREFIX, fragment);
+    if url.len() > PERMALINK_MAX_LENGTH {
+        return Err(format!(
+            "Permalink of {} characters exceeds the limit of {};  use `save()` instead",
+            url.len(),
+            PERMALINK_MAX_LENGTH
+        )
+        .into());
+    }
+
+    if url.len() > PERMALINK_WARN_LENGTH {
+        let msg = format!(
+            "Permalink of {} characters may be truncated when shared",
+            url.len()
+        );
+
+        web_sys::console::warn_1(&msg.into());
+    }
+
+    Ok(url)
+}
+
+/// The config in `url`'s permalink fragment, as JSON for `restore()`, or
+/// `None` if `url` has no permalink fragment.
+fn parse_permalink(url: &str) -> Result<Option<JsValue>, JsValue> {
+    match url.split_once(PERMALINK_PREFIX) {
+        Some((_, fragment)) => {
+            let update = ViewerConfigUpdate::from_url_fragment(fragment)?;
+            Ok(Some(JsValue::from_serde(&update).into_jserror()?))
+        }
+        None => Ok(None),
+    }
+}
+
+impl PerspectiveViewerElement {
+    /// Apply a partial `ViewConfigUpdate` which empties some fields, and
+    /// redraw.
+    fn clear_view_config(&self, update: ViewConfigUpdate) -> ApiFuture<()> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            session.update_view_config(update);
+            renderer
+                .draw(async { session.validate().await?.create_view().await })
+                .await
+        })
+    }
+
+    /// Move the `search()` position with `step`, then scroll to and highlight
+    /// the match there.  Shared by `searchNext()` and `searchPrev()`.
+    fn navigate_search(
+        &self,
+        step: fn(&mut SearchResults) -> Option<&SearchMatch>,
+    ) -> ApiFuture<JsValue> {
+        clone!(self.renderer, self.search);
+        ApiFuture::new(async move {
+            let current = step(&mut search.borrow_mut()).cloned();
+            match current {
+                Some(current) => {
+                    let plugin = renderer.get_active_plugin()?;
+                    let target = ScrollTarget {
+                        row: current.row,
+                        column: Some(current.column.clone()),
+                    };
+
+                    plugin.scroll_to(&target).await?;
+                    plugin.set_highlight(Some(&target))?;
+                    JsValue::from_serde(&current).into_jserror()
+                }
+                None => Ok(JsValue::NULL),
+            }
+        })
+    }
+
+    /// The handler for `KeyBindingsHandle`, which dispatches each action to
+    /// the same internal message (or export) as its button in the UI.
+    fn on_key_action(&self) -> impl Fn(KeyAction) + 'static {
+        clone!(self.root, self.renderer, self.session, self.max_export_rows);
+        let model = self.cloned();
+        move |action| match action {
+            KeyAction::ToggleConfig => {
+                if let Some(root) = root.borrow().as_ref() {
+                    root.send_message(Msg::ToggleSettingsInit(None, None));
+                }
+            }
+            KeyAction::Reset => {
+                if let Some(root) = root.borrow().as_ref() {
+                    root.send_message(Msg::Reset(false, None));
+                }
+            }
+            KeyAction::ExportCsv => {
+                let method = ExportMethod::Csv;
+                let task = check_export_rows(&renderer, &session, max_export_rows.get(), method)
+                    .map(|_| model.export_method_to_jsvalue(method));
+                ApiFuture::spawn(async move { download("untitled.csv", &task?.await?) });
+            }
+        }
+    }
+
+    /// Restyle when the OS `prefers-reduced-motion` setting changes, so that
+    /// e.g. `Pulse` backgrounds stop or resume live while `ReducedMotion::Auto`
+    /// is set.
+    fn on_reduced_motion_change(&self) -> Result<MediaQueryHandle, JsValue> {
+        clone!(self.renderer, self.session);
+        MediaQueryHandle::new(ReducedMotion::MEDIA_QUERY, move |_| {
+            if renderer.get_reduced_motion() == ReducedMotion::Auto {
+                if let Some(view) = session.get_view() {
+                    clone!(renderer);
+                    ApiFuture::spawn(async move { renderer.restyle_all(&view).await });
+                }
+            }
+        })
+    }
+
+    /// Re-apply the high-contrast overlay when the OS `prefers-contrast`
+    /// setting changes, restyling only if the overlay flipped (it does not
+    /// while `HighContrast::On` or `Off` is set).
+    fn on_high_contrast_change(&self) -> Result<MediaQueryHandle, JsValue> {
+        clone!(self.renderer, self.session, self.theme);
+        MediaQueryHandle::new(HighContrast::MEDIA_QUERY, move |_| {
+            clone!(renderer, session, theme);
+            ApiFuture::spawn(async move {
+                let changed = theme.refresh_high_contrast().await?;
+                match session.get_view() {
+                    Some(view) if changed => renderer.restyle_all(&view).await,
+                    _ => Ok(JsValue::UNDEFINED),
+                }
+            });
+        })
+    }
+
+    /// Set the `Renderer`'s `RowSizing` and restyle if it changed.  Shared by
+    /// `setRowHeight()` and `setHeaderHeight()`.
+    fn restyle_row_sizing(&self, sizing: RowSizing) -> ApiFuture<JsValue> {
+        clone!(self.renderer, self.session);
+        ApiFuture::new(async move {
+            if !renderer.set_row_sizing(sizing)? {
+                return Ok(JsValue::UNDEFINED);
+            }
+
+            match session.get_view() {
+                Some(view) => renderer.restyle_all(&view).await,
+                None => Ok(JsValue::UNDEFINED),
+            }
+        })
+    }
+
+    /// Restore a `ConfigHistory` entry, if there is one, without recording it
+    /// as a new entry.
+    fn restore_history(&self, config: Option<ViewerConfig>) -> ApiFuture<bool> {
+        let model = self.cloned();
+        clone!(
+            self.session,
+            self.renderer,
+            self.root,
+            self.theme,
+            self.history
+        );
+
+        ApiFuture::new(async move {
+            let update = match config {
+                Some(config) => ViewerConfigUpdate::decode(&config.encode(&None)?)?,
+                None => return Ok(false),
+            };
+
+            let viewer = (session, renderer, root, theme);
+            let task = restore_update(viewer, update, false, Default::default());
+            without_history(&history, &model, task).await?;
+            Ok(true)
+        })
+    }
+}
+
+/// Delete `session`'s `View` and clear the plugin, returning whether there was
+/// a `View` to delete.  Shared by `suspend()` and `setAutoSuspend()`.
+async fn suspend_view(session: &Session, renderer: &Renderer) -> Result<bool, JsValue> {
+    renderer
+        .clone()
+        .with_lock(async move {
+            if session.suspend_view() {
+                renderer.clear().await?;
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        })
+        .await
+}
+
+/// Recreate the `View` deleted by `suspend_view()` from the unchanged config
+/// and redraw, returning whether it was recreated.
+async fn resume_view(session: &Session, renderer: &Renderer) -> Result<bool, JsValue> {
+    if session.get_view().is_some() || session.get_table().is_none() {
+        return Ok(false);
+    }
+
+    renderer
+        .draw(async { session.validate().await?.create_view().await })
+        .await?;
+
+    Ok(true)
+}
+
+/// Run `task` with `history` paused, then replace the current history entry
+/// with the resulting `ViewerConfig`, so the changes `task` makes are not
+/// recorded as a new entry.
+async fn without_history(
+    history: &ConfigHistory,
+    model: &GetViewerConfigModelCloned,
+    task: impl Future<Output = Result<(), JsValue>>,
+) -> Result<(), JsValue> {
+    let _pause = history.pause();
+    task.await?;
+    history.record(model.get_viewer_config().await?);
+    Ok(())
+}
+
+/// Options for `load()`.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LoadOptions {
+    /// Skip picking the active plugin's default columns, e.g. when a
+    /// `restore()` will immediately follow.
+    #[serde(default)]
+    skip_defaults: bool,
+}
+
+/// How long `restoreBatched()` waits for further calls before rendering, in
+/// milliseconds.
+const RESTORE_BATCH_WINDOW: i32 = 50;
+
+/// A pending `restoreBatched()` render, and the callers awaiting it.
+struct RestoreBatch {
+    update: ViewerConfigUpdate,
+    listeners: Vec<Sender<Result<(), JsValue>>>,
+}
+
+/// The models of a `PerspectiveViewerElement` which `restore_update()` needs,
+/// cloned so the update may outlive the `&self` borrow.
+type RestoreHandle = (
+    Session,
+    Renderer,
+    Rc<RefCell<Option<AppHandle<PerspectiveViewer>>>>,
+    Theme,
+);
+
+/// Apply a decoded `ViewerConfigUpdate` and render it.  The update is applied
+/// only once this call holds the draw lock, so if `aborted` is set before then,
+/// it rejects without touching the viewer's state.  Shared by `restore()`,
+/// `restoreBatched()` and `batch()`.
+async fn restore_update(
+    (session, renderer, root, theme): RestoreHandle,
+    update: ViewerConfigUpdate,
+    preserve_scroll: bool,
+    aborted: Rc<Cell<bool>>,
+) -> Result<(), JsValue> {
+    let ViewerConfigUpdate {
+        mut plugin,
+        mut plugin_config,
+        settings,
+        theme: theme_name,
+        density,
+        type_defaults,
+        pinned_columns,
+        settings_width,
+        row_height,
+        header_height,
+        mut view_config,
+    } = update;
+
+    // A config saved with a plugin which is not registered on this page falls
+    // back to the default plugin, rather than failing the whole `restore()`.
+    // Its `plugin_config` belongs to the missing plugin, so it is dropped too.
+    if let PluginUpdate::Update(name) = &plugin {
+        if renderer.get_plugin(name).is_err() {
+            let msg = format!("Unknown plugin \"{}\", using default plugin", name);
+            web_sys::console::warn_1(&msg.into());
+            plugin = PluginUpdate::SetDefault;
+            plugin_config = None;
+        }
+    }
+
+    let scroll_position = RefCell::new(None);
+    let needs_restyle = Cell::new(false);
+    let draw_task = renderer.draw(async {
+        // Nothing is applied until this update holds the draw lock and can no
+        // longer be aborted, so an aborted call never clobbers state set by
+        // `restore()` calls queued after it.
+        if aborted.get() {
+            return Err("Aborted".into());
+        }
+
+        let scroll = if preserve_scroll {
+            let plugin = renderer.get_active_plugin().ok();
+            let position = plugin.as_ref().and_then(ScrollPosition::capture);
+            position.map(|x| (x, session.get_view_config().clone()))
+        } else {
+            None
+        };
+
+        if let Some(type_defaults) = type_defaults {
+            renderer.set_type_defaults(type_defaults);
+        }
+
+        if let Some(pinned_columns) = pinned_columns {
+            renderer.set_pinned_columns(pinned_columns);
+        }
+
+        match settings_width {
+            OptionalUpdate::SetDefault => renderer.set_settings_width(None),
+            OptionalUpdate::Update(x) => renderer.set_settings_width(Some(x)),
+            OptionalUpdate::Missing => false,
+        };
+
+        let plugin_changed = renderer.update_plugin(&plugin)?;
+        if plugin_changed {
+            session.set_update_column_defaults(&mut view_config, &renderer.metadata());
+        }
+
+        session.update_view_config(view_config);
+        *scroll_position.borrow_mut() = scroll
+            .filter(|(_, old)| {
+                !plugin_changed && old.is_scroll_compatible(&session.get_view_config())
+            })
+            .map(|(x, _)| x);
+
+        let prev_row_sizing = renderer.get_row_sizing();
+        let row_sizing = RowSizing {
+            row_height: match row_height {
+                OptionalUpdate::SetDefault => None,
+                OptionalUpdate::Update(x) => Some(x),
+                OptionalUpdate::Missing => prev_row_sizing.row_height,
+            },
+            header_height: match header_height {
+                OptionalUpdate::SetDefault => None,
+                OptionalUpdate::Update(x) => Some(x),
+                OptionalUpdate::Missing => prev_row_sizing.header_height,
+            },
+        };
+
+        let is_restyle = restore_theme(&theme, theme_name).await?;
+        let is_restyle = match density {
+            OptionalUpdate::SetDefault => renderer.set_density(Density::default())?,
+            OptionalUpdate::Update(x) => renderer.set_density(x)?,
+            OptionalUpdate::Missing => false,
+        } || is_restyle;
+
+        let is_restyle = renderer.set_row_sizing(row_sizing)? || is_restyle;
+        needs_restyle.set(is_restyle);
+
+        let task = root
+            .borrow()
+            .as_ref()
+            .ok_or("Already deleted")?
+            .promise_message(move |x| Msg::ToggleSettingsComplete(settings, x));
+
+        let result = async {
+            let plugin = renderer.get_active_plugin()?;
+            if let Some(plugin_config) = &plugin_config {
+                let js_config = JsValue::from_serde(plugin_config);
+                plugin.restore(&js_config.into_jserror()?);
+            }
+
+            session.validate().await?.create_view().await
+        }
+        .await;
+
+        task.await.into_jserror()?;
+        result
+    });
+
+    draw_task.await?;
+    if let Some(scroll) = scroll_position.into_inner() {
+        scroll.apply(&renderer.get_active_plugin()?);
+    }
+
+    // TODO this should be part of the API for `draw()` above, such that
+    // the plugin need not render twice when a theme is provided.
+    if needs_restyle.get() {
+        let view = session.get_view().into_jserror()?;
+        renderer.restyle_all(&view).await?;
+    }
+
+    Ok(())
+}
+
+/// Apply a `restore()`'s `theme` field, returning whether the theme changed
+/// and plugins should be restyled.
+async fn restore_theme(theme: &Theme, theme_name: ThemeUpdate) -> Result<bool, JsValue> {
+    match theme_name {
+        OptionalUpdate::SetDefault => {
+            let current_name = theme.get_name().await;
+            if None != current_name {
+                theme.set_name(None).await?;
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        }
+        OptionalUpdate::Update(x) => {
+            let current_name = theme.get_name().await;
+            if current_name.is_some() && current_name.as_ref().unwrap() != &x {
+                theme.set_name(Some(&x)).await?;
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        }
+        _ => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn resize_entry(width: f64, height: f64) -> ResizeObserverEntry {
+        json!({
+            "contentRect": {
+                "width": width,
+                "height": height
+            }
+        })
+        .unchecked_into()
+    }
+
+    fn state_helper(threshold: i32) -> ResizeObserverState {
+        let document = window().unwrap().document().unwrap();
+        let elem: HtmlElement = document.create_element("div").unwrap().unchecked_into();
+        ResizeObserverState {
+            session: Session::default(),
+            renderer: Renderer::new(&elem),
+            auto_suspend: AutoSuspend::default(),
+            elem,
+            width: 100,
+            height: 100,
+            threshold: Rc::new(Cell::new(threshold)),
+            is_pending: Rc::new(Cell::new(false)),
+            is_stale: Rc::new(Cell::new(false)),
+            on_resize: Callback::from(|()| ()),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_default_threshold_resizes() {
+        let mut state = state_helper(1);
+        assert!(state.update_dimensions(&resize_entry(101.0, 100.0)));
+        assert_eq!((state.width, state.height), (101, 100));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_sub_threshold_does_not_resize() {
+        let mut state = state_helper(10);
+        assert!(!state.update_dimensions(&resize_entry(104.0, 100.0)));
+        assert!(!state.update_dimensions(&resize_entry(109.5, 95.0)));
+        assert_eq!((state.width, state.height), (100, 100));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_accumulated_drift_resizes() {
+        let mut state = state_helper(10);
+        assert!(!state.update_dimensions(&resize_entry(105.0, 100.0)));
+        assert!(state.update_dimensions(&resize_entry(110.0, 100.0)));
+        assert_eq!((state.width, state.height), (110, 100));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_resize_coalesces_while_pending() {
+        let state = state_helper(1);
+        state.request_resize();
+        assert!(state.is_pending.get());
+        assert!(!state.is_stale.get());
+        state.request_resize();
+        state.request_resize();
+        assert!(state.is_pending.get());
+        assert!(state.is_stale.get());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_auto_suspend_visibility_cancels_pending() {
+        let state = state_helper(1);
+        let auto_suspend = &state.auto_suspend;
+        auto_suspend.delay.set(Some(1000));
+        auto_suspend.on_visibility(false, &state.session, &state.renderer);
+        auto_suspend.on_visibility(true, &state.session, &state.renderer);
+        assert_eq!(auto_suspend.generation.get(), 2);
+        assert!(!auto_suspend.is_suspended.get());
+    }
+}
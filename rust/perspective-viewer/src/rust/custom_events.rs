@@ -7,18 +7,20 @@
 // file.
 
 use crate::config::*;
+use crate::config_history::ConfigHistory;
 use crate::js::JsPerspectiveViewerPlugin;
 use crate::model::*;
 use crate::renderer::*;
-use crate::session::Session;
+use crate::session::{LoadProgress, Session};
 use crate::theme::Theme;
 use crate::utils::*;
 use crate::*;
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 use web_sys::*;
 
 /// A collection of `Subscription` which should trigger an event on the
@@ -26,7 +28,12 @@ use web_sys::*;
 /// on `CustomElements`, but when it is `drop()` the Custom Element will no
 /// longer dispatch events such as `"perspective-config-change"`.
 #[derive(Clone)]
-pub struct CustomEvents(Rc<(CustomEventsDataRc, [Subscription; 4])>);
+pub struct CustomEvents(Rc<(CustomEventsDataRc, [Subscription; 12])>);
+
+/// How long the `ViewConfig` must be unchanged before
+/// `"perspective-viewer-config-update"` fires, so that rapid UI interaction
+/// (e.g. dragging a column) dispatches one event.
+const VIEW_CONFIG_DEBOUNCE_MS: i32 = 100;
 
 #[derive(Clone)]
 struct CustomEventsDataRc(Rc<CustomEventsData>);
@@ -43,19 +50,31 @@ struct CustomEventsData {
     session: Session,
     renderer: Renderer,
     theme: Theme,
+    history: ConfigHistory,
     last_dispatched: RefCell<Option<ViewerConfig>>,
+    last_view_config: RefCell<Option<ViewConfig>>,
+    view_config_generation: Cell<u64>,
 }
 
 derive_model!(Renderer, Session, Theme for CustomEventsData);
 
 impl CustomEvents {
-    pub fn new(elem: &HtmlElement, session: &Session, renderer: &Renderer, theme: &Theme) -> Self {
+    pub fn new(
+        elem: &HtmlElement,
+        session: &Session,
+        renderer: &Renderer,
+        theme: &Theme,
+        history: &ConfigHistory,
+    ) -> Self {
         let data = CustomEventsDataRc(Rc::new(CustomEventsData {
             elem: elem.clone(),
             session: session.clone(),
             renderer: renderer.clone(),
             theme: theme.clone(),
+            history: history.clone(),
             last_dispatched: Default::default(),
+            last_view_config: Default::default(),
+            view_config_generation: Default::default(),
         }));
 
         let theme_sub = theme.theme_config_updated.add_listener({
@@ -63,6 +82,11 @@ impl CustomEvents {
             move |_| data.clone().dispatch_config_update()
         });
 
+        let theme_change_sub = theme.theme_changed.add_listener({
+            clone!(data);
+            move |(old, new)| data.dispatch_theme_changed(old, new)
+        });
+
         let settings_sub = renderer.settings_open_changed.add_listener({
             clone!(data);
             move |open| {
@@ -71,6 +95,31 @@ impl CustomEvents {
             }
         });
 
+        let density_sub = renderer.density_changed.add_listener({
+            clone!(data);
+            move |_| data.clone().dispatch_config_update()
+        });
+
+        let pinned_sub = renderer.pinned_columns_changed.add_listener({
+            clone!(data);
+            move |_| data.clone().dispatch_config_update()
+        });
+
+        let settings_width_sub = renderer.settings_width_changed.add_listener({
+            clone!(data);
+            move |_| data.clone().dispatch_config_update()
+        });
+
+        let row_sizing_sub = renderer.row_sizing_changed.add_listener({
+            clone!(data);
+            move |_| data.clone().dispatch_config_update()
+        });
+
+        let render_error_sub = renderer.render_error.add_listener({
+            clone!(data);
+            move |err| data.dispatch_render_error(&err)
+        });
+
         let plugin_sub = renderer.plugin_changed.add_listener({
             clone!(data);
             move |plugin| {
@@ -86,11 +135,29 @@ impl CustomEvents {
             }
         });
 
+        let view_config_sub = session.view_config_changed.add_listener({
+            clone!(data);
+            move |_| data.clone().dispatch_view_config_update()
+        });
+
+        let load_progress_sub = session.load_progress.add_listener({
+            clone!(data);
+            move |progress| data.dispatch_load_progress(&progress)
+        });
+
         Self(Rc::new((data, [
             theme_sub,
+            theme_change_sub,
             settings_sub,
+            density_sub,
+            pinned_sub,
+            settings_width_sub,
+            row_sizing_sub,
+            render_error_sub,
             plugin_sub,
             view_sub,
+            view_config_sub,
+            load_progress_sub,
         ])))
     }
 }
@@ -110,6 +177,23 @@ impl CustomEventsDataRc {
         self.elem.dispatch_event(&event.unwrap()).unwrap();
     }
 
+    fn dispatch_theme_changed(&self, old: Option<String>, new: Option<String>) {
+        let detail = json!({
+            "old": old.map_or(JsValue::NULL, JsValue::from),
+            "new": new.map_or(JsValue::NULL, JsValue::from),
+            "high_contrast": self.theme.is_high_contrast()
+        });
+
+        let mut event_init = web_sys::CustomEventInit::new();
+        event_init.detail(&detail);
+        let event = web_sys::CustomEvent::new_with_event_init_dict(
+            "perspective-viewer-theme-change",
+            &event_init,
+        );
+
+        self.elem.dispatch_event(&event.unwrap()).unwrap();
+    }
+
     fn dispatch_plugin_changed(&self, plugin: &JsPerspectiveViewerPlugin) {
         let mut event_init = web_sys::CustomEventInit::new();
         event_init.detail(plugin);
@@ -121,6 +205,72 @@ impl CustomEventsDataRc {
         self.elem.dispatch_event(&event.unwrap()).unwrap();
     }
 
+    fn dispatch_render_error(&self, err: &JsValue) {
+        let message = err
+            .dyn_ref::<js_sys::Error>()
+            .map(|x| String::from(x.message()))
+            .or_else(|| err.as_string())
+            .unwrap_or_else(|| "Unknown error".to_owned());
+
+        let plugin = self.renderer.get_active_plugin().map(|x| x.name()).ok();
+        let detail = json!({
+            "message": message,
+            "plugin": plugin.map_or(JsValue::NULL, JsValue::from),
+            "error": err.clone()
+        });
+
+        let mut event_init = web_sys::CustomEventInit::new();
+        event_init.detail(&detail);
+        let event = web_sys::CustomEvent::new_with_event_init_dict(
+            "perspective-viewer-render-error",
+            &event_init,
+        );
+
+        self.elem.dispatch_event(&event.unwrap()).unwrap();
+    }
+
+    fn dispatch_load_progress(&self, progress: &LoadProgress) {
+        let detail = JsValue::from_serde(progress).unwrap();
+        let mut event_init = web_sys::CustomEventInit::new();
+        event_init.detail(&detail);
+        let event = web_sys::CustomEvent::new_with_event_init_dict(
+            "perspective-viewer-load-progress",
+            &event_init,
+        );
+
+        self.elem.dispatch_event(&event.unwrap()).unwrap();
+    }
+
+    /// Dispatch `"perspective-viewer-config-update"` with the new `ViewConfig`
+    /// once it has settled for `VIEW_CONFIG_DEBOUNCE_MS`, unless it is the
+    /// same as the last one dispatched.
+    fn dispatch_view_config_update(self) {
+        let generation = self.view_config_generation.get() + 1;
+        self.view_config_generation.set(generation);
+        ApiFuture::spawn(async move {
+            set_timeout(VIEW_CONFIG_DEBOUNCE_MS).await?;
+            if generation != self.view_config_generation.get() {
+                return Ok(());
+            }
+
+            let view_config = self.session.get_view_config().clone();
+            if Some(&view_config) != self.last_view_config.borrow().as_ref() {
+                let json_config = JsValue::from_serde(&view_config).into_jserror()?;
+                let mut event_init = web_sys::CustomEventInit::new();
+                event_init.detail(&json_config);
+                let event = web_sys::CustomEvent::new_with_event_init_dict(
+                    "perspective-viewer-config-update",
+                    &event_init,
+                );
+
+                *self.last_view_config.borrow_mut() = Some(view_config);
+                self.elem.dispatch_event(&event.unwrap()).unwrap();
+            }
+
+            Ok(())
+        });
+    }
+
     fn dispatch_config_update(self) {
         ApiFuture::spawn(async move {
             let viewer_config = self.get_viewer_config().await?;
@@ -135,6 +285,7 @@ impl CustomEventsDataRc {
                     &event_init,
                 );
 
+                self.history.record(viewer_config.clone());
                 *self.last_dispatched.borrow_mut() = Some(viewer_config);
                 self.elem.dispatch_event(&event.unwrap()).unwrap();
             }
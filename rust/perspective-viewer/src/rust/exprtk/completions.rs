@@ -10,9 +10,15 @@ use crate::js::monaco::*;
 
 use super::language::*;
 
-use js_intern::*;
 use wasm_bindgen::prelude::*;
 
+#[cfg(test)]
+use wasm_bindgen_test::*;
+
+/// The character which opens the fuzzy column picker, e.g. `@gpro` to insert
+/// `"Gross Profit"`.
+pub const COLUMN_PICKER_TRIGGER: char = '@';
+
 /// This helper _must_ create the `JsValue` anew on each call, or it causes
 /// strange & subtle bugs in monaco.
 /// https://github.com/microsoft/monaco-editor/issues/1510
@@ -21,36 +27,455 @@ pub fn get_completions(
     position: JsMonacoPosition,
     token: JsMonacoTriggerToken,
 ) -> JsValue {
-    // Test the token stream until the cursor to distinguish opening from closing
-    // quotes - otherwise the column completion popup will occur at the end of a
-    // column name also.
-    let tokens = model.get_line_tokens(position.line_number());
-    let token_index = tokens.find_token_index_at_offset(position.column());
-    let prev_token_type = tokens.get_class_name(token_index) == *js_intern!("mtk23");
-
-    // `trigger_kind` matches the `triggerCharacter` we set above.
-    if token.trigger_kind() == 1 {
-        if prev_token_type {
-            JsValue::UNDEFINED
-        } else {
-            COMPLETION_COLUMN_NAMES.with(|cols| {
-                JsValue::from_serde(&RegisterCompletionItemSuggestions {
-                    suggestions: cols
-                        .borrow()
-                        .iter()
-                        .map(|col| CompletionItemSuggestion {
-                            label: format!("\"{}\"", col),
-                            kind: 14, //Constant
-                            insert_text: format!("{}\"", col),
-                            insert_text_rules: 4,
-                            documentation: format!("The values of column \"{}\"", col),
-                        })
-                        .collect::<Vec<CompletionItemSuggestion>>(),
+    let line = model.get_line_content(position.line_number());
+    let cursor = position.column().saturating_sub(1) as usize;
+    if let Some(fuzzy) = split_fuzzy_column_token(&line, cursor) {
+        return COMPLETION_COLUMN_NAMES.with(|cols| {
+            let line_number = position.line_number();
+            let range = CompletionRange {
+                start_line_number: line_number,
+                start_column: fuzzy.start as u32 + 1,
+                end_line_number: line_number,
+                end_column: position.column(),
+            };
+
+            let filter_text = format!("{}{}", COLUMN_PICKER_TRIGGER, fuzzy.text);
+            let suggestions = rank_fuzzy_columns(fuzzy.text, &cols.borrow())
+                .into_iter()
+                .enumerate()
+                .map(|(idx, col)| ColumnPickerItem {
+                    label: quote_column_name(col),
+                    kind: 14, // Constant
+                    insert_text: quote_column_name(col),
+                    filter_text: filter_text.clone(),
+                    sort_text: format!("{:05}", idx),
+                    range: range.clone(),
+                    documentation: format!("The values of column \"{}\"", col),
                 })
-                .unwrap()
+                .collect();
+
+            JsValue::from_serde(&ColumnPickerSuggestions {
+                suggestions,
+                incomplete: true,
             })
+            .unwrap()
+        });
+    }
+
+    // A `"` trigger which closes a column name has no completions, otherwise
+    // the column completion popup will occur at the end of a column name also.
+    let partial = split_partial_token(&line, cursor);
+    if token.trigger_kind() == 1 && partial.kind != PartialTokenKind::Column {
+        return JsValue::UNDEFINED;
+    }
+
+    COMPLETION_COLUMN_NAMES.with(|cols| {
+        let line_number = position.line_number();
+        let range = CompletionRange {
+            start_line_number: line_number,
+            start_column: line[..partial.start].chars().count() as u32 + 1,
+            end_line_number: line_number,
+            end_column: position.column(),
+        };
+
+        let suggestions = get_expression_completions(&line, cursor, &cols.borrow())
+            .into_iter()
+            .enumerate()
+            .map(|(idx, item)| RankedCompletionItem {
+                item,
+                filter_text: partial.text.to_owned(),
+                sort_text: format!("{:05}", idx),
+                range: range.clone(),
+            })
+            .collect();
+
+        JsValue::from_serde(&RankedCompletionSuggestions {
+            suggestions,
+            incomplete: true,
+        })
+        .unwrap()
+    })
+}
+
+/// The kind of token the cursor is currently positioned at the end of.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PartialTokenKind {
+    /// Inside an open `"` quoted column name.
+    Column,
+
+    /// A bare identifier, e.g. a function name or keyword.
+    Function,
+
+    /// Inside a `'` quoted string literal, which has no completions.
+    Literal,
+}
+
+/// The partial token immediately preceding the cursor in an expression.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PartialToken<'a> {
+    pub kind: PartialTokenKind,
+
+    /// The text of the token up to the cursor, excluding any opening quote.
+    pub text: &'a str,
+
+    /// The byte offset of `text` within the expression.
+    pub start: usize,
+}
+
+/// Split the partial token which ends at `cursor` from an expression, taking
+/// quoting into account so the tail of a column name or string literal is
+/// not mistaken for an identifier.
+///
+/// # Arguments
+/// - `expr` The expression text.
+/// - `cursor` The cursor position, in characters.
+pub fn split_partial_token(expr: &str, cursor: usize) -> PartialToken<'_> {
+    let end = expr
+        .char_indices()
+        .nth(cursor)
+        .map_or(expr.len(), |(idx, _)| idx);
+
+    let head = &expr[..end];
+    let mut quote: Option<(char, usize)> = None;
+    for (idx, c) in head.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some((c, idx + 1)),
+            (Some((open, _)), c) if c == open => quote = None,
+            _ => (),
         }
+    }
+
+    match quote {
+        Some(('"', start)) => PartialToken {
+            kind: PartialTokenKind::Column,
+            text: &head[start..],
+            start,
+        },
+        Some((_, start)) => PartialToken {
+            kind: PartialTokenKind::Literal,
+            text: &head[start..],
+            start,
+        },
+        None => {
+            let start = head
+                .char_indices()
+                .rev()
+                .find(|(_, c)| !(c.is_alphanumeric() || *c == '_'))
+                .map_or(0, |(idx, c)| idx + c.len_utf8());
+
+            PartialToken {
+                kind: PartialTokenKind::Function,
+                text: &head[start..],
+                start,
+            }
+        }
+    }
+}
+
+/// A fuzzy column reference being typed, e.g. `@gpro`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FuzzyColumnToken<'a> {
+    /// The fragment typed after the `COLUMN_PICKER_TRIGGER`.
+    pub text: &'a str,
+
+    /// The position of the `COLUMN_PICKER_TRIGGER`, in characters.
+    pub start: usize,
+}
+
+/// Split the fuzzy column reference which ends at `cursor` from an expression,
+/// if the cursor follows a `COLUMN_PICKER_TRIGGER` and an identifier-like
+/// fragment which are not inside a quoted name or string literal.
+///
+/// # Arguments
+/// - `expr` The expression text.
+/// - `cursor` The cursor position, in characters.
+pub fn split_fuzzy_column_token(expr: &str, cursor: usize) -> Option<FuzzyColumnToken<'_>> {
+    let token = split_partial_token(expr, cursor);
+    if token.kind != PartialTokenKind::Function {
+        return None;
+    }
+
+    let head = &expr[..token.start];
+    if !head.ends_with(COLUMN_PICKER_TRIGGER) {
+        return None;
+    }
+
+    let start = head.chars().count() - 1;
+    let is_quoted = split_partial_token(expr, start).kind != PartialTokenKind::Function;
+    if is_quoted {
+        None
     } else {
-        COMPLETIONS.with(|x| JsValue::from_serde(x)).unwrap()
+        Some(FuzzyColumnToken {
+            text: token.text,
+            start,
+        })
+    }
+}
+
+/// Rank `columns` by how well they match the fuzzy `pattern`, dropping those
+/// which do not contain it as a (case-insensitive) subsequence.  Prefix
+/// matches rank first, then matches on word boundaries (e.g. `gp` for
+/// `Gross Profit` or `grossProfit`), then substring matches, then any other
+/// subsequence, with ties broken by the tightest and then shortest match.
+///
+/// # Arguments
+/// - `pattern` The fragment typed after the `COLUMN_PICKER_TRIGGER`.
+/// - `columns` The column names available to this expression.
+pub fn rank_fuzzy_columns<'a>(pattern: &str, columns: &'a [String]) -> Vec<&'a String> {
+    let mut ranked = columns
+        .iter()
+        .filter_map(|col| Some((fuzzy_score(col, pattern)?, col.chars().count(), col)))
+        .collect::<Vec<_>>();
+
+    ranked.sort_by_key(|(score, len, _)| (*score, *len));
+    ranked.into_iter().map(|(_, _, col)| col).collect()
+}
+
+/// Score `name` against a fuzzy `pattern` as `(tier, span)`, where lower is
+/// better, or `None` if `pattern` is not a subsequence of `name`.
+fn fuzzy_score(name: &str, pattern: &str) -> Option<(u8, usize)> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let lower = name.to_lowercase().chars().collect::<Vec<_>>();
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+    if pattern.is_empty() {
+        return Some((0, 0));
+    }
+
+    // Case-folding may change the length of `name`, in which case word
+    // boundaries cannot be aligned, so treat every position as one.
+    let is_boundary = |idx: usize| {
+        idx == 0
+            || chars.len() != lower.len()
+            || !chars[idx - 1].is_alphanumeric()
+            || (chars[idx - 1].is_lowercase() && chars[idx].is_uppercase())
+    };
+
+    let span = |positions: &[usize]| positions[positions.len() - 1] - positions[0];
+    if lower.starts_with(&pattern) {
+        return Some((0, pattern.len() - 1));
+    }
+
+    let boundaries = subsequence_positions(&lower, &pattern, &is_boundary);
+    if let Some(positions) = boundaries {
+        return Some((1, span(&positions)));
+    }
+
+    if let Some(idx) = lower.windows(pattern.len()).position(|x| x == pattern) {
+        return Some((2, idx));
+    }
+
+    let positions = subsequence_positions(&lower, &pattern, &|_| true)?;
+    Some((3, span(&positions)))
+}
+
+/// The greedy positions of `pattern` as a subsequence of `name`, matching
+/// only positions for which `accept` is true.
+fn subsequence_positions(
+    name: &[char],
+    pattern: &[char],
+    accept: &dyn Fn(usize) -> bool,
+) -> Option<Vec<usize>> {
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut idx = 0;
+    for c in pattern {
+        let found = (idx..name.len()).find(|x| name[*x] == *c && accept(*x))?;
+        positions.push(found);
+        idx = found + 1;
+    }
+
+    Some(positions)
+}
+
+/// Quote a column name for an expression, escaping any `"` or `\` it
+/// contains.
+pub fn quote_column_name(name: &str) -> String {
+    let escaped = name.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{}\"", escaped)
+}
+
+/// Generate completion candidates for the partial token at `cursor`, drawn
+/// from `columns` and the static ExprTK function list.  Candidates whose name
+/// starts with the token are ranked first (case-sensitive matches before
+/// case-insensitive ones), followed by any which merely contain it.
+///
+/// # Arguments
+/// - `expr` The expression text.
+/// - `cursor` The cursor position, in characters.
+/// - `columns` The column names available to this expression.
+pub fn get_expression_completions(
+    expr: &str,
+    cursor: usize,
+    columns: &[String],
+) -> Vec<CompletionItemSuggestion> {
+    let token = split_partial_token(expr, cursor);
+    let mut candidates = match token.kind {
+        PartialTokenKind::Literal => vec![],
+        PartialTokenKind::Column => columns
+            .iter()
+            .map(|col| (col.clone(), column_suggestion(col, true)))
+            .collect::<Vec<_>>(),
+        PartialTokenKind::Function => {
+            let mut candidates = columns
+                .iter()
+                .map(|col| (col.clone(), column_suggestion(col, false)))
+                .collect::<Vec<_>>();
+
+            COMPLETIONS.with(|x| {
+                candidates.extend(
+                    x.suggestions
+                        .iter()
+                        .map(|item| (item.label.clone(), item.clone())),
+                )
+            });
+
+            candidates
+        }
+    };
+
+    let lower = token.text.to_lowercase();
+    candidates.retain(|(name, _)| name.to_lowercase().contains(&lower));
+    candidates.sort_by_key(|(name, _)| {
+        if name.starts_with(token.text) {
+            0
+        } else if name.to_lowercase().starts_with(&lower) {
+            1
+        } else {
+            2
+        }
+    });
+
+    candidates.into_iter().map(|(_, x)| x).collect()
+}
+
+/// Create a suggestion for column `col`, which completes either the tail of an
+/// already-open quote or a new quoted name.  The name is inserted as plain
+/// text, as its escapes would otherwise be interpreted as snippet syntax.
+fn column_suggestion(col: &str, is_open_quote: bool) -> CompletionItemSuggestion {
+    let quoted = quote_column_name(col);
+    CompletionItemSuggestion {
+        kind: 14, //Constant
+        insert_text: if is_open_quote {
+            quoted[1..].to_owned()
+        } else {
+            quoted.clone()
+        },
+        label: quoted,
+        insert_text_rules: 0,
+        documentation: format!("The values of column \"{}\"", col),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(expr: &str, cursor: usize, columns: &[&str]) -> Vec<String> {
+        let columns = columns.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        get_expression_completions(expr, cursor, &columns)
+            .into_iter()
+            .map(|x| x.label)
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_split_identifier_mid_expression() {
+        let expr = "\"Sales\" + abs(\"Profit\")";
+        let token = split_partial_token(expr, 12);
+        assert_eq!(token.kind, PartialTokenKind::Function);
+        assert_eq!(token.text, "ab");
+        assert_eq!(token.start, 10);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_split_open_column_quote() {
+        let token = split_partial_token("\"Sales\" * \"Pro", 14);
+        assert_eq!(token.kind, PartialTokenKind::Column);
+        assert_eq!(token.text, "Pro");
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_split_closed_column_quote_is_not_column() {
+        let token = split_partial_token("\"Sales\"", 7);
+        assert_eq!(token.kind, PartialTokenKind::Function);
+        assert_eq!(token.text, "");
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_split_string_literal() {
+        let token = split_partial_token("concat(\"City\", ', ab", 20);
+        assert_eq!(token.kind, PartialTokenKind::Literal);
+        assert_eq!(token.text, ", ab");
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_completions_column_prefix_ranked_first() {
+        let results = labels("\"Sales\" * \"pro", 14, &[
+            "Sales",
+            "Gross Profit",
+            "profit",
+        ]);
+        assert_eq!(results, vec!["\"profit\"", "\"Gross Profit\""]);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_completions_function_mid_expression() {
+        let results = labels("\"Sales\" + sq(\"Profit\")", 12, &["Sales", "Profit"]);
+        assert_eq!(results, vec!["sqrt"]);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_completions_escape_column_names() {
+        let columns = vec!["a \"b\"".to_owned()];
+        let results = get_expression_completions("\"a", 2, &columns);
+        assert_eq!(results[0].label, "\"a \\\"b\\\"\"");
+        assert_eq!(results[0].insert_text, "a \\\"b\\\"\"");
+        let results = get_expression_completions("a", 1, &columns);
+        assert_eq!(results[0].insert_text, "\"a \\\"b\\\"\"");
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_completions_in_literal_are_empty() {
+        assert!(labels("'Sal", 4, &["Sales"]).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_split_fuzzy_column_token() {
+        let token = split_fuzzy_column_token("\"Sales\" * @gpr", 14);
+        assert_eq!(
+            token,
+            Some(FuzzyColumnToken {
+                text: "gpr",
+                start: 10
+            })
+        );
+
+        assert_eq!(split_fuzzy_column_token("'a@b", 4), None);
+        assert_eq!(split_fuzzy_column_token("\"a@b", 4), None);
+        assert_eq!(split_fuzzy_column_token("a + b", 5), None);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_rank_fuzzy_columns() {
+        let columns = [
+            "Ship Date",
+            "Gross Profit",
+            "grossProfit",
+            "Profit",
+            "Group",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>();
+
+        let ranked = rank_fuzzy_columns("gp", &columns);
+        assert_eq!(ranked, vec!["grossProfit", "Gross Profit", "Group"]);
+        let ranked = rank_fuzzy_columns("prof", &columns);
+        assert_eq!(ranked, vec!["Profit", "grossProfit", "Gross Profit"]);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_quote_column_name() {
+        assert_eq!(quote_column_name("Sales"), "\"Sales\"");
+        assert_eq!(quote_column_name("a \"b\""), "\"a \\\"b\\\"\"");
     }
 }
@@ -30,7 +30,7 @@ pub async fn init_language() -> Result<Editor, error::Error> {
     let provider = get_completions.into_closure();
     let items = crate::json!({
             "provideCompletionItems": provider.as_ref(),
-            "triggerCharacters": ["\""]
+            "triggerCharacters": ["\"", COLUMN_PICKER_TRIGGER.to_string()]
     });
 
     provider.forget();
@@ -17,6 +17,9 @@ use language::*;
 use serde_json::error;
 use wasm_bindgen::JsCast;
 
+pub use completions::{
+    get_expression_completions, split_partial_token, PartialToken, PartialTokenKind,
+};
 pub use init::init_theme;
 pub use language::COMPLETIONS;
 
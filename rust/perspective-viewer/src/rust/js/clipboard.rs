@@ -8,51 +8,180 @@
 
 use super::mimetype::*;
 use crate::js::clipboard_item::*;
+use crate::utils::*;
 use crate::*;
 
+use futures::channel::oneshot::*;
+use js_intern::*;
 use std::cell::RefCell;
 use std::future::Future;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
 
-/// Copy a `JsPerspectiveView` to the clipboard as a CSV.
+/// Why a clipboard write failed, which the rejected `Error` of `copy()` (and
+/// the other copy methods) carries as its `code` property, so hosts can fall
+/// back to e.g. a manual copy dialog.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClipboardError {
+    /// The page is not served over HTTPS (or `localhost`), so the browser
+    /// does not expose the async clipboard API at all.
+    InsecureContext,
+
+    /// The browser does not implement `navigator.clipboard` or
+    /// `ClipboardItem`.
+    Unavailable,
+
+    /// The user or browser denied clipboard access, e.g. as the copy did not
+    /// follow a user gesture.
+    PermissionDenied,
+
+    /// The write failed for another reason.
+    WriteFailed,
+}
+
+impl ClipboardError {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::InsecureContext => "insecure_context",
+            Self::Unavailable => "unavailable",
+            Self::PermissionDenied => "permission_denied",
+            Self::WriteFailed => "write_failed",
+        }
+    }
+
+    fn message(&self) -> &'static str {
+        match self {
+            Self::InsecureContext => "Clipboard requires a secure (HTTPS) context",
+            Self::Unavailable => "Clipboard API is not available in this browser",
+            Self::PermissionDenied => "Clipboard write permission denied",
+            Self::WriteFailed => "Clipboard write failed",
+        }
+    }
+
+    /// Whether the clipboard API is present, without checking permission.
+    fn check() -> Result<(), Self> {
+        let window = web_sys::window().unwrap();
+        if !window.is_secure_context() {
+            Err(Self::InsecureContext)
+        } else if window.navigator().clipboard().is_none()
+            || !js_sys::Reflect::has(&window, js_intern!("ClipboardItem")).unwrap_or_default()
+        {
+            Err(Self::Unavailable)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Classify the rejection of `Clipboard.write()`, which is a
+    /// `DOMException` named `NotAllowedError` when permission is denied.
+    fn from_write_error(err: &JsValue) -> Self {
+        let name = js_sys::Reflect::get(err, js_intern!("name"))
+            .ok()
+            .and_then(|x| x.as_string());
+
+        match name.as_deref() {
+            Some("NotAllowedError") | Some("SecurityError") => Self::PermissionDenied,
+            _ => Self::WriteFailed,
+        }
+    }
+}
+
+impl From<ClipboardError> for JsValue {
+    fn from(err: ClipboardError) -> Self {
+        let error = js_sys::Error::new(err.message());
+        js_sys::Reflect::set(&error, &"code".into(), &err.code().into()).unwrap();
+        error.into()
+    }
+}
+
+/// Whether a clipboard write can succeed:  the clipboard API is available and
+/// clipboard write permission is not known to be denied.  Browsers without
+/// the `"clipboard-write"` permission (e.g. Firefox) only gate on the former.
+pub async fn can_copy_to_clipboard() -> bool {
+    if ClipboardError::check().is_err() {
+        return false;
+    }
+
+    let state = async {
+        let navigator = web_sys::window().unwrap().navigator();
+        let permissions = js_sys::Reflect::get(&navigator, js_intern!("permissions"))?;
+        let query = js_sys::Reflect::get(&permissions, js_intern!("query"))?
+            .dyn_into::<js_sys::Function>()?;
+
+        let args = json!({"name": "clipboard-write"});
+        let promise = query
+            .call1(&permissions, &args)?
+            .dyn_into::<js_sys::Promise>()?;
+        let status = JsFuture::from(promise).await?;
+        js_sys::Reflect::get(&status, js_intern!("state"))
+    };
+
+    let state = state.await.ok().and_then(|x| x.as_string());
+    state.as_deref() != Some("denied")
+}
+
+/// Copy a `JsPerspectiveView` to the clipboard as a CSV, rejecting with a
+/// `ClipboardError` if the clipboard is unavailable or the write fails.
 pub fn copy_to_clipboard(
     view: impl Future<Output = Result<web_sys::Blob, JsValue>>,
     mimetype: MimeType,
 ) -> impl Future<Output = Result<(), JsValue>> {
     let js_ref: Rc<RefCell<Option<web_sys::Blob>>> = Rc::new(RefCell::new(None));
-    poll(0, mimetype, js_ref.clone()).unwrap();
+    let (sender, receiver) = channel::<Result<js_sys::Promise, JsValue>>();
+    let available = ClipboardError::check();
+    if available.is_ok() {
+        poll(0, mimetype, js_ref.clone(), sender).unwrap();
+    }
+
     async move {
+        available?;
         let js_val = view.await?;
         *js_ref.borrow_mut() = Some(js_val);
+        let promise = receiver.await.into_jserror()??;
+        JsFuture::from(promise)
+            .await
+            .map_err(|x| ClipboardError::from_write_error(&x))?;
+
         Ok(())
     }
 }
 
 /// This method must be called from an event handler, subject to the browser's
 /// restrictions on clipboard access.  See
-/// [ws](https://www.w3.org/TR/clipboard-apis/#allow-read-clipboard).
+/// [ws](https://www.w3.org/TR/clipboard-apis/#allow-read-clipboard).  Sends
+/// the `Clipboard.write()` promise once the data is ready, or stops polling
+/// if the caller has given up (e.g. as generating the data failed).
 fn poll(
     count: u32,
     mimetype: MimeType,
     js_ref: Rc<RefCell<Option<web_sys::Blob>>>,
+    sender: Sender<Result<js_sys::Promise, JsValue>>,
 ) -> Result<(), JsValue> {
+    if sender.is_canceled() {
+        return Ok(());
+    }
+
     if let Some(js_val) = js_ref.borrow().as_ref() {
-        let options = js_sys::Object::new();
-        js_sys::Reflect::set(&options, &mimetype.into(), js_val)?;
-        let item = ClipboardItem::new(&options);
-        let items = [item].iter().collect::<js_sys::Array>();
-        let _promise = web_sys::window()
-            .unwrap()
-            .navigator()
-            .clipboard()
-            .into_jserror()?
-            .write(&items.into());
+        let write = || -> Result<js_sys::Promise, JsValue> {
+            let options = js_sys::Object::new();
+            js_sys::Reflect::set(&options, &mimetype.into(), js_val)?;
+            let item = ClipboardItem::new(&options);
+            let items = [item].iter().collect::<js_sys::Array>();
+            Ok(web_sys::window()
+                .unwrap()
+                .navigator()
+                .clipboard()
+                .into_jserror()?
+                .write(&items.into()))
+        };
+
+        let _ = sender.send(write());
     } else {
         clone!(js_ref);
         let f: js_sys::Function =
-            Closure::once(Box::new(move || poll(count + 1, mimetype, js_ref)))
+            Closure::once(Box::new(move || poll(count + 1, mimetype, js_ref, sender)))
                 .into_js_value()
                 .unchecked_into();
 
@@ -16,6 +16,7 @@ mod mimetype;
 pub mod monaco;
 pub mod perspective;
 pub mod plugin;
+mod preset_storage;
 pub mod resize_observer;
 mod testing;
 
@@ -26,6 +27,7 @@ pub use self::clipboard::*;
 pub use self::mimetype::*;
 pub use self::perspective::*;
 pub use self::plugin::*;
+pub use self::preset_storage::*;
 pub use self::resize_observer::*;
 // pub use self::testing::enable_weak_link_test;
 
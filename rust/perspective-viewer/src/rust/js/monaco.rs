@@ -125,6 +125,12 @@ extern "C" {
     #[wasm_bindgen(method, js_name = "setPosition")]
     pub fn set_position(this: &JsMonacoEditor, value: &JsValue);
 
+    #[wasm_bindgen(method, js_name = "getSelection")]
+    pub fn get_selection(this: &JsMonacoEditor) -> JsValue;
+
+    #[wasm_bindgen(method, js_name = "executeEdits")]
+    pub fn execute_edits(this: &JsMonacoEditor, source: &str, edits: &js_sys::Array) -> bool;
+
     #[wasm_bindgen(method, js_name = "addCommand")]
     pub fn add_command(this: &JsMonacoEditor, key_code: u32, value: &js_sys::Function);
 
@@ -153,6 +159,9 @@ extern "C" {
         position: &JsMonacoPosition,
     ) -> JsValue;
 
+    #[wasm_bindgen(method, js_name = "getLineContent")]
+    pub fn get_line_content(this: &JsMonacoModel, line_number: u32) -> String;
+
     #[wasm_bindgen(method, js_name = "getLineTokens")]
     pub fn get_line_tokens(this: &JsMonacoModel, line_number: u32) -> JsMonacoTokens;
 
@@ -206,7 +215,7 @@ pub struct RegisterCompletionItemSuggestions {
     pub suggestions: Vec<CompletionItemSuggestion>,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CompletionItemSuggestion {
     pub label: String,
@@ -216,6 +225,61 @@ pub struct CompletionItemSuggestion {
     pub documentation: String,
 }
 
+/// The result of the fuzzy column picker, which is `incomplete` so `monaco`
+/// asks for a new ranking on every keystroke rather than re-filtering this one.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnPickerSuggestions {
+    pub suggestions: Vec<ColumnPickerItem>,
+    pub incomplete: bool,
+}
+
+/// A fuzzy column picker suggestion, which replaces `range` (the `@` and the
+/// fragment typed after it) with a quoted column name.  `filter_text` and
+/// `sort_text` defeat `monaco`'s own filtering and sorting.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColumnPickerItem {
+    pub label: String,
+    pub kind: u32,
+    pub insert_text: String,
+    pub filter_text: String,
+    pub sort_text: String,
+    pub range: CompletionRange,
+    pub documentation: String,
+}
+
+/// Completion suggestions ranked by `get_expression_completions()`, which are
+/// `incomplete` so `monaco` asks for a new ranking on every keystroke.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedCompletionSuggestions {
+    pub suggestions: Vec<RankedCompletionItem>,
+    pub incomplete: bool,
+}
+
+/// A ranked suggestion, which replaces `range` (the partial token before the
+/// cursor).  As for `ColumnPickerItem`, `filter_text` and `sort_text` defeat
+/// `monaco`'s own filtering and sorting.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedCompletionItem {
+    #[serde(flatten)]
+    pub item: CompletionItemSuggestion,
+    pub filter_text: String,
+    pub sort_text: String,
+    pub range: CompletionRange,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletionRange {
+    pub start_line_number: u32,
+    pub start_column: u32,
+    pub end_line_number: u32,
+    pub end_column: u32,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PositionArgs {
@@ -103,6 +103,12 @@ extern "C" {
         this: &JsPerspectiveView,
     ) -> Result<JsValue, JsValue>;
 
+    #[wasm_bindgen(method, catch, js_name = to_columns)]
+    pub async fn _to_columns_window(
+        this: &JsPerspectiveView,
+        options: js_sys::Object,
+    ) -> Result<JsValue, JsValue>;
+
     #[wasm_bindgen(method, catch, js_name = num_rows)]
     pub async fn _num_rows(this: &JsPerspectiveView) -> Result<JsValue, JsValue>;
 
@@ -162,6 +168,7 @@ impl JsPerspectiveView {
     async_typed!(_to_csv, to_csv(&self, options: js_sys::Object) -> js_sys::JsString);
     async_typed!(_to_arrow, to_arrow(&self) -> js_sys::ArrayBuffer);
     async_typed!(_to_columns, to_columns(&self) -> js_sys::Object);
+    async_typed!(_to_columns_window, to_columns_window(&self, options: js_sys::Object) -> js_sys::Object);
     async_typed!(_num_rows, num_rows(&self) -> f64);
     async_typed!(_num_columns, num_columns(&self) -> f64);
     async_typed!(_schema, schema(&self) -> JsPerspectiveViewSchema);
@@ -169,6 +176,68 @@ impl JsPerspectiveView {
     // async_typed!(_get_config, get_config(&self) -> JsPerspectiveViewConfig);
 }
 
+// Lazily import `@finos/perspective` so the engine is only fetched by users
+// who `load()` serialized data directly.
+#[cfg_attr(
+    not(test),
+    wasm_bindgen(inline_js = "
+    export async function shared_worker() {
+        const perspective = await import(
+            /* webpackChunkName: \"perspective\" */
+            /* webpackMode: \"lazy\" */
+            \"@finos/perspective\"
+        );
+
+        return perspective.default.shared_worker();
+    }
+")
+)]
+#[cfg_attr(
+    test,
+    wasm_bindgen(inline_js = "
+    export async function shared_worker() {
+        await import('/dist/pkg/perspective.js');
+        return window.perspective.shared_worker();
+    }
+")
+)]
+extern "C" {
+    #[wasm_bindgen(js_name = "shared_worker")]
+    async fn _shared_worker() -> JsValue;
+}
+
+/// Is `data` an Arrow buffer which `load()` should construct a `Table` from,
+/// rather than a `Table` itself?
+pub fn is_arrow_buffer(data: &JsValue) -> bool {
+    data.is_instance_of::<js_sys::ArrayBuffer>() || data.is_instance_of::<js_sys::Uint8Array>()
+}
+
+/// Create a `Table` from an Arrow `ArrayBuffer` or `Uint8Array` on the
+/// shared Perspective worker.
+pub async fn arrow_to_table(data: &JsValue) -> Result<JsPerspectiveTable, JsValue> {
+    let buffer = match data.dyn_ref::<js_sys::Uint8Array>() {
+        Some(array) => array.slice(0, array.length()).buffer(),
+        None => data.clone().dyn_into::<js_sys::ArrayBuffer>()?,
+    };
+
+    if buffer.byte_length() == 0 {
+        return Err("Failed to load Arrow: buffer is empty".into());
+    }
+
+    let worker = _shared_worker()
+        .await
+        .unchecked_into::<JsPerspectiveWorker>();
+    worker.table(buffer.unchecked_into()).await.map_err(|err| {
+        let msg = err
+            .dyn_ref::<js_sys::Error>()
+            .map(|x| String::from(x.message()))
+            .or_else(|| err.as_string())
+            .unwrap_or_else(|| "unknown error".to_owned());
+
+        format!("Failed to load Arrow: {}", msg).into()
+    })
+}
+
 #[derive(Deserialize)]
 #[serde()]
 pub struct PerspectiveValidationError {
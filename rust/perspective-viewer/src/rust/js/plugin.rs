@@ -6,10 +6,14 @@
 // of the Apache License 2.0.  The full license can be found in the LICENSE
 // file.
 
+use crate::config::SingleAggregate;
 use crate::utils::*;
 
+use js_intern::*;
 use serde::*;
+use std::str::FromStr;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
 use super::perspective::JsPerspectiveView;
 
@@ -39,6 +43,15 @@ extern "C" {
     #[wasm_bindgen(method, setter)]
     pub fn set_render_warning(this: &JsPerspectiveViewerPlugin, val: bool);
 
+    #[wasm_bindgen(method, setter)]
+    pub fn set_reduced_motion(this: &JsPerspectiveViewerPlugin, val: bool);
+
+    #[wasm_bindgen(method, setter)]
+    pub fn set_print_mode(this: &JsPerspectiveViewerPlugin, val: bool);
+
+    #[wasm_bindgen(method, setter)]
+    pub fn set_redraw_fade(this: &JsPerspectiveViewerPlugin, val: bool);
+
     #[wasm_bindgen(method, getter)]
     pub fn select_mode(this: &JsPerspectiveViewerPlugin) -> JsValue;
 
@@ -48,6 +61,12 @@ extern "C" {
     #[wasm_bindgen(method, getter)]
     pub fn config_column_names(this: &JsPerspectiveViewerPlugin) -> Option<js_sys::Array>;
 
+    #[wasm_bindgen(method, getter)]
+    pub fn view_types(this: &JsPerspectiveViewerPlugin) -> Option<js_sys::Array>;
+
+    #[wasm_bindgen(method, getter)]
+    pub fn capabilities(this: &JsPerspectiveViewerPlugin) -> JsValue;
+
     #[wasm_bindgen(method)]
     pub fn save(this: &JsPerspectiveViewerPlugin) -> JsValue;
 
@@ -86,9 +105,54 @@ extern "C" {
 
     #[wasm_bindgen(method, catch)]
     pub async fn resize(this: &JsPerspectiveViewerPlugin) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch)]
+    pub async fn measure(
+        this: &JsPerspectiveViewerPlugin,
+        view: &JsPerspectiveView
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = to_image)]
+    pub async fn _to_image(
+        this: &JsPerspectiveViewerPlugin,
+        options: &JsValue
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = scroll_to)]
+    pub async fn _scroll_to(
+        this: &JsPerspectiveViewerPlugin,
+        target: &JsValue
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = set_highlight)]
+    pub fn _set_highlight(
+        this: &JsPerspectiveViewerPlugin,
+        target: &JsValue
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = get_selection)]
+    pub fn _get_selection(this: &JsPerspectiveViewerPlugin) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = set_selection)]
+    pub fn _set_selection(
+        this: &JsPerspectiveViewerPlugin,
+        selection: &JsValue
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = set_pinned_columns)]
+    pub fn _set_pinned_columns(
+        this: &JsPerspectiveViewerPlugin,
+        columns: &js_sys::Array
+    ) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = set_row_sizing)]
+    pub fn _set_row_sizing(
+        this: &JsPerspectiveViewerPlugin,
+        sizing: &JsValue
+    ) -> Result<JsValue, JsValue>;
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ColumnSelectMode {
     Toggle,
@@ -121,7 +185,324 @@ impl ViewConfigRequirements {
     }
 }
 
+/// Plugin metadata for host UIs, e.g. to build a plugin picker.  Optional
+/// fields are omitted for plugins which do not provide them.
+#[derive(Clone, Debug, Serialize)]
+pub struct PluginInfo {
+    pub name: String,
+    pub category: String,
+    pub select_mode: ColumnSelectMode,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_columns: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_columns: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cells: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub config_column_names: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view_types: Option<Vec<String>>,
+}
+
+/// The config controls a plugin supports, for host UIs to enable or disable
+/// their own controls.  Plugins declare these via an optional `capabilities`
+/// property, where any omitted field defaults to supported (and `aggregates`
+/// to every aggregate);  the remaining fields are read from the plugin's
+/// other properties and are `null` when not provided.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PluginCapabilities {
+    pub aggregates: Vec<String>,
+    pub group_by: bool,
+    pub split_by: bool,
+    pub sort: bool,
+    pub filter: bool,
+
+    #[serde(skip_deserializing)]
+    pub min_columns: Option<usize>,
+
+    #[serde(skip_deserializing)]
+    pub max_columns: Option<usize>,
+
+    #[serde(skip_deserializing)]
+    pub view_types: Option<Vec<String>>,
+}
+
+impl Default for PluginCapabilities {
+    fn default() -> Self {
+        Self {
+            aggregates: SingleAggregate::all().map(|x| x.to_string()).collect(),
+            group_by: true,
+            split_by: true,
+            sort: true,
+            filter: true,
+            min_columns: None,
+            max_columns: None,
+            view_types: None,
+        }
+    }
+}
+
+/// A plugin's preferred dimensions in pixels, as reported by
+/// `JsPerspectiveViewerPlugin::get_dimensions()`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct PluginDimensions {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Row and header heights in pixels, passed to plugins which implement the
+/// optional `Plugin::set_row_sizing` method.  `None` is the plugin's default.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct RowSizing {
+    pub row_height: Option<f64>,
+    pub header_height: Option<f64>,
+}
+
+/// The image formats a plugin may render itself to via `Plugin::to_image`.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ImageFormat {
+    #[serde(rename = "png")]
+    Png,
+
+    #[serde(rename = "svg")]
+    Svg,
+}
+
+impl FromStr for ImageFormat {
+    type Err = JsValue;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "png" => Ok(Self::Png),
+            "svg" => Ok(Self::Svg),
+            x => Err(format!("Unknown image format \"{}\"", x).into()),
+        }
+    }
+}
+
+/// The argument to `Plugin::to_image`;  omitted dimensions default to the
+/// plugin's current size.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct ImageOptions {
+    pub format: ImageFormat,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+}
+
+/// The argument to `Plugin::scroll_to` and `Plugin::set_highlight`.  `row`
+/// is an index into the plugin's current `View`, already clamped to its rows,
+/// and `column` a column name or (for `split_by` views) a column path.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ScrollTarget {
+    pub row: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+}
+
+/// A plugin's current selection, as reported by
+/// `JsPerspectiveViewerPlugin::get_selection()`.  `rows` are indices into the
+/// plugin's current `View`.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PluginSelection {
+    pub rows: Vec<usize>,
+
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<String>>,
+}
+
 impl JsPerspectiveViewerPlugin {
+    /// Whether this plugin implements the optional `Plugin::get_selection`
+    /// method.
+    pub fn supports_selection(&self) -> bool {
+        js_sys::Reflect::has(self, js_intern!("get_selection")).unwrap_or_default()
+    }
+
+    /// Query this plugin's current selection via the optional
+    /// `Plugin::get_selection` method, returning `None` if the plugin does
+    /// not support selection or nothing is selected.
+    pub fn get_selection(&self) -> Result<Option<PluginSelection>, JsValue> {
+        if !self.supports_selection() {
+            return Ok(None);
+        }
+
+        let selection = self._get_selection()?;
+        if selection.is_null() || selection.is_undefined() {
+            Ok(None)
+        } else {
+            let selection: PluginSelection = selection.into_serde().into_jserror()?;
+            Ok(Some(selection).filter(|x| !x.rows.is_empty()))
+        }
+    }
+
+    /// Replace this plugin's current selection via the optional
+    /// `Plugin::set_selection` method.
+    ///
+    /// # Errors
+    ///
+    /// If the plugin does not implement `Plugin::set_selection`.
+    pub fn set_selection(&self, selection: &PluginSelection) -> Result<(), JsValue> {
+        if !js_sys::Reflect::has(self, js_intern!("set_selection"))? {
+            return Err(format!("Plugin `{}` does not support selection", self.name()).into());
+        }
+
+        self._set_selection(&JsValue::from_serde(selection).into_jserror()?)?;
+        Ok(())
+    }
+
+    /// Whether this plugin implements the optional
+    /// `Plugin::set_pinned_columns` method.
+    pub fn supports_pinned_columns(&self) -> bool {
+        js_sys::Reflect::has(self, js_intern!("set_pinned_columns")).unwrap_or_default()
+    }
+
+    /// Tell this plugin which columns to freeze during horizontal scroll, via
+    /// the optional `Plugin::set_pinned_columns` method.  Does nothing if the
+    /// plugin does not implement it.
+    pub fn set_pinned_columns(&self, columns: &[String]) -> Result<(), JsValue> {
+        if self.supports_pinned_columns() {
+            let columns = columns.iter().map(JsValue::from).collect::<js_sys::Array>();
+            self._set_pinned_columns(&columns)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether this plugin implements the optional `Plugin::set_row_sizing`
+    /// method.
+    pub fn supports_row_sizing(&self) -> bool {
+        js_sys::Reflect::has(self, js_intern!("set_row_sizing")).unwrap_or_default()
+    }
+
+    /// Pass row and header height hints to this plugin via the optional
+    /// `Plugin::set_row_sizing` method.  Does nothing if the plugin does not
+    /// implement it.
+    pub fn set_row_sizing(&self, sizing: &RowSizing) -> Result<(), JsValue> {
+        if self.supports_row_sizing() {
+            self._set_row_sizing(&JsValue::from_serde(sizing).into_jserror()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether this plugin implements the optional `Plugin::to_image` method.
+    pub fn supports_image(&self) -> bool {
+        js_sys::Reflect::has(self, js_intern!("to_image")).unwrap_or_default()
+    }
+
+    /// Render this plugin's current state to an image `Blob` via the optional
+    /// `Plugin::to_image` method.
+    ///
+    /// # Errors
+    ///
+    /// If the plugin does not implement `Plugin::to_image`, e.g. the datagrid.
+    pub async fn to_image(&self, options: &ImageOptions) -> Result<web_sys::Blob, JsValue> {
+        if !self.supports_image() {
+            return Err(format!("Plugin `{}` does not support image export", self.name()).into());
+        }
+
+        let options = JsValue::from_serde(options).into_jserror()?;
+        let blob = self._to_image(&options).await?;
+        blob.dyn_into::<web_sys::Blob>()
+            .map_err(|_| "`Plugin::to_image` did not return a `Blob`".into())
+    }
+
+    /// Whether this plugin implements the optional `Plugin::scroll_to`
+    /// method.
+    pub fn supports_scroll_to(&self) -> bool {
+        js_sys::Reflect::has(self, js_intern!("scroll_to")).unwrap_or_default()
+    }
+
+    /// Scroll a row (and optionally column) into view via the optional
+    /// `Plugin::scroll_to` method.
+    ///
+    /// # Errors
+    ///
+    /// If the plugin does not implement `Plugin::scroll_to`.
+    pub async fn scroll_to(&self, target: &ScrollTarget) -> Result<(), JsValue> {
+        if !self.supports_scroll_to() {
+            return Err(format!("Plugin `{}` does not support scrolling", self.name()).into());
+        }
+
+        self._scroll_to(&JsValue::from_serde(target).into_jserror()?)
+            .await?;
+        Ok(())
+    }
+
+    /// Highlight a single cell (or with `None`, clear the highlight), e.g. the
+    /// current match of `search()`, via the optional `Plugin::set_highlight`
+    /// method.  Does nothing if the plugin does not implement it.
+    pub fn set_highlight(&self, target: Option<&ScrollTarget>) -> Result<(), JsValue> {
+        if js_sys::Reflect::has(self, js_intern!("set_highlight"))? {
+            self._set_highlight(&JsValue::from_serde(&target).into_jserror()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Query this plugin's preferred dimensions for `view` without drawing,
+    /// via the optional `Plugin::measure` method.  Plugins which do not
+    /// implement it report the scroll dimensions of their current content.
+    pub async fn get_dimensions(
+        &self,
+        view: &JsPerspectiveView,
+    ) -> Result<PluginDimensions, JsValue> {
+        if js_sys::Reflect::has(self, js_intern!("measure"))? {
+            self.measure(view).await?.into_serde().into_jserror()
+        } else {
+            let elem = self.unchecked_ref::<web_sys::HtmlElement>();
+            Ok(PluginDimensions {
+                width: elem.scroll_width() as f64,
+                height: elem.scroll_height() as f64,
+            })
+        }
+    }
+
+    pub fn get_info(&self) -> PluginInfo {
+        PluginInfo {
+            name: self.name(),
+            category: self.category().unwrap_or_else(|| "Custom".to_owned()),
+            select_mode: self.select_mode().into_serde().unwrap_or_default(),
+            min_columns: self.min_config_columns(),
+            max_columns: self.max_columns(),
+            max_cells: self.max_cells(),
+            config_column_names: self.config_column_names().and_then(|x| x.into_serde().ok()),
+            view_types: self.view_types().and_then(|x| x.into_serde().ok()),
+        }
+    }
+
+    /// This plugin's `PluginCapabilities`.  An invalid `capabilities`
+    /// property is ignored with a warning, as if it were omitted.
+    pub fn get_capabilities(&self) -> PluginCapabilities {
+        let capabilities = self.capabilities();
+        let capabilities = if capabilities.is_undefined() || capabilities.is_null() {
+            PluginCapabilities::default()
+        } else {
+            capabilities.into_serde().unwrap_or_else(|_| {
+                let msg = format!("Plugin `{}` has invalid `capabilities`", self.name());
+                web_sys::console::warn_1(&msg.into());
+                PluginCapabilities::default()
+            })
+        };
+
+        PluginCapabilities {
+            min_columns: self.min_config_columns(),
+            max_columns: self.max_columns(),
+            view_types: self.view_types().and_then(|x| x.into_serde().ok()),
+            ..capabilities
+        }
+    }
+
     pub fn get_requirements(&self) -> Result<ViewConfigRequirements, JsValue> {
         Ok(ViewConfigRequirements {
             min: self.min_config_columns(),
@@ -0,0 +1,128 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::utils::*;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+
+/// Perspective FFI
+#[wasm_bindgen]
+#[rustfmt::skip]
+extern "C" {
+    /// A user-provided backend for named presets, e.g. `localStorage` or a
+    /// server.  Each method may return a value or a `Promise` of one.
+    #[derive(Clone)]
+    pub type JsPresetStorage;
+
+    #[wasm_bindgen(method, catch, js_name = get)]
+    fn _get(this: &JsPresetStorage, name: &str) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = set)]
+    fn _set(this: &JsPresetStorage, name: &str, config: &JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = list)]
+    fn _list(this: &JsPresetStorage) -> Result<JsValue, JsValue>;
+}
+
+/// Await `val` if it is a `Promise`, or return it as-is otherwise.
+async fn resolve(val: JsValue) -> Result<JsValue, JsValue> {
+    JsFuture::from(js_sys::Promise::resolve(&val)).await
+}
+
+impl JsPresetStorage {
+    /// Whether `storage` implements the `get()`, `set()` and `list()` methods
+    /// of the preset storage interface.
+    pub fn is_preset_storage(storage: &JsValue) -> bool {
+        ["get", "set", "list"].iter().all(|name| {
+            js_sys::Reflect::get(storage, &JsValue::from(*name))
+                .map(|x| x.is_function())
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// Named `ViewerConfig` presets for a `<perspective-viewer>`, stored in memory
+/// unless a `JsPresetStorage` backend has been set.
+#[derive(Clone, Default)]
+pub struct PresetStorage(Rc<PresetStorageData>);
+
+#[derive(Default)]
+struct PresetStorageData {
+    custom: RefCell<Option<JsPresetStorage>>,
+    memory: RefCell<BTreeMap<String, JsValue>>,
+}
+
+impl PresetStorage {
+    /// Replace the storage backend, or revert to in-memory storage with
+    /// `None`.  Presets are not copied between backends.
+    pub fn set_storage(&self, storage: Option<JsPresetStorage>) {
+        *self.0.custom.borrow_mut() = storage;
+    }
+
+    /// Get the config stored under `name`, or `None` if there is no such
+    /// preset.
+    pub async fn get(&self, name: &str) -> Result<Option<JsValue>, JsValue> {
+        let custom = self.0.custom.borrow().clone();
+        let config = match custom {
+            Some(storage) => resolve(storage._get(name)?).await?,
+            None => self
+                .0
+                .memory
+                .borrow()
+                .get(name)
+                .cloned()
+                .unwrap_or(JsValue::UNDEFINED),
+        };
+
+        if config.is_undefined() || config.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(config))
+        }
+    }
+
+    /// Store `config` under `name`, replacing any existing preset.
+    pub async fn set(&self, name: &str, config: JsValue) -> Result<(), JsValue> {
+        let custom = self.0.custom.borrow().clone();
+        match custom {
+            Some(storage) => {
+                resolve(storage._set(name, &config)?).await?;
+            }
+            None => {
+                self.0.memory.borrow_mut().insert(name.to_owned(), config);
+            }
+        };
+
+        Ok(())
+    }
+
+    /// The names of all stored presets, sorted.
+    pub async fn list(&self) -> Result<Vec<String>, JsValue> {
+        let custom = self.0.custom.borrow().clone();
+        match custom {
+            Some(storage) => {
+                let names = resolve(storage._list()?).await?;
+                let mut names = names
+                    .dyn_into::<js_sys::Array>()?
+                    .iter()
+                    .map(|x| x.as_string())
+                    .collect::<Option<Vec<_>>>()
+                    .into_jserror()?;
+
+                names.sort();
+                Ok(names)
+            }
+            None => Ok(self.0.memory.borrow().keys().cloned().collect()),
+        }
+    }
+}
@@ -7,3 +7,5 @@
 // file.
 
 mod perspective;
+mod plugin;
+mod preset_storage;
@@ -6,6 +6,7 @@
 // of the Apache License 2.0.  The full license can be found in the LICENSE
 // file.
 
+use super::super::perspective::*;
 use super::super::testing::*;
 use crate::*;
 
@@ -60,6 +61,25 @@ pub async fn test_view_num_rows() {
     assert!(num_rows - 3_f64 < 0.01);
 }
 
+#[wasm_bindgen_test]
+pub async fn test_arrow_to_table_round_trip() {
+    let table = get_mock_table().await;
+    let view = table.view(json!({}).unchecked_ref()).await.unwrap();
+    let arrow: JsValue = view.to_arrow().await.unwrap().into();
+    assert!(is_arrow_buffer(&arrow));
+    let table = arrow_to_table(&arrow).await.unwrap();
+    let size = table.size().await.unwrap();
+    assert!(size - 3_f64 < 0.01);
+}
+
+#[wasm_bindgen_test]
+pub async fn test_arrow_to_table_invalid() {
+    let bytes: JsValue = js_sys::Uint8Array::from([1_u8, 2, 3, 4].as_slice()).into();
+    assert!(is_arrow_buffer(&bytes));
+    let err = arrow_to_table(&bytes).await.err().unwrap();
+    assert!(err.as_string().unwrap().starts_with("Failed to load Arrow"));
+}
+
 // #[wasm_bindgen_test]
 // pub async fn test_view_get_config() {
 //     let table = get_mock_table().await;
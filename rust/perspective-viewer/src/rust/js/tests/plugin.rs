@@ -0,0 +1,46 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use super::super::plugin::*;
+use crate::*;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+pub fn test_capabilities_defaults() {
+    let plugin: JsPerspectiveViewerPlugin = json!({"name": "Test"}).unchecked_into();
+    let capabilities = plugin.get_capabilities();
+    assert!(capabilities.group_by && capabilities.split_by);
+    assert!(capabilities.sort && capabilities.filter);
+    assert!(capabilities.aggregates.contains(&"sum".to_owned()));
+    assert!(capabilities.aggregates.contains(&"join".to_owned()));
+    assert_eq!(capabilities.max_columns, None);
+}
+
+#[wasm_bindgen_test]
+pub fn test_capabilities_partial() {
+    let plugin: JsPerspectiveViewerPlugin = json!({
+        "name": "Test",
+        "max_columns": 2,
+        "capabilities": {
+            "split_by": false,
+            "aggregates": ["sum"]
+        }
+    })
+    .unchecked_into();
+
+    let capabilities = plugin.get_capabilities();
+    assert!(capabilities.group_by);
+    assert!(!capabilities.split_by);
+    assert_eq!(capabilities.aggregates, vec!["sum".to_owned()]);
+    assert_eq!(capabilities.max_columns, Some(2));
+    assert_eq!(capabilities.min_columns, None);
+}
@@ -0,0 +1,40 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use super::super::preset_storage::*;
+use crate::*;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+pub async fn test_memory_presets() {
+    let presets = PresetStorage::default();
+    presets.set("b", json!({"plugin": "Y Bar"})).await.unwrap();
+    presets
+        .set("a", json!({"plugin": "Datagrid"}))
+        .await
+        .unwrap();
+    assert_eq!(presets.list().await.unwrap(), vec!["a", "b"]);
+    assert!(presets.get("a").await.unwrap().is_some());
+    assert!(presets.get("c").await.unwrap().is_none());
+}
+
+#[wasm_bindgen_test]
+pub fn test_is_preset_storage() {
+    let storage = js_sys::Object::new();
+    assert!(!JsPresetStorage::is_preset_storage(&storage));
+    for name in ["get", "set", "list"] {
+        let func = js_sys::Function::new_no_args("");
+        js_sys::Reflect::set(&storage, &JsValue::from(name), &func).unwrap();
+    }
+
+    assert!(JsPresetStorage::is_preset_storage(&storage));
+}
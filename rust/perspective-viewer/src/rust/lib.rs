@@ -17,6 +17,7 @@
 
 mod components;
 mod config;
+mod config_history;
 pub mod custom_elements;
 mod custom_events;
 mod dragdrop;
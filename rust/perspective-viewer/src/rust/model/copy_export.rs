@@ -81,6 +81,33 @@ pub trait CopyExportModel: HasSession + HasRenderer + HasTheme + GetViewerConfig
         })
     }
 
+    /// Create a blob of only the rows currently selected in the active plugin,
+    /// via the optional `Plugin::get_selection` method.
+    ///
+    /// # Errors
+    ///
+    /// If the plugin does not support selection or has nothing selected.
+    fn selection_as_jsvalue(
+        &self,
+    ) -> Pin<Box<dyn Future<Output = Result<web_sys::Blob, JsValue>>>> {
+        let renderer = self.renderer().clone();
+        let session = self.session().clone();
+        Box::pin(async move {
+            let plugin = renderer.get_active_plugin()?;
+            if !plugin.supports_selection() {
+                return Err(
+                    format!("Plugin `{}` does not support selection", plugin.name()).into(),
+                );
+            }
+
+            let selection = plugin.get_selection()?.ok_or("Plugin has no selection")?;
+            session
+                .csv_rows_as_jsvalue(&selection.rows)
+                .await?
+                .as_blob()
+        })
+    }
+
     /// Generate a result `Blob` for all types of `ExportMethod`.
     fn export_method_to_jsvalue(
         &self,
@@ -95,6 +122,18 @@ pub trait CopyExportModel: HasSession + HasRenderer + HasTheme + GetViewerConfig
                 let session = self.session().clone();
                 Box::pin(async move { session.csv_as_jsvalue(true).await?.as_blob() })
             }
+            ExportMethod::CsvNoHeader => {
+                let session = self.session().clone();
+                Box::pin(async move { session.csv_body_as_jsvalue(false).await?.as_blob() })
+            }
+            ExportMethod::CsvAllNoHeader => {
+                let session = self.session().clone();
+                Box::pin(async move { session.csv_body_as_jsvalue(true).await?.as_blob() })
+            }
+            ExportMethod::Selection => {
+                let selection_task = self.selection_as_jsvalue();
+                Box::pin(async move { selection_task.await })
+            }
             ExportMethod::Json => {
                 let session = self.session().clone();
                 Box::pin(async move { session.json_as_jsvalue(false).await?.as_blob() })
@@ -115,11 +154,15 @@ pub trait CopyExportModel: HasSession + HasRenderer + HasTheme + GetViewerConfig
                 let html_task = self.html_as_jsvalue();
                 Box::pin(async move { html_task.await })
             }
+            ExportMethod::Markdown => {
+                let session = self.session().clone();
+                Box::pin(async move { session.markdown_as_jsvalue(false).await?.as_blob() })
+            }
             ExportMethod::Png => {
                 let png_task = self.png_as_jsvalue();
                 Box::pin(async move { png_task.await })
             }
-            ExportMethod::JsonConfig => {
+            ExportMethod::JsonConfig | ExportMethod::CopyConfig => {
                 let config_task = self.get_viewer_config();
                 Box::pin(async move {
                     config_task
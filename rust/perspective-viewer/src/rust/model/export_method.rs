@@ -10,33 +10,41 @@ use crate::js::*;
 use crate::*;
 
 use std::rc::Rc;
+use wasm_bindgen::prelude::*;
 use yew::prelude::*;
 
 #[derive(Clone, Copy, Eq, PartialEq)]
 pub enum ExportMethod {
     Csv,
     CsvAll,
+    CsvNoHeader,
+    CsvAllNoHeader,
+    Selection,
     Json,
     JsonAll,
     Html,
+    Markdown,
     Png,
     Arrow,
     ArrowAll,
     JsonConfig,
+    CopyConfig,
 }
 
 impl ExportMethod {
     pub const fn as_filename(&self) -> &'static str {
         match self {
-            Self::Csv => ".csv",
-            Self::CsvAll => ".all.csv",
+            Self::Csv | Self::CsvNoHeader => ".csv",
+            Self::CsvAll | Self::CsvAllNoHeader => ".all.csv",
+            Self::Selection => ".selection.csv",
             Self::Json => ".json",
             Self::JsonAll => ".all.json",
             Self::Html => ".html",
+            Self::Markdown => ".md",
             Self::Png => ".png",
             Self::Arrow => ".arrow",
             Self::ArrowAll => ".all.arrow",
-            Self::JsonConfig => ".config.json",
+            Self::JsonConfig | Self::CopyConfig => ".config.json",
         }
     }
 
@@ -46,12 +54,54 @@ impl ExportMethod {
             _ => MimeType::TextPlain,
         }
     }
+
+    /// Parse the `mode` argument of the `copy()` and `download()` viewer
+    /// methods:  `"selection"` for the plugin's selected rows, `"html"` for a
+    /// standalone HTML snapshot, `"markdown"` for a Markdown table of the
+    /// current view, `true` for the default "flat" view, or
+    /// `false`/`undefined` for the current view.
+    pub fn from_export_mode(mode: &JsValue) -> Result<Self, JsValue> {
+        match mode.as_string() {
+            Some(x) if x == "selection" => Ok(Self::Selection),
+            Some(x) if x == "html" => Ok(Self::Html),
+            Some(x) if x == "markdown" => Ok(Self::Markdown),
+            Some(x) => Err(format!("Unknown export mode \"{}\"", x).into()),
+            None if mode.as_bool().unwrap_or_default() => Ok(Self::CsvAll),
+            None => Ok(Self::Csv),
+        }
+    }
+
+    /// Is this a CSV export without the header row, e.g. for pasting into an
+    /// existing table?
+    pub const fn is_headerless(&self) -> bool {
+        matches!(self, Self::CsvNoHeader | Self::CsvAllNoHeader)
+    }
+
+    /// The variant of this CSV method which omits the header row when
+    /// `header` is `false`.
+    ///
+    /// # Arguments
+    /// - `header` Whether to include the header row.
+    pub fn with_header(self, header: bool) -> Result<Self, JsValue> {
+        match (self, header) {
+            (x, true) => Ok(x),
+            (Self::Csv, false) => Ok(Self::CsvNoHeader),
+            (Self::CsvAll, false) => Ok(Self::CsvAllNoHeader),
+            (_, false) => Err("Omitting headers is only supported for CSV".into()),
+        }
+    }
 }
 
 impl From<ExportMethod> for Html {
     fn from(x: ExportMethod) -> Self {
+        let note = if x.is_headerless() {
+            " (no header)"
+        } else {
+            ""
+        };
+
         html! {
-            <code>{ x.as_filename() }</code>
+            <code>{ x.as_filename() }{ note }</code>
         }
     }
 }
@@ -85,6 +135,12 @@ impl From<ExportFile> for Html {
             None
         };
 
+        if x.method == ExportMethod::CopyConfig {
+            return html! {
+                <code>{ "Copy to clipboard" }</code>
+            };
+        }
+
         html_template! {
             <code class={ class }>
                 { x.name }
@@ -47,12 +47,23 @@ pub trait GetViewerConfigModel: HasSession + HasRenderer + HasTheme {
             let plugin = js_plugin.name();
             let plugin_config: serde_json::Value = js_plugin.save().into_serde().into_jserror()?;
             let theme = theme.get_name().await;
+            let type_defaults = renderer.get_type_defaults();
+            let density = renderer.get_density();
+            let pinned_columns = renderer.get_pinned_columns();
+            let settings_width = renderer.get_settings_width();
+            let row_sizing = renderer.get_row_sizing();
             Ok(ViewerConfig {
                 plugin,
                 plugin_config,
                 settings,
                 view_config,
                 theme,
+                density,
+                type_defaults,
+                pinned_columns,
+                settings_width,
+                row_height: row_sizing.row_height,
+                header_height: row_sizing.header_height,
             })
         })
     }
@@ -0,0 +1,53 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use super::structural::*;
+use crate::utils::*;
+use crate::*;
+
+use serde::Serialize;
+use std::future::Future;
+use std::pin::Pin;
+use wasm_bindgen::prelude::*;
+
+/// A snapshot of the current `View` and render statistics, as displayed in a
+/// status bar.  Fields are `None` until the corresponding value is known, e.g.
+/// `last_render_time` before the 1st render or when throttling is constant.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ViewerStats {
+    pub num_rows: Option<u32>,
+    pub virtual_rows: Option<u32>,
+    pub num_columns: Option<u32>,
+    pub is_pivot: bool,
+    pub last_render_time: Option<f64>,
+}
+
+pub trait GetViewerStatsModel: HasSession + HasRenderer {
+    /// Get the current `ViewerStats`, failing if no `Table` has been loaded.
+    fn get_viewer_stats(&self) -> Pin<Box<dyn Future<Output = Result<ViewerStats, JsValue>>>> {
+        clone!(self.renderer(), self.session());
+        Box::pin(async move {
+            session.get_table().ok_or("No table set")?;
+            let stats = session.get_table_stats().unwrap_or_default();
+            let num_columns = match session.get_view() {
+                Some(view) => Some(view.num_columns().await? as u32),
+                None => None,
+            };
+
+            Ok(ViewerStats {
+                num_rows: stats.num_rows,
+                virtual_rows: stats.virtual_rows,
+                num_columns,
+                is_pivot: stats.is_pivot,
+                last_render_time: renderer.get_last_render_time(),
+            })
+        })
+    }
+}
+
+impl<T: HasRenderer + HasSession> GetViewerStatsModel for T {}
@@ -66,6 +66,7 @@ mod copy_export;
 mod export_app;
 mod export_method;
 mod get_viewer_config;
+mod get_viewer_stats;
 mod structural;
 mod update_and_render;
 
@@ -73,5 +74,6 @@ pub use self::columns_iter_set::*;
 pub use self::copy_export::*;
 pub use self::export_method::*;
 pub use self::get_viewer_config::*;
+pub use self::get_viewer_stats::*;
 pub use self::structural::*;
 pub use self::update_and_render::*;
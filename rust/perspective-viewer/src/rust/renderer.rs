@@ -16,14 +16,19 @@
 mod activate;
 mod limits;
 mod plugin_store;
+mod reduced_motion;
 mod registry;
 mod render_timer;
+mod scroll_position;
 
 use self::activate::*;
 use self::limits::*;
 use self::plugin_store::*;
+pub use self::reduced_motion::*;
 pub use self::registry::*;
 use self::render_timer::*;
+pub use self::render_timer::{ThrottleTuning, DEFAULT_THROTTLE_WINDOW};
+pub use self::scroll_position::*;
 use crate::config::*;
 use crate::js::perspective::*;
 use crate::js::plugin::*;
@@ -43,6 +48,12 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::*;
 
+/// The narrowest the settings panel may be resized to, in pixels.
+const SETTINGS_MIN_WIDTH: i32 = 150;
+
+/// The widest the settings panel may be resized to, in pixels.
+const SETTINGS_MAX_WIDTH: i32 = 1000;
+
 #[derive(Clone)]
 pub struct Renderer(Rc<RendererData>);
 
@@ -53,6 +64,11 @@ pub struct RendererData {
     pub plugin_changed: PubSub<JsPerspectiveViewerPlugin>,
     pub limits_changed: PubSub<RenderLimits>,
     pub settings_open_changed: PubSub<bool>,
+    pub density_changed: PubSub<Density>,
+    pub pinned_columns_changed: PubSub<()>,
+    pub settings_width_changed: PubSub<Option<i32>>,
+    pub row_sizing_changed: PubSub<RowSizing>,
+    pub render_error: PubSub<JsValue>,
 }
 
 /// Mutable state
@@ -63,6 +79,14 @@ pub struct RendererMutData {
     plugins_idx: Option<usize>,
     timer: MovingWindowRenderTimer,
     is_settings_open: bool,
+    reduced_motion: ReducedMotion,
+    is_print_mode: bool,
+    is_redraw_fade: bool,
+    density: Density,
+    pinned_columns: Vec<String>,
+    settings_width: Option<i32>,
+    row_sizing: RowSizing,
+    type_defaults: HashMap<String, serde_json::Value>,
 }
 
 type RenderLimits = (usize, usize, Option<usize>, Option<usize>);
@@ -100,10 +124,23 @@ impl Renderer {
                 plugins_idx: None,
                 timer: MovingWindowRenderTimer::default(),
                 is_settings_open: false,
+                reduced_motion: ReducedMotion::default(),
+                is_print_mode: false,
+                is_redraw_fade: false,
+                density: Density::default(),
+                pinned_columns: vec![],
+                settings_width: None,
+                row_sizing: RowSizing::default(),
+                type_defaults: HashMap::default(),
             }),
             draw_lock: Default::default(),
             plugin_changed: Default::default(),
             settings_open_changed: Default::default(),
+            density_changed: Default::default(),
+            pinned_columns_changed: Default::default(),
+            settings_width_changed: Default::default(),
+            row_sizing_changed: Default::default(),
+            render_error: Default::default(),
             limits_changed: Default::default(),
         }))
     }
@@ -120,6 +157,12 @@ impl Renderer {
         Ok(())
     }
 
+    /// Release the active plugin's rendered content, without deleting it.
+    pub async fn clear(&self) -> Result<(), JsValue> {
+        self.get_active_plugin()?.clear().await?;
+        Ok(())
+    }
+
     pub fn metadata(&self) -> Ref<'_, ViewConfigRequirements> {
         Ref::map(self.borrow(), |x| &x.metadata)
     }
@@ -130,6 +173,16 @@ impl Renderer {
         self.0.borrow_mut().plugin_store.plugins().clone()
     }
 
+    /// Wait for the `document` to load, so that plugins included via
+    /// `<script>` tags have called `registerPlugin()`, then create any plugins
+    /// which are not yet instantiated.  Resolves immediately if the
+    /// `document` has already loaded.
+    pub async fn await_plugins_ready(&self) -> Result<(), JsValue> {
+        await_dom_loaded().await?;
+        self.0.borrow_mut().plugin_store.sync();
+        Ok(())
+    }
+
     /// Return all plugin names, whether they are active or not.
     pub fn get_all_plugin_categories(&self) -> HashMap<String, Vec<String>> {
         self.0.borrow_mut().plugin_store.plugin_records().clone()
@@ -160,6 +213,68 @@ impl Renderer {
         Ok(result.unwrap())
     }
 
+    /// The column style defaults for each column type, which plugins apply to
+    /// any column whose own style config does not override them.
+    pub fn get_type_defaults(&self) -> HashMap<String, serde_json::Value> {
+        self.0.borrow().type_defaults.clone()
+    }
+
+    /// Replace all column type style defaults.
+    pub fn set_type_defaults(&self, type_defaults: HashMap<String, serde_json::Value>) {
+        self.0.borrow_mut().type_defaults = type_defaults;
+    }
+
+    /// The columns plugins should freeze during horizontal scroll.
+    pub fn get_pinned_columns(&self) -> Vec<String> {
+        self.0.borrow().pinned_columns.clone()
+    }
+
+    /// Replace the pinned columns, returning whether they changed.  Plugins
+    /// pick this up on their next `draw()`.
+    pub fn set_pinned_columns(&self, columns: Vec<String>) -> bool {
+        let changed = self.0.borrow().pinned_columns != columns;
+        if changed {
+            self.0.borrow_mut().pinned_columns = columns;
+            self.pinned_columns_changed.emit_all(());
+        }
+
+        changed
+    }
+
+    /// Set the column style defaults for a single column type, or clear them
+    /// when `config` is `None`.
+    ///
+    /// # Arguments
+    /// - `column_type` The column type name, e.g. "integer".
+    /// - `config` The column style config to apply to columns of this type.
+    pub fn set_type_default(&self, column_type: &str, config: Option<serde_json::Value>) {
+        let type_defaults = &mut self.0.borrow_mut().type_defaults;
+        match config {
+            Some(config) => type_defaults.insert(column_type.to_owned(), config),
+            None => type_defaults.remove(column_type),
+        };
+    }
+
+    /// The width of the settings panel in pixels, or `None` for the default
+    /// width.
+    pub fn get_settings_width(&self) -> Option<i32> {
+        self.0.borrow().settings_width
+    }
+
+    /// Set the width of the settings panel, clamped to
+    /// `SETTINGS_MIN_WIDTH..=SETTINGS_MAX_WIDTH`, returning whether it
+    /// changed.
+    pub fn set_settings_width(&self, width: Option<i32>) -> bool {
+        let width = width.map(|x| x.clamp(SETTINGS_MIN_WIDTH, SETTINGS_MAX_WIDTH));
+        let changed = self.get_settings_width() != width;
+        if changed {
+            self.0.borrow_mut().settings_width = width;
+            self.settings_width_changed.emit_all(width);
+        }
+
+        changed
+    }
+
     pub fn is_settings_open(&self) -> bool {
         self.0.borrow().is_settings_open
     }
@@ -181,6 +296,13 @@ impl Renderer {
 
     pub async fn restyle_all(&self, view: &JsPerspectiveView) -> Result<JsValue, JsValue> {
         let plugins = self.get_all_plugins();
+        let reduced_motion = self.is_reduced_motion();
+        let row_sizing = self.get_row_sizing();
+        for plugin in plugins.iter() {
+            plugin.set_reduced_motion(reduced_motion);
+            plugin.set_row_sizing(&row_sizing)?;
+        }
+
         let tasks = plugins.iter().map(|plugin| plugin.restyle(view));
 
         join_all(tasks)
@@ -190,10 +312,139 @@ impl Renderer {
             .map(|_| JsValue::UNDEFINED)
     }
 
+    pub fn set_reduced_motion(&self, val: ReducedMotion) {
+        self.0.borrow_mut().reduced_motion = val;
+    }
+
+    pub fn get_reduced_motion(&self) -> ReducedMotion {
+        self.0.borrow().reduced_motion
+    }
+
+    /// Whether plugins should render without animation, resolving
+    /// `ReducedMotion::Auto` against the OS preference.
+    pub fn is_reduced_motion(&self) -> bool {
+        self.0.borrow().reduced_motion.is_active()
+    }
+
+    /// Set whether plugins should render their entire `View` (e.g. without
+    /// virtualization) so the browser prints every row, returning whether it
+    /// changed.  Takes effect on the next `draw()`.
+    pub fn set_print_mode(&self, val: bool) -> Result<bool, JsValue> {
+        let changed = self.is_print_mode() != val;
+        self.0.borrow_mut().is_print_mode = val;
+        self.0
+            .borrow()
+            .viewer_elem
+            .class_list()
+            .toggle_with_force("print-mode", val)?;
+
+        Ok(changed)
+    }
+
+    pub fn is_print_mode(&self) -> bool {
+        self.0.borrow().is_print_mode
+    }
+
+    /// Set whether plugins should fade in each `draw()` over the previous
+    /// render.  Takes effect on the next `draw()`.
+    pub fn set_redraw_fade(&self, val: bool) {
+        self.0.borrow_mut().is_redraw_fade = val;
+    }
+
+    /// Whether plugins should fade in their next `draw()`, which is never the
+    /// case while `is_reduced_motion()`.
+    pub fn is_redraw_fade(&self) -> bool {
+        self.0.borrow().is_redraw_fade && !self.is_reduced_motion()
+    }
+
+    /// Set the `Density` class on the viewer element, returning whether it
+    /// changed.  Plugins pick this up on their next `restyle_all()`.
+    pub fn set_density(&self, density: Density) -> Result<bool, JsValue> {
+        let changed = self.get_density() != density;
+        self.0.borrow_mut().density = density;
+        self.0
+            .borrow()
+            .viewer_elem
+            .class_list()
+            .toggle_with_force("density-compact", density == Density::Compact)?;
+
+        if changed {
+            self.density_changed.emit_all(density);
+        }
+
+        Ok(changed)
+    }
+
+    pub fn get_density(&self) -> Density {
+        self.0.borrow().density
+    }
+
+    /// Set the row and header height hints, returning whether they changed.
+    /// Plugins pick this up on their next `restyle_all()`;  if the active
+    /// plugin does not support custom sizing, this logs a warning.
+    ///
+    /// # Errors
+    ///
+    /// If either height is not a positive number.
+    pub fn set_row_sizing(&self, sizing: RowSizing) -> Result<bool, JsValue> {
+        let heights = [
+            ("row height", sizing.row_height),
+            ("header height", sizing.header_height),
+        ];
+
+        for (name, height) in heights {
+            if let Some(px) = height && !(px.is_finite() && px > 0.0) {
+                return Err(format!("Invalid {} {}, expected a positive number", name, px).into());
+            }
+        }
+
+        let changed = self.get_row_sizing() != sizing;
+        if changed {
+            self.0.borrow_mut().row_sizing = sizing;
+            if let Ok(plugin) = self.get_active_plugin()
+                && sizing != RowSizing::default()
+                && !plugin.supports_row_sizing()
+            {
+                let msg = format!("Plugin `{}` does not support custom row sizing", plugin.name());
+                web_sys::console::warn_1(&msg.into());
+            }
+
+            self.row_sizing_changed.emit_all(sizing);
+        }
+
+        Ok(changed)
+    }
+
+    pub fn get_row_sizing(&self) -> RowSizing {
+        self.0.borrow().row_sizing
+    }
+
     pub fn set_throttle(&mut self, val: Option<f64>) {
         self.0.borrow_mut().timer.set_throttle(val);
     }
 
+    /// Set the adaptive throttling parameters, used when `set_throttle(None)`.
+    pub fn set_throttle_tuning(&self, tuning: ThrottleTuning) {
+        self.render_timer().set_tuning(tuning);
+    }
+
+    /// The current delay between renders in milliseconds, whether constant or
+    /// adaptive.
+    pub fn get_throttle(&self) -> i32 {
+        self.render_timer().get_avg()
+    }
+
+    /// The duration of the most recent render in milliseconds, if measured.
+    pub fn get_last_render_time(&self) -> Option<f64> {
+        self.render_timer().get_last()
+    }
+
+    /// The active plugin's preferred dimensions for `view`, which does not
+    /// draw the plugin.
+    pub async fn measure(&self, view: &JsPerspectiveView) -> Result<PluginDimensions, JsValue> {
+        self.get_active_plugin()?.get_dimensions(view).await
+    }
+
     pub fn disable_active_plugin_render_warning(&self) {
         self.borrow_mut().metadata.render_warning = false;
         self.get_active_plugin().unwrap().set_render_warning(false);
@@ -249,7 +500,7 @@ impl Renderer {
             .debounce(async {
                 set_timeout(timer.get_avg()).await?;
                 let jsplugin = self.get_active_plugin()?;
-                jsplugin.resize().await?;
+                self.notify_render_error(jsplugin.resize().await)?;
                 Ok(())
             })
             .await
@@ -262,6 +513,25 @@ impl Renderer {
         self.draw_plugin(session, false).await
     }
 
+    /// Like `draw()`, but calls the plugin's `update()` method, so plugin state
+    /// such as scroll position is preserved across the new `View`.
+    pub async fn draw_in_place(
+        &self,
+        session: impl Future<Output = Result<&Session, JsValue>>,
+    ) -> Result<(), JsValue> {
+        let timer = self.render_timer();
+        self.draw_lock()
+            .lock(async move {
+                if let Some(view) = session.await?.get_view() {
+                    let result = timer.capture_time(self.draw_view(&view, true)).await;
+                    self.notify_render_error(result)
+                } else {
+                    Ok(())
+                }
+            })
+            .await
+    }
+
     pub async fn update(&self, session: &Session) -> Result<(), JsValue> {
         self.draw_plugin(async { Ok(session) }, true).await
     }
@@ -278,7 +548,8 @@ impl Renderer {
             }
 
             if let Some(view) = session.await?.get_view() {
-                timer.capture_time(self.draw_view(&view, is_update)).await
+                let result = timer.capture_time(self.draw_view(&view, is_update)).await;
+                self.notify_render_error(result)
             } else {
                 Ok(())
             }
@@ -294,6 +565,11 @@ impl Renderer {
 
     async fn draw_view(&self, view: &JsPerspectiveView, is_update: bool) -> Result<(), JsValue> {
         let plugin = self.get_active_plugin()?;
+        plugin.set_reduced_motion(self.is_reduced_motion());
+        plugin.set_print_mode(self.is_print_mode());
+        plugin.set_redraw_fade(self.is_redraw_fade());
+        plugin.set_pinned_columns(&self.get_pinned_columns())?;
+        plugin.set_row_sizing(&self.get_row_sizing())?;
         let meta = self.metadata().clone();
         let limits = get_row_and_col_limits(view, &meta).await?;
         self.limits_changed.emit_all(limits);
@@ -307,6 +583,18 @@ impl Renderer {
         }
     }
 
+    /// Emit `render_error` if `result` is a plugin render failure, rather than
+    /// the cancellation of a `View` replaced mid-render.
+    fn notify_render_error<T>(&self, result: Result<T, JsValue>) -> Result<T, JsValue> {
+        if let Err(err) = &result {
+            if ignore_view_delete(err.clone()).is_err() {
+                self.render_error.emit_all(err.clone());
+            }
+        }
+
+        result
+    }
+
     /// Decide whether to draw plugin or self first based on whether the panel
     /// is opening or closing, then draw with a timeout.  If the timeout
     /// triggers, draw self and resolve `on_toggle` but still await the
@@ -340,11 +628,13 @@ impl Renderer {
     /// awaited lest the plugin draw itself never trigger.
     async fn resize_with_timeout(&self, open: bool) -> Result<TaskResult, TimeoutTask<'_>> {
         let task = async move {
-            if open {
+            let result = if open {
                 self.get_active_plugin()?.resize().await
             } else {
                 self.resize_with_explicit_dimensions().await
-            }
+            };
+
+            self.notify_render_error(result)
         };
 
         let draw_lock = self.draw_lock();
@@ -37,4 +37,16 @@ impl PluginStore {
 
         self.plugin_records.as_ref().unwrap()
     }
+
+    /// Create any plugins which were registered after this store was
+    /// initialized, so that late `registerPlugin()` calls are not missed.
+    pub fn sync(&mut self) {
+        match &mut self.plugins {
+            None => self.init_lazy(),
+            Some(plugins) => {
+                plugins.extend(PLUGIN_REGISTRY.create_plugins_from(plugins.len()));
+                self.plugin_records = Some(PLUGIN_REGISTRY.available_plugin_names_by_category());
+            }
+        }
+    }
 }
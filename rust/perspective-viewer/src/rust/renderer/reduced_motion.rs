@@ -0,0 +1,88 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::utils::*;
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(test)]
+use wasm_bindgen_test::*;
+
+/// Whether plugins should avoid animations (e.g. `NumberBackgroundMode::Pulse`)
+/// and render statically instead.  `Auto` defers to the OS via the
+/// `prefers-reduced-motion` media query.  This is a render-time setting and is
+/// not persisted by `save()`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReducedMotion {
+    Auto,
+    On,
+    Off,
+}
+
+impl Default for ReducedMotion {
+    fn default() -> Self {
+        ReducedMotion::Auto
+    }
+}
+
+impl Display for ReducedMotion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::On => "on",
+            Self::Off => "off",
+        })
+    }
+}
+
+impl FromStr for ReducedMotion {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "on" => Ok(Self::On),
+            "off" => Ok(Self::Off),
+            x => Err(format!("Unknown reduced motion mode \"{}\"", x)),
+        }
+    }
+}
+
+impl ReducedMotion {
+    /// The OS preference consulted by `Auto`.
+    pub const MEDIA_QUERY: &'static str = "(prefers-reduced-motion: reduce)";
+
+    /// Resolve this setting, querying the OS preference for `Auto`.
+    pub fn is_active(&self) -> bool {
+        match self {
+            Self::On => true,
+            Self::Off => false,
+            Self::Auto => media_matches(Self::MEDIA_QUERY),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_round_trip() {
+        for mode in [ReducedMotion::Auto, ReducedMotion::On, ReducedMotion::Off] {
+            assert_eq!(ReducedMotion::from_str(&mode.to_string()), Ok(mode));
+        }
+
+        assert!(ReducedMotion::from_str("sometimes").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_overrides() {
+        assert!(ReducedMotion::On.is_active());
+        assert!(!ReducedMotion::Off.is_active());
+    }
+}
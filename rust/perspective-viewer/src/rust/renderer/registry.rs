@@ -31,11 +31,18 @@ pub struct PluginRecord {
 #[ext]
 pub impl LocalKey<Rc<RefCell<Vec<PluginRecord>>>> {
     fn create_plugins(&'static self) -> Vec<JsPerspectiveViewerPlugin> {
+        self.create_plugins_from(0)
+    }
+
+    /// Create plugin elements for the registered plugins after the first
+    /// `start`, e.g. those registered since the last call to
+    /// `create_plugins()`.
+    fn create_plugins_from(&'static self, start: usize) -> Vec<JsPerspectiveViewerPlugin> {
         register_default();
         self.with(
             |plugins| -> Result<Vec<JsPerspectiveViewerPlugin>, JsValue> {
                 let mut elements = vec![];
-                for plugin in plugins.borrow().iter() {
+                for plugin in plugins.borrow().iter().skip(start) {
                     let element = create_plugin(&plugin.tag_name);
                     let style = element.unchecked_ref::<HtmlElement>().style();
                     style.set_property("position", "absolute")?;
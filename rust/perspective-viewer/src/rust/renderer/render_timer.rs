@@ -8,7 +8,7 @@
 
 use crate::utils::*;
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::future::Future;
 use std::rc::Rc;
@@ -16,8 +16,33 @@ use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::*;
 
+/// The default number of recent renders adaptive throttling averages.
+pub const DEFAULT_THROTTLE_WINDOW: usize = 5;
+
+/// The longest adaptive throttling will wait between renders, in
+/// milliseconds.
+const MAX_ADAPTIVE_THROTTLE: f64 = 5000_f64;
+
+/// Parameters for adaptive throttling, which waits the average render time of
+/// the last `window` renders (but at least `target` milliseconds) between
+/// renders.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThrottleTuning {
+    pub window: usize,
+    pub target: f64,
+}
+
+impl Default for ThrottleTuning {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_THROTTLE_WINDOW,
+            target: 0_f64,
+        }
+    }
+}
+
 #[derive(Default, Clone)]
-pub struct MovingWindowRenderTimer(Rc<RefCell<RenderTimerType>>);
+pub struct MovingWindowRenderTimer(Rc<RefCell<RenderTimerType>>, Rc<Cell<ThrottleTuning>>);
 
 enum RenderTimerType {
     Moving(Closure<dyn Fn(JsValue)>, Rc<RefCell<Option<VecDeque<f64>>>>),
@@ -72,12 +97,13 @@ impl MovingWindowRenderTimer {
         };
 
         let result = f.await;
+        let window = self.1.get().window;
         match &mut *self.0.borrow_mut() {
             RenderTimerType::Moving(_, timings) => {
                 let mut timings = timings.borrow_mut();
                 if let Some(timings) = &mut *timings {
                     timings.push_back(perf.now() - start);
-                    if timings.len() > 5 {
+                    while timings.len() > window {
                         timings.pop_front();
                     }
                 } else {
@@ -101,23 +127,59 @@ impl MovingWindowRenderTimer {
         }
     }
 
+    /// Set the adaptive throttling parameters, which take effect whenever
+    /// throttling is not constant.
+    pub fn set_tuning(&self, tuning: ThrottleTuning) {
+        self.1.set(tuning);
+    }
+
+    /// The duration of the most recent render in milliseconds, if it was
+    /// measured, which it is not when throttling is constant.
+    pub fn get_last(&self) -> Option<f64> {
+        match &*self.0.borrow() {
+            RenderTimerType::Constant(_) => None,
+            RenderTimerType::Moving(_, timings) => timings.borrow().as_ref()?.back().copied(),
+        }
+    }
+
     pub fn get_avg(&self) -> i32 {
         match &*self.0.borrow() {
             RenderTimerType::Constant(constant) => *constant as i32,
             RenderTimerType::Moving(_, timings) => {
-                if let Some(timings) = &*timings.borrow() {
-                    let len = timings.len();
-                    if len < 5 {
-                        0_i32
-                    } else {
-                        let sum = timings.iter().sum::<f64>();
-                        let avg: f64 = sum / len as f64;
-                        f64::min(5000_f64, avg.floor()) as i32
+                let ThrottleTuning { window, target } = self.1.get();
+                let avg = match &*timings.borrow() {
+                    Some(timings) if timings.len() >= window => {
+                        timings.iter().sum::<f64>() / timings.len() as f64
                     }
-                } else {
-                    0_i32
-                }
+                    _ => 0_f64,
+                };
+
+                f64::min(MAX_ADAPTIVE_THROTTLE, f64::max(target, avg).floor()) as i32
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    pub async fn test_throttle_tuning() {
+        let timer = MovingWindowRenderTimer::default();
+        timer.set_tuning(ThrottleTuning {
+            window: 2,
+            target: 100_f64,
+        });
+
+        assert_eq!(timer.get_avg(), 100);
+        for _ in 0..3 {
+            timer.capture_time(async {}).await;
+        }
+
+        assert_eq!(timer.get_avg(), 100);
+        timer.set_tuning(ThrottleTuning::default());
+        assert!(timer.get_avg() < 100);
+    }
+}
@@ -0,0 +1,54 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::js::plugin::*;
+
+use wasm_bindgen::JsCast;
+
+/// A plugin's scroll offset, captured before a redraw so it can be reapplied
+/// afterwards.  Plugins commonly scroll an inner element (e.g. the datagrid's
+/// `<regular-table>`), so the scrolled element is recorded as an index into
+/// the plugin element's children, or `None` for the plugin element itself.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ScrollPosition {
+    child: Option<u32>,
+    top: i32,
+    left: i32,
+}
+
+impl ScrollPosition {
+    /// Capture the scroll offset of `plugin`, or `None` if neither it nor any
+    /// of its children is scrolled.
+    pub fn capture(plugin: &JsPerspectiveViewerPlugin) -> Option<ScrollPosition> {
+        let elem = plugin.unchecked_ref::<web_sys::Element>();
+        let children = elem.children();
+        std::iter::once((None, elem.clone()))
+            .chain((0..children.length()).filter_map(|idx| Some((Some(idx), children.item(idx)?))))
+            .find(|(_, x)| x.scroll_top() != 0 || x.scroll_left() != 0)
+            .map(|(child, x)| ScrollPosition {
+                child,
+                top: x.scroll_top(),
+                left: x.scroll_left(),
+            })
+    }
+
+    /// Reapply this scroll offset to the same element of `plugin`.  The
+    /// browser clamps the offset if the content has since shrunk.
+    pub fn apply(&self, plugin: &JsPerspectiveViewerPlugin) {
+        let elem = plugin.unchecked_ref::<web_sys::Element>();
+        let target = match self.child {
+            Some(idx) => elem.children().item(idx),
+            None => Some(elem.clone()),
+        };
+
+        if let Some(target) = target {
+            target.set_scroll_top(self.top);
+            target.set_scroll_left(self.left);
+        }
+    }
+}
@@ -7,13 +7,25 @@
 // file.
 
 mod column_defaults_update;
+mod config_validation;
 mod drag_drop_update;
+mod expression_snippets;
+mod expression_validation;
+mod load_progress;
+mod markdown;
 mod metadata;
 mod replace_expression_update;
+mod search;
 mod view;
 mod view_subscription;
 
+pub use self::config_validation::ConfigValidation;
+pub use self::expression_snippets::ExpressionSnippet;
+pub use self::expression_validation::{ExpressionError, ExpressionValidation};
+pub use self::load_progress::*;
+use self::markdown::*;
 use self::metadata::*;
+pub use self::search::*;
 use self::view::PerspectiveOwned;
 use self::view::View;
 pub use self::view_subscription::TableStats;
@@ -26,8 +38,8 @@ use crate::utils::*;
 use crate::*;
 
 use js_intern::*;
-use std::cell::{Ref, RefCell};
-use std::collections::HashSet;
+use std::cell::{Cell, Ref, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::iter::IntoIterator;
 use std::ops::Deref;
 use std::rc::Rc;
@@ -50,6 +62,7 @@ pub struct SessionHandle {
     pub view_created: PubSub<()>,
     pub view_config_changed: PubSub<()>,
     pub stats_changed: PubSub<()>,
+    pub load_progress: PubSub<LoadProgress>,
 }
 
 /// Mutable state for `Session`.
@@ -60,6 +73,7 @@ pub struct SessionData {
     config: ViewConfig,
     view_sub: Option<ViewSubscription>,
     stats: Option<TableStats>,
+    snippets: Option<Vec<ExpressionSnippet>>,
 }
 
 impl Deref for Session {
@@ -103,6 +117,14 @@ impl Session {
         self.borrow_mut().config.reset(reset_expressions);
     }
 
+    /// Delete this `Session`'s `View`, but preserve its config and `Table`, so
+    /// the next `create_view()` recreates an identical `View`.  Returns whether
+    /// there was a `View` to delete.
+    pub fn suspend_view(&self) -> bool {
+        let view_sub = self.borrow_mut().view_sub.take();
+        view_sub.is_some()
+    }
+
     /// Reset this (presumably shared) `Session` to its initial state, returning
     /// a bool indicating whether this `Session` had a table which was
     /// deleted. TODO Table should be an immutable constructor parameter to
@@ -123,6 +145,63 @@ impl Session {
     /// `create_view()`.
     pub async fn set_table(&self, table: JsPerspectiveTable) -> Result<JsValue, JsValue> {
         let metadata = SessionMetadata::from_table(&table).await?;
+        self.swap_table(table, metadata).await
+    }
+
+    /// Like `set_table()`, but checks that the current `ViewConfig` is
+    /// compatible with the new `Table`'s schema first, so it may be preserved.
+    /// Rejects with the list of incompatibilities, leaving this `Session`
+    /// unchanged, if any referenced column is missing or has changed type, or
+    /// any expression fails to validate.
+    pub async fn replace_table(&self, table: JsPerspectiveTable) -> Result<JsValue, JsValue> {
+        let metadata = SessionMetadata::from_table(&table).await?;
+        let config = self.get_view_config().clone();
+        let exprs = config
+            .expressions
+            .iter()
+            .map(JsValue::from)
+            .collect::<js_sys::Array>();
+
+        let valid_recs = table.validate_expressions(exprs).await?;
+        let mut errors = js_sys::Object::keys(&valid_recs.errors())
+            .iter()
+            .filter_map(|x| x.as_string())
+            .map(|x| format!("expression \"{}\" is invalid", x))
+            .collect::<Vec<_>>();
+
+        let expression_names = js_sys::Object::keys(&valid_recs.expression_schema())
+            .iter()
+            .filter_map(|x| x.as_string())
+            .collect::<HashSet<_>>();
+
+        for column in config.referenced_columns() {
+            if expression_names.contains(column) {
+                continue;
+            }
+
+            let old_type = self.metadata().get_column_table_type(column);
+            match (old_type, metadata.get_column_table_type(column)) {
+                (_, None) => errors.push(format!("\"{}\" is missing", column)),
+                (Some(old_type), Some(new_type)) if old_type != new_type => errors.push(format!(
+                    "\"{}\" changed type from {} to {}",
+                    column, old_type, new_type
+                )),
+                _ => {}
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(format!("Incompatible `Table` schema: {}", errors.join(", ")).into());
+        }
+
+        self.swap_table(table, metadata).await
+    }
+
+    async fn swap_table(
+        &self,
+        table: JsPerspectiveTable,
+        metadata: SessionMetadata,
+    ) -> Result<JsValue, JsValue> {
         self.borrow_mut().view_sub = None;
         self.borrow_mut().metadata = metadata;
         self.borrow_mut().table = Some(table);
@@ -186,24 +265,99 @@ impl Session {
         )
     }
 
-    /// Validate an expression string (as a JsValue since it comes from
-    /// `monaco`), and marshall the results.
-    pub async fn validate_expr(
-        &self,
-        expr: JsValue,
-    ) -> Result<Option<PerspectiveValidationError>, JsValue> {
-        let arr = [expr].iter().collect::<js_sys::Array>();
-        let table = self.borrow().table.as_ref().unwrap().clone();
-        let errors = table.validate_expressions(arr).await?.errors();
+    /// Validate a single expression string against the `Table` without
+    /// modifying the `ViewConfig`, returning either its inferred result type
+    /// or the validation error and its location within `expr`.
+    pub async fn validate_expr(&self, expr: &str) -> Result<ExpressionValidation, JsValue> {
+        let arr = [JsValue::from(expr)].iter().collect::<js_sys::Array>();
+        let table = self.get_table().ok_or_else(|| js_intern!("No table set"))?;
+        let valid_recs = table.validate_expressions(arr).await?;
+        let errors = valid_recs.errors();
         let error_keys = js_sys::Object::keys(&errors);
         if error_keys.length() > 0 {
             let js_err = js_sys::Reflect::get(&errors, &error_keys.get(0))?;
-            Ok(Some(js_err.into_serde().unwrap()))
+            let err: PerspectiveValidationError = js_err.into_serde().into_jserror()?;
+            let details = ExpressionError::new(expr, err);
+            Ok(ExpressionValidation {
+                valid: false,
+                expression_type: None,
+                error: Some(details.message.clone()),
+                details: Some(details),
+            })
         } else {
-            Ok(None)
+            let schema = valid_recs.expression_schema();
+            let schema_keys = js_sys::Object::keys(&schema);
+            let expression_type = js_sys::Reflect::get(&schema, &schema_keys.get(0))?.as_string();
+            Ok(ExpressionValidation {
+                valid: true,
+                expression_type,
+                error: None,
+                details: None,
+            })
         }
     }
 
+    /// Check `config` against the `Table`, listing any unknown columns,
+    /// invalid expressions or type-invalid aggregates, without modifying this
+    /// `Session` as `validate()` does.
+    ///
+    /// # Arguments
+    /// - `config` The `ViewConfig` to check.
+    pub async fn check_view_config(
+        &self,
+        config: &ViewConfig,
+    ) -> Result<ConfigValidation, JsValue> {
+        let table = self.get_table().ok_or_else(|| js_intern!("No table set"))?;
+        let arr = config
+            .expressions
+            .iter()
+            .map(JsValue::from)
+            .collect::<js_sys::Array>();
+
+        let valid_recs = table.validate_expressions(arr).await?;
+        let invalid_expressions = js_sys::Object::keys(&valid_recs.errors())
+            .iter()
+            .filter_map(|x| x.as_string())
+            .collect::<Vec<_>>();
+
+        let expression_schema: HashMap<String, Type> =
+            valid_recs.expression_schema().into_serde().into_jserror()?;
+
+        let metadata = self.metadata();
+        let table_schema = metadata.get_table_schema().into_jserror()?;
+        let column_type = |name: &str| {
+            table_schema
+                .get(name)
+                .or_else(|| expression_schema.get(name))
+                .copied()
+        };
+
+        Ok(ConfigValidation::new(
+            config,
+            invalid_expressions,
+            column_type,
+        ))
+    }
+
+    /// The aliases of those `expressions` which are invalid for the `Table`,
+    /// without modifying the `ViewConfig`.
+    pub async fn get_invalid_expressions(
+        &self,
+        expressions: &[String],
+    ) -> Result<Vec<String>, JsValue> {
+        let table = self.get_table().ok_or_else(|| js_intern!("No table set"))?;
+        let arr = expressions
+            .iter()
+            .map(JsValue::from)
+            .collect::<js_sys::Array>();
+
+        let errors = table.validate_expressions(arr).await?.errors();
+        Ok(js_sys::Object::keys(&errors)
+            .iter()
+            .filter_map(|x| x.as_string())
+            .collect())
+    }
+
     pub async fn arrow_as_vec(&self, flat: bool) -> Result<Vec<u8>, JsValue> {
         let arrow = self.flat_as_jsvalue(flat).await?.to_arrow().await?;
         Ok(js_sys::Uint8Array::new(&arrow).to_vec())
@@ -225,6 +379,256 @@ impl Session {
             .await
     }
 
+    /// Render the current (or default "flat") `View` as a GitHub-flavored
+    /// Markdown table, with numeric columns right-aligned.
+    ///
+    /// # Arguments
+    /// - `flat` Whether to export the default "flat" `View` instead.
+    pub async fn markdown_as_jsvalue(&self, flat: bool) -> Result<js_sys::JsString, JsValue> {
+        let view = self.flat_as_jsvalue(flat).await?;
+        let schema: HashMap<String, Type> = view.schema().await?.into_serde().into_jserror()?;
+        let opts = json!({"formatted": true});
+        let data = view.to_columns_window(opts.unchecked_into()).await?;
+        let columns = js_sys::Object::keys(&data)
+            .iter()
+            .filter_map(|key| {
+                let name = key.as_string()?;
+                if name.starts_with("__") && name != "__ROW_PATH__" {
+                    return None;
+                }
+
+                let cells = js_sys::Reflect::get(&data, &key)
+                    .ok()?
+                    .unchecked_into::<js_sys::Array>()
+                    .iter()
+                    .map(|x| markdown_cell(&x))
+                    .collect();
+
+                // Split-by columns are named by their path, e.g. `"a|Sales"`.
+                let col_type = name.rsplit('|').next().and_then(|x| schema.get(x));
+                let is_numeric = matches!(col_type, Some(Type::Integer | Type::Float));
+                let name = if name == "__ROW_PATH__" {
+                    "".to_owned()
+                } else {
+                    name
+                };
+
+                Some(MarkdownColumn {
+                    name,
+                    is_numeric,
+                    cells,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(markdown_table(&columns).into())
+    }
+
+    /// Like `csv_as_jsvalue()`, but without the header row.
+    ///
+    /// # Arguments
+    /// - `flat` Whether to export the default "flat" `View` instead.
+    pub async fn csv_body_as_jsvalue(&self, flat: bool) -> Result<js_sys::JsString, JsValue> {
+        let csv = String::from(self.csv_as_jsvalue(flat).await?);
+        Ok(csv.split_once('\n').map_or("", |(_, rows)| rows).into())
+    }
+
+    /// Serialize the current (or default "flat") `View` to CSV in windows of
+    /// `chunk_rows` rows, appending each to the returned `Blob` as it is
+    /// serialized so only one chunk's string is held at a time (the header is
+    /// only kept from the first).  Checked between chunks, `aborted` stops the
+    /// export with an "Aborted" error.
+    ///
+    /// # Arguments
+    /// - `flat` Whether to export the default "flat" `View` instead.
+    /// - `chunk_rows` The number of rows to serialize per chunk.
+    /// - `aborted` Set to cancel the export.
+    pub async fn csv_chunks_as_blob(
+        &self,
+        flat: bool,
+        chunk_rows: u32,
+        aborted: Rc<Cell<bool>>,
+    ) -> Result<web_sys::Blob, JsValue> {
+        let view = self.flat_as_jsvalue(flat).await?;
+        let num_rows = view.num_rows().await? as u32;
+        let mut blob = js_sys::JsString::from("").as_blob()?;
+        let mut start_row = 0;
+        let mut ends_with_newline = true;
+        loop {
+            if aborted.get() {
+                return Err("Aborted".into());
+            }
+
+            let end_row = (start_row + chunk_rows.max(1)).min(num_rows);
+            let opts = json!({
+                "formatted": true,
+                "start_row": start_row as f64,
+                "end_row": end_row as f64
+            });
+
+            let chunk = String::from(view.to_csv(opts.unchecked_into()).await?);
+
+            // Every chunk repeats the header, and may not end in a newline.
+            let rows = if start_row == 0 {
+                chunk.as_str()
+            } else {
+                chunk.split_once('\n').map_or("", |(_, rows)| rows)
+            };
+
+            if !rows.is_empty() {
+                if !ends_with_newline {
+                    blob = append_to_blob(&blob, "\n")?;
+                }
+
+                ends_with_newline = rows.ends_with('\n');
+                blob = append_to_blob(&blob, rows)?;
+            }
+
+            start_row = end_row;
+            if start_row >= num_rows {
+                return Ok(blob);
+            }
+        }
+    }
+
+    /// Fetch rows `start_row..end_row` of the current `View` in columnar form,
+    /// without serializing the rest of the `View`.
+    ///
+    /// # Arguments
+    /// - `start_row` The first row of the window, inclusive.
+    /// - `end_row` The last row of the window, exclusive.
+    /// - `columns` If provided, only these columns (and the row paths) are kept
+    ///   in the result.
+    pub async fn window_as_jsvalue(
+        &self,
+        start_row: u32,
+        end_row: u32,
+        columns: Option<Vec<String>>,
+    ) -> Result<js_sys::Object, JsValue> {
+        let view = self.get_view().ok_or("No `View` to read from")?;
+        let num_rows = view.num_rows().await? as u32;
+        if start_row > end_row || end_row > num_rows {
+            return Err(format!(
+                "Window {}..{} is out of range for a `View` of {} rows",
+                start_row, end_row, num_rows
+            )
+            .into());
+        }
+
+        let window = json!({
+            "start_row": start_row as f64,
+            "end_row": end_row as f64
+        });
+
+        let result = view.to_columns_window(window.unchecked_into()).await?;
+        if let Some(columns) = columns {
+            for name in js_sys::Object::keys(&result).iter() {
+                let name_str = name.as_string().unwrap_or_default();
+                if !name_str.starts_with("__") && !columns.contains(&name_str) {
+                    js_sys::Reflect::delete_property(&result, &name)?;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Find the cells of the current `View` which match `query`, in row-major
+    /// order, fetching it in windows of `SEARCH_CHUNK_ROWS` rows.  Returns the
+    /// matches and whether the whole `View` was searched, which is `false`
+    /// when `max_matches` were found first.
+    pub async fn search(
+        &self,
+        query: &SearchQuery,
+        max_matches: usize,
+    ) -> Result<(Vec<SearchMatch>, bool), JsValue> {
+        let view = self.get_view().ok_or("No `View` to search")?;
+        let num_rows = view.num_rows().await? as u32;
+        let mut matches = vec![];
+        for start_row in (0..num_rows).step_by(SEARCH_CHUNK_ROWS as usize) {
+            let end_row = std::cmp::min(start_row + SEARCH_CHUNK_ROWS, num_rows);
+            let window = json!({
+                "start_row": start_row as f64,
+                "end_row": end_row as f64
+            });
+
+            let columns = view.to_columns_window(window.unchecked_into()).await?;
+            if !query.search_window(&columns, start_row, &mut matches, max_matches) {
+                return Ok((matches, false));
+            }
+        }
+
+        Ok((matches, true))
+    }
+
+    /// Render the value of a single cell of the current `View` as text, by its
+    /// row and column index.
+    ///
+    /// # Arguments
+    /// - `row` The cell's row index in the `View`.
+    /// - `col` The cell's column index in the `View`.
+    pub async fn cell_as_jsvalue(&self, row: u32, col: u32) -> Result<js_sys::JsString, JsValue> {
+        let view = self.get_view().ok_or("No `View` to copy from")?;
+        let num_rows = view.num_rows().await? as u32;
+        let num_cols = view.num_columns().await? as u32;
+        if row >= num_rows || col >= num_cols {
+            return Err(format!(
+                "Cell ({}, {}) is out of range for a `View` of {} rows and {} columns",
+                row, col, num_rows, num_cols
+            )
+            .into());
+        }
+
+        let window = json!({
+            "start_row": row as f64,
+            "end_row": (row + 1) as f64,
+            "start_col": col as f64,
+            "end_col": (col + 1) as f64
+        });
+
+        let columns = view.to_columns_window(window.unchecked_into()).await?;
+        let name = js_sys::Object::keys(&columns)
+            .iter()
+            .find(|x| !x.as_string().unwrap_or_default().starts_with("__"))
+            .into_jserror()?;
+
+        let value = js_sys::Reflect::get(&columns, &name)?
+            .unchecked_into::<js_sys::Array>()
+            .get(0);
+
+        if value.is_null() || value.is_undefined() {
+            Ok(js_sys::JsString::from(""))
+        } else if value.is_string() {
+            Ok(value.unchecked_into())
+        } else {
+            js_sys::JSON::stringify(&value)
+        }
+    }
+
+    /// Render only `rows` of the current `View` as CSV, e.g. a plugin's
+    /// selection.  Contiguous runs of `rows` are fetched with a single
+    /// `to_csv()` call each, and the header is kept only from the first.
+    pub async fn csv_rows_as_jsvalue(&self, rows: &[usize]) -> Result<js_sys::JsString, JsValue> {
+        let view = self.flat_as_jsvalue(false).await?;
+        let mut csv = String::new();
+        for (start, end) in contiguous_ranges(rows) {
+            let opts = json!({
+                "formatted": true,
+                "start_row": start as f64,
+                "end_row": end as f64
+            });
+
+            let chunk = String::from(view.to_csv(opts.unchecked_into()).await?);
+            if csv.is_empty() {
+                csv.push_str(&chunk);
+            } else if let Some((_, body)) = chunk.split_once('\n') {
+                csv.push_str(body);
+            }
+        }
+
+        Ok(csv.into())
+    }
+
     pub fn get_view(&self) -> Option<View> {
         self.borrow()
             .view_sub
@@ -236,6 +640,21 @@ impl Session {
         self.borrow().stats.clone()
     }
 
+    /// The snippets listed in the expression editor, which are not part of the
+    /// `ViewConfig` and so survive `restore()` and `swap_table()`.
+    pub fn get_expression_snippets(&self) -> Vec<ExpressionSnippet> {
+        self.borrow()
+            .snippets
+            .clone()
+            .unwrap_or_else(ExpressionSnippet::builtins)
+    }
+
+    /// Replace the expression editor's snippets, or restore the built-ins if
+    /// `None`.
+    pub fn set_expression_snippets(&self, snippets: Option<Vec<ExpressionSnippet>>) {
+        self.borrow_mut().snippets = snippets;
+    }
+
     pub fn get_view_config(&self) -> Ref<ViewConfig> {
         Ref::map(self.borrow(), |x| &x.config)
     }
@@ -251,6 +670,21 @@ impl Session {
     /// # Arguments
     /// - `column` The name of the column (or expression).
     pub async fn get_column_values(&self, column: String) -> Result<Vec<String>, JsValue> {
+        self.get_column_values_range(column, None).await
+    }
+
+    /// Get a page of unique column values for a given column name, in sorted
+    /// order, so that long lists of values can be fetched incrementally.
+    ///
+    /// # Arguments
+    /// - `column` The name of the column (or expression).
+    /// - `range` The `(start, end)` indices of the values to fetch, or `None`
+    ///   for all values.
+    pub async fn get_column_values_range(
+        &self,
+        column: String,
+        range: Option<(usize, usize)>,
+    ) -> Result<Vec<String>, JsValue> {
         let expressions = self.borrow().config.expressions.clone();
         let config = ViewConfig {
             group_by: vec![column],
@@ -262,8 +696,22 @@ impl Session {
         let js_config = config.as_jsvalue()?;
         let table = self.borrow().table.clone().unwrap();
         let view = table.view(&js_config).await?;
+
+        // The first row of a `group_by` view is the total row, which is not a
+        // value and must be skipped along with the CSV header.
+        let (options, skip) = match range {
+            Some((start, end)) => (
+                json!({
+                    "start_row": (start + 1) as u32,
+                    "end_row": (end + 1) as u32
+                }),
+                1,
+            ),
+            None => (json!({}), 2),
+        };
+
         let csv = view
-            .to_csv(json!({}))
+            .to_csv(options)
             .await?
             .as_string()
             .ok_or_else(|| JsValue::from("Bad CSV"))?;
@@ -282,10 +730,57 @@ impl Session {
                     x.to_owned()
                 }
             })
-            .skip(2)
+            .skip(skip)
             .collect::<Vec<String>>())
     }
 
+    /// Get the minimum and maximum values of a numeric column, from the total
+    /// row of `group_by` views aggregated by `"low"` and `"high"`, or `None`
+    /// if the column has no values.
+    ///
+    /// # Arguments
+    /// - `column` The name of the column (or expression).
+    pub async fn get_column_range(&self, column: String) -> Result<Option<(f64, f64)>, JsValue> {
+        let mut bounds = vec![];
+        for aggregate in [SingleAggregate::Low, SingleAggregate::High] {
+            let expressions = self.borrow().config.expressions.clone();
+            let aggregate = Aggregate::SingleAggregate(aggregate);
+            let config = ViewConfig {
+                group_by: vec![column.clone()],
+                columns: vec![Some(column.clone())],
+                expressions,
+                aggregates: [(column.clone(), aggregate)].into_iter().collect(),
+                ..ViewConfig::default()
+            };
+
+            let js_config = config.as_jsvalue()?;
+            let table = self.borrow().table.clone().into_jserror()?;
+            let view = table.view(&js_config).await?;
+            let window = json!({
+                "start_row": 0,
+                "end_row": 1
+            });
+
+            let result = view.to_columns_window(window.unchecked_into()).await;
+            ApiFuture::spawn(async move {
+                view.delete().await?;
+                Ok(())
+            });
+
+            let value = js_sys::Reflect::get(&result?, &JsValue::from(&column))?
+                .unchecked_into::<js_sys::Array>()
+                .get(0)
+                .as_f64();
+
+            bounds.push(value);
+        }
+
+        match bounds[..] {
+            [Some(min), Some(max)] => Ok(Some((min, max))),
+            _ => Ok(None),
+        }
+    }
+
     pub fn set_update_column_defaults(
         &self,
         config_update: &mut ViewConfigUpdate,
@@ -376,6 +871,29 @@ impl Session {
         self.stats_changed.emit_all(());
     }
 
+    /// The concrete lower bound of a `RelativeDateFilter` on `column` as of
+    /// now.  `Date` columns compare against UTC midnight, as the filter
+    /// `<input>` does, while `Datetime` columns use local midnight.
+    pub fn resolve_relative_date(&self, column: &str, relative: RelativeDateFilter) -> FilterTerm {
+        let start = relative.start_date(local_today());
+        let posix = match self.metadata().get_column_table_type(column) {
+            Some(Type::Date) => start.and_hms(0, 0, 0).timestamp_millis() as f64,
+            _ => local_date_to_posix(start),
+        };
+
+        FilterTerm::Scalar(Scalar::DateTime(posix))
+    }
+
+    /// Replace any `RelativeDateFilter` terms in `config` with concrete lower
+    /// bounds via `resolve_relative_date()`.
+    fn resolve_relative_filters(&self, config: &mut ViewConfig) {
+        for Filter(column, _, term) in config.filter.iter_mut() {
+            if let FilterTerm::Relative { relative } = term {
+                *term = self.resolve_relative_date(column, *relative);
+            }
+        }
+    }
+
     async fn validate_view_config(&self) -> Result<(), JsValue> {
         let config = self.borrow().config.clone();
         let table_columns = self
@@ -397,6 +915,10 @@ impl Session {
             .ok_or("`restore()` called before `load()`")?
             .clone();
 
+        if let Some(duplicate) = duplicate_expression_aliases(&config.expressions).first() {
+            return Err(duplicate.to_string().into());
+        }
+
         let arr = config
             .expressions
             .iter()
@@ -458,6 +980,14 @@ impl Session {
             .aggregates
             .retain(|column, _| view_columns.contains(column.as_str()));
 
+        for (column, aggregate) in config.aggregates.iter() {
+            if !self.metadata().is_valid_aggregate(column, aggregate) {
+                return Err(
+                    format!("Invalid aggregate \"{}\" for \"{}\"", aggregate, column).into(),
+                );
+            }
+        }
+
         self.borrow_mut().config = config;
         Ok(())
     }
@@ -471,7 +1001,9 @@ impl<'a> ValidSession<'a> {
     /// `update()` subscription, consuming this `ValidSession<'_>` and returning
     /// the original `&Session`.
     pub async fn create_view(&self) -> Result<&'a Session, JsValue> {
-        let js_config = self.0.borrow().config.as_jsvalue()?;
+        let mut config = self.0.borrow().config.clone();
+        self.0.resolve_relative_filters(&mut config);
+        let js_config = config.as_jsvalue()?;
         let table = self
             .0
             .borrow()
@@ -508,3 +1040,51 @@ impl<'a> Drop for ValidSession<'a> {
         self.0.view_created.emit_all(());
     }
 }
+
+/// Format a `to_columns()` cell for `markdown_table()`;  row paths are
+/// joined by `/`.
+fn markdown_cell(value: &JsValue) -> String {
+    if value.is_null() || value.is_undefined() {
+        "".to_owned()
+    } else if let Some(x) = value.as_string() {
+        x
+    } else if let Some(path) = value.dyn_ref::<js_sys::Array>() {
+        path.iter()
+            .map(|x| markdown_cell(&x))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    } else {
+        js_sys::JSON::stringify(value)
+            .map(String::from)
+            .unwrap_or_default()
+    }
+}
+
+/// Collapse `rows` into sorted, de-duplicated `(start, end)` ranges, with
+/// `end` exclusive as expected by `View::to_csv()`.
+fn contiguous_ranges(rows: &[usize]) -> Vec<(usize, usize)> {
+    let mut sorted = rows.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for row in sorted {
+        match ranges.last_mut() {
+            Some((_, end)) if *end == row => *end += 1,
+            _ => ranges.push((row, row + 1)),
+        }
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_contiguous_ranges() {
+        assert_eq!(contiguous_ranges(&[]), vec![]);
+        assert_eq!(contiguous_ranges(&[3, 1, 2, 2, 7]), vec![(1, 4), (7, 8)]);
+    }
+}
@@ -0,0 +1,190 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::config::*;
+
+use itertools::Itertools;
+use serde::Serialize;
+
+/// The result of checking a `ViewConfig` against a `Table` without applying
+/// it.  Each field lists the names in the matching `ViewConfig` field which
+/// are invalid, e.g. a column missing from the `Table` or an `aggregates`
+/// entry whose aggregate is not valid for its column's type, while
+/// `duplicate_aliases` lists the `expressions` which share an alias.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ConfigValidation {
+    pub valid: bool,
+    pub expressions: Vec<String>,
+    pub duplicate_aliases: Vec<DuplicateAlias>,
+    pub columns: Vec<String>,
+    pub group_by: Vec<String>,
+    pub split_by: Vec<String>,
+    pub sort: Vec<String>,
+    pub filter: Vec<String>,
+    pub aggregates: Vec<String>,
+}
+
+impl ConfigValidation {
+    /// Check `config`, given the expressions the `Table` rejected and the
+    /// type of every valid column name (including expression aliases).
+    ///
+    /// # Arguments
+    /// - `config` The `ViewConfig` to check.
+    /// - `invalid_expressions` The `expressions` which failed validation.
+    /// - `column_type` The `Table` type of a column, or `None` if unknown.
+    pub fn new(
+        config: &ViewConfig,
+        invalid_expressions: Vec<String>,
+        column_type: impl Fn(&str) -> Option<Type>,
+    ) -> Self {
+        let is_numeric =
+            |name: &str| matches!(column_type(name), Some(Type::Integer | Type::Float));
+        let aggregates = config
+            .aggregates
+            .iter()
+            .filter(|(name, aggregate)| match (column_type(name), aggregate) {
+                (None, _) => true,
+                (Some(ty), Aggregate::SingleAggregate(_)) => {
+                    !ty.aggregates_iter().any(|x| &x == *aggregate)
+                }
+                (Some(_), Aggregate::MultiAggregate(MultiAggregate::WeightedMean, weight)) => {
+                    !is_numeric(name) || !is_numeric(weight)
+                }
+            })
+            .map(|(name, _)| name.clone())
+            .sorted()
+            .collect::<Vec<_>>();
+
+        let mut result = ConfigValidation {
+            valid: false,
+            expressions: invalid_expressions,
+            duplicate_aliases: duplicate_expression_aliases(&config.expressions),
+            columns: unknown(config.columns.iter().flatten(), &column_type),
+            group_by: unknown(config.group_by.iter(), &column_type),
+            split_by: unknown(config.split_by.iter(), &column_type),
+            sort: unknown(config.sort.iter().map(|x| &x.0), &column_type),
+            filter: unknown(config.filter.iter().map(|x| &x.0), &column_type),
+            aggregates,
+        };
+
+        result.valid = result.duplicate_aliases.is_empty()
+            && [
+                &result.expressions,
+                &result.columns,
+                &result.group_by,
+                &result.split_by,
+                &result.sort,
+                &result.filter,
+                &result.aggregates,
+            ]
+            .iter()
+            .all(|x| x.is_empty());
+
+        result
+    }
+}
+
+/// The names in `names` for which `column_type` is `None`.
+fn unknown<'a>(
+    names: impl Iterator<Item = &'a String>,
+    column_type: &impl Fn(&str) -> Option<Type>,
+) -> Vec<String> {
+    names
+        .filter(|x| column_type(x).is_none())
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use wasm_bindgen_test::*;
+
+    fn column_type(name: &str) -> Option<Type> {
+        match name {
+            "Name" => Some(Type::String),
+            "Sales" => Some(Type::Float),
+            _ => None,
+        }
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_valid_config() {
+        let config = ViewConfig {
+            columns: vec![Some("Sales".to_owned()), None],
+            group_by: vec!["Name".to_owned()],
+            ..ViewConfig::default()
+        };
+
+        let result = ConfigValidation::new(&config, vec![], column_type);
+        assert!(result.valid);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_invalid_config() {
+        let aggregates = HashMap::from([
+            (
+                "Name".to_owned(),
+                Aggregate::SingleAggregate(SingleAggregate::Mean),
+            ),
+            (
+                "Sales".to_owned(),
+                Aggregate::SingleAggregate(SingleAggregate::Sum),
+            ),
+        ]);
+
+        let config = ViewConfig {
+            columns: vec![Some("Sales".to_owned()), Some("Profit".to_owned())],
+            split_by: vec!["Region".to_owned()],
+            aggregates,
+            ..ViewConfig::default()
+        };
+
+        let result = ConfigValidation::new(&config, vec!["bad".to_owned()], column_type);
+        assert!(!result.valid);
+        assert_eq!(result.expressions, vec!["bad".to_owned()]);
+        assert_eq!(result.columns, vec!["Profit".to_owned()]);
+        assert_eq!(result.split_by, vec!["Region".to_owned()]);
+        assert_eq!(result.aggregates, vec!["Name".to_owned()]);
+        assert!(result.group_by.is_empty());
+        assert!(result.duplicate_aliases.is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_duplicate_aliases() {
+        let expressions = vec![
+            "// Profit\n\"Sales\" * 0.1".to_owned(),
+            "// Other\n1 + 2".to_owned(),
+            "// Profit\n\"Sales\" - \"Cost\"".to_owned(),
+        ];
+
+        let config = ViewConfig {
+            columns: vec![Some("Profit".to_owned())],
+            expressions: expressions.clone(),
+            ..ViewConfig::default()
+        };
+
+        let column_type = |name: &str| match name {
+            "Profit" | "Other" => Some(Type::Float),
+            x => column_type(x),
+        };
+
+        let result = ConfigValidation::new(&config, vec![], column_type);
+        assert!(!result.valid);
+        assert!(result.columns.is_empty());
+        assert_eq!(result.duplicate_aliases, vec![DuplicateAlias {
+            alias: "Profit".to_owned(),
+            expressions: vec![expressions[0].clone(), expressions[2].clone()],
+        }]);
+
+        assert!(result.duplicate_aliases[0]
+            .to_string()
+            .starts_with("Duplicate expression alias \"Profit\""));
+    }
+}
@@ -147,8 +147,17 @@ impl ViewConfig {
                 update.split_by = Some(config.split_by);
             }
             DragTarget::Sort => {
+                // A column is sorted at most once, so dropping an already
+                // sorted column reorders it, keeping its `SortDir`.
+                let sort_dir = self
+                    .sort
+                    .iter()
+                    .find(|x| x.0 == column)
+                    .map_or(SortDir::Asc, |x| x.1);
+
+                config.sort.retain(|x| x.0 != column);
                 let index = std::cmp::min(index as usize, config.sort.len());
-                config.sort.insert(index, Sort(column, SortDir::Asc));
+                config.sort.insert(index, Sort(column, sort_dir));
                 update.sort = Some(config.sort);
             }
             DragTarget::Filter => {
@@ -164,3 +173,74 @@ impl ViewConfig {
         update
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn sort(column: &str, sort_dir: SortDir) -> Sort {
+        Sort(column.to_owned(), sort_dir)
+    }
+
+    fn sort_config() -> ViewConfig {
+        ViewConfig {
+            sort: vec![
+                sort("a", SortDir::Desc),
+                sort("b", SortDir::AscAbs),
+                sort("c", SortDir::Asc),
+            ],
+            ..ViewConfig::default()
+        }
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_sort_reorder_keeps_sort_dir() {
+        let update = sort_config().create_drag_drop_update(
+            "a".to_owned(),
+            2,
+            DragTarget::Sort,
+            DragEffect::Move(DragTarget::Sort),
+            &ViewConfigRequirements::default(),
+        );
+
+        assert_eq!(
+            update.sort,
+            Some(vec![
+                sort("b", SortDir::AscAbs),
+                sort("c", SortDir::Asc),
+                sort("a", SortDir::Desc),
+            ])
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_sort_copy_does_not_duplicate() {
+        let update = sort_config().create_drag_drop_update(
+            "c".to_owned(),
+            0,
+            DragTarget::Sort,
+            DragEffect::Copy,
+            &ViewConfigRequirements::default(),
+        );
+
+        assert_eq!(
+            update.sort,
+            Some(vec![
+                sort("c", SortDir::Asc),
+                sort("a", SortDir::Desc),
+                sort("b", SortDir::AscAbs),
+            ])
+        );
+
+        let update = ViewConfig::default().create_drag_drop_update(
+            "a".to_owned(),
+            0,
+            DragTarget::Sort,
+            DragEffect::Copy,
+            &ViewConfigRequirements::default(),
+        );
+
+        assert_eq!(update.sort, Some(vec![sort("a", SortDir::Asc)]));
+    }
+}
@@ -0,0 +1,41 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use serde::{Deserialize, Serialize};
+
+/// A named, reusable expression fragment listed in the expression editor's
+/// snippets panel, inserted at the cursor when clicked.  Column names in the
+/// built-in snippets are placeholders for the user to replace.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExpressionSnippet {
+    pub name: String,
+    pub expression: String,
+}
+
+impl ExpressionSnippet {
+    fn new(name: &str, expression: &str) -> Self {
+        ExpressionSnippet {
+            name: name.to_owned(),
+            expression: expression.to_owned(),
+        }
+    }
+
+    /// The snippets listed when none have been set via
+    /// `setExpressionSnippets()`.
+    pub fn builtins() -> Vec<ExpressionSnippet> {
+        vec![
+            ExpressionSnippet::new(
+                "Percent Change",
+                "(\"Current\" - \"Previous\") / \"Previous\" * 100",
+            ),
+            ExpressionSnippet::new("Percent Of Total", "percent_of(\"Part\", \"Total\")"),
+            ExpressionSnippet::new("Bucket By 10", "bucket(\"Value\", 10)"),
+            ExpressionSnippet::new("Bucket By Month", "bucket(\"Date\", 'M')"),
+        ]
+    }
+}
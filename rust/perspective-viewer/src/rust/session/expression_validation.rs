@@ -0,0 +1,141 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::js::perspective::PerspectiveValidationError;
+
+use serde::Serialize;
+
+/// The result of validating a single expression against a `Table`, without
+/// adding it to the `ViewConfig`.  Serialized as `{valid, type, error,
+/// details}`, where `details` locates the `error` within the expression.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ExpressionValidation {
+    pub valid: bool,
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub expression_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<ExpressionError>,
+}
+
+/// An expression validation error, located as the `[start, end)` character
+/// offsets of the offending token within the (possibly multi-line)
+/// expression.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct ExpressionError {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl ExpressionError {
+    /// Locate an ExprTK error, reported as a zero-based `line` and `column`,
+    /// within `expr`.  The range covers the whole token at that position, or
+    /// the last character of the line if the error is reported past its end
+    /// (e.g. a missing closing parenthesis).
+    pub fn new(expr: &str, err: PerspectiveValidationError) -> Self {
+        let chars = expr.chars().collect::<Vec<_>>();
+        let line = err.line.max(0) as usize;
+        let line_start = match line {
+            0 => 0,
+            line => chars
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| **c == '\n')
+                .nth(line - 1)
+                .map_or(chars.len(), |(idx, _)| idx + 1),
+        };
+
+        let line_end = chars[line_start..]
+            .iter()
+            .position(|c| *c == '\n')
+            .map_or(chars.len(), |idx| line_start + idx);
+
+        let column = err.column.max(0) as usize;
+        let last = line_end.saturating_sub(1).max(line_start);
+        let start = (line_start + column).min(last);
+        let end = token_end(&chars, start).min(line_end).max(start + 1);
+        ExpressionError {
+            message: err.error_message,
+            start,
+            end,
+        }
+    }
+
+    /// The one-based `(line_number, column)` of the character offset
+    /// `offset` in `expr`, as used by `monaco` markers.
+    pub fn to_position(expr: &str, offset: usize) -> (u32, u32) {
+        let mut position = (1, 1);
+        for c in expr.chars().take(offset) {
+            position = match c {
+                '\n' => (position.0 + 1, 1),
+                _ => (position.0, position.1 + 1),
+            };
+        }
+
+        position
+    }
+}
+
+/// The end offset of the token which starts at `start`: an identifier or
+/// number, a quoted column name or string literal, or a single character.
+fn token_end(chars: &[char], start: usize) -> usize {
+    let is_word = |c: &char| c.is_alphanumeric() || *c == '_' || *c == '.';
+    match chars.get(start) {
+        Some(quote @ ('"' | '\'')) => chars[start + 1..]
+            .iter()
+            .position(|c| c == quote)
+            .map_or(chars.len(), |idx| start + idx + 2),
+        Some(c) if is_word(c) => chars[start..]
+            .iter()
+            .position(|c| !is_word(c))
+            .map_or(chars.len(), |idx| start + idx),
+        _ => start + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn error(expr: &str, line: i32, column: i32) -> (usize, usize) {
+        let err = PerspectiveValidationError {
+            error_message: "Error".to_owned(),
+            line,
+            column,
+        };
+
+        let err = ExpressionError::new(expr, err);
+        (err.start, err.end)
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_error_covers_token() {
+        assert_eq!(error("abs(\"Sales\") + foo", 0, 15), (15, 18));
+        assert_eq!(error("\"Sales\" * \"Profit\"", 0, 10), (10, 18));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_error_multi_line() {
+        let expr = "// Profit\nvar x := 1;\nx + bar";
+        let (start, end) = error(expr, 2, 4);
+        assert_eq!((start, end), (26, 29));
+        assert_eq!(ExpressionError::to_position(expr, start), (3, 5));
+        assert_eq!(ExpressionError::to_position(expr, end), (3, 8));
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_error_past_end_of_line() {
+        assert_eq!(error("abs(\"Sales\"", 0, 11), (10, 11));
+    }
+}
@@ -0,0 +1,72 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// The phases of `<perspective-viewer>.load()`, in the order they are
+/// reported.  `Complete` is always reported last, whether or not `load()`
+/// succeeded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadPhase {
+    SetTable,
+    Validate,
+    CreateView,
+    Complete,
+}
+
+impl LoadPhase {
+    /// An approximate percentage of the `load()` which is finished when this
+    /// phase begins.
+    const fn percent(&self) -> f64 {
+        match self {
+            Self::SetTable => 0.0,
+            Self::Validate => 33.0,
+            Self::CreateView => 67.0,
+            Self::Complete => 100.0,
+        }
+    }
+}
+
+/// The `detail` of a `"perspective-viewer-load-progress"` event.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct LoadProgress {
+    pub phase: LoadPhase,
+    pub percent: f64,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl LoadProgress {
+    pub fn new(phase: LoadPhase) -> Self {
+        LoadProgress {
+            phase,
+            percent: phase.percent(),
+            error: None,
+        }
+    }
+
+    /// The final `Complete` progress of a `load()`, with its error message
+    /// if it failed.
+    pub fn complete<T>(result: &Result<T, JsValue>) -> Self {
+        let error = result.as_ref().err().map(|err| {
+            err.dyn_ref::<js_sys::Error>()
+                .map(|x| String::from(x.message()))
+                .or_else(|| err.as_string())
+                .unwrap_or_else(|| "unknown error".to_owned())
+        });
+
+        LoadProgress {
+            error,
+            ..Self::new(LoadPhase::Complete)
+        }
+    }
+}
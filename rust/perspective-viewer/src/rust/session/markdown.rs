@@ -0,0 +1,88 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use itertools::Itertools;
+
+/// A column of already-formatted cells, for `markdown_table()`.
+pub struct MarkdownColumn {
+    pub name: String,
+    pub is_numeric: bool,
+    pub cells: Vec<String>,
+}
+
+/// Render `columns` as a GitHub-flavored Markdown table, with numeric columns
+/// right-aligned.  Shorter columns are padded with empty cells.
+pub fn markdown_table(columns: &[MarkdownColumn]) -> String {
+    let num_rows = columns
+        .iter()
+        .map(|x| x.cells.len())
+        .max()
+        .unwrap_or_default();
+
+    let header = columns.iter().map(|x| escape_cell(&x.name));
+    let align = columns
+        .iter()
+        .map(|x| if x.is_numeric { "---:" } else { "---" }.to_owned());
+
+    let rows = (0..num_rows).map(|idx| {
+        let cells = columns
+            .iter()
+            .map(|x| x.cells.get(idx).map(|x| escape_cell(x)).unwrap_or_default());
+        markdown_row(cells)
+    });
+
+    [markdown_row(header), markdown_row(align)]
+        .into_iter()
+        .chain(rows)
+        .join("\n")
+}
+
+fn markdown_row(cells: impl Iterator<Item = String>) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Cells may not contain a literal `|` or line break.
+fn escape_cell(cell: &str) -> String {
+    cell.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    fn column(name: &str, is_numeric: bool, cells: &[&str]) -> MarkdownColumn {
+        MarkdownColumn {
+            name: name.to_owned(),
+            is_numeric,
+            cells: cells.iter().map(|x| x.to_string()).collect(),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_markdown_table() {
+        let table = markdown_table(&[
+            column("Name", false, &["a", "b"]),
+            column("Sales", true, &["1.5", "20"]),
+        ]);
+
+        assert_eq!(
+            table,
+            "| Name | Sales |\n| --- | ---: |\n| a | 1.5 |\n| b | 20 |"
+        );
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_markdown_table_escapes() {
+        let table = markdown_table(&[column("A|B", false, &["x|y", "line\nbreak"])]);
+        assert_eq!(table, "| A\\|B |\n| --- |\n| x\\|y |\n| line<br>break |");
+    }
+}
@@ -187,6 +187,25 @@ impl SessionMetadata {
         self.as_ref().map(|meta| meta.edit_port)
     }
 
+    /// Returns the `Table`'s own schema, without expression columns.
+    pub fn get_table_schema(&self) -> Option<&'_ HashMap<String, Type>> {
+        self.as_ref().map(|meta| &meta.table_schema)
+    }
+
+    /// Returns the `Table` type of every column, including expression columns
+    /// (by alias), or `None` if no `Table` is loaded.
+    pub fn get_schema(&self) -> Option<HashMap<String, Type>> {
+        let meta = self.as_ref()?;
+        let exprs = meta.expr_meta.iter().flat_map(|x| x.schema.iter());
+        Some(
+            meta.table_schema
+                .iter()
+                .chain(exprs)
+                .map(|(name, ty)| (name.clone(), *ty))
+                .collect(),
+        )
+    }
+
     /// Returns the type of a column name relative to the `Table`.  Despite the
     /// name, `get_column_table_type()` also returns the `Table` type for
     /// Expressions, which despite living on the `View` still have a `table`
@@ -247,4 +266,15 @@ impl SessionMetadata {
             })
         })
     }
+
+    /// Is `aggregate` one of the `get_column_aggregates()` for the column
+    /// `name`, e.g. not `mean` for a `string` column?
+    ///
+    /// # Arguments
+    /// - `name` The column name (or expression alias) to aggregate.
+    /// - `aggregate` The aggregate to check.
+    pub fn is_valid_aggregate(&self, name: &str, aggregate: &Aggregate) -> bool {
+        self.get_column_aggregates(name)
+            .map_or(false, |mut aggs| aggs.any(|x| &x == aggregate))
+    }
 }
@@ -0,0 +1,235 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// The most matches a search collects;  the rest of the `View` is not
+/// searched once this many are found.
+pub const SEARCH_MAX_MATCHES: usize = 10_000;
+
+/// How many rows of the `View` are fetched at a time while searching.
+pub const SEARCH_CHUNK_ROWS: u32 = 10_000;
+
+/// The options argument of `search()`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SearchOptions {
+    pub regex: bool,
+    pub case_sensitive: bool,
+}
+
+/// A parsed search query, which matches a cell when its value (as text,
+/// unformatted) contains the query text or matches the query regex.
+pub enum SearchQuery {
+    Text { text: String, case_sensitive: bool },
+    Regex(js_sys::RegExp),
+}
+
+impl SearchQuery {
+    pub fn new(query: &str, options: &SearchOptions) -> Result<Self, JsValue> {
+        if options.regex {
+            let flags = if options.case_sensitive { "" } else { "i" };
+            let constructor = js_sys::Reflect::get(&js_sys::global(), &"RegExp".into())?;
+            let args = js_sys::Array::of2(&query.into(), &flags.into());
+            let regex = js_sys::Reflect::construct(constructor.unchecked_ref(), &args)?;
+            Ok(Self::Regex(regex.unchecked_into()))
+        } else if options.case_sensitive {
+            Ok(Self::Text {
+                text: query.to_owned(),
+                case_sensitive: true,
+            })
+        } else {
+            Ok(Self::Text {
+                text: query.to_lowercase(),
+                case_sensitive: false,
+            })
+        }
+    }
+
+    pub fn matches(&self, text: &str) -> bool {
+        match self {
+            Self::Text {
+                text: query,
+                case_sensitive: true,
+            } => text.contains(query.as_str()),
+            Self::Text { text: query, .. } => text.to_lowercase().contains(query.as_str()),
+            Self::Regex(regex) => regex.test(text),
+        }
+    }
+
+    /// Append the cells of a `to_columns()` window starting at `start_row`
+    /// which match this query to `matches`, in row-major order, returning
+    /// `false` if `max_matches` was reached.  Strings, numbers and booleans
+    /// are searched;  `null` and row path (`"__"`) columns are not.
+    pub fn search_window(
+        &self,
+        window: &js_sys::Object,
+        start_row: u32,
+        matches: &mut Vec<SearchMatch>,
+        max_matches: usize,
+    ) -> bool {
+        let columns = js_sys::Object::entries(window)
+            .iter()
+            .map(|x| x.unchecked_into::<js_sys::Array>())
+            .filter_map(|x| {
+                Some((
+                    x.get(0).as_string()?,
+                    x.get(1).dyn_into::<js_sys::Array>().ok()?,
+                ))
+            })
+            .filter(|(name, _)| !name.starts_with("__"))
+            .collect::<Vec<_>>();
+
+        let num_rows = columns
+            .iter()
+            .map(|x| x.1.length())
+            .max()
+            .unwrap_or_default();
+        for row in 0..num_rows {
+            for (name, values) in columns.iter() {
+                let value = values.get(row);
+                let text = value
+                    .as_string()
+                    .or_else(|| value.as_f64().map(|x| x.to_string()))
+                    .or_else(|| value.as_bool().map(|x| x.to_string()));
+
+                if text.map(|x| self.matches(&x)).unwrap_or_default() {
+                    if matches.len() >= max_matches {
+                        return false;
+                    }
+
+                    matches.push(SearchMatch {
+                        row: start_row + row,
+                        column: name.clone(),
+                    });
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// A matching cell, by its row index and column path in the `View`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct SearchMatch {
+    pub row: u32,
+    pub column: String,
+}
+
+/// The matches of the last `search()` and the current position within them,
+/// which is transient UI state and never saved.
+#[derive(Clone, Debug, Default)]
+pub struct SearchResults {
+    matches: Vec<SearchMatch>,
+    index: Option<usize>,
+}
+
+impl SearchResults {
+    pub fn new(matches: Vec<SearchMatch>) -> Self {
+        Self {
+            matches,
+            index: None,
+        }
+    }
+
+    pub fn matches(&self) -> &[SearchMatch] {
+        &self.matches
+    }
+
+    /// Move to the next match, wrapping to the first.
+    pub fn next_match(&mut self) -> Option<&SearchMatch> {
+        let len = self.matches.len();
+        self.index = self.index.map_or(Some(0), |x| Some((x + 1) % len.max(1)));
+        self.current()
+    }
+
+    /// Move to the previous match, wrapping to the last.
+    pub fn prev_match(&mut self) -> Option<&SearchMatch> {
+        let len = self.matches.len();
+        self.index = Some(
+            self.index
+                .unwrap_or(0)
+                .checked_sub(1)
+                .unwrap_or(len.max(1) - 1),
+        );
+        self.current()
+    }
+
+    fn current(&self) -> Option<&SearchMatch> {
+        self.matches.get(self.index?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use wasm_bindgen_test::*;
+
+    fn search_match(row: u32, column: &str) -> SearchMatch {
+        SearchMatch {
+            row,
+            column: column.to_owned(),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_query_matches() {
+        let text = SearchQuery::new("york", &SearchOptions::default()).unwrap();
+        assert!(text.matches("New York"));
+        let options = SearchOptions {
+            case_sensitive: true,
+            ..SearchOptions::default()
+        };
+
+        let case_sensitive = SearchQuery::new("york", &options).unwrap();
+        assert!(!case_sensitive.matches("New York"));
+        let options = SearchOptions {
+            regex: true,
+            ..SearchOptions::default()
+        };
+
+        let regex = SearchQuery::new("^new\\s", &options).unwrap();
+        assert!(regex.matches("New York"));
+        assert!(!regex.matches("Newark"));
+        assert!(SearchQuery::new("(", &options).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_search_window() {
+        let window = json!({
+            "__ROW_PATH__": [["a"], ["b"]],
+            "City": ["Boston", "Austin"],
+            "Sales": [10, 120]
+        });
+
+        let query = SearchQuery::new("10", &SearchOptions::default()).unwrap();
+        let mut matches = vec![];
+        assert!(query.search_window(&window, 5, &mut matches, 10));
+        assert_eq!(matches, vec![search_match(5, "Sales")]);
+
+        let query = SearchQuery::new("s", &SearchOptions::default()).unwrap();
+        let mut matches = vec![];
+        assert!(!query.search_window(&window, 0, &mut matches, 1));
+        assert_eq!(matches, vec![search_match(0, "City")]);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_results_navigation() {
+        let mut results = SearchResults::new(vec![search_match(0, "A"), search_match(3, "B")]);
+        assert_eq!(results.prev_match(), Some(&search_match(3, "B")));
+        assert_eq!(results.next_match(), Some(&search_match(0, "A")));
+        assert_eq!(results.next_match(), Some(&search_match(3, "B")));
+        assert_eq!(results.next_match(), Some(&search_match(0, "A")));
+        assert_eq!(SearchResults::default().next_match(), None);
+        assert_eq!(SearchResults::default().prev_match(), None);
+    }
+}
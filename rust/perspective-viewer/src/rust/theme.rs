@@ -6,15 +6,26 @@
 // of the Apache License 2.0.  The full license can be found in the LICENSE
 // file.
 
+mod high_contrast;
+
+pub use self::high_contrast::*;
+
 use crate::utils::*;
 
 use async_std::sync::Mutex;
+use std::cell::{Cell, RefCell};
 use std::ops::Deref;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::*;
 
+macro_rules! iter_index {
+    ($x:expr) => {
+        (0..$x.length()).map(|x| $x.item(x))
+    };
+}
+
 /// The available themes as detected in the browser environment or set
 /// explicitly when CORS prevents detection.  Detection is expensive and
 /// typically must be performed only once, when `document.styleSheets` is
@@ -32,7 +43,10 @@ impl Deref for Theme {
 pub struct ThemeData {
     viewer_elem: HtmlElement,
     themes: Mutex<Option<Vec<String>>>,
+    registered: RefCell<Vec<String>>,
+    high_contrast: Cell<HighContrast>,
     pub theme_config_updated: PubSub<(Vec<String>, Option<usize>)>,
+    pub theme_changed: PubSub<(Option<String>, Option<String>)>,
 }
 
 impl Theme {
@@ -40,7 +54,10 @@ impl Theme {
         let theme = Self(Rc::new(ThemeData {
             viewer_elem: elem.clone(),
             themes: Default::default(),
+            registered: Default::default(),
+            high_contrast: Default::default(),
             theme_config_updated: PubSub::default(),
+            theme_changed: PubSub::default(),
         }));
 
         ApiFuture::spawn(theme.clone().init());
@@ -48,17 +65,63 @@ impl Theme {
     }
 
     async fn init(self) -> Result<(), JsValue> {
-        self.set_theme_attribute(self.get_name().await.as_deref())
+        self.set_theme_attribute(self.get_name().await.as_deref())?;
+        self.toggle_high_contrast(self.0.high_contrast.get().is_active())
+    }
+
+    /// Whether the high-contrast overlay is currently applied.
+    pub fn is_high_contrast(&self) -> bool {
+        self.0.viewer_elem.class_list().contains("high-contrast")
+    }
+
+    fn toggle_high_contrast(&self, active: bool) -> Result<(), JsValue> {
+        self.0
+            .viewer_elem
+            .class_list()
+            .toggle_with_force("high-contrast", active)?;
+
+        Ok(())
+    }
+
+    /// Apply or remove the high-contrast overlay, which composes with (rather
+    /// than replaces) the active theme.  Emits `theme_changed` and returns
+    /// `true` if the overlay flipped, in which case plugins should be
+    /// restyled.
+    pub async fn set_high_contrast(&self, mode: HighContrast) -> Result<bool, JsValue> {
+        self.0.high_contrast.set(mode);
+        self.refresh_high_contrast().await
+    }
+
+    /// Re-resolve the current `HighContrast` mode, e.g. when the OS
+    /// `prefers-contrast` setting changes while it is `Auto`.  Like
+    /// `set_high_contrast()`, emits `theme_changed` and returns `true` only if
+    /// the overlay flipped.
+    pub async fn refresh_high_contrast(&self) -> Result<bool, JsValue> {
+        let active = self.0.high_contrast.get().is_active();
+        if active == self.is_high_contrast() {
+            return Ok(false);
+        }
+
+        self.toggle_high_contrast(active)?;
+        let name = self.get_name().await;
+        self.theme_changed.emit_all((name.clone(), name));
+        Ok(true)
     }
 
     /// Get the available theme names from the browser environment by parsing
-    /// readable stylesheets.  This method is memoized - the state can be
-    /// flushed by calling `reset()`.
+    /// readable stylesheets, plus any added by `register()`.  This method is
+    /// memoized - the state can be flushed by calling `reset()`.
     pub async fn get_themes(&self) -> Result<Vec<String>, JsValue> {
         let mut mutex = self.0.themes.lock().await;
         if mutex.is_none() {
             await_dom_loaded().await?;
-            let themes = get_theme_names(&self.0.viewer_elem)?;
+            let mut themes = get_theme_names(&self.0.viewer_elem)?;
+            for name in self.0.registered.borrow().iter() {
+                if !themes.contains(name) {
+                    themes.push(name.to_owned());
+                }
+            }
+
             *mutex = Some(themes);
         }
 
@@ -72,6 +135,42 @@ impl Theme {
         *mutex = themes;
     }
 
+    /// Add a theme from its CSS text, which should style
+    /// `perspective-viewer[theme="<name>"]`.  The CSS is injected into the
+    /// `document` as a `<style>` element, replacing the CSS of any theme
+    /// previously registered with the same name.
+    pub async fn register(&self, name: &str, css: &str) -> Result<(), JsValue> {
+        let doc = window().unwrap().document().unwrap();
+        let styles = doc.query_selector_all("style[data-perspective-theme]")?;
+        let existing = iter_index!(styles)
+            .flatten()
+            .map(|x| x.unchecked_into::<HtmlElement>())
+            .find(|x| x.get_attribute("data-perspective-theme").as_deref() == Some(name));
+
+        let style = match existing {
+            Some(style) => style,
+            None => {
+                let style = doc.create_element("style")?;
+                style.set_attribute("data-perspective-theme", name)?;
+                doc.head().into_jserror()?.append_child(&style)?;
+                style.unchecked_into()
+            }
+        };
+
+        style.set_text_content(Some(css));
+        if !self.0.registered.borrow().iter().any(|x| x == name) {
+            self.0.registered.borrow_mut().push(name.to_owned());
+        }
+
+        let mut themes = self.get_themes().await?;
+        if !themes.iter().any(|x| x == name) {
+            themes.push(name.to_owned());
+            self.reset(Some(themes)).await;
+        }
+
+        Ok(())
+    }
+
     pub async fn get_config(&self) -> Result<(Vec<String>, Option<usize>), JsValue> {
         let themes = self.get_themes().await?;
         let name = self.0.viewer_elem.get_attribute("theme");
@@ -98,9 +197,19 @@ impl Theme {
         }
     }
 
-    /// Set the theme by name, or `None` for the default theme.
+    /// Set the theme by name, or `None` for the default theme.  Emits
+    /// `theme_changed` with the old and new names if the active theme changed.
     pub async fn set_name(&self, theme: Option<&str>) -> Result<(), JsValue> {
         let (themes, _) = self.get_config().await?;
+        let old_name = self
+            .0
+            .viewer_elem
+            .get_attribute("theme")
+            .or_else(|| themes.first().cloned());
+
+        let new_name = theme
+            .map(|x| x.to_owned())
+            .or_else(|| themes.first().cloned());
         let index = if let Some(theme) = theme {
             self.set_theme_attribute(Some(theme))?;
             themes.iter().position(|x| x == theme)
@@ -113,14 +222,38 @@ impl Theme {
         };
 
         self.theme_config_updated.emit_all((themes, index));
+        if old_name != new_name {
+            self.theme_changed.emit_all((old_name, new_name));
+        }
+
         Ok(())
     }
-}
 
-macro_rules! iter_index {
-    ($x:expr) => {
-        (0..$x.length()).map(|x| $x.item(x))
-    };
+    /// Advance to the next available theme, wrapping around to the first.
+    /// Returns whether the theme changed, which it will not if fewer than two
+    /// themes are available.
+    pub async fn cycle_next(&self) -> Result<bool, JsValue> {
+        self.cycle(1).await
+    }
+
+    /// Advance to the previous available theme, wrapping around to the last.
+    /// Returns whether the theme changed, which it will not if fewer than two
+    /// themes are available.
+    pub async fn cycle_prev(&self) -> Result<bool, JsValue> {
+        self.cycle(-1).await
+    }
+
+    async fn cycle(&self, step: isize) -> Result<bool, JsValue> {
+        let (themes, index) = self.get_config().await?;
+        if themes.len() < 2 {
+            return Ok(false);
+        }
+
+        let len = themes.len() as isize;
+        let next = (index.unwrap_or_default() as isize + step).rem_euclid(len) as usize;
+        self.set_name(Some(&themes[next])).await?;
+        Ok(true)
+    }
 }
 
 fn fill_rule_theme_names(
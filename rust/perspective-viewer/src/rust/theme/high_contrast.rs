@@ -0,0 +1,89 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::utils::*;
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(test)]
+use wasm_bindgen_test::*;
+
+/// Whether to apply the high-contrast overlay (stronger borders and text) on
+/// top of the selected theme.  `Auto` defers to the OS via the
+/// `prefers-contrast` media query.  Like the theme itself, this is applied as
+/// CSS on the `<perspective-viewer>` element, but it is not persisted by
+/// `save()`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HighContrast {
+    Auto,
+    On,
+    Off,
+}
+
+impl Default for HighContrast {
+    fn default() -> Self {
+        HighContrast::Auto
+    }
+}
+
+impl Display for HighContrast {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::On => "on",
+            Self::Off => "off",
+        })
+    }
+}
+
+impl FromStr for HighContrast {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "on" => Ok(Self::On),
+            "off" => Ok(Self::Off),
+            x => Err(format!("Unknown high contrast mode \"{}\"", x)),
+        }
+    }
+}
+
+impl HighContrast {
+    /// The OS preference consulted by `Auto`.
+    pub const MEDIA_QUERY: &'static str = "(prefers-contrast: more)";
+
+    /// Resolve this setting, querying the OS preference for `Auto`.
+    pub fn is_active(&self) -> bool {
+        match self {
+            Self::On => true,
+            Self::Off => false,
+            Self::Auto => media_matches(Self::MEDIA_QUERY),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[wasm_bindgen_test]
+    pub fn test_round_trip() {
+        for mode in [HighContrast::Auto, HighContrast::On, HighContrast::Off] {
+            assert_eq!(HighContrast::from_str(&mode.to_string()), Ok(mode));
+        }
+
+        assert!(HighContrast::from_str("maximum").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn test_overrides() {
+        assert!(HighContrast::On.is_active());
+        assert!(!HighContrast::Off.is_active());
+    }
+}
@@ -50,6 +50,22 @@ where
     }
 }
 
+impl<T> ApiFuture<T>
+where
+    Result<T, JsValue>: IntoJsResult + 'static,
+{
+    /// Convert to a `Promise` with an `abort()` method which calls `on_abort`.
+    /// Cancellation is cooperative - `on_abort` should only flag the task,
+    /// which must check the flag at a point where it can stop without leaving
+    /// state half-applied.
+    pub fn into_abortable_promise(self, on_abort: impl Fn() + 'static) -> js_sys::Promise {
+        let promise = js_sys::Promise::from(self);
+        let abort = Closure::wrap(Box::new(on_abort) as Box<dyn Fn()>).into_js_value();
+        js_sys::Reflect::set(&promise, js_intern!("abort"), &abort).unwrap();
+        promise
+    }
+}
+
 impl<T> From<ApiFuture<T>> for JsValue
 where
     Result<T, JsValue>: IntoJsResult + 'static,
@@ -33,6 +33,18 @@ impl AsBlob for js_sys::JsString {
     }
 }
 
+/// Append the string `part` to `blob`, e.g. the next chunk of a CSV.  The
+/// result references `blob` rather than copying it, so the browser may keep
+/// the accumulated data out of the JavaScript heap.
+pub fn append_to_blob(blob: &web_sys::Blob, part: &str) -> Result<web_sys::Blob, JsValue> {
+    let array = [JsValue::from(blob), JsValue::from(part)]
+        .iter()
+        .collect::<js_sys::Array>();
+    let mut options = web_sys::BlobPropertyBag::new();
+    options.type_("text/plain");
+    web_sys::Blob::new_with_blob_sequence_and_options(&array, &options)
+}
+
 impl AsBlob for js_sys::Object {
     fn as_blob(&self) -> Result<web_sys::Blob, JsValue> {
         let array = [js_sys::JSON::stringify(self)?]
@@ -0,0 +1,88 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Keywords `CSS.supports()` accepts as a `color` which do not name a color
+/// of their own.
+static NON_COLOR_KEYWORDS: &[&str] = &[
+    "currentcolor",
+    "inherit",
+    "initial",
+    "revert",
+    "revert-layer",
+    "unset",
+];
+
+/// Resolve a CSS color the browser supports to `#rrggbb`, by round-tripping it
+/// through a canvas `fillStyle`, which serializes opaque colors as hex and
+/// translucent ones as `rgba()`.  Alpha is dropped, as `<input type="color">`
+/// cannot represent it.
+fn to_hex_color(color: &str) -> Option<String> {
+    let document = web_sys::window()?.document()?;
+    let canvas = document.create_element("canvas").ok()?;
+    let canvas = canvas.dyn_into::<web_sys::HtmlCanvasElement>().ok()?;
+    let ctx = canvas.get_context("2d").ok()??;
+    let ctx = ctx.unchecked_into::<web_sys::CanvasRenderingContext2d>();
+    ctx.set_fill_style(&JsValue::from(color));
+    let style = ctx.fill_style().as_string()?;
+    if style.starts_with('#') {
+        return Some(style);
+    }
+
+    let channels = style
+        .strip_prefix("rgba(")?
+        .strip_suffix(')')?
+        .split(',')
+        .take(3)
+        .map(|x| x.trim().parse::<u8>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    match channels.as_slice() {
+        [r, g, b] => Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        _ => None,
+    }
+}
+
+/// Normalize a CSS color string from user input to `#rrggbb`, the only format
+/// `<input type="color">` accepts, or `None` if it is not a color the browser
+/// supports.
+///
+/// # Arguments
+/// - `color` The color string to normalize.
+pub fn normalize_color(color: &str) -> Option<String> {
+    let color = color.trim();
+    let is_keyword = NON_COLOR_KEYWORDS.contains(&color.to_lowercase().as_str());
+    let is_supported = web_sys::Css::supports_with_value("color", color).unwrap_or_default();
+    if color.is_empty() || is_keyword || !is_supported {
+        None
+    } else {
+        to_hex_color(color)
+    }
+}
+
+/// Whether two CSS color strings are the same color, e.g. `#ABC` and
+/// `rgb(170, 187, 204)`.  Unsupported colors only match an identical string.
+pub fn is_same_color(a: &str, b: &str) -> bool {
+    a == b || matches!((normalize_color(a), normalize_color(b)), (Some(x), Some(y)) if x == y)
+}
+
+/// Normalize a CSS color string via `normalize_color()`, falling back to
+/// `default` with a console warning when `color` is not a supported color.
+///
+/// # Arguments
+/// - `color` The color string to validate.
+/// - `default` The color to use instead of an invalid `color`.
+pub fn validate_color(color: &str, default: &str) -> String {
+    normalize_color(color).unwrap_or_else(|| {
+        let msg = format!("Invalid color \"{}\", using \"{}\"", color, default);
+        web_sys::console::warn_1(&JsValue::from(msg));
+        default.to_owned()
+    })
+}
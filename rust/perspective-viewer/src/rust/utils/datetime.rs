@@ -8,7 +8,8 @@
 
 use crate::utils::*;
 
-use chrono::{DateTime, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use chrono::format::StrftimeItems;
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use wasm_bindgen::prelude::*;
 
 fn input_value_format(x: &str) -> Result<&str, JsValue> {
@@ -45,3 +46,63 @@ pub fn str_to_utc_posix(val: &str) -> Result<f64, JsValue> {
         })
         .into_jserror()
 }
+
+/// The `strftime` specifiers the datagrid's `StrftimeFormat` implements, so
+/// formats which preview correctly here also render correctly in the grid.
+/// Padding modifiers such as `%-d` are not supported.
+const SUPPORTED_SPECIFIERS: &str = "%YCymbhBdejaAuwHkIlMSpPFDTRVG";
+
+/// Check that every specifier in `format` is in `SUPPORTED_SPECIFIERS`.
+fn validate_format(format: &str) -> Result<(), String> {
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c == '%' {
+            match chars.next() {
+                Some(x) if SUPPORTED_SPECIFIERS.contains(x) => (),
+                Some(x) => return Err(format!("Unsupported specifier \"%{}\"", x)),
+                None => return Err(format!("Invalid format \"{}\"", format)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Format the current local time with a `strftime`-style `format` string, as
+/// a preview for user-supplied custom date formats.
+///
+/// # Arguments
+/// - `format` The `strftime` format string, which may only use the specifiers
+///   in `SUPPORTED_SPECIFIERS`.
+/// - `week_numbers` Whether to suffix the ISO week number.
+pub fn format_now(format: &str, week_numbers: bool) -> Result<String, String> {
+    validate_format(format)?;
+    let now = local_now();
+    let text = now
+        .format_with_items(StrftimeItems::new(format))
+        .to_string();
+    if week_numbers {
+        Ok(format!("{} (W{:02})", text, now.iso_week().week()))
+    } else {
+        Ok(text)
+    }
+}
+
+fn local_now() -> DateTime<FixedOffset> {
+    Utc.timestamp_millis(js_sys::Date::now() as i64)
+        .with_timezone(&get_local_tz())
+}
+
+/// The current date in the local timezone.
+pub fn local_today() -> NaiveDate {
+    local_now().date().naive_local()
+}
+
+/// The posix timestamp of local midnight at the start of `date`.
+pub fn local_date_to_posix(date: NaiveDate) -> f64 {
+    let midnight = get_local_tz()
+        .from_local_datetime(&date.and_hms(0, 0, 0))
+        .unwrap();
+
+    DateTime::<Utc>::from(midnight).timestamp_millis() as f64
+}
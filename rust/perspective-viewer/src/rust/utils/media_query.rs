@@ -0,0 +1,57 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use super::closure::*;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::*;
+
+/// Whether the CSS media `query` currently matches, e.g.
+/// `"(prefers-reduced-motion: reduce)"`.
+pub fn media_matches(query: &str) -> bool {
+    window()
+        .and_then(|x| x.match_media(query).ok())
+        .flatten()
+        .map(|x| x.matches())
+        .unwrap_or_default()
+}
+
+/// Calls a callback with the new match state whenever a CSS media query
+/// changes, e.g. when the user changes an OS accessibility setting, until
+/// dropped.
+pub struct MediaQueryHandle {
+    query: MediaQueryList,
+    callback: Closure<dyn Fn(JsValue)>,
+}
+
+impl MediaQueryHandle {
+    pub fn new(query: &str, on_change: impl Fn(bool) + 'static) -> Result<Self, JsValue> {
+        let query = window()
+            .unwrap()
+            .match_media(query)?
+            .ok_or_else(|| JsValue::from(format!("Unsupported media query \"{}\"", query)))?;
+
+        let callback = ({
+            let query = query.clone();
+            move |_: JsValue| on_change(query.matches())
+        })
+        .into_closure();
+
+        query.add_event_listener_with_callback("change", callback.as_ref().unchecked_ref())?;
+        Ok(Self { query, callback })
+    }
+}
+
+impl Drop for MediaQueryHandle {
+    fn drop(&mut self) {
+        self.query
+            .remove_event_listener_with_callback("change", self.callback.as_ref().unchecked_ref())
+            .unwrap();
+    }
+}
@@ -16,15 +16,18 @@ mod async_callback;
 mod blob;
 mod clone;
 mod closure;
+mod color;
 mod custom_element;
 mod datetime;
 mod debounce;
 mod download;
 mod errors;
 mod json;
+mod media_query;
 mod pubsub;
 mod request_animation_frame;
 mod scope;
+mod transition;
 mod wasm_abi;
 mod weak_scope;
 
@@ -36,15 +39,18 @@ pub use async_callback::*;
 pub use blob::*;
 pub use clone::*;
 pub use closure::*;
+pub use color::*;
 pub use custom_element::*;
 pub use datetime::*;
 pub use debounce::*;
 pub use download::*;
 pub use errors::*;
 pub use json::*;
+pub use media_query::*;
 pub use pubsub::*;
 pub use request_animation_frame::*;
 pub use scope::*;
+pub use transition::*;
 pub use wasm_abi::*;
 pub use weak_scope::*;
 
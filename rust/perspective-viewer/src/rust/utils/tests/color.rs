@@ -0,0 +1,44 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::utils::*;
+
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen_test]
+fn test_normalize_valid_colors() {
+    assert_eq!(normalize_color("#ABC"), Some("#aabbcc".to_owned()));
+    assert_eq!(normalize_color(" #00ff7f "), Some("#00ff7f".to_owned()));
+    assert_eq!(
+        normalize_color("rgb(255, 0, 12)"),
+        Some("#ff000c".to_owned())
+    );
+    assert_eq!(
+        normalize_color("rgba(255, 0, 12, 0.5)"),
+        Some("#ff000c".to_owned())
+    );
+    assert_eq!(normalize_color("SteelBlue"), Some("#4682b4".to_owned()));
+}
+
+#[wasm_bindgen_test]
+fn test_normalize_malformed_colors() {
+    assert_eq!(normalize_color(""), None);
+    assert_eq!(normalize_color("#ggg"), None);
+    assert_eq!(normalize_color("00ff7f"), None);
+    assert_eq!(normalize_color("rgb(0, 0)"), None);
+    assert_eq!(normalize_color("currentColor"), None);
+    assert_eq!(normalize_color("inherit"), None);
+    assert_eq!(normalize_color("notacolor"), None);
+}
+
+#[wasm_bindgen_test]
+fn test_validate_color_falls_back_to_default() {
+    assert_eq!(validate_color("#FFF", "#000"), "#ffffff");
+    assert_eq!(validate_color("javascript:alert(1)", "#000"), "#000");
+    assert_eq!(validate_color("rgb(", "#123456"), "#123456");
+}
@@ -7,6 +7,8 @@
 // file.
 
 mod clone;
+mod color;
 mod debounce;
 mod pubsub;
 mod request_animation_frame;
+mod transition;
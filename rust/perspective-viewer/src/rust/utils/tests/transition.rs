@@ -0,0 +1,51 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::utils::*;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+fn make_div(style: &str) -> web_sys::HtmlElement {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let div = document
+        .create_element("div")
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>();
+
+    div.set_attribute("style", style).unwrap();
+    document.body().unwrap().append_child(&div).unwrap();
+    div
+}
+
+#[wasm_bindgen_test]
+fn test_transition_duration() {
+    let div = make_div("transition: opacity 0.3s, width 100ms 250ms;");
+    assert_eq!(transition_duration(&div).unwrap(), 350_f64);
+    div.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_await_transition_end_without_transition() {
+    let div = make_div("");
+    let start = js_sys::Date::now();
+    await_transition_end(&div).await.unwrap();
+    assert!(js_sys::Date::now() - start < 100_f64);
+    div.remove();
+}
+
+#[wasm_bindgen_test]
+async fn test_await_transition_end_follows_transition() {
+    let div = make_div("opacity: 1; transition: opacity 200ms;");
+    await_animation_frame().await.unwrap();
+    let start = js_sys::Date::now();
+    div.style().set_property("opacity", "0").unwrap();
+    await_transition_end(&div).await.unwrap();
+    assert!(js_sys::Date::now() - start >= 190_f64);
+    div.remove();
+}
@@ -0,0 +1,76 @@
+////////////////////////////////////////////////////////////////////////////////
+//
+// Copyright (c) 2018, the Perspective Authors.
+//
+// This file is part of the Perspective library, distributed under the terms
+// of the Apache License 2.0.  The full license can be found in the LICENSE
+// file.
+
+use crate::utils::*;
+
+use futures::channel::oneshot::*;
+use futures::future::select;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Parse a single CSS `<time>` value, e.g. `"0.3s"` or `"200ms"`, into
+/// milliseconds.
+fn parse_css_time(time: &str) -> f64 {
+    let time = time.trim();
+    let parsed = match time.strip_suffix("ms") {
+        Some(ms) => ms.parse::<f64>().ok(),
+        None => time
+            .strip_suffix('s')
+            .and_then(|s| s.parse::<f64>().ok())
+            .map(|s| s * 1000_f64),
+    };
+
+    parsed.unwrap_or_default()
+}
+
+/// The time in milliseconds until the longest CSS transition declared on
+/// `elem` would finish, including its delay, or `0.0` if it has none.
+pub fn transition_duration(elem: &web_sys::Element) -> Result<f64, JsValue> {
+    let style = web_sys::window()
+        .unwrap()
+        .get_computed_style(elem)?
+        .into_jserror()?;
+
+    let durations = style.get_property_value("transition-duration")?;
+    let delays = style.get_property_value("transition-delay")?;
+    let delays = delays.split(',').map(parse_css_time).collect::<Vec<_>>();
+    Ok(durations
+        .split(',')
+        .map(parse_css_time)
+        .enumerate()
+        .map(|(idx, duration)| duration + delays[idx % delays.len()])
+        .fold(0_f64, f64::max))
+}
+
+/// An `async` which resolves on `elem`'s next `transitionend` event, or
+/// immediately if `elem` declares no transition.  As a transition only runs
+/// when its property actually changes, this also resolves once the declared
+/// duration has elapsed without one.
+pub async fn await_transition_end(elem: &web_sys::Element) -> Result<(), JsValue> {
+    let duration = transition_duration(elem)?;
+    if duration <= 0_f64 {
+        return Ok(());
+    }
+
+    let (sender, receiver) = channel::<()>();
+    let target = elem.clone();
+    let mut sender = Some(sender);
+    let closure = (move |event: web_sys::Event| {
+        let is_target = event.target().as_ref() == Some(target.unchecked_ref());
+        if let Some(sender) = is_target.then(|| sender.take()).flatten() {
+            let _ = sender.send(());
+        }
+    })
+    .into_closure_mut();
+
+    let func = closure.as_ref().unchecked_ref();
+    elem.add_event_listener_with_callback("transitionend", func)?;
+    let timeout = set_timeout(duration.ceil() as i32 + 50);
+    select(Box::pin(receiver), Box::pin(timeout)).await;
+    elem.remove_event_listener_with_callback("transitionend", func)
+}
@@ -70,6 +70,30 @@ export interface IPerspectiveViewerPlugin extends HTMLElement {
      */
     get min_config_columns(): number | undefined;
 
+    /**
+     * Set by `<perspective-viewer>` before each `draw()` and `restyle()`;
+     * when `true`, plugins should render animations (e.g. pulsing cell
+     * backgrounds) statically instead.  Follows the OS
+     * `prefers-reduced-motion` setting unless overridden via
+     * `setReducedMotion()`.
+     */
+    reduced_motion?: boolean;
+
+    /**
+     * Set by `<perspective-viewer>` before each `draw()`;  when `true`,
+     * plugins should render their entire `View` (e.g. disabling row
+     * virtualization) so the browser's print captures all of it.  Toggled
+     * via `setPrintMode()`.
+     */
+    print_mode?: boolean;
+
+    /**
+     * Set by `<perspective-viewer>` before each `draw()`;  when `true`,
+     * plugins may fade the new render in over the previous one.  Enabled via
+     * `setRedrawFade()`, and always `false` while `reduced_motion` is.
+     */
+    redraw_fade?: boolean;
+
     /**
      * The named column labels, if desired.  Named columns behave differently
      * in drag/drop mode than unnamed columns, having replace/swap behavior
@@ -79,6 +103,19 @@ export interface IPerspectiveViewerPlugin extends HTMLElement {
      */
     get config_column_names(): string[] | undefined;
 
+    /**
+     * Optional.  The config controls this plugin supports, reported by
+     * `<perspective-viewer>`'s `getPluginCapabilities()`.  Omitted fields
+     * default to supported, and `aggregates` to every aggregate.
+     */
+    readonly capabilities?: {
+        aggregates?: string[];
+        group_by?: boolean;
+        split_by?: boolean;
+        sort?: boolean;
+        filter?: boolean;
+    };
+
     /**
      * Render this plugin using the provided `View`.  While there is no
      * provision to cancel a render in progress per se, calling a method on
@@ -129,6 +166,75 @@ export interface IPerspectiveViewerPlugin extends HTMLElement {
      */
     resize(): Promise<void>;
 
+    /**
+     * Optional.  Report this plugin's preferred dimensions in pixels for
+     * `view`, without drawing.  Called by `<perspective-viewer>`'s
+     * `measure()` method;  when absent, the plugin element's scroll
+     * dimensions are used instead.
+     */
+    measure?(view: perspective.View): Promise<{width: number; height: number}>;
+
+    /**
+     * Optional.  Render this plugin's current state to an image `Blob` in
+     * the requested `format`, scaled to `width` and `height` when provided.
+     * Called by `<perspective-viewer>`'s `toImage()` method, which rejects
+     * for plugins without it.
+     */
+    to_image?(options: {
+        format: "png" | "svg";
+        width?: number;
+        height?: number;
+    }): Promise<Blob>;
+
+    /**
+     * Optional.  Report this plugin's current selection, as row indices into
+     * the current `View` (and optionally column names), or `undefined` when
+     * nothing is selected.  Used by `<perspective-viewer>`'s
+     * `copy("selection")` and `download("selection")` methods.
+     */
+    get_selection?(): {rows: number[]; columns?: string[]} | undefined;
+
+    /**
+     * Optional.  Replace this plugin's current selection, in the same format
+     * returned by `get_selection()`.  Called by `<perspective-viewer>`'s
+     * `setSelection()` method.
+     */
+    set_selection?(selection: {rows: number[]; columns?: string[]}): void;
+
+    /**
+     * Optional.  Scroll `row` (an index into the current `View`, already
+     * clamped to its rows) and, when provided, the column named `column`
+     * into view.  Called by `<perspective-viewer>`'s `scrollTo()` method,
+     * which rejects for plugins without it.
+     */
+    scroll_to?(target: {row: number; column?: string}): Promise<void>;
+
+    /**
+     * Optional.  Highlight the cell at `target`, or clear the highlight if
+     * `target` is `null`.  Called by `<perspective-viewer>`'s `searchNext()`
+     * and `searchPrev()` methods after `scroll_to()`.
+     */
+    set_highlight?(target: {row: number; column: string} | null): void;
+
+    /**
+     * Optional.  Set the columns to freeze to the left during horizontal
+     * scroll.  Called by `<perspective-viewer>` before each `draw()` with the
+     * columns set via `setPinnedColumns()` or `restore()`;  plugins without
+     * this method do not support pinning.
+     */
+    set_pinned_columns?(columns: string[]): void;
+
+    /**
+     * Optional.  Set the row and column header heights in pixels, where
+     * `null` is the plugin's default.  Called by `<perspective-viewer>`
+     * before each `draw()` and `restyle()` with the heights set via
+     * `setRowHeight()`, `setHeaderHeight()` or `restore()`.
+     */
+    set_row_sizing?(sizing: {
+        row_height: number | null;
+        header_height: number | null;
+    }): void;
+
     /**
      * Notify the plugin that the style environment has changed.  Useful for
      * plugins which read CSS styles via `window.getComputedStyle()`.
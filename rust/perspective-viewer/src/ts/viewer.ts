@@ -13,9 +13,57 @@ import type * as perspective from "@finos/perspective";
 export type PerspectiveViewerConfig = perspective.ViewConfig & {
     plugin?: string;
     settings?: boolean;
+    density?: "comfortable" | "compact";
     plugin_config?: any;
+    type_defaults?: {[column_type: string]: any};
+    pinned_columns?: string[];
+    settings_width?: number | null;
+    row_height?: number | null;
+    header_height?: number | null;
 };
 
+/**
+ * The result of `diffConfig()`.  `added` maps fields only the new config has
+ * to their values, `removed` lists fields only the old config has, `changed`
+ * maps fields whose values differ to their `old` and `new` values, and
+ * `update` is the partial config which `restore()`s one into the other.
+ */
+export type PerspectiveViewerConfigDiff = {
+    added: PerspectiveViewerConfig;
+    removed: string[];
+    changed: {[field: string]: {old: any; new: any}};
+    update: PerspectiveViewerConfig;
+};
+
+/**
+ * A storage backend for named presets, set via `setPresetStorage()`.  Each
+ * method may return its result directly or as a `Promise`.
+ */
+export interface PerspectiveViewerPresetStorage {
+    get(
+        name: string
+    ):
+        | PerspectiveViewerConfig
+        | undefined
+        | Promise<PerspectiveViewerConfig | undefined>;
+    set(name: string, config: PerspectiveViewerConfig): void | Promise<void>;
+    list(): string[] | Promise<string[]>;
+}
+
+/**
+ * The mutation interface passed to the callback of `batch()`.  Each method
+ * merges a change into the batch without rendering;  a field set more than
+ * once takes its last value.
+ */
+export interface PerspectiveViewerConfigBatch {
+    restore(config: PerspectiveViewerConfig | string | ArrayBuffer): void;
+    setColumns(columns: (string | null)[] | null): void;
+    setGroupBy(group_by: string[] | null): void;
+    setSplitBy(split_by: string[] | null): void;
+    setSort(sort: perspective.ViewConfig["sort"] | null): void;
+    setFilter(filter: perspective.ViewConfig["filter"] | null): void;
+}
+
 /**
  * The Custom Elements implementation for `<perspective-viewer>`, as well at its
  * API.  `PerspectiveViewerElement` should not be constructed directly (like its
@@ -43,7 +91,14 @@ export interface IPerspectiveViewerElement {
      * deleted, but it will bed de-referenced by this `<perspective-viewer>`.
      *
      * @category Data
-     * @param data A `Promise` which resolves to the `perspective.Table`
+     * @param data A `Promise` which resolves to the `perspective.Table`, or
+     * an Arrow `ArrayBuffer` or `Uint8Array`.  A `perspective.Table` created
+     * from Arrow data is owned by the caller, who may retrieve it via
+     * `getTable()` to `delete()` it.
+     * @param options An optional object with the field `skip_defaults`,
+     * which when `true` skips choosing the active plugin's default columns
+     * for the new `perspective.Table`.  Use this when a `restore()` will
+     * immediately follow, to avoid briefly rendering the defaults.
      * @returns {Promise<void>} A promise which resolves once the data is
      * loaded, a `perspective.View` has been created, and the active plugin has
      * rendered.
@@ -59,8 +114,56 @@ export interface IPerspectiveViewerElement {
      * const tbl = perspective.table("x,y\n1,a\n2,b");
      * my_viewer.load(tbl);
      * ```
+     * @example <caption>Load an Arrow ArrayBuffer</caption>
+     * ```javascript
+     * const my_viewer = document.getElementById('#my_viewer');
+     * const resp = await fetch("superstore.arrow");
+     * my_viewer.load(await resp.arrayBuffer());
+     * ```
+     * @example <caption>Load and restore a saved config</caption>
+     * ```javascript
+     * const my_viewer = document.getElementById('#my_viewer');
+     * my_viewer.load(tbl, {skip_defaults: true});
+     * await my_viewer.restore(saved_config);
+     * ```
      */
-    load(table: Promise<perspective.Table> | perspective.Table): Promise<void>;
+    load(
+        table:
+            | Promise<perspective.Table | ArrayBuffer | Uint8Array>
+            | perspective.Table
+            | ArrayBuffer
+            | Uint8Array,
+        options?: {skip_defaults?: boolean}
+    ): Promise<void>;
+
+    /**
+     * Replace this `<perspective-viewer>`'s `perspective.Table` while keeping
+     * the current view config, unlike `load()` which resets it.  The
+     * `perspective.View` is recreated in place, so the active plugin's state
+     * (e.g. scroll position) is preserved.  As with `load()`, the previous
+     * `perspective.Table` is not deleted.
+     *
+     * @category Data
+     * @param data A `perspective.Table`, an Arrow `ArrayBuffer` or
+     * `Uint8Array`, or a `Promise` which resolves to one.
+     * @returns A `Promise` which resolves once the new `perspective.View` has
+     * rendered, or rejects with a description of each incompatibility if the
+     * view config references columns which the new `perspective.Table` lacks
+     * or which have changed type, leaving the previous `perspective.Table`
+     * loaded.
+     * @example
+     * ```javascript
+     * const tbl = await worker.table(await resp.arrayBuffer());
+     * await viewer.replaceTable(tbl);
+     * ```
+     */
+    replaceTable(
+        data:
+            | Promise<perspective.Table | ArrayBuffer | Uint8Array>
+            | perspective.Table
+            | ArrayBuffer
+            | Uint8Array
+    ): Promise<void>;
 
     /**
      * Redraw this `<perspective-viewer>` and plugin when its dimensions or
@@ -103,6 +206,69 @@ export interface IPerspectiveViewerElement {
      */
     setAutoSize(autosize): void;
 
+    /**
+     * Set the minimum change in width or height, in pixels, which will
+     * trigger an auto-size redraw.  Raising this avoids redrawing on every
+     * frame of a CSS transition;  smaller changes are ignored until they
+     * accumulate past the threshold.  Defaults to `1`.
+     *
+     * @category Util
+     * @param threshold The threshold in pixels, or `undefined` to reset to the
+     * default.
+     * @example
+     * ```javascript
+     * viewer.setResizeThreshold(20);
+     * ```
+     */
+    setResizeThreshold(threshold?: number): void;
+
+    /**
+     * Set the maximum number of rows `download()` and `copy()` will export.
+     * When the row count (from the viewer's current stats, or the plugin's
+     * selection for `"selection"` exports) exceeds this limit, these methods
+     * reject with a descriptive error before any data is serialized, instead
+     * of freezing the browser tab on a very large `View`.  There is no limit
+     * by default.
+     *
+     * @category UI
+     * @param max_rows The row limit, or `undefined` to remove it.
+     * @example
+     * ```javascript
+     * viewer.setMaxExportRows(1_000_000);
+     * try {
+     *     await viewer.download();
+     * } catch (e) {
+     *     alert(e);
+     * }
+     * ```
+     */
+    setMaxExportRows(max_rows?: number): void;
+
+    /**
+     * Override this viewer's keyboard shortcuts, which are handled while focus
+     * is within the viewer but not while typing in an input or the expression
+     * editor.  The defaults are `"Alt+Shift+C"` to toggle the settings panel,
+     * `"Alt+Shift+E"` to download a `.csv` and `"Alt+Shift+R"` to reset.
+     *
+     * Chords are `+`-separated modifiers (`Ctrl`, `Shift`, `Alt`, `Meta`)
+     * followed by a key.  Letter and digit keys match the physical key, so
+     * `"Alt+E"` works regardless of the character `Alt` produces.
+     *
+     * @category UI
+     * @param bindings An object mapping `"toggle_config"`, `"export_csv"` or
+     * `"reset"` to a chord, or to `null` to unbind the action.  Actions not in
+     * `bindings` are unchanged;  `null` restores all of the defaults.
+     * @example
+     * ```javascript
+     * viewer.setKeybindings({export_csv: "Ctrl+Shift+S", reset: null});
+     * ```
+     */
+    setKeybindings(bindings?: {
+        toggle_config?: string | null;
+        export_csv?: string | null;
+        reset?: string | null;
+    } | null): void;
+
     /**
      * Returns the `perspective.Table()` which was supplied to `load()`
      *
@@ -120,6 +286,373 @@ export interface IPerspectiveViewerElement {
      */
     getTable(wait_for_table?: boolean): Promise<perspective.Table>;
 
+    /**
+     * Set the columns to freeze to the left while scrolling horizontally, and
+     * redraw.  Only plugins which implement `set_pinned_columns()` act on
+     * this;  others log a warning and ignore it.  Pinned columns are persisted
+     * by `save()` as `pinned_columns`, and marked in the column selector.
+     *
+     * @category Persistence
+     * @param columns An array of column names, or `undefined` to unpin all
+     * columns.
+     * @example
+     * ```javascript
+     * await viewer.setPinnedColumns(["Order ID", "Customer"]);
+     * ```
+     */
+    setPinnedColumns(columns?: string[]): Promise<void>;
+
+    /**
+     * Get the `columns` of this viewer's config, in order, without the rest
+     * of `save()`.  Empty slots (e.g. an unset chart axis) are `null`.
+     *
+     * @category Persistence
+     * @returns An array of column names.
+     * @example
+     * ```javascript
+     * const columns = viewer.getColumns();
+     * ```
+     */
+    getColumns(): Array<string | null>;
+
+    /**
+     * Replace only the `columns` of this viewer's config and redraw, leaving
+     * `group_by`, `filter`, the plugin and all other settings unchanged.
+     * Rejects, without changing the config, if any column is not in the
+     * `perspective.Table` or `expressions`.
+     *
+     * @category Persistence
+     * @param columns An array of column names, in order, which may contain
+     * `null` for empty slots.
+     * @example <caption>Hide a column</caption>
+     * ```javascript
+     * const columns = viewer.getColumns().filter((x) => x !== "Sales");
+     * await viewer.setColumns(columns);
+     * ```
+     */
+    setColumns(columns: Array<string | null>): Promise<void>;
+
+    /**
+     * Get the type of every column in the loaded `perspective.Table`,
+     * including `expressions` by alias.  Types are the `Table` types, before
+     * any aggregate is applied, e.g. for choosing which style controls to
+     * show for a column.
+     *
+     * @category Data
+     * @returns A `Promise` which resolves to an object mapping column names to
+     * types, or rejects if no `perspective.Table` is loaded.
+     * @example
+     * ```javascript
+     * const schema = await viewer.getSchema();
+     * if (schema["Sales"] === "float") {
+     *     // ...
+     * }
+     * ```
+     */
+    getSchema(): Promise<{[column: string]: string}>;
+
+    /**
+     * Get the aggregates which are valid for a column given its type, e.g.
+     * `mean` is only valid for numeric columns.  `restore()` rejects (and
+     * resets the config) when `aggregates` contains an invalid entry, so use
+     * this to populate aggregate pickers in a host application.
+     *
+     * @category Data
+     * @param column The column name (or expression alias).
+     * @returns An array of aggregates, in the form accepted by `aggregates`
+     * in `restore()`.  Weighted means are `["weighted mean", column]` pairs.
+     * @example
+     * ```javascript
+     * const aggs = viewer.getValidAggregates("Sales");
+     * if (aggs.includes("mean")) {
+     *     await viewer.restore({aggregates: {Sales: "mean"}});
+     * }
+     * ```
+     */
+    getValidAggregates(column: string): Array<string | [string, string]>;
+
+    /**
+     * Check a config against the loaded `perspective.Table` without applying
+     * it, e.g. to warn before a `restore()` of a saved config whose columns
+     * have since been removed from the table.  Unlike `restore()`, an
+     * invalid config does not reset this `<perspective-viewer>`.
+     *
+     * @category Persistence
+     * @param config A config in any format `restore()` accepts, checked as if
+     * applied to the current config.  Defaults to the current config.
+     * @returns A `Promise` which resolves to `{valid, ...}`, where every
+     * other field lists the invalid names in the matching config field, and
+     * `duplicate_aliases` the `expressions` which share an alias (of which
+     * only one would be usable), or rejects if no `perspective.Table` is
+     * loaded.
+     * @example
+     * ```javascript
+     * const {valid, columns} = await viewer.validateConfig(saved);
+     * if (!valid) {
+     *     console.warn("Missing columns", columns);
+     * }
+     * ```
+     */
+    validateConfig(
+        config?: PerspectiveViewerConfig | string | ArrayBuffer
+    ): Promise<{
+        valid: boolean;
+        expressions: string[];
+        duplicate_aliases: Array<{alias: string; expressions: string[]}>;
+        columns: string[];
+        group_by: string[];
+        split_by: string[];
+        sort: string[];
+        filter: string[];
+        aggregates: string[];
+    }>;
+
+    /**
+     * Validate an expression against the loaded `perspective.Table`'s schema
+     * without adding it to this `<perspective-viewer>`'s `expressions`.
+     *
+     * @category Data
+     * @param expr The expression string to validate.
+     * @returns A `Promise` which resolves to an object
+     * `{valid, type, error, details}`, where `type` is the inferred result
+     * type of a valid expression and `error` is the validation message for an
+     * invalid one.  `details` is `{message, start, end}`, the character
+     * offsets of the offending token within `expr` (which may span multiple
+     * lines).
+     * @example
+     * ```javascript
+     * const {valid, type} = await viewer.validateExpression(`"Sales" * 2`);
+     * ```
+     * @example <caption>Highlight the error</caption>
+     * ```javascript
+     * const {details} = await viewer.validateExpression(expr);
+     * if (details) {
+     *     input.setSelectionRange(details.start, details.end);
+     * }
+     * ```
+     */
+    validateExpression(
+        expr: string
+    ): Promise<{
+        valid: boolean;
+        type?: string;
+        error?: string;
+        details?: {message: string; start: number; end: number};
+    }>;
+
+    /**
+     * Get this viewer's `expressions`, keyed by alias.  Values omit the
+     * `// alias` first line, and an expression without one is its own alias.
+     *
+     * @category Data
+     * @returns An object mapping each alias to its expression, in order.
+     * @example
+     * ```javascript
+     * const expressions = viewer.getExpressions();
+     * // {"Profit": "\"Sales\" - \"Cost\""}
+     * ```
+     */
+    getExpressions(): {[alias: string]: string};
+
+    /**
+     * Replace this viewer's `expressions` and redraw, leaving the rest of the
+     * config alone.  Every expression is validated against the `Table` first;
+     * if any is invalid, or an expression which is removed is still used by
+     * e.g. `columns`, nothing is applied and this rejects with the failing
+     * aliases.
+     *
+     * @category Data
+     * @param expressions An object mapping each alias to its expression, in
+     * the format returned by `getExpressions()`.
+     * @example
+     * ```javascript
+     * await viewer.setExpressions({
+     *     ...viewer.getExpressions(),
+     *     Margin: `"Profit" / "Sales"`,
+     * });
+     * ```
+     */
+    setExpressions(expressions: {[alias: string]: string}): Promise<void>;
+
+    /**
+     * Get completion suggestions for the partial token at `cursor` in an
+     * expression, drawn from this `<perspective-viewer>`'s column names and
+     * the ExprTK function list.  Suggestions whose name starts with the
+     * partial token are listed first.
+     *
+     * @category Data
+     * @param expr The expression text.
+     * @param cursor The cursor position within `expr`, in characters.
+     * @returns An array of completion descriptors in the same format as
+     * `getExprTKCommands()`.
+     * @example
+     * ```javascript
+     * const suggestions = viewer.getExpressionCompletions(`"Sales" + ab`, 12);
+     * ```
+     */
+    getExpressionCompletions(expr: string, cursor: number): Array<any>;
+
+    /**
+     * Get statistics for the current `perspective.View` and its most recent
+     * render, e.g. for display in a host application's status bar.  Fields
+     * are `null` until known; `last_render_time` is only measured when
+     * adaptive throttling is enabled (see `setThrottle()`).
+     *
+     * @category Data
+     * @returns A `Promise` which resolves to the current statistics, or
+     * rejects if no `perspective.Table` has been loaded.
+     * @example
+     * ```javascript
+     * const {num_rows, num_columns} = await viewer.getStats();
+     * ```
+     */
+    getStats(): Promise<{
+        num_rows: number | null;
+        virtual_rows: number | null;
+        num_columns: number | null;
+        is_pivot: boolean;
+        last_render_time: number | null;
+    }>;
+
+    /**
+     * Get the active plugin's preferred dimensions for the current
+     * `perspective.View` without drawing, e.g. for host layout calculations.
+     * Plugins may implement the optional `measure()` method to report their
+     * natural size;  otherwise the plugin's current scroll dimensions are
+     * returned.
+     *
+     * @category Util
+     * @returns A `Promise` which resolves to the preferred dimensions in
+     * pixels, or rejects if no plugin or `perspective.View` is ready.
+     * @example
+     * ```javascript
+     * const {width, height} = await viewer.measure();
+     * ```
+     */
+    measure(): Promise<{width: number; height: number}>;
+
+    /**
+     * Render the active plugin's current state to an image, e.g. for an
+     * "export chart" button or embedding in a report.  Plugins opt in by
+     * implementing the optional `to_image()` method;  the datagrid does not.
+     *
+     * @category UI
+     * @param format `"png"` (the default) or `"svg"`.
+     * @param width The image width in pixels, defaulting to the plugin's
+     * current width.
+     * @param height The image height in pixels, defaulting to the plugin's
+     * current height.
+     * @returns A `Promise` which resolves to the image `Blob`, or rejects if
+     * the active plugin cannot produce an image.
+     * @example
+     * ```javascript
+     * const blob = await viewer.toImage("svg");
+     * const url = URL.createObjectURL(blob);
+     * ```
+     */
+    toImage(
+        format?: "png" | "svg",
+        width?: number,
+        height?: number
+    ): Promise<Blob>;
+
+    /**
+     * Scroll a row (and optionally a column) of the active plugin into view,
+     * e.g. to "jump to" a result from a search outside the viewer.  Plugins
+     * opt in by implementing the optional `scroll_to()` method.
+     *
+     * @category UI
+     * @param row The row index in the current `View`.  Out-of-range indices
+     * scroll to the first or last row rather than rejecting.
+     * @param column The name of a column in the current `View`.
+     * @returns A `Promise` which resolves once scrolled, or rejects if the
+     * active plugin does not support scrolling or `column` is unknown.
+     * @example
+     * ```javascript
+     * await viewer.scrollTo(1000, "Sales");
+     * ```
+     */
+    scrollTo(row: number, column?: string): Promise<void>;
+
+    /**
+     * Find the cells of the current `View` whose values contain `query`, and
+     * reset the position of `searchNext()` and `searchPrev()`.  At most
+     * 10,000 matches are returned, with a console warning when the `View`
+     * has more.  The query and current match are not part of `save()`.
+     *
+     * @category UI
+     * @param query The text (or with `regex`, the pattern) to find.
+     * @param options `regex` treats `query` as a regular expression, and
+     * `case_sensitive` disables case-insensitive matching.
+     * @returns A `Promise` which resolves to the matching cells in row-major
+     * order, or rejects if `query` is an invalid regular expression.
+     * @example
+     * ```javascript
+     * const matches = await viewer.search("york");
+     * await viewer.searchNext();
+     * ```
+     */
+    search(
+        query: string,
+        options?: {regex?: boolean; case_sensitive?: boolean}
+    ): Promise<Array<{row: number; column: string}>>;
+
+    /**
+     * Scroll to and highlight the next match of the last `search()`,
+     * wrapping to the first.
+     *
+     * @category UI
+     * @returns A `Promise` which resolves to the match, or `null` if there
+     * are none.
+     */
+    searchNext(): Promise<{row: number; column: string} | null>;
+
+    /**
+     * Scroll to and highlight the previous match of the last `search()`,
+     * wrapping to the last.
+     *
+     * @category UI
+     * @returns A `Promise` which resolves to the match, or `null` if there
+     * are none.
+     */
+    searchPrev(): Promise<{row: number; column: string} | null>;
+
+    /**
+     * Select rows (and optionally columns) in the active plugin, e.g. to
+     * highlight rows in response to an event elsewhere in the host
+     * application.  Plugins opt in by implementing the optional
+     * `set_selection()` method.
+     *
+     * @category Util
+     * @param selection The rows to select, as indices into the current
+     * `perspective.View`, and optionally the columns to select by name.
+     * @returns A `Promise` which resolves when the selection has been applied,
+     * or rejects if the active plugin does not support selection.
+     * @example
+     * ```javascript
+     * await viewer.setSelection({rows: [0, 1, 2]});
+     * ```
+     */
+    setSelection(selection: {rows: number[]; columns?: string[]}): Promise<void>;
+
+    /**
+     * Get the active plugin's current selection, in the format accepted by
+     * `setSelection()`.
+     *
+     * @category Util
+     * @returns A `Promise` which resolves to the current selection, or `null`
+     * if nothing is selected, or rejects if the active plugin does not
+     * support selection.
+     * @example
+     * ```javascript
+     * const selection = await viewer.getSelection();
+     * if (selection) {
+     *     console.log(`${selection.rows.length} rows selected`);
+     * }
+     * ```
+     */
+    getSelection(): Promise<{rows: number[]; columns?: string[]} | null>;
+
     /**
      * Returns the underlying `perspective.View` currently configured for this
      * `<perspective-viewer>`.  Because ownership of the `perspective.View` is
@@ -166,49 +699,346 @@ export interface IPerspectiveViewerElement {
      * `load()`-ed, with the same (or a type-compatible superset) `Schema`.
      * It does not need have the same rows, or even be populated.
      *
+     * If `config.plugin` names a plugin which is not registered on this page,
+     * the default plugin is used instead (with a console warning) and
+     * `config.plugin_config` is ignored;  the rest of the config is still
+     * applied, and a subsequent `save()` returns the default plugin's name.
+     *
      * @category Persistence
      * @param config returned by `save()`.  This can be any format returned by
      * `save()`; the specific deserialization is chosen by `typeof config`.
+     * @param preserve_scroll Whether to keep the plugin's scroll position
+     * rather than scrolling back to the top.  This only applies when the
+     * plugin, `group_by`, `split_by`, `columns`, `sort` and `filter` are
+     * unchanged, as otherwise the same offset may address different rows.
+     * @param format The format of `config`, one of `"json"`, `"string"` or
+     * `"arraybuffer"`.  When omitted, the format is inferred from
+     * `typeof config`, which treats every string as `"string"` format;  pass
+     * `"json"` to restore from JSON text, e.g. from `save("json")` followed
+     * by `JSON.stringify()`.
+     * @param record_history Whether `undo()` can revert this change, which is
+     * the default.  Pass `false` for changes the user should not be able to
+     * undo, e.g. syncing state from another viewer.
      * @returns A promise which resolves when the changes have been applied and
-     * rendered.
+     * rendered.  This promise has an additional `abort()` method, which
+     * cancels a `restore()` that is still waiting for a previous render to
+     * finish (e.g. when the user changes configs rapidly);  none of its
+     * `config` is applied, any `restore()` calls made after it still take
+     * effect, and the promise rejects with `"Aborted"`.  Once its own render
+     * has begun, a `restore()` can no longer be aborted.
      * @example <caption>Restore a viewer from `localStorage`</caption>
      * ```javascript
      * const viewer = document.querySelector("perspective-viewer");
      * const token = localStorage.getItem("viewer_state");
      * await viewer.restore(token);
      * ```
+     * @example <caption>Re-apply a dashboard's config without scrolling</caption>
+     * ```javascript
+     * await viewer.restore(await viewer.save(), true);
+     * ```
+     * @example <caption>Restore a viewer from JSON text</caption>
+     * ```javascript
+     * const json = JSON.stringify(await viewer.save("json"));
+     * await viewer.restore(json, false, "json");
+     * ```
+     * @example <caption>Cancel a superseded `restore()`</caption>
+     * ```javascript
+     * let pending;
+     * slider.addEventListener("input", () => {
+     *     pending?.abort();
+     *     pending = viewer.restore({filter: [["x", "<", slider.value]]});
+     *     pending.catch(() => {});
+     * });
+     * ```
      */
     restore(
+        config: PerspectiveViewerConfig | string | ArrayBuffer,
+        preserve_scroll?: boolean,
+        format?: "json" | "string" | "arraybuffer",
+        record_history?: boolean
+    ): Promise<void> & {abort(): void};
+
+    /**
+     * Revert this element to its state before the last user change (or
+     * `restore()`).  Changes made in quick succession, e.g. while dragging a
+     * column, are undone as one.
+     *
+     * @category Persistence
+     * @returns `false` if there was nothing to undo.
+     * @example <caption>Bind Ctrl+Z and Ctrl+Shift+Z</caption>
+     * ```javascript
+     * document.addEventListener("keydown", (event) => {
+     *     if (event.ctrlKey && event.key.toLowerCase() === "z") {
+     *         event.shiftKey ? viewer.redo() : viewer.undo();
+     *     }
+     * });
+     * ```
+     */
+    undo(): Promise<boolean>;
+
+    /**
+     * Re-apply the last change reverted by `undo()`.  Any new change after an
+     * `undo()` discards the changes which could be redone.
+     *
+     * @category Persistence
+     * @returns `false` if there was nothing to redo.
+     */
+    redo(): Promise<boolean>;
+
+    /**
+     * Set how many states `undo()` can step back through, which defaults to
+     * 50.  Older states are discarded when the limit is reached.
+     *
+     * @category Persistence
+     * @param size The maximum number of states, or `null` for the default.
+     */
+    setHistorySize(size: number | null): void;
+
+    /**
+     * Like `restore()`, but coalesces calls made in quick succession (within
+     * ~50ms of the first pending call) into a single render.  The configs are
+     * merged in call order, so a field set by a later call replaces the same
+     * field from an earlier one, while fields a later call omits are kept.
+     * Every call's returned promise resolves (or rejects) together, once the
+     * merged config has been rendered.  This is useful for UI controls which
+     * may fire many updates per frame.
+     *
+     * @category Persistence
+     * @param config A (possibly partial) config, in any format accepted by
+     * `restore()`.
+     * @returns A promise which resolves when the batch containing this
+     * `config` has been applied and rendered.
+     * @example <caption>Apply a group and filter in one render</caption>
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * viewer.restoreBatched({group_by: ["State"]});
+     * await viewer.restoreBatched({filter: [["Sales", ">", 100]]});
+     * ```
+     */
+    restoreBatched(
         config: PerspectiveViewerConfig | string | ArrayBuffer
     ): Promise<void>;
 
     /**
-     * Serialize this element's attribute/interaction state, but _not_ the
-     * `perspective.Table` or its `Schema`.  `save()` is designed to be used in
-     * conjunction with `restore()` to persist user settings and bookmarks, but
-     * the `PerspectiveViewerConfig` object returned in `json` format can also
-     * be written by hand quite easily, which is useful for authoring
-     * pre-conceived configs.
+     * Apply several changes with a single render.  `callback` is called with
+     * a `PerspectiveViewerConfigBatch` which records changes without
+     * rendering;  when `callback` returns (or the `Promise` it returns
+     * resolves), the recorded changes are applied together, as by one
+     * `restore()` call.  If `callback` throws or its `Promise` rejects, none of
+     * the changes are applied.  Unlike `restoreBatched()`, the batch does not
+     * depend on timing.
+     *
+     * @category Persistence
+     * @param callback A function which records changes on the batch it is
+     * passed.  The batch cannot be used after `callback` completes.
+     * @returns A promise which resolves when the changes have been applied
+     * and rendered, or rejects with the error `callback` raised.
+     * @example <caption>Pivot and filter in one render</caption>
+     * ```javascript
+     * await viewer.batch((batch) => {
+     *     batch.setGroupBy(["State"]);
+     *     batch.setSort([["Sales", "desc"]]);
+     *     batch.setFilter([["Sales", ">", 100]]);
+     * });
+     * ```
+     */
+    batch(
+        callback: (
+            batch: PerspectiveViewerConfigBatch
+        ) => void | Promise<void>
+    ): Promise<void>;
+
+    /**
+     * Serialize this element's attribute/interaction state, but _not_ the
+     * `perspective.Table` or its `Schema`.  `save()` is designed to be used in
+     * conjunction with `restore()` to persist user settings and bookmarks, but
+     * the `PerspectiveViewerConfig` object returned in `json` format can also
+     * be written by hand quite easily, which is useful for authoring
+     * pre-conceived configs.
+     *
+     * @category Persistence
+     * @param format The serialization format - `json` (JavaScript object),
+     * `arraybuffer` or `string`.  `restore()` uses the returned config's type
+     * to infer format.
+     * @returns a serialized element in the chosen format.
+     * @example <caption>Save a viewer to `localStorage`</caption>
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * const token = await viewer.save("string");
+     * localStorage.setItem("viewer_state", token);
+     * ```
+     */
+    save(): Promise<PerspectiveViewerConfig>;
+    save(format: "json"): Promise<PerspectiveViewerConfig>;
+    save(format: "arraybuffer"): Promise<ArrayBuffer>;
+    save(format: "string"): Promise<string>;
+    save(
+        format?: "json" | "arraybuffer" | "string"
+    ): Promise<PerspectiveViewerConfig | string | ArrayBuffer>;
+
+    /**
+     * Compare two `save()` configs, listing the fields `added`, `removed` and
+     * `changed` between them, and computing the minimal `restore()` `update`
+     * which transforms one into the other.  In `update`, fields which are the
+     * same in both are omitted, and fields `newConfig` resets to their
+     * default (or omits while `oldConfig` has them) are `null`.
+     *
+     * @category Persistence
+     * @param oldConfig A config returned by `save()`, in any format.
+     * @param newConfig A config returned by `save()`, in any format.
+     * @returns A `PerspectiveViewerConfigDiff`, whose `update` applied via
+     * `restore()` to an element in the `oldConfig` state yields the
+     * `newConfig` state.
+     * @example <caption>Undo history as a list of deltas</caption>
+     * ```javascript
+     * const before = await viewer.save();
+     * await viewer.restore({group_by: ["State"]});
+     * const {changed, update} = viewer.diffConfig(before, await viewer.save());
+     * // changed: {group_by: {old: [], new: ["State"]}}
+     * // update: {group_by: ["State"]}
+     * ```
+     */
+    diffConfig(
+        oldConfig: PerspectiveViewerConfig | string | ArrayBuffer,
+        newConfig: PerspectiveViewerConfig | string | ArrayBuffer
+    ): PerspectiveViewerConfigDiff;
+
+    /**
+     * Get a shareable URL which encodes this element's current `save()`
+     * config in its fragment (as `#perspective=...`, the `"string"` format in
+     * the URL-safe base64 alphabet), for `restoreFromUrl()`.  Logs a warning
+     * for URLs over 2,000 characters, which some browsers and chat clients
+     * truncate, and rejects for URLs over 8,192 characters.
+     *
+     * @category Persistence
+     * @param base_url The URL to link to, whose fragment is replaced;
+     * defaults to `window.location.href`.
+     * @returns A `Promise` which resolves to the permalink URL.
+     * @example
+     * ```javascript
+     * share_button.href = await viewer.getPermalink();
+     * ```
+     */
+    getPermalink(base_url?: string): Promise<string>;
+
+    /**
+     * Copy the URL `getPermalink()` returns to the system clipboard.
+     *
+     * @category Persistence
+     * @param base_url The URL to link to, whose fragment is replaced;
+     * defaults to `window.location.href`.
+     */
+    copyPermalink(base_url?: string): Promise<void>;
+
+    /**
+     * Restore this element from a URL created by `getPermalink()`, as
+     * `restore()` would.
+     *
+     * @category Persistence
+     * @param url The URL to read, defaulting to `window.location.href`.
+     * @returns A `Promise` which resolves to `false`, without changing this
+     * element, if `url` has no permalink fragment, and `true` once restored.
+     * @example <caption>Restore a shared link on page load</caption>
+     * ```javascript
+     * await viewer.load(table);
+     * await viewer.restoreFromUrl();
+     * ```
+     */
+    restoreFromUrl(url?: string): Promise<boolean>;
+
+    /**
+     * Store this element's current `save()` config as a named preset,
+     * replacing any existing preset of the same name.  Presets are kept in
+     * memory unless a backend is set with `setPresetStorage()`.
+     *
+     * @category Persistence
+     * @param name The name of the preset.
+     * @example
+     * ```javascript
+     * await viewer.saveNamedPreset("By Region");
+     * ```
+     */
+    saveNamedPreset(name: string): Promise<void>;
+
+    /**
+     * List the names of this element's stored presets, sorted.
+     *
+     * @category Persistence
+     * @returns The preset names.
+     */
+    listPresets(): Promise<string[]>;
+
+    /**
+     * Restore this element to a named preset, as `restore()` would.  Rejects
+     * if there is no preset with this name.
+     *
+     * @category Persistence
+     * @param name The name of the preset.
+     * @example
+     * ```javascript
+     * for (const name of await viewer.listPresets()) {
+     *     const button = document.createElement("button");
+     *     button.textContent = name;
+     *     button.onclick = () => viewer.applyPreset(name);
+     *     toolbar.appendChild(button);
+     * }
+     * ```
+     */
+    applyPreset(name: string): Promise<void>;
+
+    /**
+     * Set the backend which `saveNamedPreset()`, `listPresets()` and
+     * `applyPreset()` use, in place of the default in-memory store.  Presets
+     * are not copied from the previous backend.
+     *
+     * @category Persistence
+     * @param storage An object implementing `get()`, `set()` and `list()`,
+     * or `null` to revert to in-memory storage.
+     * @example <caption>Keep presets in `localStorage`</caption>
+     * ```javascript
+     * const PREFIX = "viewer-preset:";
+     * viewer.setPresetStorage({
+     *     get: (name) => JSON.parse(localStorage.getItem(PREFIX + name)),
+     *     set: (name, config) =>
+     *         localStorage.setItem(PREFIX + name, JSON.stringify(config)),
+     *     list: () =>
+     *         Object.keys(localStorage)
+     *             .filter((key) => key.startsWith(PREFIX))
+     *             .map((key) => key.slice(PREFIX.length)),
+     * });
+     * ```
+     */
+    setPresetStorage(storage: PerspectiveViewerPresetStorage | null): void;
+
+    /**
+     * Set the default column style config for every column of a type, e.g.
+     * so that all `"integer"` columns show 0 decimals.  Plugins use these as
+     * the defaults for the column style menu, and a column's own style config
+     * still overrides them.  Type defaults are included in `save()` as
+     * `type_defaults`, and can be restored via `restore()`.
      *
      * @category Persistence
-     * @param format The serialization format - `json` (JavaScript object),
-     * `arraybuffer` or `string`.  `restore()` uses the returned config's type
-     * to infer format.
-     * @returns a serialized element in the chosen format.
-     * @example <caption>Save a viewer to `localStorage`</caption>
+     * @param column_type The column type to set defaults for, one of
+     * `"integer"`, `"float"`, `"string"`, `"boolean"`, `"date"` or
+     * `"datetime"`.
+     * @param config A column style config, or `null` to clear the defaults
+     * for this type.
+     * @returns A promise which resolves when the plugin has re-rendered with
+     * the new defaults.
+     * @example <caption>Show integers without decimals</caption>
      * ```javascript
-     * const viewer = document.querySelector("perspective-viewer");
-     * const token = await viewer.save("string");
-     * localStorage.setItem("viewer_state", token);
+     * await viewer.setTypeDefaults("integer", {fixed: 0});
      * ```
      */
-    save(): Promise<PerspectiveViewerConfig>;
-    save(format: "json"): Promise<PerspectiveViewerConfig>;
-    save(format: "arraybuffer"): Promise<ArrayBuffer>;
-    save(format: "string"): Promise<string>;
-    save(
-        format?: "json" | "arraybuffer" | "string"
-    ): Promise<PerspectiveViewerConfig | string | ArrayBuffer>;
+    setTypeDefaults(column_type: string, config: any | null): Promise<void>;
+
+    /**
+     * Get the column style defaults set by `setTypeDefaults()`.
+     *
+     * @category Persistence
+     * @returns An object of column style configs keyed by column type.
+     */
+    getTypeDefaults(): {[column_type: string]: any};
 
     /**
      * Flush any pending modifications to this `<perspective-viewer>`.  Since
@@ -219,6 +1049,9 @@ export interface IPerspectiveViewerElement {
      * `flush()`
      *
      * @category Util
+     * @param timeout_ms If `load()` has not yet been called, how many
+     * milliseconds to wait for it before rejecting.  Waits indefinitely if
+     * omitted.
      * @returns {Promise<void>} A promise which resolves when the current
      * pending state changes have been applied and rendered.
      * @example <caption>Flush an unawaited `restore()`</caption>
@@ -229,7 +1062,7 @@ export interface IPerspectiveViewerElement {
      * console.log("Viewer has been rendered with a pivot!");
      * ```
      */
-    flush(): Promise<void>;
+    flush(timeout_ms?: number): Promise<void>;
 
     /**
      * Reset's this element's view state and attributes to default.  Does not
@@ -246,6 +1079,54 @@ export interface IPerspectiveViewerElement {
      */
     reset(all): Promise<void>;
 
+    /**
+     * Remove all `filter` terms from this viewer's config and redraw, leaving
+     * the other settings unchanged.
+     *
+     * @category Persistence
+     * @example
+     * ```javascript
+     * await viewer.clearFilter();
+     * ```
+     */
+    clearFilter(): Promise<void>;
+
+    /**
+     * Remove all `sort` terms from this viewer's config and redraw, leaving
+     * the other settings unchanged.
+     *
+     * @category Persistence
+     * @example
+     * ```javascript
+     * await viewer.clearSort();
+     * ```
+     */
+    clearSort(): Promise<void>;
+
+    /**
+     * Remove all `group_by` columns from this viewer's config and redraw, leaving
+     * the other settings unchanged.
+     *
+     * @category Persistence
+     * @example
+     * ```javascript
+     * await viewer.clearGroupBy();
+     * ```
+     */
+    clearGroupBy(): Promise<void>;
+
+    /**
+     * Remove all `split_by` columns from this viewer's config and redraw, leaving
+     * the other settings unchanged.
+     *
+     * @category Persistence
+     * @example
+     * ```javascript
+     * await viewer.clearSplitBy();
+     * ```
+     */
+    clearSplitBy(): Promise<void>;
+
     /**
      * Deletes this element and clears it's internal state (but not its
      * user state).  This (or the underlying `perspective.view`'s equivalent
@@ -258,13 +1139,121 @@ export interface IPerspectiveViewerElement {
     delete(): Promise<void>;
 
     /**
-     * Download this element's data as a CSV file.
+     * Deletes this element's `perspective.View` and clears the plugin's
+     * rendered content, but keeps the `perspective.Table` and config, e.g. to
+     * free memory while this element is hidden in an inactive tab.  The
+     * `View` is recreated, exactly as configured before, by `resume()` (or
+     * by any `restore()` call).
+     *
+     * @category Util
+     * @returns A promise which resolves to `true` if a `View` was deleted.
+     * @example
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * await viewer.suspend();
+     * // ... later, when the viewer is shown again
+     * await viewer.resume();
+     * ```
+     */
+    suspend(): Promise<boolean>;
+
+    /**
+     * Recreates the `perspective.View` deleted by `suspend()` and redraws.
+     * Does nothing if this element is not suspended.
+     *
+     * @category Util
+     * @returns A promise which resolves to `true` if the `View` was
+     * recreated.
+     */
+    resume(): Promise<boolean>;
+
+    /**
+     * Opt-in to automatically `suspend()`-ing this element once it has been
+     * detached from the DOM or hidden (e.g. `display: none`) for `delay`
+     * milliseconds, and `resume()`-ing it when it is shown again.  Visibility
+     * is observed by the auto-size `ResizeObserver`, so this has no effect
+     * after `setAutoSize(false)`.
+     *
+     * @category Util
+     * @param delay The delay in milliseconds, or `undefined` to disable
+     * auto-suspend (the default).
+     * @example
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * viewer.setAutoSuspend(30000);
+     * ```
+     */
+    setAutoSuspend(delay?: number): void;
+
+    /**
+     * Set the snippets listed in the expression editor's side panel.  Clicking
+     * a snippet inserts its `expression` at the cursor.  Snippets are not part
+     * of the `save()` config, so a team's shared snippets survive `restore()`
+     * and `load()`.
+     *
+     * @category UI
+     * @param snippets An array of `{name, expression}` objects, or
+     * `undefined` to restore the built-in snippets.
+     * @example
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * viewer.setExpressionSnippets([
+     *     {name: "Margin", expression: '"Profit" / "Sales"'},
+     * ]);
+     * ```
+     */
+    setExpressionSnippets(
+        snippets?: Array<{name: string; expression: string}>
+    ): void;
+
+    /**
+     * Download this element's data as a CSV file, a Markdown table, or as a
+     * standalone HTML snapshot.
+     *
+     * @category UI
+     * @param mode Whether to use the element's current view
+     * config (`false`), to use a default "flat" view (`true`), or to export
+     * only the rows selected in the plugin (`"selection"`).  `"selection"`
+     * rejects if the plugin does not support selection or has nothing
+     * selected.
+     * `"html"` instead downloads `untitled.html`, a self-contained page which
+     * embeds the `perspective.Table` as Arrow and restores this element's
+     * `save()` config, and `"markdown"` downloads `untitled.md`, a
+     * GitHub-flavored Markdown table of the current view.
+     * @example
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * await viewer.download("selection");
+     * ```
+     * @example <caption>Share a snapshot of this viewer</caption>
+     * ```javascript
+     * await viewer.download("html");
+     * ```
+     */
+    download(mode?: boolean | "selection" | "html" | "markdown"): Promise<void>;
+
+    /**
+     * Download this element's data as a CSV file, like `download()`, but
+     * serialized in chunks of rows which are assembled into the file's `Blob`
+     * as they are produced.  This keeps peak memory lower than `download()`
+     * for very large views.
      *
-     * @category UI Action
-     * @param flat Whether to use the element's current view
-     * config, or to use a default "flat" view.
+     * @category UI
+     * @param flat Whether to use the element's current view config (`false`)
+     * or a default "flat" view (`true`).
+     * @returns A promise which resolves when the file has been downloaded.
+     * This promise has an additional `abort()` method, which stops the export
+     * between chunks;  the promise then rejects with `"Aborted"` and no
+     * (partial) file is downloaded.
+     * @example
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * const task = viewer.downloadStreaming(true);
+     * cancel.addEventListener("click", () => task.abort());
+     * await task;
+     * ```
      */
-    download(flat: boolean): Promise<void>;
+    downloadStreaming(flat?: boolean): Promise<void> & {abort(): void};
 
     /**
      * Copies this element's view data (as a CSV) to the clipboard.  This method
@@ -272,9 +1261,21 @@ export interface IPerspectiveViewerElement {
      * restrictions on clipboard access.  See
      * {@link https://www.w3.org/TR/clipboard-apis/#allow-read-clipboard}.
      *
-     * @category UI Action
-     * @param flat Whether to use the element's current view
-     * config, or to use a default "flat" view.
+     * @category UI
+     * @param mode Whether to use the element's current view
+     * config (`false`), to use a default "flat" view (`true`), or to copy
+     * only the rows selected in the plugin (`"selection"`).  `"selection"`
+     * rejects if the plugin does not support selection or has nothing
+     * selected.
+     * `"markdown"` copies the current view as a GitHub-flavored Markdown
+     * table, e.g. for pasting into an issue.
+     * @param headers Whether to include the header row, defaults to `true`.
+     * Pass `false` to copy only the data rows, e.g. to paste into an existing
+     * table.  Only supported for CSV.
+     * @returns A `Promise` which resolves once the data is on the clipboard,
+     * or rejects with an `Error` whose `code` is `"insecure_context"` (the
+     * page is not served over HTTPS), `"unavailable"`, `"permission_denied"`
+     * or `"write_failed"` if the clipboard write fails.
      * @example
      * ```javascript
      * const viewer = document.querySelector("perspective-viewer");
@@ -283,8 +1284,79 @@ export interface IPerspectiveViewerElement {
      *     await viewer.copy();
      * });
      * ```
+     * @example <caption>Copy only the data rows</caption>
+     * ```javascript
+     * await viewer.copy(false, false);
+     * ```
+     */
+    copy(
+        mode?: boolean | "selection" | "markdown",
+        headers?: boolean
+    ): Promise<void>;
+
+    /**
+     * Check whether `copy()` can write to the system clipboard:  the page is
+     * a secure (HTTPS) context, the browser implements the clipboard API,
+     * and clipboard write permission has not been denied.  Browsers which do
+     * not expose the `"clipboard-write"` permission only check the former.
+     *
+     * @category UI
+     * @returns A `Promise` which resolves to whether the clipboard is
+     * writable.
+     * @example <caption>Fall back to a manual copy dialog</caption>
+     * ```javascript
+     * if (await viewer.canCopy()) {
+     *     await viewer.copy();
+     * } else {
+     *     const view = await viewer.getView();
+     *     showCopyDialog(await view.to_csv());
+     * }
+     * ```
+     */
+    canCopy(): Promise<boolean>;
+
+    /**
+     * Copies a single cell's value from this element's view to the clipboard,
+     * as plain text.  Like `copy()`, this method must be called from an event
+     * handler, subject to the browser's restrictions on clipboard access.
+     *
+     * @category UI
+     * @param row The cell's row index in the current view.
+     * @param column The cell's column index in the current view.
+     * @returns A promise which rejects if the coordinates are out of range or
+     * no view has been loaded.
+     * @example <caption>Copy a cell from a context menu</caption>
+     * ```javascript
+     * menu_copy.addEventListener("click", async () => {
+     *     await viewer.copyCell(row, column);
+     * });
+     * ```
+     */
+    copyCell(row: number, column: number): Promise<void>;
+
+    /**
+     * Read a window of rows from this element's view, in columnar format
+     * (like `View.to_columns()`).  Unlike `download()`, only the requested
+     * rows are serialized, which suits host UIs that virtualize their own
+     * rendering.
+     *
+     * @category Data
+     * @param start_row The first row of the window, inclusive.
+     * @param end_row The last row of the window, exclusive.
+     * @param columns If provided, only these columns are included;  row
+     * paths (`__ROW_PATH__`) are always included for grouped views.
+     * @returns A promise to an object of column name to values, which rejects
+     * if the range is invalid or no view has been loaded.
+     * @example
+     * ```javascript
+     * const rows = await viewer.getViewWindow(100, 150, ["Sales", "Profit"]);
+     * ```
      */
-    copy(flat: boolean): Promise<void>;
+    getViewWindow(
+        start_row: number,
+        end_row: number,
+        columns?: string[]
+    ): Promise<Record<string, any[]>>;
 
     /**
      * Restyles the elements and to pick up any style changes.  While most of
@@ -297,6 +1369,128 @@ export interface IPerspectiveViewerElement {
      */
     restyleElement(): Promise<void>;
 
+    /**
+     * Override whether plugins render animations, such as the datagrid's
+     * pulsing cell backgrounds, then restyle.  By default this follows the
+     * OS `prefers-reduced-motion` setting, restyling whenever it changes.
+     * This is a render-time setting and is not persisted by `save()`.
+     *
+     * @category Util
+     * @param mode `"on"` to disable animations, `"off"` to enable them, or
+     * `"auto"` (the default) to follow the OS setting.
+     * @example
+     * ```javascript
+     * await viewer.setReducedMotion("on");
+     * ```
+     */
+    setReducedMotion(mode?: "auto" | "on" | "off"): Promise<void>;
+
+    /**
+     * Render the entire view, rather than just the rows visible in the
+     * viewport, so that the browser's print (Ctrl+P) captures every row.
+     * Plugins opt in by reading the `print_mode` property during `draw()`;
+     * the datagrid disables its virtualization while this is enabled.  Logs
+     * a warning for views of more than 10,000 rows, which may be slow to
+     * render.  Not persisted by `save()`.
+     *
+     * @category UI
+     * @param enabled Whether to render for print, or restore normal
+     * rendering.
+     * @example
+     * ```javascript
+     * await viewer.setPrintMode(true);
+     * window.print();
+     * await viewer.setPrintMode(false);
+     * ```
+     */
+    setPrintMode(enabled: boolean): Promise<void>;
+
+    /**
+     * Fade each render in over the previous one, e.g. when a `restore()`
+     * changes the `sort` or `filter`, rather than redrawing instantly.  This
+     * is a fade of the whole redraw;  rows do not animate between their old
+     * and new positions.  Intended for presentation dashboards;  off by
+     * default.  Plugins opt in by reading the `redraw_fade` property during
+     * `draw()`, which is `false` while reduced motion is active (see
+     * `setReducedMotion()`).  Not persisted by `save()`.
+     *
+     * @category Util
+     * @param enabled Whether to fade in subsequent draws.
+     * @example
+     * ```javascript
+     * viewer.setRedrawFade(true);
+     * await viewer.restore({sort: [["Sales", "desc"]]});
+     * ```
+     */
+    setRedrawFade(enabled: boolean): void;
+
+    /**
+     * Set the row height and cell padding of plugins which support it, then
+     * restyle.  `"compact"` is applied as the `density-compact` class on this
+     * element, which plugin stylesheets select on.  Unlike
+     * `setReducedMotion()`, the density is persisted by `save()` as the
+     * `density` field, and a `restore()` without it keeps the current
+     * density.
+     *
+     * @category Util
+     * @param density `"compact"`, or `"comfortable"` (the default).
+     * @example
+     * ```javascript
+     * await viewer.setDensity("compact");
+     * ```
+     */
+    setDensity(density?: "comfortable" | "compact"): Promise<void>;
+
+    /**
+     * Set the row height of plugins which implement `set_row_sizing()`, then
+     * restyle.  For finer control than `setDensity()`;  plugins without
+     * custom sizing ignore this and log a warning.  Persisted by `save()` as
+     * the `row_height` field.
+     *
+     * @category Util
+     * @param height The row height in pixels, which must be positive, or
+     * `null` for the plugin's default.
+     * @example
+     * ```javascript
+     * await viewer.setRowHeight(28);
+     * ```
+     */
+    setRowHeight(height?: number | null): Promise<void>;
+
+    /**
+     * Set the column header height of plugins which implement
+     * `set_row_sizing()`, then restyle.  Persisted by `save()` as the
+     * `header_height` field.
+     *
+     * @category Util
+     * @param height The header height in pixels, which must be positive, or
+     * `null` for the plugin's default.
+     * @example
+     * ```javascript
+     * await viewer.setHeaderHeight(40);
+     * ```
+     */
+    setHeaderHeight(height?: number | null): Promise<void>;
+
+    /**
+     * Apply a high-contrast overlay, with stronger borders and text, on top of
+     * the selected theme, then restyle.  By default this follows the OS
+     * `prefers-contrast` setting, updating whenever it changes.  The overlay
+     * is applied as the `high-contrast` class on this element, which custom
+     * themes may also style, and fires `perspective-viewer-theme-change` only
+     * when it toggles.
+     * This is a render-time setting and is not persisted by `save()`.
+     *
+     * @category Util
+     * @param mode `"on"` or `"off"` to force the overlay, or `"auto"` (the
+     * default) to follow the OS setting.
+     * @example
+     * ```javascript
+     * await viewer.setHighContrast("on");
+     * ```
+     */
+    setHighContrast(mode?: "auto" | "on" | "off"): Promise<void>;
+
     /**
      * Sets the theme names available via the `<perspective-viewer>` status bar
      * UI.  Typically these will be auto-detected simply by including the
@@ -321,6 +1515,61 @@ export interface IPerspectiveViewerElement {
      */
     resetThemes(themes?: Array<string>): Promise<void>;
 
+    /**
+     * Register themes from their CSS text, without adding `<style>` or
+     * `<link>` tags to the document yourself.  Registered themes are listed
+     * in the status bar UI and can be selected via `restore()`, alongside
+     * any auto-detected themes.  Registering a theme name again replaces its
+     * CSS, and the plugin is restyled if the active theme is affected.
+     *
+     * @category Util
+     * @param themes An object mapping theme names to CSS text, which should
+     * style `perspective-viewer[theme="<name>"]`.
+     * @example
+     * ```javascript
+     * await viewer.setThemes({
+     *     "Ocean": `perspective-viewer[theme="Ocean"] {
+     *         --theme-name: "Ocean";
+     *         background-color: #012;
+     *         color: #cde;
+     *     }`,
+     * });
+     *
+     * await viewer.restore({theme: "Ocean"});
+     * ```
+     */
+    setThemes(themes: Record<string, string>): Promise<void>;
+
+    /**
+     * Get the name of the currently applied theme, e.g. to keep an external
+     * theme picker in sync with this `<perspective-viewer>`.
+     *
+     * @category Util
+     * @returns A `Promise` which resolves to the theme name, or `null` if no
+     * themes are available.
+     * @example
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * const theme = await viewer.getTheme();
+     * ```
+     */
+    getTheme(): Promise<string | null>;
+
+    /**
+     * Switch to the next available theme, wrapping around to the first, and
+     * restyle.  Useful for e.g. kiosk dashboards which rotate themes.  Does
+     * nothing if fewer than two themes are available.
+     *
+     * @category Util
+     * @param reverse Cycle to the previous theme instead of the next.
+     * @example
+     * ```javascript
+     * const viewer = document.querySelector("perspective-viewer");
+     * setInterval(() => viewer.cycleTheme(), 60000);
+     * ```
+     */
+    cycleTheme(reverse?: boolean): Promise<void>;
+
     /**
      * Gets the edit port, the port number for which `Table` updates from this
      * `<perspective-viewer>` are generated.  This port number will be present
@@ -362,6 +1611,37 @@ export interface IPerspectiveViewerElement {
      */
     setThrottle(value?: number): void;
 
+    /**
+     * Tune adaptive throttling, which (when no constant throttle is set via
+     * `setThrottle()`) waits the average render time of the last `window`
+     * renders between renders.  On slow devices, a wider `window` smooths out
+     * render time spikes, and a `target` caps the frame rate regardless of
+     * how fast renders are.  Tuning persists across `setThrottle()` calls,
+     * and is reflected in the value `getThrottle()` returns.
+     *
+     * @category Util
+     * @param window The number of recent renders to average, defaulting to
+     * `5`.  `getThrottle()` returns `target` (or `0`) until this many renders
+     * have been measured.
+     * @param target The minimum wait between renders in milliseconds,
+     * defaulting to `0`.
+     * @example <caption>Average 20 renders, at most 10 frames per second</caption>
+     * ```javascript
+     * viewer.setThrottleTuning(20, 100);
+     * ```
+     */
+    setThrottleTuning(window?: number, target?: number): void;
+
+    /**
+     * Get the current wait between renders in milliseconds:  the constant
+     * throttle from `setThrottle()` if one is set, or else the adaptive
+     * throttle computed from recent render times and `setThrottleTuning()`.
+     *
+     * @category Util
+     * @returns The throttle in milliseconds.
+     */
+    getThrottle(): number;
+
     /**
      * Opens/closes the element's config menu, equivalent to clicking the
      * settings button in the UI.  This method is equivalent to
@@ -371,9 +1651,11 @@ export interface IPerspectiveViewerElement {
      *
      * Calling `toggleConfig()` may be delayed if an async render is currently
      * in process, and it may only partially render the UI if `load()` has not
-     * yet resolved.
+     * yet resolved.  The returned `Promise` resolves only after any CSS
+     * transition on the settings panel has finished, so layout may be
+     * measured immediately afterwards.
      *
-     * @category UI Action
+     * @category UI
      * @param force If supplied, explicitly set the config state to "open"
      * (`true`) or "closed" (`false`).
      * @example
@@ -383,6 +1665,22 @@ export interface IPerspectiveViewerElement {
      */
     toggleConfig(force?: boolean): Promise<void>;
 
+    /**
+     * Whether the element's config menu is currently open.  This reflects the
+     * rendered state, so while a `toggleConfig()` call is pending it reports
+     * the state from before the toggle.
+     *
+     * @category UI
+     * @returns `true` if the config menu is open.
+     * @example
+     * ```javascript
+     * if (!viewer.isConfigOpen()) {
+     *     await viewer.toggleConfig(true);
+     * }
+     * ```
+     */
+    isConfigOpen(): boolean;
+
     /**
      * Get the currently active plugin custom element instance, or a specific
      * named instance if requested.  `getPlugin(name)` does not activate the
@@ -400,6 +1698,60 @@ export interface IPerspectiveViewerElement {
      */
     getPlugin(name?: string): Promise<HTMLElement>;
 
+    /**
+     * Get a plugin's metadata, suitable for building a plugin picker without
+     * inspecting the plugin Custom Element directly.  Fields which the plugin
+     * does not provide are omitted.
+     *
+     * @category Plugin
+     * @param name Optionally a specific plugin name, defaulting to the current
+     * active plugin.
+     * @returns An object with `name`, `category` and `select_mode` fields, and
+     * optionally `min_columns`, `max_columns`, `max_cells`,
+     * `config_column_names` and `view_types`.
+     * @example
+     * ```javascript
+     * const {name, category} = viewer.getPluginInfo("Datagrid");
+     * ```
+     */
+    getPluginInfo(name?: string): {
+        name: string;
+        category: string;
+        select_mode: "toggle" | "select";
+        min_columns?: number;
+        max_columns?: number;
+        max_cells?: number;
+        config_column_names?: Array<string>;
+        view_types?: Array<string>;
+    };
+
+    /**
+     * Get the config controls the active plugin supports, e.g. to disable a
+     * host UI's split-by control for a plugin which ignores `split_by`.
+     * Capabilities the plugin does not declare via its `capabilities`
+     * property default to supported, and every field is always present.
+     *
+     * @category Plugin
+     * @returns The aggregates the plugin can render, whether it supports
+     * `group_by`, `split_by`, `sort` and `filter`, its column limits and
+     * `view_types`, or `null` for limits it does not declare.
+     * @example
+     * ```javascript
+     * const {split_by} = viewer.getPluginCapabilities();
+     * split_by_button.disabled = !split_by;
+     * ```
+     */
+    getPluginCapabilities(): {
+        aggregates: Array<string>;
+        group_by: boolean;
+        split_by: boolean;
+        sort: boolean;
+        filter: boolean;
+        min_columns: number | null;
+        max_columns: number | null;
+        view_types: Array<string> | null;
+    };
+
     /**
      * Get all plugin custom element instances, in order of registration.
      *
@@ -413,6 +1765,23 @@ export interface IPerspectiveViewerElement {
      */
     getAllPlugins(): Array<HTMLElement>;
 
+    /**
+     * Wait for all plugins to be ready, then return them as `getAllPlugins()`
+     * does.  Unlike `getAllPlugins()`, this includes plugins which called
+     * `registerPlugin()` from a `<script>` loaded after this element first
+     * rendered.  Resolves immediately if the `document` has already loaded.
+     *
+     * @category Plugin
+     * @returns A `Promise` which resolves to an `Array` of plugin Custom
+     * Elements.
+     * @example
+     * ```javascript
+     * const plugins = await viewer.awaitPluginsReady();
+     * const names = plugins.map((x) => x.name);
+     * ```
+     */
+    awaitPluginsReady(): Promise<Array<HTMLElement>>;
+
     /**
      * Get the raw pointer to this `<perspective-viewer>` WASM model, such that
      * it may be passed back to WASM function calls that take a
@@ -61,6 +61,12 @@ utils.with_server({}, () => {
                         settings: true,
                         sort: [],
                         theme: null,
+                        header_height: null,
+                        row_height: null,
+                        settings_width: null,
+                        pinned_columns: [],
+                        type_defaults: {},
+                        density: "comfortable",
                     });
 
                     return await get_contents(page);
@@ -287,6 +287,24 @@ utils.with_server({}, () => {
                         expect(config.settings).toEqual(false);
                         delete config.settings;
 
+                        expect(config.density).toEqual("comfortable");
+                        delete config.density;
+
+                        expect(config.type_defaults).toEqual({});
+                        delete config.type_defaults;
+
+                        expect(config.pinned_columns).toEqual([]);
+                        delete config.pinned_columns;
+
+                        expect(config.settings_width).toEqual(null);
+                        delete config.settings_width;
+
+                        expect(config.row_height).toEqual(null);
+                        delete config.row_height;
+
+                        expect(config.header_height).toEqual(null);
+                        delete config.header_height;
+
                         expect(config).toEqual(current);
                         expect(convert(old, {replace_defaults: true})).toEqual(
                             current
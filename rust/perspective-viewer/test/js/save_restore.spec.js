@@ -49,6 +49,12 @@ utils.with_server({}, () => {
                     settings: true,
                     sort: [],
                     theme: null,
+                    header_height: null,
+                    row_height: null,
+                    settings_width: null,
+                    pinned_columns: [],
+                    type_defaults: {},
+                    density: "comfortable",
                 });
 
                 return await get_contents(page);
@@ -81,6 +87,12 @@ utils.with_server({}, () => {
                         settings: true,
                         sort: [],
                         theme: null,
+                        header_height: null,
+                        row_height: null,
+                        settings_width: null,
+                        pinned_columns: [],
+                        type_defaults: {},
+                        density: "comfortable",
                     });
 
                     const config2 = await page.evaluate(async () => {
@@ -122,6 +134,12 @@ utils.with_server({}, () => {
                         settings: true,
                         sort: [],
                         theme: null,
+                        header_height: null,
+                        row_height: null,
+                        settings_width: null,
+                        pinned_columns: [],
+                        type_defaults: {},
+                        density: "comfortable",
                     });
 
                     const config3 = await page.evaluate(async (config) => {
@@ -143,6 +161,100 @@ utils.with_server({}, () => {
                         settings: true,
                         sort: [],
                         theme: null,
+                        header_height: null,
+                        row_height: null,
+                        settings_width: null,
+                        pinned_columns: [],
+                        type_defaults: {},
+                        density: "comfortable",
+                    });
+
+                    return await get_contents(page);
+                }
+            );
+
+            test.capture(
+                "aborting restore keeps the previous config",
+                async (page) => {
+                    const result = await page.evaluate(async () => {
+                        const viewer =
+                            document.querySelector("perspective-viewer");
+                        await viewer.getTable();
+                        await viewer.restore({
+                            settings: true,
+                            group_by: ["State"],
+                            columns: ["Profit", "Sales"],
+                        });
+
+                        const task = viewer.restore({
+                            group_by: ["Region"],
+                            split_by: ["Category"],
+                        });
+
+                        task.abort();
+                        const error = await task.then(
+                            () => null,
+                            (e) => `${e}`
+                        );
+
+                        const config = await viewer.save();
+                        return {
+                            error,
+                            group_by: config.group_by,
+                            split_by: config.split_by,
+                        };
+                    });
+
+                    expect(result).toEqual({
+                        error: expect.stringContaining("Aborted"),
+                        group_by: ["State"],
+                        split_by: [],
+                    });
+
+                    return await get_contents(page);
+                }
+            );
+
+            test.capture(
+                "aborting a queued restore keeps later restores",
+                async (page) => {
+                    const result = await page.evaluate(async () => {
+                        const viewer =
+                            document.querySelector("perspective-viewer");
+                        await viewer.getTable();
+                        await viewer.restore({
+                            settings: true,
+                            columns: ["Profit", "Sales"],
+                        });
+
+                        const first = viewer.restore({
+                            group_by: ["Region"],
+                            split_by: ["Category"],
+                        });
+
+                        const second = viewer.restore({group_by: ["State"]});
+                        first.abort();
+                        const errors = await Promise.all(
+                            [first, second].map((task) =>
+                                task.then(
+                                    () => null,
+                                    (e) => `${e}`
+                                )
+                            )
+                        );
+
+                        const config = await viewer.save();
+                        return {
+                            errors,
+                            group_by: config.group_by,
+                            split_by: config.split_by,
+                        };
+                    });
+
+                    expect(result).toEqual({
+                        errors: [expect.stringContaining("Aborted"), null],
+                        group_by: ["State"],
+                        split_by: [],
                     });
 
                     return await get_contents(page);